@@ -84,7 +84,7 @@ fn parse(input: DeriveInput, errors: &mut Vec<syn::Error>) -> proc_macro2::Token
 		},
 	};
 
-	let (tag_fields, properties_field) = match get_fields(errors, data) {
+	let (tag_fields, properties_field, audio_data_range_field) = match get_fields(errors, data) {
 		Some(fields) => fields,
 		None => return proc_macro2::TokenStream::new(),
 	};
@@ -169,6 +169,28 @@ fn parse(input: DeriveInput, errors: &mut Vec<syn::Error>) -> proc_macro2::Token
 
 	let getters = get_getters(&tag_fields, &struct_name);
 
+	// The range of the audio data is optional, since it can't be known for every file
+	let (audio_data_range, audio_data_range_getter) = if audio_data_range_field.is_some() {
+		(
+			quote! { .with_audio_data_range(input.audio_data_range) },
+			quote! {
+				impl #struct_name {
+					/// Returns the range of the audio data in the stream
+					///
+					/// This excludes any tags or metadata that come before or after the audio data.
+					pub fn audio_data_range(&self) -> std::ops::Range<u64> {
+						self.audio_data_range.clone()
+					}
+				}
+			},
+		)
+	} else {
+		(
+			proc_macro2::TokenStream::new(),
+			proc_macro2::TokenStream::new(),
+		)
+	};
+
 	quote! {
 		#assert_properties_impl
 
@@ -186,12 +208,15 @@ fn parse(input: DeriveInput, errors: &mut Vec<syn::Error>) -> proc_macro2::Token
 						#( #conditions )*
 
 						tags
-					}
+					},
 				)
+				#audio_data_range
 			}
 		}
 
 		#( #getters )*
+
+		#audio_data_range_getter
 	}
 }
 
@@ -207,9 +232,14 @@ struct FieldContents {
 fn get_fields<'a>(
 	errors: &mut Vec<syn::Error>,
 	data: &'a DataStruct,
-) -> Option<(Vec<FieldContents>, Option<&'a syn::Field>)> {
+) -> Option<(
+	Vec<FieldContents>,
+	Option<&'a syn::Field>,
+	Option<&'a syn::Field>,
+)> {
 	let mut tag_fields = Vec::new();
 	let mut properties_field = None;
+	let mut audio_data_range_field = None;
 
 	for field in &data.fields {
 		let name = field.ident.clone().unwrap();
@@ -245,9 +275,13 @@ fn get_fields<'a>(
 		if name == "properties" {
 			properties_field = Some(field);
 		}
+
+		if name == "audio_data_range" {
+			audio_data_range_field = Some(field);
+		}
 	}
 
-	Some((tag_fields, properties_field))
+	Some((tag_fields, properties_field, audio_data_range_field))
 }
 
 fn opt_file_type(struct_name: String) -> Option<proc_macro2::TokenStream> {
@@ -0,0 +1,142 @@
+use std::io::Read;
+
+// Sample rates indexed by the sampling frequency index, the remaining indices are reserved
+pub(super) const SAMPLE_RATES: [u32; 13] = [
+	96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+
+// Each raw data block holds 1024 samples per channel
+pub(super) const SAMPLES_PER_BLOCK: u64 = 1024;
+
+// The header without the CRC
+pub(super) const HEADER_LEN: u16 = 7;
+
+pub(crate) fn verify_adts_sync(frame_sync: [u8; 2]) -> bool {
+	// 12 set bits, followed by the MPEG version and the layer, which is always 0
+	frame_sync[0] == 0xFF && frame_sync[1] & 0xF6 == 0xF0
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// An AAC profile (the MPEG-4 audio object type - 1)
+pub enum AacProfile {
+	/// AAC Main
+	Main,
+	/// AAC LC (Low Complexity)
+	LowComplexity,
+	/// AAC SSR (Scalable Sample Rate)
+	ScalableSampleRate,
+	/// AAC LTP (Long Term Prediction)
+	///
+	/// NOTE: This is reserved in MPEG-2 streams
+	LongTermPrediction,
+}
+
+impl Default for AacProfile {
+	fn default() -> Self {
+		Self::LowComplexity
+	}
+}
+
+// https://wiki.multimedia.cx/index.php/ADTS
+//
+// Sync word (12)
+// MPEG version (1), 0 for MPEG-4 and 1 for MPEG-2
+// Layer (2), always 0
+// Protection absent (1), 0 if the header is followed by a CRC
+// Profile (2)
+// Sampling frequency index (4)
+// Private bit (1)
+// Channel configuration (3)
+// Originality (1)
+// Home (1)
+// Copyright ID bit (1)
+// Copyright ID start (1)
+// Frame length (13), including the header
+// Buffer fullness (11)
+// Number of raw data blocks in the frame - 1 (2)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) struct ADTSHeader {
+	pub(super) profile: AacProfile,
+	pub(super) sample_rate: u32,
+	pub(super) channel_configuration: u8,
+	pub(super) frame_length: u16,
+	pub(super) blocks: u8,
+}
+
+impl ADTSHeader {
+	// Returns `None` if the header is invalid
+	pub(super) fn read<R>(reader: &mut R) -> std::io::Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let mut header = [0; HEADER_LEN as usize];
+		reader.read_exact(&mut header)?;
+
+		if !verify_adts_sync([header[0], header[1]]) {
+			return Ok(None);
+		}
+
+		let profile = match header[2] >> 6 {
+			0 => AacProfile::Main,
+			1 => AacProfile::LowComplexity,
+			2 => AacProfile::ScalableSampleRate,
+			_ => AacProfile::LongTermPrediction,
+		};
+
+		let sample_rate = match SAMPLE_RATES.get(usize::from((header[2] >> 2) & 0x0F)) {
+			Some(sample_rate) => *sample_rate,
+			None => return Ok(None),
+		};
+
+		let channel_configuration = ((header[2] & 0x01) << 2) | (header[3] >> 6);
+
+		let frame_length = (u16::from(header[3] & 0x03) << 11)
+			| (u16::from(header[4]) << 3)
+			| u16::from(header[5] >> 5);
+
+		let protection_absent = header[1] & 0x01 == 1;
+		let header_len = if protection_absent {
+			HEADER_LEN
+		} else {
+			HEADER_LEN + 2
+		};
+
+		if frame_length < header_len {
+			return Ok(None);
+		}
+
+		Ok(Some(Self {
+			profile,
+			sample_rate,
+			channel_configuration,
+			frame_length,
+			blocks: (header[6] & 0x03) + 1,
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{ADTSHeader, AacProfile};
+
+	#[test]
+	fn adts_header() {
+		// MPEG-4, no CRC, LC, 44.1kHz, 2 channels, a 16 byte frame with 1 raw data block
+		let header = [0xFF, 0xF1, 0x50, 0x80, 0x02, 0x1F, 0xFC];
+
+		assert_eq!(
+			ADTSHeader::read(&mut &header[..]).unwrap(),
+			Some(ADTSHeader {
+				profile: AacProfile::LowComplexity,
+				sample_rate: 44100,
+				channel_configuration: 2,
+				frame_length: 16,
+				blocks: 1,
+			})
+		);
+
+		// An MPEG frame sync, with layer 3
+		let header = [0xFF, 0xFB, 0x50, 0x80, 0x02, 0x1F, 0xFC];
+		assert_eq!(ADTSHeader::read(&mut &header[..]).unwrap(), None);
+	}
+}
@@ -0,0 +1,131 @@
+use super::header::{ADTSHeader, AacProfile, HEADER_LEN, SAMPLES_PER_BLOCK};
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+use crate::properties::FileProperties;
+
+use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+/// An AAC (ADTS) file's audio properties
+pub struct AacProperties {
+	pub(crate) profile: AacProfile,
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) channels: u8,
+	pub(crate) channel_configuration: u8,
+}
+
+impl From<AacProperties> for FileProperties {
+	fn from(input: AacProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: None,
+			channels: Some(input.channels),
+		}
+	}
+}
+
+impl AacProperties {
+	/// Duration
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Channel count
+	///
+	/// This will be 0 if the channels are defined in the stream, see [`AacProperties::channel_configuration`]
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+
+	/// AAC profile
+	pub fn profile(&self) -> AacProfile {
+		self.profile
+	}
+
+	/// The channel configuration from the ADTS header
+	///
+	/// A value of 0 means the channels are defined in a program config element in the stream.
+	pub fn channel_configuration(&self) -> u8 {
+		self.channel_configuration
+	}
+}
+
+// The properties are taken from the first frame, and the duration is determined by counting
+// the raw data blocks in every frame up to `stream_end`
+pub(super) fn read_properties<R>(
+	reader: &mut R,
+	first_header: ADTSHeader,
+	stream_end: u64,
+	file_length: u64,
+) -> Result<AacProperties>
+where
+	R: Read + Seek,
+{
+	let stream_start = reader.stream_position()?;
+
+	let mut properties = AacProperties {
+		profile: first_header.profile,
+		sample_rate: first_header.sample_rate,
+		channel_configuration: first_header.channel_configuration,
+		// Channel configuration 7 is 7.1
+		channels: match first_header.channel_configuration {
+			7 => 8,
+			configuration => configuration,
+		},
+		..AacProperties::default()
+	};
+
+	let mut blocks = 0_u64;
+	let mut pos = stream_start;
+
+	while pos + u64::from(HEADER_LEN) <= stream_end {
+		reader.seek(SeekFrom::Start(pos))?;
+
+		match ADTSHeader::read(reader)? {
+			Some(header) if pos + u64::from(header.frame_length) <= stream_end => {
+				blocks += u64::from(header.blocks);
+				pos += u64::from(header.frame_length);
+			},
+			_ => break,
+		}
+	}
+
+	if blocks == 0 {
+		return Err(FileDecodingError::new(FileType::AAC, "File contains no ADTS frames").into());
+	}
+
+	let length = blocks * SAMPLES_PER_BLOCK * 1000 / u64::from(properties.sample_rate);
+	properties.duration = Duration::from_millis(length);
+
+	if length > 0 {
+		let stream_length = pos - stream_start;
+
+		properties.overall_bitrate = ((file_length * 8) / length) as u32;
+		properties.audio_bitrate = ((stream_length * 8) / length) as u32;
+	}
+
+	Ok(properties)
+}
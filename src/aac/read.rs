@@ -0,0 +1,107 @@
+use super::header::ADTSHeader;
+use super::properties::AacProperties;
+use super::AacFile;
+use crate::ape::constants::APE_PREAMBLE;
+use crate::ape::header::read_ape_header;
+#[cfg(feature = "ape")]
+use crate::ape::tag::read::read_ape_tag;
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::read::parse_id3v2_with_options;
+use crate::id3::{find_id3v1, find_id3v2, find_lyrics3v2, ID3FindResults};
+use crate::probe::ParseOptions;
+
+use std::io::{Read, Seek, SeekFrom};
+
+pub(super) fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<AacFile>
+where
+	R: Read + Seek,
+{
+	read_from_with_options(reader, read_properties, &mut ParseOptions::new())
+}
+
+#[allow(unused_variables)]
+pub(crate) fn read_from_with_options<R>(
+	reader: &mut R,
+	read_properties: bool,
+	options: &mut ParseOptions<'_>,
+) -> Result<AacFile>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+	let file_length = reader.seek(SeekFrom::End(0))? - start;
+	reader.seek(SeekFrom::Start(start))?;
+
+	let mut file = AacFile::default();
+
+	#[allow(unused_variables)]
+	if let ID3FindResults(Some(header), Some(content)) = find_id3v2(reader, true)? {
+		#[cfg(feature = "id3v2")]
+		{
+			let reader = &mut &*content;
+
+			let id3v2 = parse_id3v2_with_options(reader, header, options)?;
+			file.id3v2_tag = Some(id3v2);
+		}
+	}
+
+	let stream_start = reader.stream_position()?;
+
+	let first_header = match ADTSHeader::read(reader) {
+		Ok(Some(header)) => header,
+		_ => {
+			return Err(FileDecodingError::new(FileType::AAC, "File missing an ADTS header").into())
+		},
+	};
+
+	#[allow(unused_variables)]
+	let ID3FindResults(id3v1_header, id3v1) = find_id3v1(reader, true)?;
+
+	if id3v1_header.is_some() {
+		#[cfg(feature = "id3v1")]
+		{
+			file.id3v1_tag = id3v1;
+		}
+	}
+
+	// Next, check for a Lyrics3v2 tag, and skip over it, as it's no use to us
+	find_lyrics3v2(reader)?;
+
+	// Next, search for an APE tag footer
+	//
+	// Starts with ['A', 'P', 'E', 'T', 'A', 'G', 'E', 'X']
+	// Exactly 32 bytes long
+	// Strongly recommended to be at the end of the file
+	let mut stream_end = reader.stream_position()?;
+
+	if stream_end >= stream_start + 32 {
+		reader.seek(SeekFrom::Current(-32))?;
+
+		let mut ape_preamble = [0; 8];
+		reader.read_exact(&mut ape_preamble)?;
+
+		if &ape_preamble == APE_PREAMBLE {
+			let ape_header = read_ape_header(reader, true)?;
+			stream_end = stream_end.saturating_sub(u64::from(ape_header.size));
+
+			#[cfg(feature = "ape")]
+			{
+				let ape = read_ape_tag(reader, ape_header)?;
+				file.ape_tag = Some(ape);
+			}
+		}
+	}
+
+	file.audio_data_range = stream_start.min(stream_end)..stream_end;
+
+	file.properties = if read_properties {
+		reader.seek(SeekFrom::Start(stream_start))?;
+		super::properties::read_properties(reader, first_header, stream_end, file_length)?
+	} else {
+		AacProperties::default()
+	};
+
+	Ok(file)
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "ape")]
+use crate::ape::tag::ApeTagVersion;
 use crate::error::{FileDecodingError, Result};
 use crate::file::FileType;
 use crate::traits::SeekStreamLen;
@@ -12,6 +14,8 @@ pub(crate) struct ApeHeader {
 	pub(crate) size: u32,
 	#[cfg(feature = "ape")]
 	pub(crate) item_count: u32,
+	#[cfg(feature = "ape")]
+	pub(crate) version: ApeTagVersion,
 }
 
 pub(crate) fn read_ape_header<R>(data: &mut R, footer: bool) -> Result<ApeHeader>
@@ -64,5 +68,11 @@ where
 		size,
 		#[cfg(feature = "ape")]
 		item_count,
+		#[cfg(feature = "ape")]
+		version: if version == 1000 {
+			ApeTagVersion::V1
+		} else {
+			ApeTagVersion::V2
+		},
 	})
 }
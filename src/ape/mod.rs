@@ -8,7 +8,7 @@
 pub(crate) mod constants;
 pub(crate) mod header;
 mod properties;
-mod read;
+pub(crate) mod read;
 pub(crate) mod write;
 
 #[cfg(feature = "id3v1")]
@@ -16,6 +16,8 @@ use crate::id3::v1::tag::ID3v1Tag;
 #[cfg(feature = "id3v2")]
 use crate::id3::v2::tag::ID3v2Tag;
 
+use std::ops::Range;
+
 use lofty_attr::LoftyFile;
 
 // Exports
@@ -23,7 +25,7 @@ use lofty_attr::LoftyFile;
 cfg_if::cfg_if! {
 	if #[cfg(feature = "ape")] {
 		pub(crate) mod tag;
-		pub use tag::ApeTag;
+		pub use tag::{ApeTag, ApeTagVersion};
 		pub use tag::item::ApeItem;
 
 		pub use crate::picture::APE_PICTURE_TYPES;
@@ -50,4 +52,6 @@ pub struct ApeFile {
 	pub(crate) ape_tag: Option<ApeTag>,
 	/// The file's audio properties
 	pub(crate) properties: ApeProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
 }
@@ -19,6 +19,8 @@ pub struct ApeProperties {
 	pub(crate) sample_rate: u32,
 	pub(crate) bit_depth: u8,
 	pub(crate) channels: u8,
+	pub(crate) compression_level: u16,
+	pub(crate) total_frames: u32,
 }
 
 impl From<ApeProperties> for FileProperties {
@@ -69,6 +71,24 @@ impl ApeProperties {
 	pub fn version(&self) -> u16 {
 		self.version
 	}
+
+	/// Compression level
+	///
+	/// The standard levels are:
+	///
+	/// * 1000: Fast
+	/// * 2000: Normal
+	/// * 3000: High
+	/// * 4000: Extra High
+	/// * 5000: Insane
+	pub fn compression_level(&self) -> u16 {
+		self.compression_level
+	}
+
+	/// Total number of frames
+	pub fn total_frames(&self) -> u32 {
+		self.total_frames
+	}
 }
 
 pub(super) fn read_properties<R>(
@@ -129,8 +149,10 @@ where
 		)
 	})?;
 
+	let compression_level = u16::from_le_bytes([header[0], header[1]]);
+
 	// Skip the first 4 bytes of the header
-	// Compression type (2)
+	// Compression level (2)
 	// Format flags (2)
 	let header_read = &mut &header[4..];
 
@@ -173,6 +195,8 @@ where
 		sample_rate,
 		bit_depth: bits_per_sample as u8,
 		channels: channels as u8,
+		compression_level,
+		total_frames,
 	})
 }
 
@@ -195,7 +219,7 @@ where
 	})?;
 
 	// We don't need all the header data, so just make 2 slices
-	let header_first = &mut &header[..8];
+	let header_first = &mut &header[..10];
 
 	// Skipping 8 bytes
 	// WAV header length (4)
@@ -239,7 +263,7 @@ where
 		return Err(FileDecodingError::new(FileType::APE, "File contains no frames").into());
 	}
 
-	let final_frame_blocks = data.read_u32::<LittleEndian>()?;
+	let final_frame_blocks = header_second.read_u32::<LittleEndian>()?;
 
 	let (duration, overall_bitrate, audio_bitrate) = get_duration_bitrate(
 		file_length,
@@ -258,6 +282,8 @@ where
 		sample_rate,
 		bit_depth,
 		channels: channels as u8,
+		compression_level,
+		total_frames,
 	})
 }
 
@@ -271,7 +297,7 @@ fn get_duration_bitrate(
 ) -> (Duration, u32, u32) {
 	let mut total_samples = u64::from(final_frame_blocks);
 
-	if total_samples > 1 {
+	if total_frames > 1 {
 		total_samples += u64::from(blocks_per_frame) * u64::from(total_frames - 1)
 	}
 
@@ -295,3 +321,45 @@ fn get_duration_bitrate(
 		(Duration::ZERO, overall_bitrate, audio_bitrate)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::read_properties;
+
+	use std::io::Cursor;
+	use std::time::Duration;
+
+	#[test]
+	fn properties_lt_3980() {
+		let mut header = Vec::new();
+		header.extend(3970_u16.to_le_bytes());
+		// Compression level
+		header.extend(2000_u16.to_le_bytes());
+		// Format flags
+		header.extend(0_u16.to_le_bytes());
+		// Channels
+		header.extend(2_u16.to_le_bytes());
+		// Sample rate
+		header.extend(44100_u32.to_le_bytes());
+		// WAV header and tail lengths
+		header.extend(44_u32.to_le_bytes());
+		header.extend(0_u32.to_le_bytes());
+		// Total frames
+		header.extend(2_u32.to_le_bytes());
+		// Final frame blocks
+		header.extend(1000_u32.to_le_bytes());
+
+		let properties = read_properties(&mut Cursor::new(header), 100_000, 100_000).unwrap();
+
+		assert_eq!(properties.version(), 3970);
+		assert_eq!(properties.compression_level(), 2000);
+		assert_eq!(properties.total_frames(), 2);
+		assert_eq!(properties.channels(), 2);
+		assert_eq!(properties.bit_depth(), 16);
+		assert_eq!(properties.sample_rate(), 44100);
+
+		// One full frame of 294,912 blocks, and a final frame of 1000 blocks
+		assert_eq!(properties.duration(), Duration::from_millis(6710));
+		assert_eq!(properties.bitrate(), 120);
+	}
+}
@@ -8,12 +8,25 @@ use crate::file::FileType;
 #[cfg(feature = "id3v1")]
 use crate::id3::v1::tag::ID3v1Tag;
 #[cfg(feature = "id3v2")]
-use crate::id3::v2::{read::parse_id3v2, tag::ID3v2Tag};
+use crate::id3::v2::{read::parse_id3v2_with_options, tag::ID3v2Tag};
 use crate::id3::{find_id3v1, find_id3v2, find_lyrics3v2, ID3FindResults};
+use crate::probe::ParseOptions;
 
 use std::io::{Read, Seek, SeekFrom};
 
 pub(crate) fn read_from<R>(data: &mut R, read_properties: bool) -> Result<ApeFile>
+where
+	R: Read + Seek,
+{
+	read_from_with_options(data, read_properties, &mut ParseOptions::new())
+}
+
+#[allow(unused_variables)]
+pub(crate) fn read_from_with_options<R>(
+	data: &mut R,
+	read_properties: bool,
+	options: &mut ParseOptions<'_>,
+) -> Result<ApeFile>
 where
 	R: Read + Seek,
 {
@@ -45,7 +58,7 @@ where
 		{
 			let reader = &mut &*content;
 
-			let id3v2 = parse_id3v2(reader, header)?;
+			let id3v2 = parse_id3v2_with_options(reader, header, options)?;
 			id3v2_tag = Some(id3v2)
 		}
 	}
@@ -131,6 +144,7 @@ where
 	// Starts with ['A', 'P', 'E', 'T', 'A', 'G', 'E', 'X']
 	// Exactly 32 bytes long
 	// Strongly recommended to be at the end of the file
+	let mut audio_end = data.stream_position()?;
 	data.seek(SeekFrom::Current(-32))?;
 
 	let mut ape_preamble = [0; 8];
@@ -139,6 +153,7 @@ where
 	if &ape_preamble == APE_PREAMBLE {
 		let ape_header = read_ape_header(data, true)?;
 		stream_len -= u64::from(ape_header.size);
+		audio_end = audio_end.saturating_sub(u64::from(ape_header.size));
 
 		#[cfg(feature = "ape")]
 		{
@@ -167,5 +182,7 @@ where
 		} else {
 			ApeProperties::default()
 		},
+		// The stream starts at the "MAC " signature
+		audio_data_range: (mac_start - 4).min(audio_end)..audio_end,
 	})
 }
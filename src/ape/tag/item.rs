@@ -27,28 +27,8 @@ impl ApeItem {
 	/// * `key` has a bad length (must be 2 to 255, inclusive)
 	/// * `key` contains invalid characters (must be in the range 0x20 to 0x7E, inclusive)
 	pub fn new(key: String, value: ItemValue) -> Result<Self> {
-		if INVALID_KEYS.contains(&&*key.to_uppercase()) {
-			return Err(FileDecodingError::new(
-				FileType::APE,
-				"APE tag item contains an illegal key",
-			)
-			.into());
-		}
-
-		if !(2..=255).contains(&key.len()) {
-			return Err(FileDecodingError::new(
-				FileType::APE,
-				"APE tag item key has an invalid length (< 2 || > 255)",
-			)
-			.into());
-		}
-
-		if key.chars().any(|c| !(0x20..=0x7E).contains(&(c as u32))) {
-			return Err(FileDecodingError::new(
-				FileType::APE,
-				"APE tag item key contains invalid characters",
-			)
-			.into());
+		if let Err(message) = verify_key(&key) {
+			return Err(FileDecodingError::new(FileType::APE, message).into());
 		}
 
 		Ok(Self {
@@ -58,11 +38,28 @@ impl ApeItem {
 		})
 	}
 
+	// Used when reading, where invalid keys are kept so they can be inspected
+	pub(crate) fn new_unchecked(key: String, value: ItemValue) -> Self {
+		Self {
+			read_only: false,
+			key,
+			value,
+		}
+	}
+
 	/// Returns the item key
 	pub fn key(&self) -> &str {
 		&self.key
 	}
 
+	/// Whether the item key is valid
+	///
+	/// Items created with [`ApeItem::new`] always have valid keys, but items read from a file may not.
+	/// A tag containing an item with an invalid key can't be written. See [`ApeItem::new`] for the restrictions.
+	pub fn has_valid_key(&self) -> bool {
+		verify_key(&self.key).is_ok()
+	}
+
 	/// Returns the item value
 	pub fn value(&self) -> &ItemValue {
 		&self.value
@@ -78,6 +75,22 @@ impl ApeItem {
 	}
 }
 
+pub(crate) fn verify_key(key: &str) -> std::result::Result<(), &'static str> {
+	if INVALID_KEYS.contains(&&*key.to_uppercase()) {
+		return Err("APE tag item contains an illegal key");
+	}
+
+	if !(2..=255).contains(&key.len()) {
+		return Err("APE tag item key has an invalid length (< 2 || > 255)");
+	}
+
+	if key.chars().any(|c| !(0x20..=0x7E).contains(&(c as u32))) {
+		return Err("APE tag item key contains invalid characters");
+	}
+
+	Ok(())
+}
+
 impl TryFrom<TagItem> for ApeItem {
 	type Error = LoftyError;
 
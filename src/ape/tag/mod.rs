@@ -2,12 +2,15 @@ pub(crate) mod item;
 pub(crate) mod read;
 mod write;
 
-use crate::ape::tag::item::{ApeItem, ApeItemRef};
+use crate::ape::tag::item::{verify_key, ApeItem, ApeItemRef};
 use crate::error::{LoftyError, Result};
+use crate::tag::item::ItemValueRef;
 use crate::tag::item::{ItemKey, ItemValue, TagItem};
+use crate::tag::utils::{is_num_pair_key, join_num_pair, num_pair, split_num_pair};
 use crate::tag::{Tag, TagType};
 use crate::traits::{Accessor, TagExt};
 
+use std::borrow::Cow;
 use std::convert::TryInto;
 use std::fs::{File, OpenOptions};
 use std::io::Write;
@@ -52,8 +55,11 @@ macro_rules! impl_accessor {
 ///
 /// ## Supported file types
 ///
+/// * [`FileType::AAC`](crate::FileType::AAC)
 /// * [`FileType::APE`](crate::FileType::APE)
 /// * [`FileType::MPEG`](crate::FileType::MPEG)
+/// * [`FileType::Musepack`](crate::FileType::Musepack)
+/// * [`FileType::TTA`](crate::FileType::TTA)
 /// * [`FileType::WavPack`](crate::FileType::WavPack)
 ///
 /// ## Item storage
@@ -74,9 +80,34 @@ macro_rules! impl_accessor {
 pub struct ApeTag {
 	/// Whether or not to mark the tag as read only
 	pub read_only: bool,
+	/// The version to write the tag as
+	///
+	/// When reading a tag, this is the version of the tag in the file.
+	pub version: ApeTagVersion,
 	pub(super) items: Vec<ApeItem>,
 }
 
+/// The version of an `APE` tag
+///
+/// See [`ApeTag::version`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApeTagVersion {
+	/// APEv1 (`1000`)
+	///
+	/// This is only useful for compatibility with old software. An APEv1 tag has no header, and
+	/// can only hold text items. Since there are no flags, [`ApeTag::read_only`] and
+	/// [`ApeItem::read_only`] are ignored.
+	V1,
+	/// APEv2 (`2000`)
+	V2,
+}
+
+impl Default for ApeTagVersion {
+	fn default() -> Self {
+		Self::V2
+	}
+}
+
 impl ApeTag {
 	/// Get an [`ApeItem`] by key
 	///
@@ -108,27 +139,66 @@ impl ApeTag {
 		&self.items
 	}
 
+	/// Returns the size of the tag once written, including the header (if [`ApeTag::version`] has one) and footer
+	///
+	/// This can be used to check if the tag will fit before writing it. A tag larger than
+	/// 4 GiB can't be written, causing [`ErrorKind::TagTooLarge`](crate::error::ErrorKind::TagTooLarge).
+	///
+	/// NOTE: An empty tag will not be written, and has a size of 0.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ape::{ApeItem, ApeTag};
+	/// use lofty::ItemValue;
+	///
+	/// let mut tag = ApeTag::default();
+	/// assert_eq!(tag.estimated_size(), 0);
+	///
+	/// tag.insert(ApeItem::new(String::from("Title"), ItemValue::Text(String::from("Foo")))?);
+	///
+	/// // A 32 byte header and footer, with a single 17 byte item
+	/// assert_eq!(tag.estimated_size(), 81);
+	/// # Ok::<(), lofty::LoftyError>(())
+	/// ```
+	pub fn estimated_size(&self) -> u64 {
+		write::tag_size(self.items.iter().map(Into::into), self.version)
+	}
+
 	fn split_num_pair(&self, key: &str) -> (Option<u32>, Option<u32>) {
 		if let Some(ApeItem {
 			value: ItemValue::Text(ref text),
 			..
 		}) = self.get_key(key)
 		{
-			let mut split = text.split('/').flat_map(str::parse::<u32>);
-			return (split.next(), split.next());
+			return split_num_pair(text);
 		}
 
 		(None, None)
 	}
+
+	fn set_num_pair(&mut self, key: &str, current: Option<u32>, total: Option<u32>) {
+		match num_pair(current, total) {
+			Some(value) => self.insert(ApeItem::text(key, value)),
+			None => self.remove_key(key),
+		}
+	}
 }
 
 impl Accessor for ApeTag {
 	impl_accessor!(
-		artist  => "Artist";
-		title   => "Title";
-		album   => "Album";
-		genre   => "GENRE";
-		comment => "Comment";
+		artist       => "Artist";
+		title        => "Title";
+		album        => "Album";
+		genre        => "GENRE";
+		comment      => "Comment";
+		album_artist => "Album Artist";
+		composer     => "Composer";
+		initial_key  => "InitialKey";
+		title_sort => "TITLESORT";
+		artist_sort => "ARTISTSORT";
+		album_sort => "ALBUMSORT";
+		album_artist_sort => "ALBUMARTISTSORT";
 	);
 
 	fn track(&self) -> Option<u32> {
@@ -136,11 +206,11 @@ impl Accessor for ApeTag {
 	}
 
 	fn set_track(&mut self, value: u32) {
-		self.insert(ApeItem::text("Track", value.to_string()))
+		self.set_num_pair("Track", Some(value), self.track_total());
 	}
 
 	fn remove_track(&mut self) {
-		self.remove_key("Track");
+		self.set_num_pair("Track", None, self.track_total());
 	}
 
 	fn track_total(&self) -> Option<u32> {
@@ -148,21 +218,11 @@ impl Accessor for ApeTag {
 	}
 
 	fn set_track_total(&mut self, value: u32) {
-		let current_track = self.split_num_pair("Track").0.unwrap_or(1);
-
-		self.insert(ApeItem::text(
-			"Track",
-			format!("{}/{}", current_track, value),
-		));
+		self.set_num_pair("Track", self.track(), Some(value));
 	}
 
 	fn remove_track_total(&mut self) {
-		let existing_track_number = self.track();
-		self.remove_key("Track");
-
-		if let Some(track) = existing_track_number {
-			self.insert(ApeItem::text("Track", track.to_string()));
-		}
+		self.set_num_pair("Track", self.track(), None);
 	}
 
 	fn disk(&self) -> Option<u32> {
@@ -170,11 +230,11 @@ impl Accessor for ApeTag {
 	}
 
 	fn set_disk(&mut self, value: u32) {
-		self.insert(ApeItem::text("Disc", value.to_string()));
+		self.set_num_pair("Disc", Some(value), self.disk_total());
 	}
 
 	fn remove_disk(&mut self) {
-		self.remove_key("Disc");
+		self.set_num_pair("Disc", None, self.disk_total());
 	}
 
 	fn disk_total(&self) -> Option<u32> {
@@ -182,18 +242,31 @@ impl Accessor for ApeTag {
 	}
 
 	fn set_disk_total(&mut self, value: u32) {
-		let current_disk = self.split_num_pair("Disc").0.unwrap_or(1);
-
-		self.insert(ApeItem::text("Disc", format!("{}/{}", current_disk, value)));
+		self.set_num_pair("Disc", self.disk(), Some(value));
 	}
 
 	fn remove_disk_total(&mut self) {
-		let existing_track_number = self.track();
-		self.remove_key("Disc");
+		self.set_num_pair("Disc", self.disk(), None);
+	}
 
-		if let Some(track) = existing_track_number {
-			self.insert(ApeItem::text("Disc", track.to_string()));
+	fn bpm(&self) -> Option<u16> {
+		if let Some(ApeItem {
+			value: ItemValue::Text(ref text),
+			..
+		}) = self.get_key("BPM")
+		{
+			return crate::tag::utils::parse_bpm(text);
 		}
+
+		None
+	}
+
+	fn set_bpm(&mut self, value: u16) {
+		self.insert(ApeItem::text("BPM", value.to_string()));
+	}
+
+	fn remove_bpm(&mut self) {
+		self.remove_key("BPM");
 	}
 
 	fn year(&self) -> Option<u32> {
@@ -230,7 +303,7 @@ impl TagExt for ApeTag {
 	///
 	/// * `path` does not exist
 	/// * See [`ApeTag::save_to`]
-	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err> {
 		self.save_to(&mut OpenOptions::new().read(true).write(true).open(path)?)
 	}
 
@@ -238,11 +311,14 @@ impl TagExt for ApeTag {
 	///
 	/// # Errors
 	///
-	/// * Attempting to write the tag to a format that does not support it
+	/// * Attempting to write the tag to a format that does not support it ([`ErrorKind::UnsupportedTag`](crate::error::ErrorKind::UnsupportedTag))
+	/// * The file's format could not be determined ([`ErrorKind::UnknownFormat`](crate::error::ErrorKind::UnknownFormat))
 	/// * An existing tag has an invalid size
-	fn save_to(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+	/// * [`ApeTag::version`] is [`ApeTagVersion::V1`], and the tag has a non-text item
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err> {
 		ApeTagRef {
 			read_only: self.read_only,
+			version: self.version,
 			items: self.items.iter().map(Into::into),
 		}
 		.write_to(file)
@@ -253,9 +329,11 @@ impl TagExt for ApeTag {
 	/// # Errors
 	///
 	/// * [`std::io::Error`]
+	/// * [`ApeTag::version`] is [`ApeTagVersion::V1`], and the tag has a non-text item
 	fn dump_to<W: Write>(&self, writer: &mut W) -> std::result::Result<(), Self::Err> {
 		ApeTagRef {
 			read_only: self.read_only,
+			version: self.version,
 			items: self.items.iter().map(Into::into),
 		}
 		.dump_to(writer)
@@ -284,8 +362,12 @@ impl From<ApeTag> for Tag {
 		) -> Option<()> {
 			let mut split = content.splitn(2, '/');
 			let current = split.next()?.to_string();
-			tag.items
-				.push(TagItem::new(current_key, ItemValue::Text(current)));
+
+			// A missing number is stored as 0 when there is a total
+			if !current.is_empty() && current != "0" {
+				tag.items
+					.push(TagItem::new(current_key, ItemValue::Text(current)));
+			}
 
 			if let Some(total) = split.next() {
 				tag.items
@@ -323,9 +405,22 @@ impl From<ApeTag> for Tag {
 }
 
 impl From<Tag> for ApeTag {
-	fn from(input: Tag) -> Self {
+	fn from(mut input: Tag) -> Self {
 		let mut ape_tag = Self::default();
 
+		// Track and disc numbers are stored together as "current/total"
+		for (key, current_key, total_key) in [
+			("Track", ItemKey::TrackNumber, ItemKey::TrackTotal),
+			("Disc", ItemKey::DiscNumber, ItemKey::DiscTotal),
+		] {
+			if let Some(value) = join_num_pair(&input, &current_key, &total_key) {
+				ape_tag.insert(ApeItem::text(key, value));
+			}
+
+			input.remove_key(&current_key);
+			input.remove_key(&total_key);
+		}
+
 		for item in input.items {
 			if let Ok(ape_item) = item.try_into() {
 				ape_tag.insert(ape_item)
@@ -351,6 +446,7 @@ where
 	I: Iterator<Item = ApeItemRef<'a>>,
 {
 	pub(crate) read_only: bool,
+	pub(crate) version: ApeTagVersion,
 	pub(crate) items: I,
 }
 
@@ -358,7 +454,7 @@ impl<'a, I> ApeTagRef<'a, I>
 where
 	I: Iterator<Item = ApeItemRef<'a>>,
 {
-	pub(crate) fn write_to(&mut self, file: &mut File) -> Result<()> {
+	pub(crate) fn write_to(&mut self, file: &mut File) -> Result<bool> {
 		write::write_to(file, self)
 	}
 
@@ -370,20 +466,50 @@ where
 	}
 }
 
-pub(crate) fn tagitems_into_ape(items: &[TagItem]) -> impl Iterator<Item = ApeItemRef<'_>> {
-	items.iter().filter_map(|i| {
-		i.key().map_key(TagType::APE, true).map(|key| ApeItemRef {
+pub(crate) fn tagitems_into_ape(tag: &Tag) -> impl Iterator<Item = ApeItemRef<'_>> {
+	// Track and disc numbers are stored together as "current/total"
+	let num_pairs = [
+		(
+			"Track",
+			join_num_pair(tag, &ItemKey::TrackNumber, &ItemKey::TrackTotal),
+		),
+		(
+			"Disc",
+			join_num_pair(tag, &ItemKey::DiscNumber, &ItemKey::DiscTotal),
+		),
+	];
+
+	let items = tag
+		.items()
+		.iter()
+		.filter(|i| !is_num_pair_key(i.key()))
+		.filter_map(|i| {
+			// Unknown keys that can't be written to an APE tag are skipped
+			let key = i
+				.key()
+				.map_key(TagType::APE, true)
+				.filter(|key| verify_key(key).is_ok());
+
+			key.map(|key| ApeItemRef {
+				read_only: false,
+				key,
+				value: (&i.item_value).into(),
+			})
+		});
+
+	items.chain(num_pairs.into_iter().filter_map(|(key, value)| {
+		value.map(|value| ApeItemRef {
 			read_only: false,
 			key,
-			value: (&i.item_value).into(),
+			value: ItemValueRef::Text(Cow::Owned(value)),
 		})
-	})
+	}))
 }
 
 #[cfg(test)]
 mod tests {
 	use crate::ape::header::read_ape_header;
-	use crate::ape::{ApeItem, ApeTag};
+	use crate::ape::{ApeItem, ApeTag, ApeTagVersion};
 	use crate::{ItemValue, Tag, TagExt, TagType};
 
 	use std::io::{Cursor, Seek, SeekFrom};
@@ -514,4 +640,112 @@ mod tests {
 		verify_key(&ape_tag, "Track", "1");
 		verify_key(&ape_tag, "Genre", "Classical");
 	}
+
+	#[test]
+	fn ape_invalid_keys() {
+		for key in ["ID3", "oggs", "A", "Fo\u{e9}"] {
+			assert!(ApeItem::new(String::from(key), ItemValue::Text(String::new())).is_err());
+		}
+
+		let mut tag = ApeTag::default();
+		tag.insert(
+			ApeItem::new(String::from("Taf"), ItemValue::Text(String::from("Foo"))).unwrap(),
+		);
+
+		let mut tag_bytes = Vec::new();
+		tag.dump_to(&mut tag_bytes).unwrap();
+
+		// Turn the key into the reserved "Tag"
+		let key_pos = tag_bytes.windows(4).position(|w| w == b"Taf\0").unwrap();
+		tag_bytes[key_pos + 2] = b'g';
+
+		let mut reader = Cursor::new(tag_bytes);
+		reader.seek(SeekFrom::Current(8)).unwrap();
+
+		let header = read_ape_header(&mut reader, false).unwrap();
+		let parsed_tag = crate::ape::tag::read::read_ape_tag(&mut reader, header).unwrap();
+
+		// The item is kept, but it can't be written back
+		let item = parsed_tag.get_key("Tag").unwrap();
+		assert!(!item.has_valid_key());
+		assert!(parsed_tag.dump_to(&mut Vec::new()).is_err());
+	}
+
+	#[test]
+	fn estimated_size() {
+		let mut tag = ApeTag::default();
+		tag.insert(
+			ApeItem::new(String::from("Title"), ItemValue::Text(String::from("Foo"))).unwrap(),
+		);
+		tag.insert(
+			ApeItem::new(
+				String::from("Cover Art (Front)"),
+				ItemValue::Binary(vec![0; 100]),
+			)
+			.unwrap(),
+		);
+		tag.insert(
+			ApeItem::new(
+				String::from("Web"),
+				ItemValue::Locator(String::from("https://example.com")),
+			)
+			.unwrap(),
+		);
+
+		let mut tag_bytes = Vec::new();
+		tag.dump_to(&mut tag_bytes).unwrap();
+
+		assert_eq!(tag.estimated_size(), tag_bytes.len() as u64);
+	}
+
+	#[test]
+	fn write_apev1() {
+		let mut tag = ApeTag {
+			read_only: true,
+			version: ApeTagVersion::V1,
+			..ApeTag::default()
+		};
+		tag.insert(
+			ApeItem::new(String::from("Title"), ItemValue::Text(String::from("Foo"))).unwrap(),
+		);
+
+		let mut tag_bytes = Vec::new();
+		tag.dump_to(&mut tag_bytes).unwrap();
+
+		// Only a footer, with no flags
+		assert_eq!(tag.estimated_size(), tag_bytes.len() as u64);
+		assert_eq!(tag_bytes.len(), 17 + 32);
+
+		let footer = &tag_bytes[17..];
+		assert_eq!(&footer[..8], b"APETAGEX");
+		assert_eq!(&footer[8..12], &1000_u32.to_le_bytes());
+		assert_eq!(&footer[20..], &[0; 12]);
+
+		let mut reader = Cursor::new(&tag_bytes);
+		reader.seek(SeekFrom::Start(17 + 8)).unwrap();
+
+		let header = read_ape_header(&mut reader, true).unwrap();
+		let parsed_tag = crate::ape::tag::read::read_ape_tag(&mut reader, header).unwrap();
+
+		let item = parsed_tag.get_key("Title").unwrap();
+		assert_eq!(item.value(), &ItemValue::Text(String::from("Foo")));
+		assert!(!item.read_only);
+
+		// The version is kept, so the tag is written back as APEv1
+		assert_eq!(parsed_tag.version, ApeTagVersion::V1);
+
+		let mut rewritten_bytes = Vec::new();
+		parsed_tag.clone().dump_to(&mut rewritten_bytes).unwrap();
+		assert_eq!(rewritten_bytes, tag_bytes);
+
+		// APEv1 can only hold text
+		tag.insert(
+			ApeItem::new(
+				String::from("Cover Art (Front)"),
+				ItemValue::Binary(vec![0; 4]),
+			)
+			.unwrap(),
+		);
+		assert!(tag.dump_to(&mut Vec::new()).is_err());
+	}
 }
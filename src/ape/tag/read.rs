@@ -1,6 +1,5 @@
 use super::item::ApeItem;
 use super::ApeTag;
-use crate::ape::constants::INVALID_KEYS;
 use crate::ape::header::ApeHeader;
 use crate::error::{FileDecodingError, Result};
 use crate::file::FileType;
@@ -15,7 +14,10 @@ pub(crate) fn read_ape_tag<R>(data: &mut R, header: ApeHeader) -> Result<ApeTag>
 where
 	R: Read + Seek,
 {
-	let mut tag = ApeTag::default();
+	let mut tag = ApeTag {
+		version: header.version,
+		..ApeTag::default()
+	};
 	let mut remaining_size = header.size;
 
 	for _ in 0..header.item_count {
@@ -43,19 +45,11 @@ where
 			FileDecodingError::new(FileType::APE, "APE tag item contains a non UTF-8 key")
 		})?;
 
-		if INVALID_KEYS.contains(&&*key.to_uppercase()) {
-			return Err(FileDecodingError::new(
-				FileType::APE,
-				"APE tag item contains an illegal key",
-			)
-			.into());
-		}
-
 		let read_only = (flags & 1) == 1;
 		let item_type = (flags >> 1) & 3;
 
 		// TODO: This could use a warning
-		if value_size == 0 || key.len() < 2 || key.len() > 255 {
+		if value_size == 0 {
 			continue;
 		}
 
@@ -85,7 +79,8 @@ where
 			},
 		};
 
-		let mut item = ApeItem::new(key, parsed_value)?;
+		// Items with invalid keys are kept, but can be identified with `ApeItem::has_valid_key`
+		let mut item = ApeItem::new_unchecked(key, parsed_value);
 
 		if read_only {
 			item.read_only = true;
@@ -1,12 +1,12 @@
-use super::item::ApeItemRef;
+use super::item::{verify_key, ApeItem, ApeItemRef};
 use super::read::read_ape_tag;
-use super::ApeTagRef;
+use super::{ApeTagRef, ApeTagVersion};
 use crate::ape::constants::APE_PREAMBLE;
 use crate::ape::header::read_ape_header;
-use crate::error::{FileDecodingError, Result};
+use crate::error::{ErrorKind, FileDecodingError, FileEncodingError, LoftyError, Result};
 use crate::file::FileType;
 use crate::id3::{find_id3v1, find_id3v2, find_lyrics3v2};
-use crate::macros::err;
+use crate::macros::{err, try_vec};
 use crate::probe::Probe;
 use crate::tag::item::ItemValueRef;
 
@@ -15,16 +15,28 @@ use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
 use byteorder::{LittleEndian, WriteBytesExt};
 
+// The header and footer are both 32 bytes
+const HEADER_SIZE: u64 = 32;
+const FOOTER_SIZE: u64 = 32;
+
 #[allow(clippy::shadow_unrelated)]
-pub(crate) fn write_to<'a, I>(data: &mut File, tag: &mut ApeTagRef<'a, I>) -> Result<()>
+pub(crate) fn write_to<'a, I>(data: &mut File, tag: &mut ApeTagRef<'a, I>) -> Result<bool>
 where
 	I: Iterator<Item = ApeItemRef<'a>>,
 {
 	let probe = Probe::new(data).guess_file_type()?;
 
 	match probe.file_type() {
-		Some(FileType::APE | FileType::MPEG | FileType::WavPack) => {},
-		_ => err!(UnsupportedTag),
+		Some(
+			FileType::AAC
+			| FileType::APE
+			| FileType::MPEG
+			| FileType::Musepack
+			| FileType::TTA
+			| FileType::WavPack,
+		) => {},
+		Some(_) => err!(UnsupportedTag),
+		None => err!(UnknownFormat),
 	}
 
 	let data = probe.into_inner();
@@ -36,7 +48,7 @@ where
 	data.read_exact(&mut ape_preamble)?;
 
 	// We have to check the APE tag for any read only items first
-	let mut read_only = None;
+	let mut read_only = Vec::new();
 
 	// An APE tag in the beginning of a file is against the spec
 	// If one is found, it'll be removed and rewritten at the bottom, where it should be
@@ -50,14 +62,10 @@ where
 		let header = read_ape_header(data, false)?;
 		let size = header.size;
 
-		let mut existing = read_ape_tag(data, header)?;
+		let existing = read_ape_tag(data, header)?;
 
 		// Only keep metadata around that's marked read only
-		existing.items.retain(|i| i.read_only);
-
-		if !existing.items.is_empty() {
-			read_only = Some(existing)
-		}
+		keep_read_only(&mut read_only, existing.items);
 
 		header_ape_tag = (true, (start, start + u64::from(size)))
 	} else {
@@ -85,13 +93,9 @@ where
 		let header = read_ape_header(data, true)?;
 		let size = header.size;
 
-		let mut existing = read_ape_tag(data, header)?;
-
-		existing.items.retain(|i| i.read_only);
+		let existing = read_ape_tag(data, header)?;
 
-		if !existing.items.is_empty() {
-			read_only = Some(existing)
-		}
+		keep_read_only(&mut read_only, existing.items);
 
 		// Since the "start" was really at the end of the tag, this sanity check seems necessary
 		if let Some(start) = start.checked_sub(size as usize) {
@@ -106,37 +110,129 @@ where
 	}
 
 	// Preserve any metadata marked as read only
-	let tag = if let Some(read_only) = read_only {
+	let tag = if read_only.is_empty() {
+		create_ape_tag(tag)?
+	} else {
+		let mut items: Vec<ApeItemRef<'_>> = read_only.iter().map(Into::into).collect();
+
+		// The new items replace the existing ones, unless they're read only
+		for item in tag.items.by_ref() {
+			if !read_only
+				.iter()
+				.any(|existing| existing.key().eq_ignore_ascii_case(item.key))
+			{
+				items.push(item);
+			}
+		}
+
 		create_ape_tag(&mut ApeTagRef {
-			read_only: read_only.read_only,
-			items: read_only.items.iter().map(Into::into),
+			read_only: tag.read_only,
+			version: tag.version,
+			items: items.into_iter(),
 		})?
-	} else {
-		create_ape_tag(tag)?
 	};
 
-	data.rewind()?;
+	let tag_range = ape_tag_location.unwrap_or(ape_position as usize..ape_position as usize);
 
-	let mut file_bytes = Vec::new();
-	data.read_to_end(&mut file_bytes)?;
+	// A tag at the beginning of the file has to be removed, so the entire file needs to be rewritten
+	if header_ape_tag.0 {
+		data.rewind()?;
 
-	// Write the tag in the appropriate place
-	if let Some(range) = ape_tag_location {
-		file_bytes.splice(range, tag);
-	} else {
-		file_bytes.splice(ape_position as usize..ape_position as usize, tag);
-	}
+		let mut file_bytes = Vec::new();
+		data.read_to_end(&mut file_bytes)?;
 
-	// Now, if there was a tag at the beginning, remove it
-	if header_ape_tag.0 {
+		file_bytes.splice(tag_range, tag);
 		file_bytes.drain(header_ape_tag.1 .0 as usize..header_ape_tag.1 .1 as usize);
+
+		data.rewind()?;
+		data.set_len(0)?;
+		data.write_all(&file_bytes)?;
+
+		return Ok(true);
+	}
+
+	// Nothing to do if the tag is identical to the existing one
+	if tag.len() == tag_range.len() {
+		let mut existing = try_vec![0; tag_range.len()];
+
+		data.seek(SeekFrom::Start(tag_range.start as u64))?;
+		data.read_exact(&mut existing)?;
+
+		if existing == tag {
+			return Ok(false);
+		}
 	}
 
-	data.rewind()?;
-	data.set_len(0)?;
-	data.write_all(&file_bytes)?;
+	// Otherwise, the tag is at the end of the file.
+	// The only thing that can follow it is an ID3v1 and/or Lyrics3v2 tag, so we only
+	// need to rewrite the tag and whatever comes after it.
+	data.seek(SeekFrom::Start(tag_range.end as u64))?;
+
+	let mut trailing = Vec::new();
+	data.read_to_end(&mut trailing)?;
+
+	data.seek(SeekFrom::Start(tag_range.start as u64))?;
+	data.write_all(&tag)?;
+	data.write_all(&trailing)?;
 
-	Ok(())
+	let new_len = data.stream_position()?;
+	data.set_len(new_len)?;
+
+	Ok(true)
+}
+
+// The size of the tag once written, including the header (APEv2 only) and footer
+pub(super) fn tag_size<'a, I>(items: I, version: ApeTagVersion) -> u64
+where
+	I: Iterator<Item = ApeItemRef<'a>>,
+{
+	let mut items = items.peekable();
+
+	// Nothing is written for an empty tag
+	if items.peek().is_none() {
+		return 0;
+	}
+
+	header_size(version) + FOOTER_SIZE + items.map(|item| item_size(&item)).sum::<u64>()
+}
+
+fn header_size(version: ApeTagVersion) -> u64 {
+	match version {
+		ApeTagVersion::V1 => 0,
+		ApeTagVersion::V2 => HEADER_SIZE,
+	}
+}
+
+// The size stored in the tag includes the items and footer, but not the header
+fn max_tag_size(version: ApeTagVersion) -> u64 {
+	u64::from(u32::MAX) + header_size(version)
+}
+
+fn item_size(item: &ApeItemRef<'_>) -> u64 {
+	let value_len = match &item.value {
+		ItemValueRef::Binary(value) => value.len(),
+		ItemValueRef::Text(value) => value.len(),
+		ItemValueRef::Locator(value) => value.len(),
+	};
+
+	// Value size (4), flags (4), the null terminated key, and the value
+	8 + item.key.len() as u64 + 1 + value_len as u64
+}
+
+// Keeps the read only items of an existing tag, skipping any keys that were already found
+//
+// Items with invalid keys can't be written back, so they're dropped
+fn keep_read_only(read_only: &mut Vec<ApeItem>, items: Vec<ApeItem>) {
+	for item in items {
+		if item.read_only
+			&& item.has_valid_key()
+			&& !read_only
+				.iter()
+				.any(|existing| existing.key().eq_ignore_ascii_case(item.key()))
+		{
+			read_only.push(item);
+		}
+	}
 }
 
 pub(super) fn create_ape_tag<'a, I>(tag: &mut ApeTagRef<'a, I>) -> Result<Vec<u8>>
@@ -154,13 +250,35 @@ where
 	let mut tag_write = Cursor::new(Vec::<u8>::new());
 
 	let mut item_count = 0_u32;
+	let version = tag.version;
+	let mut size = header_size(version) + FOOTER_SIZE;
+	let max_size = max_tag_size(version);
 
 	for item in peek {
-		let (mut flags, value) = match item.value {
+		if let Err(message) = verify_key(item.key) {
+			return Err(FileEncodingError::new(FileType::APE, message).into());
+		}
+
+		if version == ApeTagVersion::V1 && !matches!(item.value, ItemValueRef::Text(_)) {
+			return Err(FileEncodingError::new(
+				FileType::APE,
+				"APEv1 tags can only contain text items",
+			)
+			.into());
+		}
+
+		// Once the tag is too large, the remaining items are only counted, so the
+		// final size can be reported
+		size += item_size(&item);
+		if size > max_size {
+			continue;
+		}
+
+		let (mut flags, value) = match &item.value {
 			ItemValueRef::Binary(value) => {
 				tag_write.write_u32::<LittleEndian>(value.len() as u32)?;
 
-				(1_u32 << 1, value)
+				(1_u32 << 1, *value)
 			},
 			ItemValueRef::Text(value) => {
 				tag_write.write_u32::<LittleEndian>(value.len() as u32)?;
@@ -174,7 +292,10 @@ where
 			},
 		};
 
-		if item.read_only {
+		// APEv1 has no flags
+		if version == ApeTagVersion::V1 {
+			flags = 0;
+		} else if item.read_only {
 			flags |= 1_u32
 		}
 
@@ -186,22 +307,38 @@ where
 		item_count += 1;
 	}
 
-	let size = tag_write.get_ref().len();
-
-	if size as u64 + 32 > u64::from(u32::MAX) {
-		err!(TooMuchData);
+	if size > max_size {
+		return Err(LoftyError::new(ErrorKind::TagTooLarge {
+			size,
+			limit: max_size,
+		}));
 	}
 
+	// Checked above, this can't exceed `u32::MAX`
+	let stored_size = (size - header_size(version)) as u32;
+
 	let mut footer = [0_u8; 32];
 	let mut footer = Cursor::new(&mut footer[..]);
 
 	footer.write_all(APE_PREAMBLE)?;
 	// This is the APE tag version
-	// Even if we read a v1 tag, we end up adding a header anyway
-	footer.write_u32::<LittleEndian>(2000)?;
+	footer.write_u32::<LittleEndian>(match version {
+		ApeTagVersion::V1 => 1000,
+		ApeTagVersion::V2 => 2000,
+	})?;
 	// The total size includes the 32 bytes of the footer
-	footer.write_u32::<LittleEndian>((size + 32) as u32)?;
+	footer.write_u32::<LittleEndian>(stored_size)?;
 	footer.write_u32::<LittleEndian>(item_count)?;
+
+	// APEv1 only has a footer, and no flags
+	if version == ApeTagVersion::V1 {
+		footer.write_u32::<LittleEndian>(0)?;
+		footer.write_u64::<LittleEndian>(0)?;
+
+		tag_write.write_all(footer.get_ref())?;
+		return Ok(tag_write.into_inner());
+	}
+
 	// Bit 29 unset: this is the footer
 	// Bit 30 set: tag contains a footer
 	// Bit 31 set: tag contains a header
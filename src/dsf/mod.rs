@@ -0,0 +1,33 @@
+//! DSF (DSD Stream File) specific items
+//!
+//! ## File notes
+//!
+//! DSF stores an ID3v2 tag at the end of the file, which is located using the
+//! metadata pointer in the "DSD " chunk. A zero pointer means the file has no tag.
+mod properties;
+pub(crate) mod read;
+
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::tag::ID3v2Tag;
+
+use std::ops::Range;
+
+use lofty_attr::LoftyFile;
+
+// Exports
+pub use properties::DsfProperties;
+
+/// A DSF file
+#[derive(Default, LoftyFile)]
+#[lofty(read_fn = "read::read_from")]
+#[lofty(file_type = "DSF")]
+pub struct DsfFile {
+	/// An ID3v2 tag
+	#[cfg(feature = "id3v2")]
+	#[lofty(tag_type = "ID3v2")]
+	pub(crate) id3v2_tag: Option<ID3v2Tag>,
+	/// The file's audio properties
+	pub(crate) properties: DsfProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
+}
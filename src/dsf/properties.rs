@@ -0,0 +1,128 @@
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+use crate::properties::FileProperties;
+
+use std::io::Read;
+use std::time::Duration;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+/// A DSF file's audio properties
+pub struct DsfProperties {
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) channels: u8,
+	pub(crate) bits_per_sample: u8,
+}
+
+impl From<DsfProperties> for FileProperties {
+	fn from(input: DsfProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: Some(input.bits_per_sample),
+			channels: Some(input.channels),
+		}
+	}
+}
+
+impl DsfProperties {
+	/// Duration
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+
+	/// Bits per sample
+	///
+	/// DSD audio is always 1-bit, so this will always be `1`.
+	pub fn bits_per_sample(&self) -> u8 {
+		self.bits_per_sample
+	}
+}
+
+// https://dsd-guide.com/sites/default/files/white-papers/DSFFileFormatSpec_E.pdf
+pub(super) fn read_properties<R>(fmt_chunk: &mut R, file_length: u64) -> Result<DsfProperties>
+where
+	R: Read,
+{
+	let format_version = fmt_chunk.read_u32::<LittleEndian>()?;
+	let format_id = fmt_chunk.read_u32::<LittleEndian>()?;
+
+	if format_version != 1 || format_id != 0 {
+		return Err(FileDecodingError::new(
+			FileType::DSF,
+			"File has an unsupported format version or ID",
+		)
+		.into());
+	}
+
+	let _channel_type = fmt_chunk.read_u32::<LittleEndian>()?;
+	let channels = fmt_chunk.read_u32::<LittleEndian>()?;
+	let sample_rate = fmt_chunk.read_u32::<LittleEndian>()?;
+
+	// This is actually the bit order of the sample data, with 1 being LSB first and 8 being MSB first.
+	// Either way, the samples themselves are 1-bit.
+	let bits_per_sample = fmt_chunk.read_u32::<LittleEndian>()?;
+	if bits_per_sample != 1 && bits_per_sample != 8 {
+		return Err(FileDecodingError::new(
+			FileType::DSF,
+			"File has an invalid bits per sample value",
+		)
+		.into());
+	}
+
+	if !(1..=6).contains(&channels) {
+		return Err(
+			FileDecodingError::new(FileType::DSF, "File has an invalid channel count").into(),
+		);
+	}
+
+	// The sample count is per channel
+	let sample_count = fmt_chunk.read_u64::<LittleEndian>()?;
+
+	let mut properties = DsfProperties {
+		sample_rate,
+		channels: channels as u8,
+		bits_per_sample: 1,
+		..DsfProperties::default()
+	};
+
+	if sample_rate > 0 && sample_count > 0 {
+		let length = sample_count * 1000 / u64::from(sample_rate);
+
+		properties.duration = Duration::from_millis(length);
+		properties.audio_bitrate = (u64::from(sample_rate) * u64::from(channels) / 1000) as u32;
+
+		if length > 0 {
+			properties.overall_bitrate = crate::div_ceil(file_length * 8, length) as u32;
+		}
+	}
+
+	Ok(properties)
+}
@@ -0,0 +1,137 @@
+use super::properties::DsfProperties;
+use super::DsfFile;
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::read::parse_id3v2_with_options;
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::read_id3v2_header;
+use crate::macros::err;
+use crate::probe::ParseOptions;
+
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+// "DSD " chunk: ID (4) + chunk size (8) + total file size (8) + metadata pointer (8)
+const DSD_CHUNK_SIZE: u64 = 28;
+// "fmt " chunk: ID (4) + chunk size (8) + 40 bytes of format information
+const FMT_CHUNK_SIZE: u64 = 52;
+
+pub(super) fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<DsfFile>
+where
+	R: Read + Seek,
+{
+	read_from_with_options(reader, read_properties, &mut ParseOptions::new())
+}
+
+#[allow(unused_variables)]
+pub(crate) fn read_from_with_options<R>(
+	reader: &mut R,
+	read_properties: bool,
+	options: &mut ParseOptions<'_>,
+) -> Result<DsfFile>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+	let file_length = reader.seek(SeekFrom::End(0))? - start;
+	reader.seek(SeekFrom::Start(start))?;
+
+	let mut dsd_id = [0; 4];
+	reader.read_exact(&mut dsd_id)?;
+
+	if &dsd_id != b"DSD " {
+		err!(UnknownFormat);
+	}
+
+	if reader.read_u64::<LittleEndian>()? != DSD_CHUNK_SIZE {
+		return Err(FileDecodingError::new(
+			FileType::DSF,
+			"File has an invalid \"DSD \" chunk size",
+		)
+		.into());
+	}
+
+	let _total_file_size = reader.read_u64::<LittleEndian>()?;
+	let metadata_pointer = reader.read_u64::<LittleEndian>()?;
+
+	let mut fmt_id = [0; 4];
+	reader.read_exact(&mut fmt_id)?;
+
+	if &fmt_id != b"fmt " {
+		return Err(FileDecodingError::new(
+			FileType::DSF,
+			"File does not contain a \"fmt \" chunk",
+		)
+		.into());
+	}
+
+	if reader.read_u64::<LittleEndian>()? != FMT_CHUNK_SIZE {
+		return Err(FileDecodingError::new(
+			FileType::DSF,
+			"File has an invalid \"fmt \" chunk size",
+		)
+		.into());
+	}
+
+	let properties = if read_properties {
+		super::properties::read_properties(reader, file_length)?
+	} else {
+		DsfProperties::default()
+	};
+
+	// The "data" chunk follows the "fmt " chunk, and its size includes the 12 byte header
+	let data_chunk_start = start + DSD_CHUNK_SIZE + FMT_CHUNK_SIZE;
+	reader.seek(SeekFrom::Start(data_chunk_start))?;
+
+	let mut data_id = [0; 4];
+	reader.read_exact(&mut data_id)?;
+
+	if &data_id != b"data" {
+		return Err(FileDecodingError::new(
+			FileType::DSF,
+			"File does not contain a \"data\" chunk",
+		)
+		.into());
+	}
+
+	let data_chunk_size = reader.read_u64::<LittleEndian>()?;
+
+	if data_chunk_size < 12 {
+		return Err(FileDecodingError::new(
+			FileType::DSF,
+			"File has an invalid \"data\" chunk size",
+		)
+		.into());
+	}
+
+	#[cfg(feature = "id3v2")]
+	let mut id3v2_tag = None;
+
+	// A pointer of zero means there is no metadata chunk
+	if metadata_pointer != 0 {
+		if metadata_pointer >= file_length {
+			return Err(FileDecodingError::new(
+				FileType::DSF,
+				"File has a metadata pointer past the end of the file",
+			)
+			.into());
+		}
+
+		#[cfg(feature = "id3v2")]
+		{
+			reader.seek(SeekFrom::Start(start + metadata_pointer))?;
+
+			let header = read_id3v2_header(reader)?;
+			id3v2_tag = Some(parse_id3v2_with_options(reader, header, options)?);
+		}
+	}
+
+	Ok(DsfFile {
+		#[cfg(feature = "id3v2")]
+		id3v2_tag,
+		properties,
+		audio_data_range: data_chunk_start + 12..data_chunk_start + data_chunk_size,
+	})
+}
@@ -4,6 +4,8 @@
 //! which can be extended at any time.
 
 use crate::file::FileType;
+use crate::tag::item::ItemKey;
+use crate::tag::TagType;
 
 use std::collections::TryReserveError;
 use std::fmt::{Debug, Display, Formatter};
@@ -20,6 +22,11 @@ pub enum ErrorKind {
 	// File format related errors
 	/// Unable to guess the format
 	UnknownFormat,
+	/// Arises when reading a format from a non-seekable reader, when its tags
+	/// aren't at the start of the stream
+	///
+	/// See [`read_from_reader`](crate::read_from_reader)
+	RequiresSeek,
 
 	// File data related errors
 	/// Attempting to read/write an abnormally large amount of data
@@ -34,12 +41,40 @@ pub enum ErrorKind {
 	NotAPicture,
 	/// Attempted to write a picture that the format does not support
 	UnsupportedPicture,
+	/// Attempted to write a placeholder picture, see [`Picture::load`](crate::Picture::load)
+	UnloadedPicture,
+	/// Arises when a tag has more pictures than allowed by [`ProbeOptions::max_pictures`](crate::ProbeOptions::max_pictures)
+	TooManyPictures {
+		/// The maximum number of pictures allowed
+		limit: u32,
+	},
+	/// Arises when a picture is larger than allowed by [`ProbeOptions::max_picture_bytes`](crate::ProbeOptions::max_picture_bytes)
+	PictureTooLarge {
+		/// The size of the picture, in bytes
+		size: u64,
+		/// The largest size allowed, in bytes
+		limit: u64,
+	},
 
 	// Tag related errors
 	/// Arises when writing a tag to a file type that doesn't support it
 	UnsupportedTag,
 	/// Arises when a tag is expected (Ex. found an "ID3 " chunk in a WAV file), but isn't found
 	FakeTag,
+	/// Arises when a tag is larger than its format allows
+	///
+	/// When writing, this is currently only used for APE tags, see [`ApeTag::estimated_size`](crate::ape::ApeTag::estimated_size).
+	/// When reading, this is used for tags larger than [`ProbeOptions::max_tag_bytes`](crate::ProbeOptions::max_tag_bytes).
+	TagTooLarge {
+		/// The size of the tag, in bytes
+		size: u64,
+		/// The largest size the format allows, in bytes
+		limit: u64,
+	},
+	/// Arises when an item can't be represented in a [`TagType`]
+	///
+	/// See [`TagBuilder`](crate::TagBuilder)
+	UnsupportedItemKey(TagType, ItemKey),
 	/// Errors that arise while decoding text
 	TextDecode(&'static str),
 	/// Errors that arise while reading/writing ID3v2 tags
@@ -59,6 +94,9 @@ pub enum ErrorKind {
 	Io(std::io::Error),
 	/// Failure to allocate enough memory
 	Alloc(TryReserveError),
+	#[cfg(feature = "picture-tools")]
+	/// Errors that arise while decoding/encoding pictures
+	Image(image::ImageError),
 }
 
 #[derive(Debug, Clone)]
@@ -257,92 +295,167 @@ impl Display for FileEncodingError {
 	}
 }
 
+/// The element of a file that an error occurred in
+///
+/// See [`LoftyError::location`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorLocation {
+	id: String,
+	offset: u64,
+}
+
+impl ErrorLocation {
+	/// Create an `ErrorLocation` from an element ID and offset
+	pub fn new(id: impl Into<String>, offset: u64) -> Self {
+		Self {
+			id: id.into(),
+			offset,
+		}
+	}
+
+	/// Returns the element ID, such as a chunk's FourCC or an ID3v2 frame ID
+	pub fn id(&self) -> &str {
+		&self.id
+	}
+
+	/// Returns the offset of the element's header
+	///
+	/// For chunks, this is relative to the start of the stream.
+	///
+	/// NOTE: For ID3v2 frames, this is relative to the start of the tag (including its header),
+	/// **not** the stream. Additionally, if an ID3v2.2 or ID3v2.3 tag is unsynchronised, this
+	/// is the offset into the tag *after* the unsynchronisation scheme has been reversed, so it may
+	/// not line up with the bytes on disk.
+	pub fn offset(&self) -> u64 {
+		self.offset
+	}
+}
+
+impl Display for ErrorLocation {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "\"{}\" at offset {:#X}", self.id, self.offset)
+	}
+}
+
 /// Errors that could occur within Lofty
 pub struct LoftyError {
 	kind: ErrorKind,
+	location: Option<ErrorLocation>,
 }
 
 impl LoftyError {
 	/// Create a `LoftyError` from an [`ErrorKind`]
 	pub fn new(kind: ErrorKind) -> Self {
-		Self { kind }
+		Self {
+			kind,
+			location: None,
+		}
 	}
 
 	/// Returns the [`ErrorKind`]
 	pub fn kind(&self) -> &ErrorKind {
 		&self.kind
 	}
+
+	/// Returns the chunk or frame that caused the error, if known
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::error::{ErrorKind, ErrorLocation, LoftyError};
+	///
+	/// let err = LoftyError::new(ErrorKind::TooMuchData).with_location(ErrorLocation::new("COMM", 0x1A4));
+	///
+	/// let location = err.location().unwrap();
+	/// assert_eq!(location.id(), "COMM");
+	/// assert_eq!(location.offset(), 0x1A4);
+	/// ```
+	pub fn location(&self) -> Option<&ErrorLocation> {
+		self.location.as_ref()
+	}
+
+	/// Attach an [`ErrorLocation`] to the error
+	///
+	/// NOTE: This does nothing if a location is already set, since the innermost
+	/// element is the most useful one to report. This means that for an ID3v2 tag stored in a
+	/// chunk (such as the `ID3 ` chunk in WAV and AIFF files), only the frame is reported, and its
+	/// offset is relative to the tag. See [`ErrorLocation::offset`].
+	#[must_use]
+	pub fn with_location(mut self, location: ErrorLocation) -> Self {
+		if self.location.is_none() {
+			self.location = Some(location);
+		}
+
+		self
+	}
 }
 
 impl std::error::Error for LoftyError {}
 
 impl Debug for LoftyError {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-		write!(f, "{:?}", self.kind)
+		write!(f, "{:?}", self.kind)?;
+
+		if let Some(ref location) = self.location {
+			write!(f, " ({})", location)?;
+		}
+
+		Ok(())
 	}
 }
 
 impl From<ID3v2Error> for LoftyError {
 	fn from(input: ID3v2Error) -> Self {
-		Self {
-			kind: ErrorKind::ID3v2(input),
-		}
+		Self::new(ErrorKind::ID3v2(input))
 	}
 }
 
 impl From<FileDecodingError> for LoftyError {
 	fn from(input: FileDecodingError) -> Self {
-		Self {
-			kind: ErrorKind::FileDecoding(input),
-		}
+		Self::new(ErrorKind::FileDecoding(input))
 	}
 }
 
 impl From<FileEncodingError> for LoftyError {
 	fn from(input: FileEncodingError) -> Self {
-		Self {
-			kind: ErrorKind::FileEncoding(input),
-		}
+		Self::new(ErrorKind::FileEncoding(input))
 	}
 }
 
 impl From<ogg_pager::PageError> for LoftyError {
 	fn from(input: PageError) -> Self {
-		Self {
-			kind: ErrorKind::OggPage(input),
-		}
+		Self::new(ErrorKind::OggPage(input))
 	}
 }
 
 impl From<std::io::Error> for LoftyError {
 	fn from(input: std::io::Error) -> Self {
-		Self {
-			kind: ErrorKind::Io(input),
-		}
+		Self::new(ErrorKind::Io(input))
 	}
 }
 
 impl From<std::string::FromUtf8Error> for LoftyError {
 	fn from(input: std::string::FromUtf8Error) -> Self {
-		Self {
-			kind: ErrorKind::StringFromUtf8(input),
-		}
+		Self::new(ErrorKind::StringFromUtf8(input))
 	}
 }
 
 impl From<std::str::Utf8Error> for LoftyError {
 	fn from(input: std::str::Utf8Error) -> Self {
-		Self {
-			kind: ErrorKind::StrFromUtf8(input),
-		}
+		Self::new(ErrorKind::StrFromUtf8(input))
+	}
+}
+
+#[cfg(feature = "picture-tools")]
+impl From<image::ImageError> for LoftyError {
+	fn from(input: image::ImageError) -> Self {
+		Self::new(ErrorKind::Image(input))
 	}
 }
 
 impl From<std::collections::TryReserveError> for LoftyError {
 	fn from(input: TryReserveError) -> Self {
-		Self {
-			kind: ErrorKind::Alloc(input),
-		}
+		Self::new(ErrorKind::Alloc(input))
 	}
 }
 
@@ -355,10 +468,16 @@ impl Display for LoftyError {
 			ErrorKind::StrFromUtf8(ref err) => write!(f, "{}", err),
 			ErrorKind::Io(ref err) => write!(f, "{}", err),
 			ErrorKind::Alloc(ref err) => write!(f, "{}", err),
+			#[cfg(feature = "picture-tools")]
+			ErrorKind::Image(ref err) => write!(f, "{}", err),
 
 			ErrorKind::UnknownFormat => {
 				write!(f, "No format could be determined from the provided file")
 			},
+			ErrorKind::RequiresSeek => write!(
+				f,
+				"The format's tags can only be read from a seekable reader"
+			),
 			ErrorKind::TooMuchData => write!(
 				f,
 				"An abnormally large amount of data was provided, and an overflow occurred"
@@ -367,11 +486,39 @@ impl Display for LoftyError {
 			ErrorKind::UnsupportedPicture => {
 				write!(f, "Picture: attempted to write an unsupported picture")
 			},
+			ErrorKind::UnloadedPicture => {
+				write!(
+					f,
+					"Picture: attempted to write a picture that hasn't been loaded"
+				)
+			},
+			ErrorKind::TooManyPictures { limit } => write!(
+				f,
+				"Picture: the tag has more than the limit of {} pictures",
+				limit
+			),
+			ErrorKind::PictureTooLarge { size, limit } => write!(
+				f,
+				"Picture: the picture is {} bytes, which exceeds the limit of {} bytes",
+				size, limit
+			),
 			ErrorKind::UnsupportedTag => write!(
 				f,
 				"Attempted to write a tag to a format that does not support it"
 			),
 			ErrorKind::FakeTag => write!(f, "Reading: Expected a tag, found invalid data"),
+			ErrorKind::TagTooLarge { size, limit } => write!(
+				f,
+				"The tag is {} bytes, which exceeds the limit of {} bytes by {}",
+				size,
+				limit,
+				size.saturating_sub(limit)
+			),
+			ErrorKind::UnsupportedItemKey(tag_type, ref item_key) => write!(
+				f,
+				"The item {:?} can't be represented in a {:?} tag",
+				item_key, tag_type
+			),
 			ErrorKind::TextDecode(message) => write!(f, "Text decoding: {}", message),
 			ErrorKind::ID3v2(ref id3v2_err) => write!(f, "{}", id3v2_err),
 			ErrorKind::BadAtom(message) => write!(f, "MP4 Atom: {}", message),
@@ -379,6 +526,12 @@ impl Display for LoftyError {
 			// Files
 			ErrorKind::FileDecoding(ref file_decode_err) => write!(f, "{}", file_decode_err),
 			ErrorKind::FileEncoding(ref file_encode_err) => write!(f, "{}", file_encode_err),
+		}?;
+
+		if let Some(ref location) = self.location {
+			write!(f, " (in {})", location)?;
 		}
+
+		Ok(())
 	}
 }
@@ -1,13 +1,20 @@
-use crate::error::Result;
+#[cfg(feature = "id3v2")]
+use crate::error::FileEncodingError;
+use crate::error::{LoftyError, Result};
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::ID3v2Tag;
+#[cfg(feature = "id3v2")]
+use crate::mpeg::MPEGWriteOptions;
 use crate::properties::FileProperties;
-use crate::tag::{Tag, TagType};
+use crate::tag::{MergePolicy, Tag, TagType};
 use crate::traits::TagExt;
 
 use crate::resolve::CUSTOM_RESOLVERS;
 use std::convert::TryInto;
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Seek};
+use std::ops::Range;
 use std::path::Path;
 
 /// Provides various methods for interaction with a file
@@ -44,6 +51,12 @@ pub struct TaggedFile {
 	pub(crate) properties: FileProperties,
 	/// A collection of the file's tags
 	pub(crate) tags: Vec<Tag>,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Option<Range<u64>>,
+	/// The errors skipped over while reading, see [`ParseMode::Relaxed`](crate::ParseMode::Relaxed)
+	pub(crate) parse_errors: Vec<LoftyError>,
+	/// The primary tag preference, see [`ProbeOptions::tag_preference`](crate::ProbeOptions::tag_preference)
+	pub(crate) tag_preference: Option<Vec<TagType>>,
 }
 
 impl TaggedFile {
@@ -54,9 +67,20 @@ impl TaggedFile {
 			ty,
 			properties,
 			tags,
+			audio_data_range: None,
+			parse_errors: Vec::new(),
+			tag_preference: None,
 		}
 	}
 
+	#[doc(hidden)]
+	/// This exists for use in `lofty_attr`, there's no real use for this externally
+	#[must_use]
+	pub fn with_audio_data_range(mut self, audio_data_range: Range<u64>) -> Self {
+		self.audio_data_range = Some(audio_data_range);
+		self
+	}
+
 	/// Returns the file's [`FileType`]
 	///
 	/// # Examples
@@ -96,24 +120,44 @@ impl TaggedFile {
 		self.tags.as_slice()
 	}
 
-	/// Returns the file type's primary [`TagType`]
+	/// Returns the file's primary [`TagType`]
+	///
+	/// This is the first tag type in [`ProbeOptions::tag_preference`](crate::ProbeOptions::tag_preference)
+	/// that is present in the file. Otherwise, this is the file type's primary tag type,
+	/// see [`FileType::primary_tag_type`].
 	///
-	/// See [`FileType::primary_tag_type`]
+	/// NOTE: This also decides which tag [`TaggedFile::primary_tag_mut`] and
+	/// [`TaggedFile::primary_tag_merged`] use, so with a preference, edits made through them
+	/// will be saved to the preferred tag. Code that creates a new tag to save from this
+	/// (Ex. `Tag::new(tagged_file.primary_tag_type())`) will also write the preferred tag type.
+	/// Use [`FileType::primary_tag_type`] to always target the format's own primary tag.
 	///
 	/// # Examples
 	///
 	/// ```rust
-	/// use lofty::TagType;
+	/// use lofty::{Probe, ProbeOptions, TagType};
 	///
 	/// # fn main() -> lofty::Result<()> {
 	/// # let path_to_mp3 = "tests/files/assets/minimal/full_test.mp3";
 	/// let mut tagged_file = lofty::read_from_path(path_to_mp3, true)?;
 	///
 	/// assert_eq!(tagged_file.primary_tag_type(), TagType::ID3v2);
+	///
+	/// // Prefer APE tags over ID3v2
+	/// let options = ProbeOptions::new().tag_preference(Some(vec![TagType::APE, TagType::ID3v2]));
+	///
+	/// let tagged_file = Probe::open(path_to_mp3)?.options(options).read(true)?;
+	///
+	/// assert_eq!(tagged_file.primary_tag_type(), TagType::APE);
 	/// # Ok(()) }
 	/// ```
 	pub fn primary_tag_type(&self) -> TagType {
-		self.ty.primary_tag_type()
+		self.tag_preference
+			.iter()
+			.flatten()
+			.copied()
+			.find(|tag_type| self.tags.iter().any(|tag| tag.tag_type() == *tag_type))
+			.unwrap_or_else(|| self.ty.primary_tag_type())
 	}
 
 	/// Determines whether the file supports the given [`TagType`]
@@ -184,7 +228,7 @@ impl TaggedFile {
 
 	/// Returns the primary tag
 	///
-	/// See [`FileType::primary_tag_type`]
+	/// See [`TaggedFile::primary_tag_type`]
 	///
 	/// # Examples
 	///
@@ -209,7 +253,7 @@ impl TaggedFile {
 
 	/// Gets a mutable reference to the file's "Primary tag"
 	///
-	/// See [`FileType::primary_tag_type`]
+	/// See [`TaggedFile::primary_tag_type`]
 	///
 	/// # Examples
 	///
@@ -234,6 +278,53 @@ impl TaggedFile {
 		self.tag_mut(self.primary_tag_type())
 	}
 
+	/// Creates a combined view of all of the file's tags
+	///
+	/// The remaining tags are merged into a copy of the primary tag (or an empty tag of the
+	/// primary [`TagType`] if there isn't one), in the order they appear in [`TaggedFile::tags`].
+	/// This uses [`MergePolicy::PreferNonEmpty`], see [`Tag::merge`].
+	///
+	/// This will return `None` if the file has no tags.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{Accessor, TagType};
+	///
+	/// # fn main() -> lofty::Result<()> {
+	/// # let path_to_mp3 = "tests/files/assets/minimal/full_test.mp3";
+	/// // Read an MP3 file with ID3v2, APE, and ID3v1 tags
+	/// let tagged_file = lofty::read_from_path(path_to_mp3, true)?;
+	///
+	/// let tag = tagged_file.primary_tag_merged().unwrap();
+	///
+	/// // The primary tag's values take precedence
+	/// assert_eq!(tag.tag_type(), TagType::ID3v2);
+	/// assert_eq!(tag.artist(), Some("Foo artist"));
+	/// # Ok(()) }
+	/// ```
+	pub fn primary_tag_merged(&self) -> Option<Tag> {
+		if self.tags.is_empty() {
+			return None;
+		}
+
+		let primary_tag_type = self.primary_tag_type();
+		let mut merged = self
+			.primary_tag()
+			.cloned()
+			.unwrap_or_else(|| Tag::new(primary_tag_type));
+
+		for tag in self
+			.tags
+			.iter()
+			.filter(|t| t.tag_type() != primary_tag_type)
+		{
+			merged.merge(tag.clone(), MergePolicy::PreferNonEmpty);
+		}
+
+		Some(merged)
+	}
+
 	/// Gets the first tag, if there are any
 	///
 	/// NOTE: This will grab the first available tag, you cannot rely on the result being
@@ -397,6 +488,69 @@ impl TaggedFile {
 		self.tags.clear()
 	}
 
+	/// Returns the range of the audio data in the stream
+	///
+	/// This is the part of the stream that remains after excluding all tags and metadata, such as a
+	/// leading ID3v2 tag or trailing ID3v1 and APE tags. It can be used to copy the raw audio data
+	/// without having to locate it again.
+	///
+	/// This depends on the format:
+	///
+	/// * FLAC: The first audio frame after the metadata blocks, up to the end of the stream
+	/// * MP4: The content of the `mdat` atom
+	/// * WAV, AIFF, and DSF: The content of the `data`, `SSND`, and `data` chunks, respectively
+	/// * OGG: The first page after the header packets, up to the end of the stream
+	/// * MPEG: The first MPEG frame, up to the start of any trailing tags. If the properties were not read,
+	///   this starts right after the tags at the start of the stream. If reading stopped early
+	///   (see [`ProbeOptions::tag_types`](crate::ProbeOptions::tag_types)), any trailing tags that weren't
+	///   searched for are included.
+	/// * AAC, APE, Musepack, TTA, and WavPack: The start of the stream (after any leading tags), up to the start of any
+	///   trailing tags
+	///
+	/// This will be `None` for Matroska files, since the audio data is split into clusters, and files
+	/// read with a custom resolver that doesn't provide the range.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # fn main() -> lofty::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let tagged_file = lofty::read_from_path(path, true)?;
+	///
+	/// if let Some(range) = tagged_file.audio_data_range() {
+	/// 	println!("The audio data spans {} bytes", range.end - range.start);
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn audio_data_range(&self) -> Option<Range<u64>> {
+		self.audio_data_range.clone()
+	}
+
+	/// Returns the errors that were skipped over while reading the file
+	///
+	/// This will always be empty, unless the file was read with [`ParseMode::Relaxed`](crate::ParseMode::Relaxed),
+	/// or it has malformed pictures in its Vorbis Comments, which are always skipped.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{ParseMode, Probe, ProbeOptions};
+	///
+	/// # fn main() -> lofty::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let options = ProbeOptions::new().parse_mode(ParseMode::Relaxed);
+	///
+	/// let tagged_file = Probe::open(path)?.options(options).read(true)?;
+	///
+	/// for err in tagged_file.parse_errors() {
+	/// 	eprintln!("Skipped invalid data: {}", err);
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn parse_errors(&self) -> &[LoftyError] {
+		&self.parse_errors
+	}
+
 	/// Attempts to write all tags to a path
 	///
 	/// # Errors
@@ -415,12 +569,14 @@ impl TaggedFile {
 	/// tagged_file.save_to_path(path)?;
 	/// # Ok(()) }
 	/// ```
-	pub fn save_to_path(&self, path: impl AsRef<Path>) -> Result<()> {
+	pub fn save_to_path(&self, path: impl AsRef<Path>) -> Result<bool> {
 		self.save_to(&mut OpenOptions::new().read(true).write(true).open(path)?)
 	}
 
 	/// Attempts to write all tags to a file
 	///
+	/// This returns `true` if any of the tags modified the file, see [`TagExt::save_to`].
+	///
 	/// # Errors
 	///
 	/// See [`Tag::save_to`], however this is applicable to every tag in the `TaggedFile`.
@@ -440,12 +596,118 @@ impl TaggedFile {
 	/// tagged_file.save_to(&mut file)?;
 	/// # Ok(()) }
 	/// ```
-	pub fn save_to(&self, file: &mut File) -> Result<()> {
+	pub fn save_to(&self, file: &mut File) -> Result<bool> {
+		let mut changed = false;
 		for tag in &self.tags {
-			tag.save_to(file)?;
+			// Every tag has to probe the file from the start
+			file.rewind()?;
+			changed |= tag.save_to(file)?;
+		}
+
+		Ok(changed)
+	}
+
+	/// Attempts to write all tags to an MPEG file, using the provided [`MPEGWriteOptions`]
+	///
+	/// This is the same as [`TaggedFile::save_to`], with the ID3v1 policy applied once all tags
+	/// have been written. The policy is only applied if the `TaggedFile` has an ID3v2 tag.
+	///
+	/// NOTE: With [`ID3v1Policy::Mirror`](crate::mpeg::ID3v1Policy::Mirror), the ID3v1 tag
+	/// will be replaced by the ID3v2 tag's fields, even if the `TaggedFile` has an ID3v1 tag.
+	///
+	/// # Errors
+	///
+	/// * The `TaggedFile` is not an MPEG file
+	/// * See [`ID3v2Tag::save_to_mpeg`](crate::id3::v2::ID3v2Tag::save_to_mpeg)
+	///
+	/// # Examples
+	///
+	/// ```rust,ignore
+	/// use lofty::mpeg::{ID3v1Policy, MPEGWriteOptions};
+	/// use std::fs::OpenOptions;
+	///
+	/// # fn main() -> lofty::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let mut tagged_file = lofty::read_from_path(path, true)?;
+	///
+	/// // Edit the tags
+	///
+	/// let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+	/// tagged_file.save_to_mpeg(&mut file, MPEGWriteOptions::new().id3v1(ID3v1Policy::Remove))?;
+	/// # Ok(()) }
+	/// ```
+	#[cfg(feature = "id3v2")]
+	pub fn save_to_mpeg(&self, file: &mut File, options: MPEGWriteOptions) -> Result<bool> {
+		if self.ty != FileType::MPEG {
+			return Err(FileEncodingError::new(FileType::MPEG, "File is not an MPEG file").into());
+		}
+
+		let mut changed = self.save_to(file)?;
+
+		if let Some(tag) = self.tag(TagType::ID3v2) {
+			file.rewind()?;
+			changed |= crate::mpeg::write::write_id3v1_policy(
+				file,
+				&ID3v2Tag::from(tag.clone()),
+				options.id3v1,
+			)?;
+		}
+
+		Ok(changed)
+	}
+
+	/// Attempts to write all tags to a copy of `source`, located at `dest`
+	///
+	/// The file at `source` is left untouched, and its audio data is copied over as is.
+	///
+	/// The copy is written to a temporary file next to `dest`, which is renamed once all tags
+	/// have been written. This means `dest` will never be left partially written.
+	///
+	/// NOTE: If `dest` already exists, it will be replaced.
+	///
+	/// # Errors
+	///
+	/// * `source` doesn't exist
+	/// * `dest` is not writable
+	/// * See [`TaggedFile::save_to`]
+	///
+	/// # Examples
+	///
+	/// ```rust,ignore
+	/// # fn main() -> lofty::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let mut tagged_file = lofty::read_from_path(path, true)?;
+	///
+	/// // Edit the tags
+	///
+	/// tagged_file.save_copy_to(path, "tagged.mp3")?;
+	/// # Ok(()) }
+	/// ```
+	pub fn save_copy_to(&self, source: impl AsRef<Path>, dest: impl AsRef<Path>) -> Result<()> {
+		let dest = dest.as_ref();
+
+		let mut temp_name = OsString::from(".");
+		temp_name.push(dest.file_name().unwrap_or_else(|| OsStr::new("lofty")));
+		temp_name.push(".tmp");
+
+		let temp_path = dest.with_file_name(temp_name);
+
+		let ret = (|| {
+			std::fs::copy(source, &temp_path)?;
+
+			let mut temp_file = OpenOptions::new().read(true).write(true).open(&temp_path)?;
+			self.save_to(&mut temp_file)?;
+			temp_file.sync_all()?;
+
+			std::fs::rename(&temp_path, dest)?;
+			Ok(())
+		})();
+
+		if ret.is_err() {
+			let _ = std::fs::remove_file(&temp_path);
 		}
 
-		Ok(())
+		ret
 	}
 }
 
@@ -480,14 +742,20 @@ impl AudioFile for TaggedFile {
 #[non_exhaustive]
 /// The type of file read
 pub enum FileType {
+	AAC,
 	AIFF,
 	APE,
+	DSF,
 	FLAC,
+	Mka,
 	MPEG,
 	MP4,
+	Musepack,
+	OggFlac,
 	Opus,
 	Vorbis,
 	Speex,
+	TTA,
 	WAV,
 	WavPack,
 	Custom(&'static str),
@@ -497,12 +765,13 @@ impl FileType {
 	#[allow(unreachable_patterns, clippy::match_same_arms)]
 	/// Returns the file type's "primary" [`TagType`], or the one most likely to be used in the target format
 	///
-	/// | [`FileType`]             | [`TagType`]      |
-	/// |--------------------------|------------------|
-	/// | `AIFF`, `MP3`, `WAV`     | `Id3v2`          |
-	/// | `APE` , `WavPack`        | `Ape`            |
-	/// | `FLAC`, `Opus`, `Vorbis` | `VorbisComments` |
-	/// | `MP4`                    | `Mp4Ilst`        |
+	/// | [`FileType`]                              | [`TagType`]      |
+	/// |-------------------------------------------|------------------|
+	/// | `AAC`, `AIFF`, `DSF`, `MP3`, `TTA`, `WAV` | `Id3v2`          |
+	/// | `APE`, `Musepack`, `WavPack`              | `Ape`            |
+	/// | `FLAC`, `OggFlac`, `Opus`, `Vorbis`       | `VorbisComments` |
+	/// | `Mka`                                     | `Matroska`       |
+	/// | `MP4`                                     | `Mp4Ilst`        |
 	///
 	/// # Panics
 	///
@@ -523,16 +792,24 @@ impl FileType {
 			#[cfg(all(not(feature = "id3v2"), feature = "riff_info_list"))]
 			FileType::WAV => TagType::RIFFInfo,
 			#[cfg(all(not(feature = "id3v2"), feature = "id3v1"))]
-			FileType::MPEG => TagType::ID3v1,
+			FileType::AAC | FileType::MPEG | FileType::TTA => TagType::ID3v1,
 			#[cfg(all(not(feature = "id3v2"), not(feature = "id3v1"), feature = "ape"))]
-			FileType::MPEG => TagType::APE,
-			FileType::AIFF | FileType::MPEG | FileType::WAV => TagType::ID3v2,
+			FileType::AAC | FileType::MPEG | FileType::TTA => TagType::APE,
+			FileType::AAC
+			| FileType::AIFF
+			| FileType::DSF
+			| FileType::MPEG
+			| FileType::TTA
+			| FileType::WAV => TagType::ID3v2,
 			#[cfg(all(not(feature = "ape"), feature = "id3v1"))]
-			FileType::MPEG | FileType::WavPack => TagType::ID3v1,
-			FileType::APE | FileType::WavPack => TagType::APE,
-			FileType::FLAC | FileType::Opus | FileType::Vorbis | FileType::Speex => {
-				TagType::VorbisComments
-			},
+			FileType::MPEG | FileType::Musepack | FileType::WavPack => TagType::ID3v1,
+			FileType::APE | FileType::Musepack | FileType::WavPack => TagType::APE,
+			FileType::FLAC
+			| FileType::OggFlac
+			| FileType::Opus
+			| FileType::Vorbis
+			| FileType::Speex => TagType::VorbisComments,
+			FileType::Mka => TagType::Matroska,
 			FileType::MP4 => TagType::MP4ilst,
 			FileType::Custom(c) => {
 				if let Some(r) = crate::resolve::lookup_resolver(c) {
@@ -564,21 +841,52 @@ impl FileType {
 	pub fn supports_tag_type(&self, tag_type: TagType) -> bool {
 		match self {
 			#[cfg(feature = "id3v2")]
-			FileType::AIFF | FileType::APE | FileType::MPEG | FileType::WAV
+			FileType::AAC
+			| FileType::AIFF
+			| FileType::APE
+			| FileType::MPEG
+			| FileType::TTA
+			| FileType::WAV
 				if tag_type == TagType::ID3v2 =>
 			{
 				true
 			},
+			#[cfg(feature = "id3v2")]
+			FileType::DSF => tag_type == TagType::ID3v2,
 			#[cfg(feature = "aiff_text_chunks")]
 			FileType::AIFF if tag_type == TagType::AIFFText => true,
 			#[cfg(feature = "id3v1")]
-			FileType::APE | FileType::MPEG | FileType::WavPack if tag_type == TagType::ID3v1 => true,
+			FileType::AAC
+			| FileType::APE
+			| FileType::MPEG
+			| FileType::Musepack
+			| FileType::TTA
+			| FileType::WavPack
+				if tag_type == TagType::ID3v1 =>
+			{
+				true
+			},
 			#[cfg(feature = "ape")]
-			FileType::APE | FileType::MPEG | FileType::WavPack if tag_type == TagType::APE => true,
-			#[cfg(feature = "vorbis_comments")]
-			FileType::Opus | FileType::FLAC | FileType::Vorbis | FileType::Speex => {
-				tag_type == TagType::VorbisComments
+			FileType::AAC
+			| FileType::APE
+			| FileType::MPEG
+			| FileType::Musepack
+			| FileType::TTA
+			| FileType::WavPack
+				if tag_type == TagType::APE =>
+			{
+				true
 			},
+			#[cfg(feature = "lyrics3v2")]
+			FileType::MPEG if tag_type == TagType::Lyrics3v2 => true,
+			#[cfg(feature = "vorbis_comments")]
+			FileType::Opus
+			| FileType::FLAC
+			| FileType::OggFlac
+			| FileType::Vorbis
+			| FileType::Speex => tag_type == TagType::VorbisComments,
+			#[cfg(feature = "matroska_tags")]
+			FileType::Mka => tag_type == TagType::Matroska,
 			#[cfg(feature = "mp4_ilst")]
 			FileType::MP4 => tag_type == TagType::MP4ilst,
 			#[cfg(feature = "riff_info_list")]
@@ -614,9 +922,13 @@ impl FileType {
 		let ext = ext.as_ref().to_str()?.to_ascii_lowercase();
 
 		match ext.as_str() {
+			"aac" | "adts" => Some(Self::AAC),
 			"ape" => Some(Self::APE),
 			"aiff" | "aif" | "afc" | "aifc" => Some(Self::AIFF),
+			"dsf" => Some(Self::DSF),
+			"mka" | "mkv" | "webm" => Some(Self::Mka),
 			"mp3" | "mp2" | "mp1" => Some(Self::MPEG),
+			"mpc" | "mp+" | "mpp" => Some(Self::Musepack),
 			"wav" | "wave" => Some(Self::WAV),
 			"wv" => Some(Self::WavPack),
 			"opus" => Some(Self::Opus),
@@ -624,6 +936,7 @@ impl FileType {
 			"ogg" => Some(Self::Vorbis),
 			"mp4" | "m4a" | "m4b" | "m4p" | "m4r" | "m4v" | "3gp" => Some(Self::MP4),
 			"spx" => Some(Self::Speex),
+			"tta" => Some(Self::TTA),
 			e => {
 				if let Some((ty, _)) = CUSTOM_RESOLVERS
 					.lock()
@@ -658,6 +971,122 @@ impl FileType {
 		ext.and_then(Self::from_ext)
 	}
 
+	/// Attempts to extract a [`FileType`] from an extension, which may start with a dot
+	///
+	/// This is the same as [`FileType::from_ext`], which is case-insensitive.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::FileType;
+	///
+	/// assert_eq!(FileType::from_extension(".FLAC"), Some(FileType::FLAC));
+	/// assert_eq!(FileType::from_extension("m4a"), Some(FileType::MP4));
+	/// ```
+	pub fn from_extension(ext: &str) -> Option<Self> {
+		Self::from_ext(ext.strip_prefix('.').unwrap_or(ext))
+	}
+
+	/// Attempts to determine a [`FileType`] from a MIME type
+	///
+	/// This is case-insensitive, and any parameters are ignored, with the exception of the `codecs`
+	/// parameter for `audio/ogg` (Ex. `audio/ogg; codecs=opus`).
+	///
+	/// NOTE: This will never return a [`FileType::Custom`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::FileType;
+	///
+	/// assert_eq!(FileType::from_mime_type("audio/flac"), Some(FileType::FLAC));
+	/// assert_eq!(FileType::from_mime_type("audio/MPEG"), Some(FileType::MPEG));
+	/// assert_eq!(
+	/// 	FileType::from_mime_type("audio/ogg; codecs=opus"),
+	/// 	Some(FileType::Opus)
+	/// );
+	/// ```
+	pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+		let mime_type = mime_type.to_ascii_lowercase();
+
+		let mut parts = mime_type.split(';').map(str::trim);
+		let essence = parts.next().unwrap_or_default();
+
+		match essence {
+			"audio/aac" | "audio/aacp" | "audio/x-aac" | "audio/x-hx-aac-adts" => Some(Self::AAC),
+			"audio/ape" | "audio/x-ape" | "audio/x-monkeys-audio" => Some(Self::APE),
+			"audio/aiff" | "audio/x-aiff" => Some(Self::AIFF),
+			"audio/dsf" | "audio/x-dsf" => Some(Self::DSF),
+			"audio/flac" | "audio/x-flac" => Some(Self::FLAC),
+			"audio/flac+ogg" | "audio/x-flac+ogg" => Some(Self::OggFlac),
+			"audio/matroska" | "audio/x-matroska" | "video/x-matroska" | "audio/webm"
+			| "video/webm" => Some(Self::Mka),
+			"audio/mpeg" | "audio/mp3" | "audio/x-mp3" | "audio/mpeg3" | "audio/x-mpeg"
+			| "audio/mpa" => Some(Self::MPEG),
+			"audio/mp4" | "audio/m4a" | "audio/x-m4a" | "video/mp4" => Some(Self::MP4),
+			"audio/musepack" | "audio/x-musepack" => Some(Self::Musepack),
+			"audio/opus" => Some(Self::Opus),
+			"audio/speex" | "audio/x-speex" => Some(Self::Speex),
+			"audio/vorbis" | "audio/x-vorbis" => Some(Self::Vorbis),
+			"audio/ogg" | "application/ogg" => {
+				let codecs = parts
+					.find_map(|param| param.strip_prefix("codecs="))
+					.map(|codecs| codecs.trim_matches('"'));
+
+				match codecs {
+					Some("flac") => Some(Self::OggFlac),
+					Some("opus") => Some(Self::Opus),
+					Some("speex") => Some(Self::Speex),
+					Some("vorbis") | None => Some(Self::Vorbis),
+					Some(_) => None,
+				}
+			},
+			"audio/tta" | "audio/x-tta" => Some(Self::TTA),
+			"audio/wav" | "audio/x-wav" | "audio/wave" | "audio/vnd.wave" => Some(Self::WAV),
+			"audio/wavpack" | "audio/x-wavpack" => Some(Self::WavPack),
+			_ => None,
+		}
+	}
+
+	/// Returns the most common MIME type for the [`FileType`]
+	///
+	/// This will return `None` for [`FileType::Custom`].
+	///
+	/// The returned MIME type can always be passed back to [`FileType::from_mime_type`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::FileType;
+	///
+	/// assert_eq!(FileType::FLAC.primary_mime_type(), Some("audio/flac"));
+	/// assert_eq!(FileType::MPEG.primary_mime_type(), Some("audio/mpeg"));
+	///
+	/// let mime_type = FileType::OggFlac.primary_mime_type().unwrap();
+	/// assert_eq!(FileType::from_mime_type(mime_type), Some(FileType::OggFlac));
+	/// ```
+	pub fn primary_mime_type(&self) -> Option<&'static str> {
+		match self {
+			FileType::AAC => Some("audio/aac"),
+			FileType::AIFF => Some("audio/aiff"),
+			FileType::APE => Some("audio/ape"),
+			FileType::DSF => Some("audio/dsf"),
+			FileType::FLAC => Some("audio/flac"),
+			FileType::Mka => Some("audio/x-matroska"),
+			FileType::MPEG => Some("audio/mpeg"),
+			FileType::MP4 => Some("audio/mp4"),
+			FileType::Musepack => Some("audio/musepack"),
+			FileType::Opus => Some("audio/opus"),
+			FileType::Speex => Some("audio/speex"),
+			FileType::OggFlac => Some("audio/flac+ogg"),
+			FileType::Vorbis => Some("audio/ogg"),
+			FileType::TTA => Some("audio/x-tta"),
+			FileType::WAV => Some("audio/wav"),
+			FileType::WavPack => Some("audio/wavpack"),
+			FileType::Custom(_) => None,
+		}
+	}
+
 	/// Attempts to extract a [`FileType`] from a buffer
 	///
 	/// NOTES:
@@ -728,11 +1157,16 @@ impl FileType {
 	}
 
 	fn quick_type_guess(buf: &[u8]) -> Option<Self> {
+		use crate::aac::header::verify_adts_sync;
 		use crate::mpeg::header::verify_frame_sync;
 
 		// Safe to index, since we return early on an empty buffer
 		match buf[0] {
 			77 if buf.starts_with(b"MAC") => Some(Self::APE),
+			77 if buf.starts_with(b"MPCK") || buf.starts_with(b"MP+") => Some(Self::Musepack),
+			68 if buf.starts_with(b"DSD ") => Some(Self::DSF),
+			// ADTS headers start with an MPEG frame sync, so they have to be checked first
+			255 if buf.len() >= 2 && verify_adts_sync([buf[0], buf[1]]) => Some(Self::AAC),
 			255 if buf.len() >= 2 && verify_frame_sync([buf[0], buf[1]]) => Some(Self::MPEG),
 			70 if buf.len() >= 12 && &buf[..4] == b"FORM" => {
 				let id = &buf[8..12];
@@ -750,18 +1184,22 @@ impl FileType {
 					return Some(Self::Opus);
 				} else if &buf[28..36] == b"Speex   " {
 					return Some(Self::Speex);
+				} else if &buf[28..33] == b"\x7FFLAC" {
+					return Some(Self::OggFlac);
 				}
 
 				None
 			},
 			102 if buf.starts_with(b"fLaC") => Some(Self::FLAC),
-			82 if buf.len() >= 12 && &buf[..4] == b"RIFF" => {
+			82 | 66 if buf.len() >= 12 && matches!(&buf[..4], b"RIFF" | b"RF64" | b"BW64") => {
 				if &buf[8..12] == b"WAVE" {
 					return Some(Self::WAV);
 				}
 
 				None
 			},
+			84 if buf.starts_with(b"TTA1") => Some(Self::TTA),
+			26 if buf.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) => Some(Self::Mka),
 			119 if buf.len() >= 4 && &buf[..4] == b"wvpk" => Some(Self::WavPack),
 			_ if buf.len() >= 8 && &buf[4..8] == b"ftyp" => Some(Self::MP4),
 			_ => None,
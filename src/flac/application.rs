@@ -0,0 +1,85 @@
+use super::block::{write_blocks, BLOCK_ID_APPLICATION, DEFAULT_PADDING};
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+
+use std::fs::File;
+
+/// A FLAC `APPLICATION` block
+///
+/// These are used by third-party applications to store their own data, identified by
+/// an ID registered with the FLAC project.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApplicationBlock {
+	/// The registered application ID
+	pub id: [u8; 4],
+	/// The application data
+	pub data: Vec<u8>,
+}
+
+impl ApplicationBlock {
+	/// Create a new [`ApplicationBlock`]
+	pub fn new(id: [u8; 4], data: Vec<u8>) -> Self {
+		Self { id, data }
+	}
+
+	pub(crate) fn parse(content: &[u8]) -> Result<Self> {
+		if content.len() < 4 {
+			return Err(FileDecodingError::new(
+				FileType::FLAC,
+				"Application block is too short to contain an ID",
+			)
+			.into());
+		}
+
+		let (id, data) = content.split_at(4);
+
+		Ok(Self {
+			id: [id[0], id[1], id[2], id[3]],
+			data: data.to_vec(),
+		})
+	}
+
+	/// Convert an [`ApplicationBlock`] into a FLAC `APPLICATION` block
+	///
+	/// NOTE: This does not include the block header
+	pub fn as_bytes(&self) -> Vec<u8> {
+		let mut content = Vec::with_capacity(self.data.len() + 4);
+		content.extend(self.id);
+		content.extend(&self.data);
+		content
+	}
+
+	/// Writes the block to a FLAC file
+	///
+	/// This will replace any existing `APPLICATION` blocks with the same ID, keeping the order of all
+	/// other blocks. An existing padding block will be resized to absorb the change in size where possible.
+	///
+	/// # Errors
+	///
+	/// * `file` is not a valid FLAC file
+	/// * The block is too large
+	pub fn save_to(&self, file: &mut File) -> Result<()> {
+		write_blocks(
+			file,
+			&[(BLOCK_ID_APPLICATION, self.as_bytes())],
+			|ty, content| ty == BLOCK_ID_APPLICATION && content.starts_with(&self.id),
+			DEFAULT_PADDING,
+		)
+		.map(|_| ())
+	}
+
+	/// Removes all `APPLICATION` blocks with the specified ID from a FLAC file
+	///
+	/// # Errors
+	///
+	/// * `file` is not a valid FLAC file
+	pub fn remove_from(file: &mut File, id: [u8; 4]) -> Result<()> {
+		write_blocks(
+			file,
+			&[],
+			|ty, content| ty == BLOCK_ID_APPLICATION && content.starts_with(&id),
+			DEFAULT_PADDING,
+		)
+		.map(|_| ())
+	}
+}
@@ -1,12 +1,27 @@
+use super::read::verify_flac;
 use crate::error::Result;
-use crate::macros::try_vec;
+use crate::id3::find_id3v2;
+use crate::macros::{err, try_vec};
 
-use std::io::{Read, Seek};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
 
-use byteorder::{BigEndian, ReadBytesExt};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+pub(crate) const BLOCK_ID_STREAMINFO: u8 = 0;
+pub(crate) const BLOCK_ID_PADDING: u8 = 1;
+pub(crate) const BLOCK_ID_APPLICATION: u8 = 2;
+pub(crate) const BLOCK_ID_CUESHEET: u8 = 5;
+
+pub(crate) const BLOCK_ID_VORBIS_COMMENTS: u8 = 4;
+pub(crate) const BLOCK_ID_PICTURE: u8 = 6;
+
+pub(crate) const MAX_BLOCK_SIZE: usize = 16_777_215;
+
+// The amount of padding used when there isn't enough to absorb a change in size
+pub(crate) const DEFAULT_PADDING: u32 = 1024;
 
 pub(crate) struct Block {
-	pub(super) byte: u8,
 	pub(super) ty: u8,
 	pub(super) last: bool,
 	pub(crate) content: Vec<u8>,
@@ -20,12 +35,7 @@ impl Block {
 		R: Read + Seek,
 	{
 		let start = data.stream_position()?;
-
-		let byte = data.read_u8()?;
-		let last = (byte & 0x80) != 0;
-		let ty = byte & 0x7F;
-
-		let size = data.read_u24::<BigEndian>()?;
+		let (ty, last, size) = Self::read_header(data)?;
 
 		let mut content = try_vec![0; size as usize];
 		data.read_exact(&mut content)?;
@@ -33,7 +43,6 @@ impl Block {
 		let end = data.stream_position()?;
 
 		Ok(Self {
-			byte,
 			ty,
 			last,
 			content,
@@ -41,4 +50,213 @@ impl Block {
 			end,
 		})
 	}
+
+	// Reads the block type, last block flag, and block size
+	pub(crate) fn read_header<R>(data: &mut R) -> Result<(u8, bool, u32)>
+	where
+		R: Read,
+	{
+		let byte = data.read_u8()?;
+		let last = (byte & 0x80) != 0;
+		let ty = byte & 0x7F;
+
+		let size = data.read_u24::<BigEndian>()?;
+
+		Ok((ty, last, size))
+	}
+}
+
+// The metadata blocks of a file, see `read_blocks`
+pub(super) struct MetadataBlocks {
+	stream_info: Block,
+	pub(super) blocks: Vec<Block>,
+	audio_start: u64,
+}
+
+/// Reads the `STREAMINFO` block, and every metadata block following it
+pub(super) fn read_blocks(data: &mut File) -> Result<MetadataBlocks> {
+	data.rewind()?;
+
+	// The stream may start with an ID3v2 tag
+	find_id3v2(data, false)?;
+
+	let stream_info = verify_flac(data)?;
+
+	let mut blocks = Vec::new();
+	let mut last_block = stream_info.last;
+	while !last_block {
+		let block = Block::read(data)?;
+		last_block = block.last;
+
+		blocks.push(block);
+	}
+
+	let audio_start = data.stream_position()?;
+
+	Ok(MetadataBlocks {
+		stream_info,
+		blocks,
+		audio_start,
+	})
+}
+
+/// Replaces the metadata blocks matching `replace` with `blocks`
+///
+/// See [`replace_blocks`]
+pub(super) fn write_blocks<F>(
+	data: &mut File,
+	blocks: &[(u8, Vec<u8>)],
+	replace: F,
+	padding: u32,
+) -> Result<bool>
+where
+	F: FnMut(u8, &[u8]) -> bool,
+{
+	let metadata = read_blocks(data)?;
+	replace_blocks(data, &metadata, blocks, replace, padding)
+}
+
+/// Replaces the metadata blocks matching `replace` with `blocks`, using the previously read `metadata`
+///
+/// The new blocks take the place of the first replaced block, or are placed before any padding.
+///
+/// All padding is merged into a single block, which is resized to absorb the change in size.
+/// If there isn't enough padding, or the file has none, the entire file is rewritten with `padding` bytes of padding.
+///
+/// Nothing is written if the replaced blocks are identical to `blocks` and the file already has padding
+/// (or `padding` is 0), in which case this returns `false`.
+pub(super) fn replace_blocks<F>(
+	data: &mut File,
+	metadata: &MetadataBlocks,
+	blocks: &[(u8, Vec<u8>)],
+	mut replace: F,
+	padding: u32,
+) -> Result<bool>
+where
+	F: FnMut(u8, &[u8]) -> bool,
+{
+	let stream_info = &metadata.stream_info;
+	let stream_info_start = stream_info.start;
+	let audio_start = metadata.audio_start;
+
+	let existing = metadata
+		.blocks
+		.iter()
+		.map(|block| {
+			let replaced = replace(block.ty, &block.content);
+			(block.ty, &block.content, replaced)
+		})
+		.collect::<Vec<_>>();
+
+	// A file without any padding will have the requested padding added, even if the blocks are unchanged
+	let missing_padding = padding > 0
+		&& !existing
+			.iter()
+			.any(|(block_ty, _, _)| *block_ty == BLOCK_ID_PADDING);
+
+	let replaced_blocks = existing
+		.iter()
+		.filter(|(_, _, replaced)| *replaced)
+		.map(|(ty, content, _)| (*ty, content.as_slice()));
+	if !missing_padding
+		&& replaced_blocks.eq(blocks.iter().map(|(ty, content)| (*ty, content.as_slice())))
+	{
+		return Ok(false);
+	}
+
+	let mut new_blocks: Vec<(u8, &[u8])> = Vec::with_capacity(existing.len() + blocks.len());
+	let mut insert_pos = None;
+	let mut padding_pos = None;
+
+	for (block_ty, content, replaced) in &existing {
+		if *replaced {
+			insert_pos.get_or_insert(new_blocks.len());
+		} else if *block_ty == BLOCK_ID_PADDING {
+			insert_pos.get_or_insert(new_blocks.len());
+			padding_pos.get_or_insert(new_blocks.len());
+		} else {
+			new_blocks.push((*block_ty, content.as_slice()));
+		}
+	}
+
+	let insert_pos = insert_pos.unwrap_or(new_blocks.len());
+	new_blocks.splice(
+		insert_pos..insert_pos,
+		blocks.iter().map(|(ty, content)| (*ty, content.as_slice())),
+	);
+
+	// The padding is placed after the new blocks if they were inserted in front of it
+	let padding_pos = padding_pos.map(|pos| {
+		if pos >= insert_pos {
+			pos + blocks.len()
+		} else {
+			pos
+		}
+	});
+
+	let metadata_size =
+		|blocks: &mut dyn Iterator<Item = usize>| blocks.map(|len| len as u64 + 4).sum::<u64>();
+
+	let old_size = metadata_size(&mut existing.iter().map(|(_, content, _)| content.len()));
+	let new_size = metadata_size(&mut new_blocks.iter().map(|(_, content)| content.len()));
+
+	// Use the freed up space for padding, so the audio data can stay in place
+	let (padding_size, in_place) = match old_size.checked_sub(new_size) {
+		Some(0) if !missing_padding => (None, true),
+		Some(free) if free >= 4 && free - 4 <= MAX_BLOCK_SIZE as u64 => (Some(free - 4), true),
+		_ => ((padding > 0).then(|| u64::from(padding)), false),
+	};
+
+	let padding_block = padding_size.map(|size| vec![0; size as usize]);
+	if let Some(padding_block) = &padding_block {
+		new_blocks.insert(
+			padding_pos.unwrap_or(new_blocks.len()),
+			(BLOCK_ID_PADDING, padding_block.as_slice()),
+		);
+	}
+
+	let mut file_bytes = Vec::new();
+	write_block(
+		&mut file_bytes,
+		BLOCK_ID_STREAMINFO,
+		new_blocks.is_empty(),
+		&stream_info.content,
+	)?;
+
+	for (i, (block_ty, content)) in new_blocks.iter().enumerate() {
+		write_block(
+			&mut file_bytes,
+			*block_ty,
+			i == new_blocks.len() - 1,
+			content,
+		)?;
+	}
+
+	data.seek(SeekFrom::Start(stream_info_start))?;
+
+	if in_place {
+		data.write_all(&file_bytes)?;
+		return Ok(true);
+	}
+
+	data.seek(SeekFrom::Start(audio_start))?;
+	data.read_to_end(&mut file_bytes)?;
+
+	data.seek(SeekFrom::Start(stream_info_start))?;
+	data.set_len(stream_info_start)?;
+	data.write_all(&file_bytes)?;
+
+	Ok(true)
+}
+
+fn write_block(writer: &mut Vec<u8>, ty: u8, last: bool, content: &[u8]) -> Result<()> {
+	if content.len() > MAX_BLOCK_SIZE {
+		err!(TooMuchData);
+	}
+
+	writer.write_u8(if last { ty | 0x80 } else { ty & 0x7F })?;
+	writer.write_u24::<BigEndian>(content.len() as u32)?;
+	writer.write_all(content)?;
+
+	Ok(())
 }
@@ -0,0 +1,331 @@
+use super::block::{write_blocks, BLOCK_ID_CUESHEET, DEFAULT_PADDING};
+use crate::error::{FileDecodingError, FileEncodingError, Result};
+use crate::file::FileType;
+
+use std::fs::File;
+use std::io::{Read, Write};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+const CATALOG_NUMBER_LEN: usize = 128;
+const ISRC_LEN: usize = 12;
+
+/// A FLAC cue sheet (`CUESHEET` metadata block)
+///
+/// This stores the track and index points of a CD-DA rip, or any other media using a cue sheet.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CueSheet {
+	/// The media catalog number
+	///
+	/// For CD-DA, this is a 13 digit number. This must be at most 128 bytes of ASCII.
+	pub catalog_number: String,
+	/// The number of lead-in samples
+	///
+	/// This is only meaningful for CD-DA cue sheets, and should be 0 otherwise.
+	pub lead_in_samples: u64,
+	/// Whether the cue sheet corresponds to a compact disc
+	pub is_compact_disc: bool,
+	/// The tracks of the cue sheet
+	///
+	/// The last track is the lead-out track.
+	pub tracks: Vec<CueSheetTrack>,
+}
+
+/// A track in a [`CueSheet`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CueSheetTrack {
+	/// The offset of the first index point, in samples, relative to the beginning of the audio data
+	pub offset: u64,
+	/// The track number
+	///
+	/// A track number of 0 is not allowed, and the lead-out track is 170 for CD-DA, or 255 otherwise.
+	pub number: u8,
+	/// The track's ISRC, which must be 12 bytes of ASCII (or empty)
+	pub isrc: String,
+	/// Whether the track contains audio
+	pub is_audio: bool,
+	/// Whether the track was recorded with pre-emphasis
+	pub pre_emphasis: bool,
+	/// The track's index points
+	pub indices: Vec<CueSheetTrackIndex>,
+}
+
+/// An index point in a [`CueSheetTrack`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CueSheetTrackIndex {
+	/// The offset of the index point, in samples, relative to the track offset
+	pub offset: u64,
+	/// The index point number
+	pub number: u8,
+}
+
+impl CueSheet {
+	pub(crate) fn parse(content: &[u8]) -> Result<Self> {
+		let reader = &mut &*content;
+
+		let catalog_number = read_string(reader, CATALOG_NUMBER_LEN)?;
+		let lead_in_samples = reader.read_u64::<BigEndian>()?;
+
+		// The first bit is the compact disc flag, followed by 7 + 258 * 8 reserved bits
+		let is_compact_disc = reader.read_u8()? & 0x80 != 0;
+		skip(reader, 258)?;
+
+		let track_count = reader.read_u8()?;
+
+		let mut tracks = Vec::with_capacity(track_count as usize);
+		for _ in 0..track_count {
+			let offset = reader.read_u64::<BigEndian>()?;
+			let number = reader.read_u8()?;
+			let isrc = read_string(reader, ISRC_LEN)?;
+
+			// The first bit is the track type (0 = audio), and the second is the pre-emphasis flag.
+			// These are followed by 6 + 13 * 8 reserved bits
+			let flags = reader.read_u8()?;
+			skip(reader, 13)?;
+
+			let index_count = reader.read_u8()?;
+
+			let mut indices = Vec::with_capacity(index_count as usize);
+			for _ in 0..index_count {
+				let offset = reader.read_u64::<BigEndian>()?;
+				let number = reader.read_u8()?;
+
+				// 3 * 8 reserved bits
+				skip(reader, 3)?;
+
+				indices.push(CueSheetTrackIndex { offset, number });
+			}
+
+			tracks.push(CueSheetTrack {
+				offset,
+				number,
+				isrc,
+				is_audio: flags & 0x80 == 0,
+				pre_emphasis: flags & 0x40 != 0,
+				indices,
+			});
+		}
+
+		Ok(Self {
+			catalog_number,
+			lead_in_samples,
+			is_compact_disc,
+			tracks,
+		})
+	}
+
+	/// Convert a [`CueSheet`] into a FLAC `CUESHEET` block
+	///
+	/// NOTE: This does not include the block header
+	///
+	/// # Errors
+	///
+	/// * The catalog number is longer than 128 bytes, or any ISRC is not 12 bytes
+	/// * Either of them contain non-ASCII characters
+	/// * There are more than 255 tracks, or a track has more than 255 index points
+	pub fn as_bytes(&self) -> Result<Vec<u8>> {
+		let mut content = Vec::new();
+
+		write_string(&mut content, &self.catalog_number, CATALOG_NUMBER_LEN)?;
+		content.write_u64::<BigEndian>(self.lead_in_samples)?;
+
+		content.write_u8(if self.is_compact_disc { 0x80 } else { 0 })?;
+		content.write_all(&[0; 258])?;
+
+		content.write_u8(count(self.tracks.len())?)?;
+
+		for track in &self.tracks {
+			content.write_u64::<BigEndian>(track.offset)?;
+			content.write_u8(track.number)?;
+
+			if !track.isrc.is_empty() && track.isrc.len() != ISRC_LEN {
+				return Err(FileEncodingError::new(
+					FileType::FLAC,
+					"Cue sheet track ISRC must be 12 bytes",
+				)
+				.into());
+			}
+			write_string(&mut content, &track.isrc, ISRC_LEN)?;
+
+			let mut flags = 0;
+			if !track.is_audio {
+				flags |= 0x80;
+			}
+			if track.pre_emphasis {
+				flags |= 0x40;
+			}
+
+			content.write_u8(flags)?;
+			content.write_all(&[0; 13])?;
+
+			content.write_u8(count(track.indices.len())?)?;
+
+			for index in &track.indices {
+				content.write_u64::<BigEndian>(index.offset)?;
+				content.write_u8(index.number)?;
+				content.write_all(&[0; 3])?;
+			}
+		}
+
+		Ok(content)
+	}
+
+	/// Writes the cue sheet to a FLAC file
+	///
+	/// This will replace any existing `CUESHEET` block.
+	///
+	/// # Errors
+	///
+	/// * `file` is not a valid FLAC file
+	/// * See [`CueSheet::as_bytes`]
+	pub fn save_to(&self, file: &mut File) -> Result<()> {
+		write_blocks(
+			file,
+			&[(BLOCK_ID_CUESHEET, self.as_bytes()?)],
+			|ty, _| ty == BLOCK_ID_CUESHEET,
+			DEFAULT_PADDING,
+		)
+		.map(|_| ())
+	}
+
+	/// Removes the `CUESHEET` block from a FLAC file
+	///
+	/// # Errors
+	///
+	/// * `file` is not a valid FLAC file
+	pub fn remove_from(file: &mut File) -> Result<()> {
+		write_blocks(file, &[], |ty, _| ty == BLOCK_ID_CUESHEET, DEFAULT_PADDING).map(|_| ())
+	}
+}
+
+// Fixed size strings are padded with NUL characters
+fn read_string(reader: &mut &[u8], len: usize) -> Result<String> {
+	if reader.len() < len {
+		return Err(FileDecodingError::new(FileType::FLAC, "Cue sheet block is too short").into());
+	}
+
+	let (bytes, rest) = reader.split_at(len);
+	*reader = rest;
+
+	let end = bytes.iter().position(|b| *b == 0).unwrap_or(len);
+	if !bytes[..end].is_ascii() {
+		return Err(FileDecodingError::new(
+			FileType::FLAC,
+			"Cue sheet contains a non-ASCII string",
+		)
+		.into());
+	}
+
+	Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+fn write_string(writer: &mut Vec<u8>, value: &str, len: usize) -> Result<()> {
+	if value.len() > len || !value.is_ascii() {
+		return Err(FileEncodingError::new(
+			FileType::FLAC,
+			"Cue sheet strings must be ASCII, and fit in their fields",
+		)
+		.into());
+	}
+
+	writer.write_all(value.as_bytes())?;
+	writer.resize(writer.len() + (len - value.len()), 0);
+
+	Ok(())
+}
+
+fn skip(reader: &mut &[u8], len: u64) -> Result<()> {
+	let skipped = std::io::copy(&mut reader.take(len), &mut std::io::sink())?;
+	if skipped != len {
+		return Err(FileDecodingError::new(FileType::FLAC, "Cue sheet block is too short").into());
+	}
+
+	Ok(())
+}
+
+fn count(len: usize) -> Result<u8> {
+	u8::try_from(len).map_err(|_| {
+		FileEncodingError::new(
+			FileType::FLAC,
+			"Cue sheets can only have 255 tracks and index points per track",
+		)
+		.into()
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{CueSheet, CueSheetTrack, CueSheetTrackIndex};
+
+	fn cue_sheet() -> CueSheet {
+		CueSheet {
+			catalog_number: String::from("1234567890123"),
+			lead_in_samples: 88200,
+			is_compact_disc: true,
+			tracks: vec![
+				CueSheetTrack {
+					offset: 0,
+					number: 1,
+					isrc: String::from("USABC1234567"),
+					is_audio: true,
+					pre_emphasis: false,
+					indices: vec![
+						CueSheetTrackIndex {
+							offset: 0,
+							number: 0,
+						},
+						CueSheetTrackIndex {
+							offset: 588,
+							number: 1,
+						},
+					],
+				},
+				CueSheetTrack {
+					offset: 441_000,
+					number: 2,
+					isrc: String::new(),
+					is_audio: false,
+					pre_emphasis: true,
+					indices: vec![CueSheetTrackIndex {
+						offset: 0,
+						number: 1,
+					}],
+				},
+				CueSheetTrack {
+					offset: 882_000,
+					number: 170,
+					..CueSheetTrack::default()
+				},
+			],
+		}
+	}
+
+	#[test]
+	fn cue_sheet_round_trip() {
+		let cue_sheet = cue_sheet();
+		let bytes = cue_sheet.as_bytes().unwrap();
+
+		// Header (396) + 3 tracks (36 each) + 3 index points (12 each)
+		assert_eq!(bytes.len(), 396 + 3 * 36 + 3 * 12);
+
+		// The compact disc flag
+		assert_eq!(bytes[136], 0x80);
+
+		// The second track is a non-audio track with pre-emphasis
+		let second_track = 396 + 36 + 2 * 12;
+		assert_eq!(&bytes[second_track + 9..second_track + 21], &[0; 12]);
+		assert_eq!(bytes[second_track + 21], 0xC0);
+
+		assert_eq!(CueSheet::parse(&bytes).unwrap(), cue_sheet);
+	}
+
+	#[test]
+	fn cue_sheet_invalid() {
+		let mut cue_sheet = cue_sheet();
+		cue_sheet.tracks[0].isrc = String::from("FOO");
+		assert!(cue_sheet.as_bytes().is_err());
+
+		let bytes = self::cue_sheet().as_bytes().unwrap();
+		assert!(CueSheet::parse(&bytes[..bytes.len() - 1]).is_err());
+	}
+}
@@ -4,9 +4,11 @@
 //!
 //! * See [`FlacFile`]
 
+mod application;
 pub(crate) mod block;
+mod cue_sheet;
 pub(crate) mod properties;
-mod read;
+pub(crate) mod read;
 #[cfg(feature = "vorbis_comments")]
 pub(crate) mod write;
 
@@ -14,10 +16,19 @@ pub(crate) mod write;
 use crate::id3::v2::tag::ID3v2Tag;
 #[cfg(feature = "vorbis_comments")]
 use crate::ogg::VorbisComments;
-use crate::properties::FileProperties;
+
+use std::ops::Range;
 
 use lofty_attr::LoftyFile;
 
+// Exports
+
+pub use application::ApplicationBlock;
+pub use cue_sheet::{CueSheet, CueSheetTrack, CueSheetTrackIndex};
+pub use properties::FlacProperties;
+#[cfg(feature = "vorbis_comments")]
+pub use write::FlacWriteOptions;
+
 /// A FLAC file
 ///
 /// ## Notes
@@ -40,6 +51,35 @@ pub struct FlacFile {
 	#[cfg(feature = "vorbis_comments")]
 	#[lofty(tag_type = "VorbisComments")]
 	pub(crate) vorbis_comments_tag: Option<VorbisComments>,
+	/// The file's cue sheet
+	pub(crate) cue_sheet: Option<CueSheet>,
+	/// The file's application blocks
+	pub(crate) application_blocks: Vec<ApplicationBlock>,
 	/// The file's audio properties
-	pub(crate) properties: FileProperties,
+	pub(crate) properties: FlacProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
+}
+
+impl FlacFile {
+	/// Returns the file's [`CueSheet`], if it has one
+	///
+	/// To write a cue sheet, see [`CueSheet::save_to`].
+	pub fn cue_sheet(&self) -> Option<&CueSheet> {
+		self.cue_sheet.as_ref()
+	}
+
+	/// Returns the file's [`ApplicationBlock`]s, in the order they appear
+	///
+	/// To write an application block, see [`ApplicationBlock::save_to`].
+	pub fn application_blocks(&self) -> &[ApplicationBlock] {
+		&self.application_blocks
+	}
+
+	/// Returns all [`ApplicationBlock`]s with the specified ID
+	pub fn application_blocks_by_id(&self, id: [u8; 4]) -> impl Iterator<Item = &ApplicationBlock> {
+		self.application_blocks
+			.iter()
+			.filter(move |block| block.id == id)
+	}
 }
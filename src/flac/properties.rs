@@ -6,23 +6,136 @@ use std::time::Duration;
 
 use byteorder::{BigEndian, ReadBytesExt};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+/// A FLAC file's audio properties
+pub struct FlacProperties {
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) bit_depth: u8,
+	pub(crate) channels: u8,
+	pub(crate) min_block_size: u16,
+	pub(crate) max_block_size: u16,
+	pub(crate) min_frame_size: u32,
+	pub(crate) max_frame_size: u32,
+	pub(crate) md5_signature: [u8; 16],
+	pub(crate) total_samples: u64,
+	pub(crate) duration_estimated: bool,
+}
+
+impl From<FlacProperties> for FileProperties {
+	fn from(input: FlacProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: Some(input.bit_depth),
+			channels: Some(input.channels),
+		}
+	}
+}
+
+impl FlacProperties {
+	/// Duration
+	///
+	/// This is calculated from the total number of samples, unless it's unknown.
+	/// See [`FlacProperties::is_duration_estimated`].
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Whether the duration is an estimate
+	///
+	/// The total number of samples is allowed to be `0` (unknown), in which case the duration is
+	/// estimated from the stream length and the average frame size.
+	pub fn is_duration_estimated(&self) -> bool {
+		self.duration_estimated
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	///
+	/// This is the exact 20-bit value from STREAMINFO
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Bits per sample (4-32)
+	pub fn bit_depth(&self) -> u8 {
+		self.bit_depth
+	}
+
+	/// Channel count (1-8)
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+
+	/// Total number of samples (per channel)
+	///
+	/// NOTE: A value of `0` means the number of samples is unknown
+	pub fn total_samples(&self) -> u64 {
+		self.total_samples
+	}
+
+	/// The minimum block size (in samples) used in the stream
+	pub fn min_block_size(&self) -> u16 {
+		self.min_block_size
+	}
+
+	/// The maximum block size (in samples) used in the stream
+	pub fn max_block_size(&self) -> u16 {
+		self.max_block_size
+	}
+
+	/// The minimum frame size (in bytes) used in the stream
+	///
+	/// NOTE: A value of `0` means the size is unknown
+	pub fn min_frame_size(&self) -> u32 {
+		self.min_frame_size
+	}
+
+	/// The maximum frame size (in bytes) used in the stream
+	///
+	/// NOTE: A value of `0` means the size is unknown
+	pub fn max_frame_size(&self) -> u32 {
+		self.max_frame_size
+	}
+
+	/// The MD5 signature of the unencoded audio data
+	///
+	/// This is the value stored in the STREAMINFO block, it is **not** verified against the audio data.
+	///
+	/// This will be `None` if the signature is all zeros, meaning the encoder didn't calculate it.
+	pub fn md5_signature(&self) -> Option<[u8; 16]> {
+		(self.md5_signature != [0; 16]).then(|| self.md5_signature)
+	}
+}
+
 pub(crate) fn read_properties<R>(
 	stream_info: &mut R,
 	stream_length: u64,
 	file_length: u64,
-) -> Result<FileProperties>
+) -> Result<FlacProperties>
 where
 	R: Read,
 {
-	// Skip 4 bytes
-	// Minimum block size (2)
-	// Maximum block size (2)
-	stream_info.read_u32::<BigEndian>()?;
+	let min_block_size = stream_info.read_u16::<BigEndian>()?;
+	let max_block_size = stream_info.read_u16::<BigEndian>()?;
 
-	// Skip 6 bytes
-	// Minimum frame size (3)
-	// Maximum frame size (3)
-	stream_info.read_uint::<BigEndian>(6)?;
+	let min_frame_size = stream_info.read_u24::<BigEndian>()?;
+	let max_frame_size = stream_info.read_u24::<BigEndian>()?;
 
 	// Read 4 bytes
 	// Sample rate (20 bits)
@@ -36,24 +149,103 @@ where
 	let channels = ((info >> 9) & 7) + 1;
 
 	// Read the remaining 32 bits of the total samples
-	let total_samples = stream_info.read_u32::<BigEndian>()? | (info << 28);
+	let total_samples =
+		(u64::from(info & 0b1111) << 32) | u64::from(stream_info.read_u32::<BigEndian>()?);
 
-	let mut properties = FileProperties {
-		sample_rate: Some(sample_rate),
-		bit_depth: Some(bits_per_sample as u8),
-		channels: Some(channels as u8),
-		..FileProperties::default()
+	// Only the first 18 bytes of the block are required to read the properties,
+	// so a truncated signature is treated as unknown
+	let mut md5_signature = [0; 16];
+	if stream_info.read_exact(&mut md5_signature).is_err() {
+		md5_signature = [0; 16];
+	}
+
+	let mut properties = FlacProperties {
+		sample_rate,
+		bit_depth: bits_per_sample as u8,
+		channels: channels as u8,
+		min_block_size,
+		max_block_size,
+		min_frame_size,
+		max_frame_size,
+		md5_signature,
+		total_samples,
+		..FlacProperties::default()
 	};
 
-	if sample_rate > 0 && total_samples > 0 {
-		let length = (u64::from(total_samples) * 1000) / u64::from(sample_rate);
-		properties.duration = Duration::from_millis(length);
+	if sample_rate == 0 {
+		return Ok(properties);
+	}
 
-		if length > 0 && file_length > 0 && stream_length > 0 {
-			properties.overall_bitrate = Some(((file_length * 8) / length) as u32);
-			properties.audio_bitrate = Some(((stream_length * 8) / length) as u32);
+	let length = if total_samples > 0 {
+		(total_samples * 1000) / u64::from(sample_rate)
+	} else {
+		// Without the total samples, the best we can do is estimate the number of frames.
+		// This is only possible with a fixed block size, and known frame sizes.
+		if min_block_size != max_block_size || min_frame_size == 0 || max_frame_size == 0 {
+			return Ok(properties);
 		}
+
+		properties.duration_estimated = true;
+
+		// The stream length divided by the average frame size
+		let frames = (stream_length * 2) / (u64::from(min_frame_size) + u64::from(max_frame_size));
+		(frames * u64::from(max_block_size) * 1000) / u64::from(sample_rate)
+	};
+
+	properties.duration = Duration::from_millis(length);
+
+	if length > 0 && file_length > 0 && stream_length > 0 {
+		properties.overall_bitrate = ((file_length * 8) / length) as u32;
+		properties.audio_bitrate = ((stream_length * 8) / length) as u32;
 	}
 
 	Ok(properties)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::read_properties;
+
+	use std::time::Duration;
+
+	// A STREAMINFO block for a 44.1kHz, 16-bit stereo stream, with a fixed block size of 4096
+	fn stream_info(total_samples: u64) -> Vec<u8> {
+		let mut stream_info = Vec::new();
+		stream_info.extend(4096_u16.to_be_bytes());
+		stream_info.extend(4096_u16.to_be_bytes());
+		stream_info.extend(&1000_u32.to_be_bytes()[1..]);
+		stream_info.extend(&3000_u32.to_be_bytes()[1..]);
+
+		let info = (44100_u64 << 44) | (1 << 41) | (15 << 36) | total_samples;
+		stream_info.extend(info.to_be_bytes());
+		stream_info.extend([0; 16]);
+
+		stream_info
+	}
+
+	#[test]
+	fn total_samples() {
+		// Larger than 32 bits
+		let total_samples = 0x1_0000_0000;
+
+		let properties = read_properties(&mut &stream_info(total_samples)[..], 0, 0).unwrap();
+		assert_eq!(properties.total_samples(), total_samples);
+		assert_eq!(
+			properties.duration(),
+			Duration::from_millis(total_samples * 1000 / 44100)
+		);
+		assert!(!properties.is_duration_estimated());
+	}
+
+	#[test]
+	fn estimated_duration() {
+		// 100 frames with an average size of 2000 bytes, each holding 4096 samples
+		let properties = read_properties(&mut &stream_info(0)[..], 200_000, 200_000).unwrap();
+		assert_eq!(properties.total_samples(), 0);
+		assert_eq!(
+			properties.duration(),
+			Duration::from_millis(409_600 * 1000 / 44100)
+		);
+		assert!(properties.is_duration_estimated());
+	}
+}
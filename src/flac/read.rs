@@ -1,11 +1,16 @@
-use super::block::Block;
+use super::application::ApplicationBlock;
+use super::block::{
+	Block, BLOCK_ID_APPLICATION, BLOCK_ID_CUESHEET, BLOCK_ID_PICTURE, BLOCK_ID_VORBIS_COMMENTS,
+};
+use super::cue_sheet::CueSheet;
+use super::properties::FlacProperties;
 use super::FlacFile;
 use crate::error::{FileDecodingError, Result};
 use crate::file::FileType;
 #[cfg(feature = "id3v2")]
-use crate::id3::v2::read::parse_id3v2;
+use crate::id3::v2::read::parse_id3v2_with_options;
 use crate::id3::{find_id3v2, ID3FindResults};
-use crate::properties::FileProperties;
+use crate::probe::ParseOptions;
 #[cfg(feature = "vorbis_comments")]
 use crate::{
 	ogg::{read::read_comments, tag::VorbisComments},
@@ -40,7 +45,19 @@ where
 	Ok(block)
 }
 
-pub(crate) fn read_from<R>(data: &mut R, read_properties: bool) -> Result<FlacFile>
+pub(super) fn read_from<R>(data: &mut R, read_properties: bool) -> Result<FlacFile>
+where
+	R: Read + Seek,
+{
+	read_from_with_options(data, read_properties, &mut ParseOptions::new())
+}
+
+// Without `options.read_pictures`, `PICTURE` blocks are read as placeholders, skipping the picture data
+pub(crate) fn read_from_with_options<R>(
+	data: &mut R,
+	read_properties: bool,
+	options: &mut ParseOptions<'_>,
+) -> Result<FlacFile>
 where
 	R: Read + Seek,
 {
@@ -49,16 +66,20 @@ where
 		id3v2_tag: None,
 		#[cfg(feature = "vorbis_comments")]
 		vorbis_comments_tag: None,
-		properties: FileProperties::default(),
+		cue_sheet: None,
+		application_blocks: Vec::new(),
+		properties: FlacProperties::default(),
+		audio_data_range: 0..0,
 	};
 
 	// It is possible for a FLAC file to contain an ID3v2 tag
+	#[allow(unused_variables)]
 	if let ID3FindResults(Some(header), Some(content)) = find_id3v2(data, true)? {
 		#[cfg(feature = "id3v2")]
 		{
 			let reader = &mut &*content;
 
-			let id3v2 = parse_id3v2(reader, header)?;
+			let id3v2 = parse_id3v2_with_options(reader, header, options)?;
 			flac_file.id3v2_tag = Some(id3v2)
 		}
 	}
@@ -83,7 +104,43 @@ where
 		pictures: vec![],
 	};
 
+	// The combined size of the Vorbis Comments and `PICTURE` blocks
+	let mut tag_size = 0;
+	#[cfg(feature = "vorbis_comments")]
+	let mut picture_count = 0;
+
 	while !last_block {
+		let start = data.stream_position()?;
+		#[allow(unused_variables)]
+		let (ty, last, size) = Block::read_header(data)?;
+
+		if ty == BLOCK_ID_VORBIS_COMMENTS || ty == BLOCK_ID_PICTURE {
+			tag_size += u64::from(size);
+			options.limits.check_tag_size(tag_size)?;
+		}
+
+		#[cfg(feature = "vorbis_comments")]
+		if ty == BLOCK_ID_PICTURE {
+			let check = options.limits.check_picture_count(picture_count + 1);
+			if !options.keep_picture(check)? {
+				last_block = last;
+				data.seek(SeekFrom::Current(i64::from(size)))?;
+
+				continue;
+			}
+
+			if !options.read_pictures {
+				last_block = last;
+				picture_count += 1;
+				tag.pictures
+					.push(Picture::flac_placeholder(data, u64::from(size))?);
+
+				continue;
+			}
+		}
+
+		data.seek(SeekFrom::Start(start))?;
+
 		let block = Block::read(data)?;
 		last_block = block.last;
 
@@ -97,11 +154,32 @@ where
 
 		match block.ty {
 			#[cfg(feature = "vorbis_comments")]
-			4 => read_comments(&mut &*block.content, block.content.len() as u64, &mut tag)?,
+			4 => read_comments(
+				&mut &*block.content,
+				block.content.len() as u64,
+				&mut tag,
+				options,
+			)?,
 			#[cfg(feature = "vorbis_comments")]
-			6 => tag
-				.pictures
-				.push(Picture::from_flac_bytes(&block.content, false)?),
+			6 => {
+				let picture = Picture::from_flac_bytes(&block.content, false)?;
+
+				let check = options
+					.limits
+					.check_picture_size(picture.0.data().len() as u64);
+				if options.keep_picture(check)? {
+					picture_count += 1;
+					tag.pictures.push(picture)
+				}
+			},
+			// Invalid application blocks are skipped, they don't affect the rest of the file
+			BLOCK_ID_APPLICATION => {
+				if let Ok(application_block) = ApplicationBlock::parse(&block.content) {
+					flac_file.application_blocks.push(application_block);
+				}
+			},
+			// The cue sheet is optional, an invalid one is treated as missing
+			BLOCK_ID_CUESHEET => flac_file.cue_sheet = CueSheet::parse(&block.content).ok(),
 			_ => {},
 		}
 	}
@@ -116,13 +194,14 @@ where
 		let current = data.stream_position()?;
 		let end = data.seek(SeekFrom::End(0))?;
 
+		flac_file.audio_data_range = current..end;
 		(end - current, end)
 	};
 
 	flac_file.properties = if read_properties {
 		super::properties::read_properties(&mut &*stream_info.content, stream_length, file_length)?
 	} else {
-		FileProperties::default()
+		FlacProperties::default()
 	};
 
 	Ok(flac_file)
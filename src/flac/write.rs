@@ -1,177 +1,147 @@
-use super::block::Block;
-use super::read::verify_flac;
-use crate::error::Result;
-use crate::macros::err;
+use super::block::{
+	read_blocks, replace_blocks, MetadataBlocks, BLOCK_ID_PICTURE, BLOCK_ID_VORBIS_COMMENTS,
+	DEFAULT_PADDING,
+};
+use crate::error::{FileEncodingError, Result};
+use crate::file::FileType;
+use crate::ogg::constants::DEFAULT_VENDOR;
 use crate::ogg::tag::VorbisCommentsRef;
 use crate::ogg::write::create_comments;
 use crate::picture::{Picture, PictureInformation};
+use crate::tag::utils::verify_pictures_loaded;
 
 use std::fs::File;
-use std::io::{Cursor, Read, Seek, SeekFrom, Write};
-
-use byteorder::{LittleEndian, WriteBytesExt};
+use std::io::{Cursor, Seek, SeekFrom, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+/// Options to control how FLAC files are written
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lofty::flac::FlacWriteOptions;
+/// use lofty::ogg::VorbisComments;
+///
+/// # fn main() -> lofty::Result<()> {
+/// # let mut flac_file = std::fs::OpenOptions::new().read(true).write(true).open("foo.flac")?;
+/// let tag = VorbisComments::default();
+///
+/// // Leave 4KB of padding behind if the file needs to be rewritten
+/// tag.save_to_flac(&mut flac_file, FlacWriteOptions { padding: 4096 })?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FlacWriteOptions {
+	/// The size of the padding block to write when the file has to be rewritten
+	///
+	/// An existing padding block will always be used to absorb changes in size where possible,
+	/// so the audio data doesn't have to move. This is only used when there isn't enough padding,
+	/// or when the file has no padding block at all.
+	///
+	/// The default is 1024 bytes. A value of 0 will not write a padding block.
+	pub padding: u32,
+}
 
-const MAX_BLOCK_SIZE: u32 = 16_777_215;
+impl Default for FlacWriteOptions {
+	fn default() -> Self {
+		Self {
+			padding: DEFAULT_PADDING,
+		}
+	}
+}
 
 pub(crate) fn write_to<'a, II, IP>(
 	data: &mut File,
 	tag: &mut VorbisCommentsRef<'a, II, IP>,
-) -> Result<()>
+	options: FlacWriteOptions,
+) -> Result<bool>
 where
 	II: Iterator<Item = (&'a str, &'a str)>,
 	IP: Iterator<Item = (&'a Picture, PictureInformation)>,
 {
-	let stream_info = verify_flac(data)?;
-	let stream_info_end = stream_info.end as usize;
-
-	let mut last_block = stream_info.last;
-
-	let mut file_bytes = Vec::new();
-	data.read_to_end(&mut file_bytes)?;
-
-	let mut cursor = Cursor::new(file_bytes);
-
-	let mut padding = false;
-	let mut last_block_info = (
-		stream_info.byte,
-		stream_info_end - ((stream_info.end - stream_info.start) as u32 + 4) as usize,
-		stream_info_end,
-	);
-
-	let mut blocks_remove = Vec::new();
-
-	while !last_block {
-		let block = Block::read(&mut cursor)?;
-		let start = block.start;
-		let end = block.end;
+	let metadata = read_blocks(data)?;
 
-		let block_type = block.ty;
-		last_block = block.last;
+	let mut blocks = Vec::new();
 
-		if last_block {
-			last_block_info = (block.byte, (end - start) as usize, end as usize)
+	// Retain the file's vendor string, unless the tag has its own
+	let vendor = if tag.vendor.is_empty() {
+		match file_vendor(&metadata)? {
+			Some(file_vendor) if !file_vendor.is_empty() => file_vendor,
+			_ => DEFAULT_VENDOR.as_bytes(),
 		}
+	} else {
+		tag.vendor.as_bytes()
+	};
 
-		match block_type {
-			4 | 6 => blocks_remove.push((start, end)),
-			1 => padding = true,
-			_ => {},
-		}
+	if let Some(comment_block) = create_comment_block(vendor, &mut tag.items)? {
+		blocks.push((BLOCK_ID_VORBIS_COMMENTS, comment_block));
 	}
 
-	let mut file_bytes = cursor.into_inner();
-
-	if !padding {
-		let mut first_byte = 0_u8;
-		first_byte |= last_block_info.0 & 0x7F;
-
-		file_bytes[last_block_info.1] = first_byte;
-
-		let mut padding_block = [0; 1028];
-		let mut padding_byte = 0;
-		padding_byte |= 0x80;
-		padding_byte |= 1 & 0x7F;
-
-		padding_block[0] = padding_byte;
+	let pictures = tag.pictures.by_ref().collect::<Vec<_>>();
+	verify_pictures_loaded(pictures.iter().map(|(pic, _)| *pic))?;
 
-		// [0, 4, 0] = 1024
-		padding_block[2] = 4;
-
-		file_bytes.splice(last_block_info.2..last_block_info.2, padding_block);
+	for (pic, info) in pictures {
+		blocks.push((BLOCK_ID_PICTURE, pic.as_flac_bytes(info, false)));
 	}
 
-	let mut comment_blocks = Cursor::new(Vec::new());
-
-	create_comment_block(&mut comment_blocks, tag.vendor, &mut tag.items)?;
-
-	let mut comment_blocks = comment_blocks.into_inner();
-
-	create_picture_blocks(&mut comment_blocks, &mut tag.pictures)?;
-
-	if blocks_remove.is_empty() {
-		file_bytes.splice(0..0, comment_blocks);
-	} else {
-		blocks_remove.sort_unstable();
-		blocks_remove.reverse();
-
-		let first = blocks_remove.pop().unwrap(); // Infallible
-
-		for (s, e) in &blocks_remove {
-			file_bytes.drain(*s as usize..*e as usize);
-		}
+	replace_blocks(
+		data,
+		&metadata,
+		&blocks,
+		|ty, _| ty == BLOCK_ID_VORBIS_COMMENTS || ty == BLOCK_ID_PICTURE,
+		options.padding,
+	)
+}
 
-		file_bytes.splice(first.0 as usize..first.1 as usize, comment_blocks);
+// Gets the vendor string of the existing Vorbis Comments block, if there is one
+fn file_vendor(metadata: &MetadataBlocks) -> Result<Option<&[u8]>> {
+	let block = match metadata
+		.blocks
+		.iter()
+		.find(|block| block.ty == BLOCK_ID_VORBIS_COMMENTS)
+	{
+		Some(block) => block,
+		None => return Ok(None),
+	};
+
+	let mut content = block.content.as_slice();
+	let vendor_len = content.read_u32::<LittleEndian>()?;
+
+	match content.get(..vendor_len as usize) {
+		Some(vendor) => Ok(Some(vendor)),
+		None => Err(FileEncodingError::new(
+			FileType::FLAC,
+			"Vorbis Comments block has an invalid vendor string size",
+		)
+		.into()),
 	}
-
-	data.seek(SeekFrom::Start(stream_info_end as u64))?;
-	data.set_len(stream_info_end as u64)?;
-	data.write_all(&file_bytes)?;
-
-	Ok(())
 }
 
 fn create_comment_block(
-	writer: &mut Cursor<Vec<u8>>,
-	vendor: &str,
+	vendor: &[u8],
 	items: &mut dyn Iterator<Item = (&str, &str)>,
-) -> Result<()> {
+) -> Result<Option<Vec<u8>>> {
 	let mut peek = items.peekable();
 
-	if peek.peek().is_some() {
-		let mut byte = 0_u8;
-		byte |= 4 & 0x7F;
-
-		writer.write_u8(byte)?;
-		writer.write_u32::<LittleEndian>(vendor.len() as u32)?;
-		writer.write_all(vendor.as_bytes())?;
-
-		let item_count_pos = writer.stream_position()?;
-		let mut count = 0;
-
-		writer.write_u32::<LittleEndian>(count)?;
-
-		create_comments(writer, &mut count, &mut peek)?;
-
-		let len = (writer.get_ref().len() - 1) as u32;
-
-		if len > MAX_BLOCK_SIZE {
-			err!(TooMuchData);
-		}
-
-		let comment_end = writer.stream_position()?;
-
-		writer.seek(SeekFrom::Start(item_count_pos))?;
-		writer.write_u32::<LittleEndian>(count)?;
-
-		writer.seek(SeekFrom::Start(comment_end))?;
-		writer
-			.get_mut()
-			.splice(1..1, len.to_be_bytes()[1..].to_vec());
+	if peek.peek().is_none() {
+		return Ok(None);
 	}
 
-	Ok(())
-}
+	let mut writer = Cursor::new(Vec::new());
 
-fn create_picture_blocks(
-	writer: &mut Vec<u8>,
-	pictures: &mut dyn Iterator<Item = (&Picture, PictureInformation)>,
-) -> Result<()> {
-	let mut byte = 0_u8;
-	byte |= 6 & 0x7F;
+	writer.write_u32::<LittleEndian>(vendor.len() as u32)?;
+	writer.write_all(vendor)?;
 
-	for (pic, info) in pictures {
-		writer.write_u8(byte)?;
+	let item_count_pos = writer.stream_position()?;
+	let mut count = 0;
 
-		let pic_bytes = pic.as_flac_bytes(info, false);
-		let pic_len = pic_bytes.len() as u32;
+	writer.write_u32::<LittleEndian>(count)?;
 
-		if pic_len > MAX_BLOCK_SIZE {
-			err!(TooMuchData);
-		}
+	create_comments(&mut writer, &mut count, &mut peek)?;
 
-		writer.write_all(&pic_len.to_be_bytes()[1..])?;
-		writer.write_all(pic_bytes.as_slice())?;
-	}
+	writer.seek(SeekFrom::Start(item_count_pos))?;
+	writer.write_u32::<LittleEndian>(count)?;
 
-	Ok(())
+	Ok(Some(writer.into_inner()))
 }
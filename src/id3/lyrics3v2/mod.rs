@@ -0,0 +1,33 @@
+//! Lyrics3v2 items
+//!
+//! # Lyrics3v2 notes
+//!
+//! See also: [`Lyrics3v2Tag`]
+//!
+//! Lyrics3v2 tags are found at the end of MPEG files, after any APE tag and before the ID3v1 tag.
+//!
+//! ## Fields
+//!
+//! A Lyrics3v2 tag is made up of fields, each identified by a 3 character ID. These include:
+//!
+//! * `IND` - Indications (Ex. whether the lyrics contain timestamps)
+//! * `LYR` - Lyrics
+//! * `INF` - Additional information
+//! * `AUT` - Lyrics author
+//! * `EAL` - Extended album name
+//! * `EAR` - Extended artist name
+//! * `ETT` - Extended track title
+//! * `IMG` - Links to image files
+//!
+//! All values are stored as Latin-1 text, and are limited to 99,999 bytes.
+//! Characters outside of the Latin-1 range will be replaced with `?`.
+
+cfg_if::cfg_if! {
+	if #[cfg(feature = "lyrics3v2")] {
+		pub(crate) mod tag;
+		pub use tag::Lyrics3v2Tag;
+
+		pub(crate) mod read;
+		pub(crate) mod write;
+	}
+}
@@ -0,0 +1,50 @@
+use super::tag::Lyrics3v2Tag;
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+
+pub(super) const LYRICS3V2_BEGIN: &[u8] = b"LYRICSBEGIN";
+pub(super) const FIELD_SIZE_LEN: usize = 5;
+
+/// Parses the content of a Lyrics3v2 tag, excluding the size and "LYRICS200" at the end
+pub(crate) fn parse_lyrics3v2(content: &[u8]) -> Result<Lyrics3v2Tag> {
+	let decode_err = |message| FileDecodingError::new(FileType::MPEG, message);
+
+	let mut content = content
+		.strip_prefix(LYRICS3V2_BEGIN)
+		.ok_or_else(|| decode_err("Lyrics3v2 tag doesn't start with \"LYRICSBEGIN\""))?;
+
+	let mut tag = Lyrics3v2Tag::default();
+
+	while !content.is_empty() {
+		if content.len() < 3 + FIELD_SIZE_LEN {
+			return Err(decode_err("Lyrics3v2 tag contains an incomplete field").into());
+		}
+
+		let (id, rest) = content.split_at(3);
+		let (size, rest) = rest.split_at(FIELD_SIZE_LEN);
+
+		if !super::tag::verify_id(id) {
+			return Err(decode_err("Lyrics3v2 tag contains an invalid field ID").into());
+		}
+
+		let size = std::str::from_utf8(size)
+			.ok()
+			.and_then(|size| size.parse::<usize>().ok())
+			.ok_or_else(|| decode_err("Lyrics3v2 field has an invalid size string"))?;
+
+		if size > rest.len() {
+			return Err(decode_err("Lyrics3v2 field is larger than the tag").into());
+		}
+
+		let (value, rest) = rest.split_at(size);
+		content = rest;
+
+		// The values are Latin-1
+		let value = value.iter().map(|b| char::from(*b)).collect::<String>();
+		let id = String::from_utf8_lossy(id).into_owned();
+
+		tag.insert(id, value);
+	}
+
+	Ok(tag)
+}
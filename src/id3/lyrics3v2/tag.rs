@@ -0,0 +1,326 @@
+use crate::error::{LoftyError, Result};
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::{Language, LanguageFrame, TextEncoding};
+use crate::tag::item::{ItemKey, ItemValue, TagItem};
+use crate::tag::{Tag, TagType};
+use crate::traits::{Accessor, TagExt};
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+macro_rules! impl_accessor {
+	($($name:ident => $id:literal;)+) => {
+		paste::paste! {
+			$(
+				fn $name(&self) -> Option<&str> {
+					self.get($id)
+				}
+
+				fn [<set_ $name>](&mut self, value: String) {
+					self.insert(String::from($id), value)
+				}
+
+				fn [<remove_ $name>](&mut self) {
+					self.remove($id)
+				}
+			)+
+		}
+	}
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone)]
+/// A Lyrics3v2 tag
+///
+/// ## Supported file types
+///
+/// * [`FileType::MPEG`](crate::FileType::MPEG)
+///
+/// ## Conversions
+///
+/// ### From `Tag`
+///
+/// Two conditions must be met:
+///
+/// * The [`TagItem`] has a value other than [`ItemValue::Binary`](crate::ItemValue::Binary)
+/// * It has a key that maps to a Lyrics3v2 field, or is an unknown key made up of 3 uppercase ASCII letters
+pub struct Lyrics3v2Tag {
+	/// A collection of field ID-value pairs
+	pub(crate) fields: Vec<(String, String)>,
+}
+
+impl Lyrics3v2Tag {
+	/// Get a field by ID
+	pub fn get(&self, id: &str) -> Option<&str> {
+		self.fields
+			.iter()
+			.find(|(i, _)| i.eq_ignore_ascii_case(id))
+			.map(|(_, v)| v.as_str())
+	}
+
+	/// Insert a field
+	///
+	/// NOTE: This will do nothing if `id` is not made up of 3 ASCII letters
+	///
+	/// This will replace any field with the same ID
+	pub fn insert(&mut self, mut id: String, value: String) {
+		if verify_id(id.as_bytes()) {
+			id.make_ascii_uppercase();
+
+			self.remove(&id);
+			self.fields.push((id, value))
+		}
+	}
+
+	/// Remove a field by ID
+	pub fn remove(&mut self, id: &str) {
+		self.fields.retain(|(i, _)| !i.eq_ignore_ascii_case(id));
+	}
+
+	/// Returns the tag's fields in (ID, value) pairs
+	pub fn fields(&self) -> &[(String, String)] {
+		self.fields.as_slice()
+	}
+
+	/// Returns the lyrics (`LYR` field)
+	pub fn lyrics(&self) -> Option<&str> {
+		self.get("LYR")
+	}
+
+	/// Returns the additional information (`INF` field)
+	pub fn additional_info(&self) -> Option<&str> {
+		self.get("INF")
+	}
+
+	/// Returns the lyrics author (`AUT` field)
+	pub fn lyrics_author(&self) -> Option<&str> {
+		self.get("AUT")
+	}
+
+	/// Convert the lyrics into an ID3v2 `USLT` frame
+	///
+	/// The frame has an unknown language (`XXX`) and an empty description.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::lyrics3v2::Lyrics3v2Tag;
+	/// use lofty::id3::v2::{Frame, FrameFlags, FrameValue, ID3v2Tag};
+	///
+	/// let mut lyrics3v2 = Lyrics3v2Tag::default();
+	/// lyrics3v2.insert(String::from("LYR"), String::from("Foo lyrics"));
+	///
+	/// let mut id3v2 = ID3v2Tag::default();
+	///
+	/// if let Some(uslt) = lyrics3v2.to_unsync_text() {
+	/// 	let frame = Frame::new("USLT", FrameValue::UnSyncText(uslt), FrameFlags::default());
+	/// 	id3v2.insert(frame.unwrap());
+	/// }
+	/// ```
+	#[cfg(feature = "id3v2")]
+	pub fn to_unsync_text(&self) -> Option<LanguageFrame> {
+		self.lyrics().map(|lyrics| LanguageFrame {
+			encoding: TextEncoding::UTF8,
+			language: Language::UNKNOWN,
+			description: String::new(),
+			content: lyrics.to_string(),
+		})
+	}
+}
+
+impl Accessor for Lyrics3v2Tag {
+	impl_accessor!(
+		artist  => "EAR";
+		title   => "ETT";
+		album   => "EAL";
+		comment => "INF";
+	);
+}
+
+impl TagExt for Lyrics3v2Tag {
+	type Err = LoftyError;
+
+	fn is_empty(&self) -> bool {
+		self.fields.is_empty()
+	}
+
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err> {
+		self.save_to(&mut OpenOptions::new().read(true).write(true).open(path)?)
+	}
+
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err> {
+		Lyrics3v2TagRef {
+			fields: self.fields.iter().map(|(i, v)| (i.as_str(), v.as_str())),
+		}
+		.write_to(file)?;
+
+		Ok(true)
+	}
+
+	/// Dumps the tag to a writer
+	///
+	/// # Errors
+	///
+	/// * [`std::io::Error`]
+	/// * A field value is larger than 99,999 bytes
+	fn dump_to<W: Write>(&self, writer: &mut W) -> std::result::Result<(), Self::Err> {
+		Lyrics3v2TagRef {
+			fields: self.fields.iter().map(|(i, v)| (i.as_str(), v.as_str())),
+		}
+		.dump_to(writer)
+	}
+
+	fn remove_from_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+		TagType::Lyrics3v2.remove_from_path(path)
+	}
+
+	fn remove_from(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+		TagType::Lyrics3v2.remove_from(file)
+	}
+
+	fn clear(&mut self) {
+		self.fields.clear();
+	}
+}
+
+impl From<Lyrics3v2Tag> for Tag {
+	fn from(input: Lyrics3v2Tag) -> Self {
+		let mut tag = Tag::new(TagType::Lyrics3v2);
+
+		for (id, value) in input.fields {
+			let item_key = ItemKey::from_key(TagType::Lyrics3v2, &id);
+			tag.items
+				.push(TagItem::new(item_key, ItemValue::Text(value)));
+		}
+
+		tag
+	}
+}
+
+impl From<Tag> for Lyrics3v2Tag {
+	fn from(input: Tag) -> Self {
+		let mut lyrics3v2 = Lyrics3v2Tag::default();
+
+		for item in input.items {
+			if let ItemValue::Text(value) | ItemValue::Locator(value) = item.item_value {
+				if let Some(id) = item.item_key.map_key(TagType::Lyrics3v2, true) {
+					lyrics3v2.insert(id.to_string(), value);
+				}
+			}
+		}
+
+		lyrics3v2
+	}
+}
+
+pub(crate) struct Lyrics3v2TagRef<'a, I>
+where
+	I: Iterator<Item = (&'a str, &'a str)>,
+{
+	pub(crate) fields: I,
+}
+
+impl<'a, I> Lyrics3v2TagRef<'a, I>
+where
+	I: Iterator<Item = (&'a str, &'a str)>,
+{
+	pub(crate) fn write_to(&mut self, file: &mut File) -> Result<()> {
+		super::write::write_lyrics3v2(file, self)
+	}
+
+	pub(crate) fn dump_to<W: Write>(&mut self, writer: &mut W) -> Result<()> {
+		let temp = super::write::create_lyrics3v2(&mut self.fields)?;
+		writer.write_all(&temp)?;
+
+		Ok(())
+	}
+}
+
+pub(crate) fn tagitems_into_lyrics3v2(items: &[TagItem]) -> impl Iterator<Item = (&str, &str)> {
+	items.iter().filter_map(|i| {
+		let id = i.key().map_key(TagType::Lyrics3v2, true);
+
+		match (id, i.value()) {
+			(Some(id), ItemValue::Text(val) | ItemValue::Locator(val))
+				if verify_id(id.as_bytes()) =>
+			{
+				Some((id, val.as_str()))
+			},
+			_ => None,
+		}
+	})
+}
+
+pub(crate) fn verify_id(id: &[u8]) -> bool {
+	id.len() == 3 && id.iter().all(u8::is_ascii_alphabetic)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::id3::lyrics3v2::read::parse_lyrics3v2;
+	use crate::id3::lyrics3v2::Lyrics3v2Tag;
+	use crate::id3::v2::Language;
+	use crate::{Accessor, ItemKey, Tag, TagExt, TagType};
+
+	const LYRICS3V2: &[u8] =
+		b"LYRICSBEGININD0000210LYR00010Foo lyricsEAR00010Bar artistETT00009Foo title";
+
+	fn expected() -> Lyrics3v2Tag {
+		let mut tag = Lyrics3v2Tag::default();
+		tag.insert(String::from("IND"), String::from("10"));
+		tag.insert(String::from("LYR"), String::from("Foo lyrics"));
+		tag.insert(String::from("EAR"), String::from("Bar artist"));
+		tag.insert(String::from("ETT"), String::from("Foo title"));
+		tag
+	}
+
+	#[test]
+	fn parse_lyrics3v2_fields() {
+		let parsed = parse_lyrics3v2(LYRICS3V2).unwrap();
+
+		assert_eq!(parsed, expected());
+		assert_eq!(parsed.lyrics(), Some("Foo lyrics"));
+		assert_eq!(parsed.artist(), Some("Bar artist"));
+		assert_eq!(parsed.title(), Some("Foo title"));
+
+		// Incomplete fields
+		assert!(parse_lyrics3v2(&LYRICS3V2[..LYRICS3V2.len() - 1]).is_err());
+		assert!(parse_lyrics3v2(b"LYRICSBEGINLYR0001").is_err());
+	}
+
+	#[test]
+	fn lyrics3v2_re_read() {
+		let mut bytes = Vec::new();
+		expected().dump_to(&mut bytes).unwrap();
+
+		// The content is followed by the 6 digit size and "LYRICS200"
+		let content_len = bytes.len() - 15;
+		assert_eq!(&bytes[..content_len], LYRICS3V2);
+		assert_eq!(&bytes[content_len..], b"000074LYRICS200");
+
+		assert_eq!(parse_lyrics3v2(&bytes[..content_len]).unwrap(), expected());
+	}
+
+	#[test]
+	fn lyrics3v2_to_tag() {
+		let tag: Tag = expected().into();
+
+		assert_eq!(tag.tag_type(), TagType::Lyrics3v2);
+		assert_eq!(tag.get_string(&ItemKey::Lyrics), Some("Foo lyrics"));
+		assert_eq!(tag.get_string(&ItemKey::TrackArtist), Some("Bar artist"));
+		assert_eq!(tag.get_string(&ItemKey::TrackTitle), Some("Foo title"));
+
+		let lyrics3v2: Lyrics3v2Tag = tag.into();
+		assert_eq!(lyrics3v2, expected());
+	}
+
+	#[test]
+	#[cfg(feature = "id3v2")]
+	fn lyrics3v2_to_uslt() {
+		let uslt = expected().to_unsync_text().unwrap();
+
+		assert_eq!(uslt.language, Language::UNKNOWN);
+		assert_eq!(uslt.content, "Foo lyrics");
+		assert!(Lyrics3v2Tag::default().to_unsync_text().is_none());
+	}
+}
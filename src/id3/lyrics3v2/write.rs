@@ -0,0 +1,103 @@
+use super::read::{FIELD_SIZE_LEN, LYRICS3V2_BEGIN};
+use super::tag::Lyrics3v2TagRef;
+use crate::error::{FileEncodingError, Result};
+use crate::file::FileType;
+use crate::id3::{find_id3v1, find_lyrics3v2, ID3FindResults};
+use crate::macros::err;
+use crate::probe::Probe;
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+// The 6 digit tag size, followed by "LYRICS200"
+const FOOTER_LEN: u64 = 15;
+
+const MAX_FIELD_SIZE: usize = 99_999;
+const MAX_TAG_SIZE: usize = 999_999;
+
+pub(crate) fn write_lyrics3v2<'a, I>(
+	file: &mut File,
+	tag: &mut Lyrics3v2TagRef<'a, I>,
+) -> Result<()>
+where
+	I: Iterator<Item = (&'a str, &'a str)>,
+{
+	let probe = Probe::new(file).guess_file_type()?;
+
+	match probe.file_type() {
+		Some(FileType::MPEG) => {},
+		_ => err!(UnsupportedTag),
+	}
+
+	let file = probe.into_inner();
+
+	let lyrics3v2 = create_lyrics3v2(&mut tag.fields)?;
+
+	// The tag goes right before the ID3v1 tag, replacing any existing tag
+	find_id3v1(file, false)?;
+	let id3v1_start = file.stream_position()?;
+
+	let mut lyrics3v2_start = id3v1_start;
+	if id3v1_start >= FOOTER_LEN {
+		let ID3FindResults(header, _) = find_lyrics3v2(file)?;
+		if header.is_some() {
+			lyrics3v2_start = file.stream_position()?;
+		}
+	}
+
+	file.seek(SeekFrom::Start(id3v1_start))?;
+
+	let mut trailing = Vec::new();
+	file.read_to_end(&mut trailing)?;
+
+	file.seek(SeekFrom::Start(lyrics3v2_start))?;
+	file.write_all(&lyrics3v2)?;
+	file.write_all(&trailing)?;
+
+	let new_len = file.stream_position()?;
+	file.set_len(new_len)?;
+
+	Ok(())
+}
+
+pub(super) fn create_lyrics3v2<'a>(
+	fields: &mut dyn Iterator<Item = (&'a str, &'a str)>,
+) -> Result<Vec<u8>> {
+	let mut fields = fields.filter(|(_, value)| !value.is_empty()).peekable();
+
+	// Unnecessary to write anything if there's no metadata
+	if fields.peek().is_none() {
+		return Ok(Vec::new());
+	}
+
+	let mut content = LYRICS3V2_BEGIN.to_vec();
+
+	for (id, value) in fields {
+		// Anything outside of the Latin-1 range is replaced
+		let value = value
+			.chars()
+			.map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+			.collect::<Vec<u8>>();
+
+		if value.len() > MAX_FIELD_SIZE {
+			return Err(FileEncodingError::new(
+				FileType::MPEG,
+				"Lyrics3v2 field values must be at most 99,999 bytes",
+			)
+			.into());
+		}
+
+		content.extend(id.as_bytes());
+		content.extend(format!("{:0width$}", value.len(), width = FIELD_SIZE_LEN).as_bytes());
+		content.extend(value);
+	}
+
+	if content.len() > MAX_TAG_SIZE {
+		err!(TooMuchData);
+	}
+
+	content.extend(format!("{:06}", content.len()).as_bytes());
+	content.extend(b"LYRICS200");
+
+	Ok(content)
+}
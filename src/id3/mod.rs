@@ -3,6 +3,7 @@
 //! ID3 does things differently than other tags, making working with them a little more effort than other formats.
 //! Check the other modules for important notes and/or warnings.
 
+pub mod lyrics3v2;
 pub mod v1;
 pub mod v2;
 
@@ -22,15 +23,21 @@ where
 	let mut header = None;
 	let mut size = 0_u32;
 
+	// Reader is too small to contain a Lyrics3v2 tag
+	if data.stream_position()? < 15 {
+		return Ok(ID3FindResults(header, size));
+	}
+
 	data.seek(SeekFrom::Current(-15))?;
 
 	let mut lyrics3v2 = [0; 15];
 	data.read_exact(&mut lyrics3v2)?;
 
-	if &lyrics3v2[7..] == b"LYRICS200" {
+	// The tag ends with a 6 digit size, followed by "LYRICS200"
+	if &lyrics3v2[6..] == b"LYRICS200" {
 		header = Some(());
 
-		let lyrics_size = std::str::from_utf8(&lyrics3v2[..7])?;
+		let lyrics_size = std::str::from_utf8(&lyrics3v2[..6])?;
 		let lyrics_size = lyrics_size.parse::<u32>().map_err(|_| {
 			LoftyError::new(ErrorKind::TextDecode(
 				"Lyrics3v2 tag has an invalid size string",
@@ -61,6 +68,11 @@ where
 	let mut id3v1 = None;
 	let mut header = None;
 
+	// Reader is too small to contain an ID3v1 tag
+	if data.seek(SeekFrom::End(0))? < 128 {
+		return Ok(ID3FindResults(header, id3v1));
+	}
+
 	data.seek(SeekFrom::End(-128))?;
 
 	let mut id3v1_header = [0; 3];
@@ -40,18 +40,24 @@ macro_rules! impl_accessor {
 ///
 /// ## Supported file types
 ///
+/// * [`FileType::AAC`](crate::FileType::AAC)
 /// * [`FileType::APE`](crate::FileType::APE)
 /// * [`FileType::MP3`](crate::FileType::MPEG)
+/// * [`FileType::Musepack`](crate::FileType::Musepack)
+/// * [`FileType::TTA`](crate::FileType::TTA)
 /// * [`FileType::WavPack`](crate::FileType::WavPack)
 ///
 /// ## Conversions
 ///
 /// ### From `Tag`
 ///
-/// Two checks are performed when converting a genre:
+/// The following checks are performed when converting a genre:
 ///
-/// * [`GENRES`] contains the string
-/// * The [`ItemValue`](crate::ItemValue) can be parsed into a `u8`
+/// * [`GENRES`] contains the string (case-insensitive)
+/// * The [`ItemValue`](crate::ItemValue) is a genre index, such as "17" or "(17)"
+/// * [`GENRES`] contains a string that only differs in punctuation and spacing (Ex. "Hip Hop" => "Hip-Hop")
+///
+/// Indices outside of [`GENRES`] (such as 255) are treated as an unknown genre.
 #[derive(Default, Debug, PartialEq, Eq, Clone)]
 pub struct ID3v1Tag {
 	/// Track title, 30 bytes max
@@ -68,7 +74,7 @@ pub struct ID3v1Tag {
 	/// A V1 tag may have been read, which limits this field to 30 bytes.
 	/// A V1.1 tag, however, only has 28 bytes available.
 	///
-	/// **Lofty** will *always* write a V1.1 tag.
+	/// **Lofty** will write a V1.1 tag if there is a track number, otherwise the comment can use all 30 bytes.
 	pub comment: Option<String>,
 	/// The track number, 1 byte max
 	///
@@ -78,6 +84,8 @@ pub struct ID3v1Tag {
 	/// look for a null byte at the end of the comment to differentiate
 	/// between V1 and V1.1.
 	/// * A V1 tag may have been read, which does *not* have a track number.
+	///
+	/// A track number of 0 is treated as no track number.
 	pub track_number: Option<u8>,
 	/// The track's genre, 1 byte max
 	///
@@ -90,25 +98,13 @@ impl Accessor for ID3v1Tag {
 	impl_accessor!(title, artist, album,);
 
 	fn genre(&self) -> Option<&str> {
-		if let Some(g) = self.genre {
-			let g = g as usize;
-
-			if g < GENRES.len() {
-				return Some(GENRES[g]);
-			}
-		}
-
-		None
+		// Anything outside of the table, such as the commonly used 255, is unknown
+		self.genre.and_then(|g| GENRES.get(g as usize)).copied()
 	}
 
 	fn set_genre(&mut self, genre: String) {
-		let g_str = genre.as_str();
-
-		for (i, g) in GENRES.iter().enumerate() {
-			if g.eq_ignore_ascii_case(g_str) {
-				self.genre = Some(i as u8);
-				break;
-			}
+		if let Some(index) = genre_index(&genre) {
+			self.genre = Some(index);
 		}
 	}
 
@@ -121,7 +117,7 @@ impl Accessor for ID3v1Tag {
 	}
 
 	fn set_track(&mut self, value: u32) {
-		self.track_number = Some(value as u8);
+		self.track_number = u8::try_from(value).ok().filter(|track| *track != 0);
 	}
 
 	fn remove_track(&mut self) {
@@ -181,11 +177,11 @@ impl TagExt for ID3v1Tag {
 			&& self.genre.is_none()
 	}
 
-	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err> {
 		self.save_to(&mut OpenOptions::new().read(true).write(true).open(path)?)
 	}
 
-	fn save_to(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err> {
 		Into::<Id3v1TagRef<'_>>::into(self).write_to(file)
 	}
 
@@ -250,21 +246,56 @@ impl From<Tag> for ID3v1Tag {
 			comment: input.get_string(&ItemKey::Comment).map(str::to_owned),
 			track_number: input
 				.get_string(&ItemKey::TrackNumber)
-				.map(|g| g.parse::<u8>().ok())
-				.and_then(|g| g),
-			genre: input
-				.get_string(&ItemKey::Genre)
-				.map(|g| {
-					GENRES
-						.iter()
-						.position(|v| v == &g)
-						.map_or_else(|| g.parse::<u8>().ok(), |p| Some(p as u8))
-				})
-				.and_then(|g| g),
+				.and_then(|t| t.parse::<u8>().ok())
+				.filter(|t| *t != 0),
+			genre: input.get_string(&ItemKey::Genre).and_then(genre_index),
 		}
 	}
 }
 
+/// Finds the index of a genre in [`GENRES`]
+///
+/// If there is no exact (case-insensitive) match, this will try:
+///
+/// * A numeric genre, such as "17" or ID3v2's "(17)"
+/// * The nearest name, ignoring punctuation and spacing, and treating "&" as "and" (Ex. "Hip Hop" => "Hip-Hop")
+pub(crate) fn genre_index(genre: &str) -> Option<u8> {
+	fn normalize(genre: &str) -> String {
+		genre
+			.replace('&', "and")
+			.chars()
+			.filter(char::is_ascii_alphanumeric)
+			.map(|c| c.to_ascii_lowercase())
+			.collect()
+	}
+
+	let genre = genre.trim();
+
+	if let Some(index) = GENRES.iter().position(|g| g.eq_ignore_ascii_case(genre)) {
+		return Some(index as u8);
+	}
+
+	// ID3v2 genres may reference an ID3v1 genre as "(17)", optionally followed by a name
+	let numeric = genre
+		.strip_prefix('(')
+		.and_then(|g| g.split_once(')'))
+		.map_or(genre, |(index, _)| index);
+
+	if let Ok(index) = numeric.parse::<u8>() {
+		return (usize::from(index) < GENRES.len()).then(|| index);
+	}
+
+	let normalized = normalize(genre);
+	if normalized.is_empty() {
+		return None;
+	}
+
+	GENRES
+		.iter()
+		.position(|g| normalize(g) == normalized)
+		.map(|index| index as u8)
+}
+
 pub(crate) struct Id3v1TagRef<'a> {
 	pub title: Option<&'a str>,
 	pub artist: Option<&'a str>,
@@ -299,17 +330,9 @@ impl<'a> Into<Id3v1TagRef<'a>> for &'a Tag {
 			comment: self.get_string(&ItemKey::Comment),
 			track_number: self
 				.get_string(&ItemKey::TrackNumber)
-				.map(|g| g.parse::<u8>().ok())
-				.and_then(|g| g),
-			genre: self
-				.get_string(&ItemKey::Genre)
-				.map(|g| {
-					GENRES
-						.iter()
-						.position(|v| v == &g)
-						.map_or_else(|| g.parse::<u8>().ok(), |p| Some(p as u8))
-				})
-				.and_then(|g| g),
+				.and_then(|t| t.parse::<u8>().ok())
+				.filter(|t| *t != 0),
+			genre: self.get_string(&ItemKey::Genre).and_then(genre_index),
 		}
 	}
 }
@@ -325,7 +348,7 @@ impl<'a> Id3v1TagRef<'a> {
 			&& self.genre.is_none()
 	}
 
-	pub(crate) fn write_to(&self, file: &mut File) -> Result<()> {
+	pub(crate) fn write_to(&self, file: &mut File) -> Result<bool> {
 		super::write::write_id3v1(file, self)
 	}
 
@@ -396,4 +419,73 @@ mod tests {
 		assert_eq!(id3v1_tag.track_number, Some(1));
 		assert_eq!(id3v1_tag.genre, Some(32));
 	}
+
+	#[test]
+	fn id3v1_1_track_number() {
+		use crate::Accessor;
+
+		let comment = "A comment that is 30 bytes...!";
+
+		let mut tag = ID3v1Tag {
+			comment: Some(String::from(comment)),
+			track_number: Some(5),
+			..ID3v1Tag::default()
+		};
+
+		// With a track number, the comment is limited to 28 bytes
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+		assert_eq!(&writer[125..127], &[0, 5]);
+
+		let parsed_tag = crate::id3::v1::read::parse_id3v1(writer.try_into().unwrap());
+		assert_eq!(parsed_tag.track(), Some(5));
+		assert_eq!(parsed_tag.comment.as_deref(), Some(&comment[..28]));
+
+		// Without one, the full 30 bytes are used
+		tag.set_track(0);
+		assert_eq!(tag.track_number, None);
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		let parsed_tag = crate::id3::v1::read::parse_id3v1(writer.try_into().unwrap());
+		assert_eq!(parsed_tag.track(), None);
+		assert_eq!(parsed_tag.comment.as_deref(), Some(comment));
+	}
+
+	#[test]
+	fn genre_mapping() {
+		use crate::Accessor;
+
+		let mut tag = ID3v1Tag {
+			genre: Some(17),
+			..ID3v1Tag::default()
+		};
+		assert_eq!(tag.genre(), Some("Rock"));
+
+		// Unknown indices
+		tag.genre = Some(192);
+		assert_eq!(tag.genre(), None);
+		tag.genre = Some(255);
+		assert_eq!(tag.genre(), None);
+
+		for (genre, index) in [
+			("rock", 17),
+			("(17)", 17),
+			("(17)Rock", 17),
+			("79", 79),
+			("Hip Hop", 7),
+			("rhythmic soul", 118),
+			("Drum & Bass", 127),
+		] {
+			tag.genre = None;
+			tag.set_genre(String::from(genre));
+			assert_eq!(tag.genre, Some(index), "{}", genre);
+		}
+
+		// No match leaves the genre untouched
+		tag.set_genre(String::from("Not a genre"));
+		tag.set_genre(String::from("255"));
+		assert_eq!(tag.genre, Some(127));
+	}
 }
@@ -6,16 +6,23 @@ use crate::macros::err;
 use crate::probe::Probe;
 
 use std::fs::File;
-use std::io::{Cursor, Read, Seek, Write};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
 use byteorder::WriteBytesExt;
 
 #[allow(clippy::shadow_unrelated)]
-pub(crate) fn write_id3v1(writer: &mut File, tag: &Id3v1TagRef<'_>) -> Result<()> {
+pub(crate) fn write_id3v1(writer: &mut File, tag: &Id3v1TagRef<'_>) -> Result<bool> {
 	let probe = Probe::new(writer).guess_file_type()?;
 
 	match probe.file_type() {
-		Some(FileType::APE | FileType::MPEG | FileType::WavPack) => {},
+		Some(
+			FileType::AAC
+			| FileType::APE
+			| FileType::MPEG
+			| FileType::Musepack
+			| FileType::TTA
+			| FileType::WavPack,
+		) => {},
 		_ => err!(UnsupportedTag),
 	}
 
@@ -24,7 +31,12 @@ pub(crate) fn write_id3v1(writer: &mut File, tag: &Id3v1TagRef<'_>) -> Result<()
 	// This will seek us to the writing position
 	let ID3FindResults(header, _) = find_id3v1(writer, false)?;
 
-	if tag.is_empty() && header.is_some() {
+	if tag.is_empty() {
+		// Nothing to remove
+		if header.is_none() {
+			return Ok(false);
+		}
+
 		writer.rewind()?;
 
 		let mut file_bytes = Vec::new();
@@ -34,14 +46,26 @@ pub(crate) fn write_id3v1(writer: &mut File, tag: &Id3v1TagRef<'_>) -> Result<()
 		writer.set_len(0)?;
 		writer.write_all(&file_bytes[..file_bytes.len() - 128])?;
 
-		return Ok(());
+		return Ok(true);
 	}
 
 	let tag = encode(tag)?;
 
+	// Nothing to do if the tag is identical to the existing one
+	if header.is_some() {
+		let mut existing = [0; 128];
+		writer.read_exact(&mut existing)?;
+
+		if existing[..] == tag[..] {
+			return Ok(false);
+		}
+
+		writer.seek(SeekFrom::Current(-128))?;
+	}
+
 	writer.write_all(&tag)?;
 
-	Ok(())
+	Ok(true)
 }
 
 pub(super) fn encode(tag: &Id3v1TagRef<'_>) -> std::io::Result<Vec<u8>> {
@@ -76,12 +100,21 @@ pub(super) fn encode(tag: &Id3v1TagRef<'_>) -> std::io::Result<Vec<u8>> {
 	let year = resize_string(tag.year, 4)?;
 	writer.write_all(&year)?;
 
-	let comment = resize_string(tag.comment, 28)?;
-	writer.write_all(&comment)?;
-
-	writer.write_u8(0)?;
+	// ID3v1.1 uses the last 2 bytes of the comment for a null terminator and the track number
+	match tag.track_number.filter(|t| *t != 0) {
+		Some(track_number) => {
+			let comment = resize_string(tag.comment, 28)?;
+			writer.write_all(&comment)?;
+
+			writer.write_u8(0)?;
+			writer.write_u8(track_number)?;
+		},
+		None => {
+			let comment = resize_string(tag.comment, 30)?;
+			writer.write_all(&comment)?;
+		},
+	}
 
-	writer.write_u8(tag.track_number.unwrap_or(0))?;
 	writer.write_u8(tag.genre.unwrap_or(255))?;
 
 	Ok(writer)
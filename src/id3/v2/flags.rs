@@ -6,6 +6,8 @@ use super::restrictions::TagRestrictions;
 /// Flags that apply to the entire tag
 pub struct ID3v2TagFlags {
 	/// Whether or not all frames are unsynchronised. See [`FrameFlags::unsynchronisation`](crate::id3::v2::FrameFlags::unsynchronisation)
+	///
+	/// When writing, an `ID3v2.4` tag will unsynchronise each frame, while an `ID3v2.3` tag is unsynchronised as a whole.
 	pub unsynchronisation: bool,
 	/// Indicates if the tag is in an experimental stage
 	pub experimental: bool,
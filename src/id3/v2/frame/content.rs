@@ -1,8 +1,12 @@
 use crate::error::{ID3v2Error, ID3v2ErrorKind, LoftyError, Result};
 use crate::id3::v2::frame::FrameValue;
+use crate::id3::v2::items::chapter::{Chapter, TableOfContents};
+use crate::id3::v2::items::encapsulated_object::GeneralEncapsulatedObject;
 use crate::id3::v2::items::encoded_text_frame::EncodedTextFrame;
+use crate::id3::v2::items::language::Language;
 use crate::id3::v2::items::language_frame::LanguageFrame;
 use crate::id3::v2::items::popularimeter::Popularimeter;
+use crate::id3::v2::items::sync_text::SynchronizedText;
 use crate::id3::v2::util::text_utils::{
 	decode_text, read_to_terminator, utf16_decode, TextEncoding,
 };
@@ -34,8 +38,19 @@ pub(super) fn parse_content(
 		// WFED (Podcast URL), GRP1 (Grouping), MVNM (Movement Name), MVIN (Movement Number)
 		"WFED" | "GRP1" | "MVNM" | "MVIN" => parse_text(content, version)?,
 		_ if id.starts_with('W') => parse_link(content)?,
-		"POPM" => Some(parse_popularimeter(content)?),
-		// SYLT, GEOB, and any unknown frames
+		"POPM" => Some(FrameValue::Popularimeter(Popularimeter::parse(content)?)),
+		"SYLT" => Some(FrameValue::SynchronizedText(SynchronizedText::parse(
+			content,
+		)?)),
+		"CHAP" => Some(FrameValue::Chapter(Chapter::parse(content, version)?)),
+		"CTOC" => Some(FrameValue::TableOfContents(TableOfContents::parse(
+			content, version,
+		)?)),
+		"GEOB" => Some(FrameValue::GeneralEncapsulatedObject(
+			GeneralEncapsulatedObject::parse(content)?,
+		)),
+		"PRIV" => parse_private(content)?,
+		// Any unknown frames
 		_ => Some(FrameValue::Binary(content.to_vec())),
 	})
 }
@@ -124,7 +139,7 @@ fn parse_text_language(
 
 	let information = LanguageFrame {
 		encoding,
-		language,
+		language: Language::from_bytes(language),
 		description: description.unwrap_or_default(),
 		content,
 	};
@@ -152,41 +167,20 @@ fn parse_text(content: &mut &[u8], version: ID3v2Version) -> Result<Option<Frame
 	}))
 }
 
-fn parse_link(content: &mut &[u8]) -> Result<Option<FrameValue>> {
-	if content.is_empty() {
-		return Ok(None);
-	}
+fn parse_private(content: &mut &[u8]) -> Result<Option<FrameValue>> {
+	let owner = decode_text(content, TextEncoding::Latin1, true)?.unwrap_or_default();
 
-	let link = decode_text(content, TextEncoding::Latin1, true)?.unwrap_or_default();
-
-	Ok(Some(FrameValue::URL(link)))
+	Ok(Some(FrameValue::Private {
+		owner,
+		data: content.to_vec(),
+	}))
 }
 
-fn parse_popularimeter(content: &mut &[u8]) -> Result<FrameValue> {
-	let email = decode_text(content, TextEncoding::Latin1, true)?;
-	let rating = content.read_u8()?;
-
-	let counter;
-	let remaining_size = content.len();
-	if remaining_size > 8 {
-		counter = u64::MAX;
-	} else {
-		let mut c = Vec::with_capacity(8);
-		content.read_to_end(&mut c)?;
-
-		let needed_zeros = 8 - remaining_size;
-		for _ in 0..needed_zeros {
-			c.insert(0, 0);
-		}
-
-		counter = u64::from_be_bytes(c.try_into().unwrap());
-	}
+// An empty link is still kept, so the frame isn't lost
+fn parse_link(content: &mut &[u8]) -> Result<Option<FrameValue>> {
+	let link = decode_text(content, TextEncoding::Latin1, true)?.unwrap_or_default();
 
-	Ok(FrameValue::Popularimeter(Popularimeter {
-		email: email.unwrap_or_default(),
-		rating,
-		counter,
-	}))
+	Ok(Some(FrameValue::URL(link)))
 }
 
 fn verify_encoding(encoding: u8, version: ID3v2Version) -> Result<TextEncoding> {
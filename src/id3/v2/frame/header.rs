@@ -32,13 +32,10 @@ where
 	Ok(Some((frame_id, size, FrameFlags::default())))
 }
 
-pub(crate) fn parse_header<R>(
-	reader: &mut R,
+pub(crate) fn parse_header(
+	reader: &mut &[u8],
 	synchsafe: bool,
-) -> Result<Option<(FrameID, u32, FrameFlags)>>
-where
-	R: Read,
-{
+) -> Result<Option<(FrameID, u32, FrameFlags)>> {
 	let mut frame_header = [0; 10];
 	match reader.read_exact(&mut frame_header) {
 		Ok(_) => {},
@@ -62,7 +59,7 @@ where
 	let mut id_str = std::str::from_utf8(&frame_header[..frame_id_end])
 		.map_err(|_| ID3v2Error::new(ID3v2ErrorKind::BadFrameID))?;
 
-	let mut size = u32::from_be_bytes([
+	let size = u32::from_be_bytes([
 		frame_header[4],
 		frame_header[5],
 		frame_header[6],
@@ -78,10 +75,7 @@ where
 		id_str = upgrade_v3(id_str).unwrap_or(id_str);
 	}
 
-	// unsynch the frame size if necessary
-	if synchsafe {
-		size = crate::id3::v2::util::unsynch_u32(size);
-	}
+	let size = frame_size(size, synchsafe, reader);
 
 	let frame_id = FrameID::new(id_str)?;
 
@@ -91,6 +85,43 @@ where
 	Ok(Some((frame_id, size, flags)))
 }
 
+// Get the real size of an ID3v2.3/4 frame from its header, unsynching it if necessary
+//
+// Some encoders write ID3v2.4 frame sizes as plain integers. A synchsafe integer
+// never has the high bit of a byte set, so those sizes can be used as-is. Otherwise,
+// the plain size is only used if it leads to the next frame and the synchsafe size doesn't.
+//
+// `following` is the data following the frame header, up to the end of the tag.
+pub(crate) fn frame_size(size: u32, synchsafe: bool, following: &[u8]) -> u32 {
+	if !synchsafe || size & 0x8080_8080 != 0 {
+		return size;
+	}
+
+	let unsynched = crate::id3::v2::util::unsynch_u32(size);
+	if unsynched != size
+		&& !next_frame_follows(following, unsynched)
+		&& next_frame_follows(following, size)
+	{
+		return size;
+	}
+
+	unsynched
+}
+
+// Whether a frame of `size` is followed by another frame header, padding, or the end of the tag
+fn next_frame_follows(following: &[u8], size: u32) -> bool {
+	match following.get(size as usize..) {
+		Some([] | [0, ..]) => true,
+		Some(next) => {
+			next.len() >= 10
+				&& next[..4]
+					.iter()
+					.all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+		},
+		None => false,
+	}
+}
+
 pub(crate) fn parse_flags(flags: u16, v4: bool) -> FrameFlags {
 	FrameFlags {
 		tag_alter_preservation: if v4 {
@@ -39,6 +39,11 @@ impl FrameID {
 		}
 	}
 
+	// Whether `id` is a valid ID3v2.3/4 frame ID
+	pub(crate) fn is_frame_id(id: &str) -> bool {
+		id.len() == 4 && Self::verify_id(id).is_ok()
+	}
+
 	pub(crate) fn verify_id(id_str: &str) -> Result<()> {
 		for c in id_str.chars() {
 			if !('A'..='Z').contains(&c) && !('0'..='9').contains(&c) {
@@ -63,10 +68,14 @@ impl TryFrom<&ItemKey> for FrameID {
 			{
 				Ok(Self::Valid(unknown.clone()))
 			},
-			k => k.map_key(TagType::ID3v2, false).map_or(
-				Err(ID3v2Error::new(ID3v2ErrorKind::BadFrameID).into()),
-				|id| Ok(Self::Valid(id.to_string())),
-			),
+			// Some keys map to TXXX/WXXX descriptions, rather than frame IDs
+			k => k
+				.map_key(TagType::ID3v2, false)
+				.filter(|id| Self::is_frame_id(id))
+				.map_or(
+					Err(ID3v2Error::new(ID3v2ErrorKind::BadFrameID).into()),
+					|id| Ok(Self::Valid(id.to_string())),
+				),
 		}
 	}
 }
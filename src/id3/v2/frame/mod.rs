@@ -1,13 +1,17 @@
 pub(super) mod content;
-mod header;
+pub(super) mod header;
 pub(super) mod id;
 pub(super) mod read;
 
 use super::util::text_utils::TextEncoding;
 use crate::error::{ID3v2Error, ID3v2ErrorKind, LoftyError, Result};
+use crate::id3::v2::items::chapter::{Chapter, TableOfContents};
+use crate::id3::v2::items::encapsulated_object::GeneralEncapsulatedObject;
 use crate::id3::v2::items::encoded_text_frame::EncodedTextFrame;
+use crate::id3::v2::items::language::Language;
 use crate::id3::v2::items::language_frame::LanguageFrame;
-use crate::id3::v2::util::text_utils::encode_text;
+use crate::id3::v2::items::sync_text::SynchronizedText;
+use crate::id3::v2::util::text_utils::{encode_text, is_latin1};
 use crate::id3::v2::util::upgrade::{upgrade_v2, upgrade_v3};
 use crate::id3::v2::ID3v2Version;
 use crate::picture::Picture;
@@ -170,12 +174,29 @@ pub enum FrameValue {
 	},
 	/// Represents a "POPM" frame
 	Popularimeter(Popularimeter),
+	/// Represents a "SYLT" frame
+	SynchronizedText(SynchronizedText),
+	/// Represents a "CHAP" frame
+	Chapter(Chapter),
+	/// Represents a "CTOC" frame
+	TableOfContents(TableOfContents),
+	/// Represents a "GEOB" frame
+	GeneralEncapsulatedObject(GeneralEncapsulatedObject),
+	/// Represents a "PRIV" frame
+	///
+	/// There may be multiple "PRIV" frames with the same owner, see [`ID3v2Tag::private_frames`](crate::id3::v2::ID3v2Tag::private_frames)
+	Private {
+		/// The owner identifier, usually a URL or email address
+		///
+		/// This is always [`TextEncoding::Latin1`]
+		owner: String,
+		/// The private data
+		data: Vec<u8>,
+	},
 	/// Binary data
 	///
 	/// NOTES:
 	///
-	/// * This is used for "GEOB" and "SYLT" frames, see
-	/// [`GeneralEncapsulatedObject::parse`](crate::id3::v2::GeneralEncapsulatedObject::parse) and [`SynchronizedText::parse`](crate::id3::v2::SynchronizedText::parse) respectively
 	/// * This is used for **all** frames with an ID of [`FrameID::Outdated`]
 	/// * This is used for unknown frames
 	Binary(Vec<u8>),
@@ -195,24 +216,144 @@ impl From<ItemValue> for FrameValue {
 }
 
 impl FrameValue {
-	pub(super) fn as_bytes(&self) -> Result<Vec<u8>> {
+	pub(in crate::id3::v2) fn as_bytes(&self, version: ID3v2Version) -> Result<Vec<u8>> {
+		let is_v3 = version == ID3v2Version::V3;
+
+		// Text that can't be represented in Latin-1 falls back to a Unicode encoding, and
+		// ID3v2.3 only supports Latin-1 and UTF-16 with a BOM, so any other encoding needs to be replaced
+		let new_encoding = self.with_encoding(|encoding, latin1| match encoding {
+			TextEncoding::Latin1 if !latin1 && is_v3 => Some(TextEncoding::UTF16),
+			TextEncoding::Latin1 if !latin1 => Some(TextEncoding::UTF8),
+			TextEncoding::UTF8 | TextEncoding::UTF16BE if is_v3 => Some(TextEncoding::UTF16),
+			_ => None,
+		});
+
+		if let Some(value) = new_encoding {
+			return value.as_bytes(version);
+		}
+
 		Ok(match self {
-			FrameValue::Comment(lf) | FrameValue::UnSyncText(lf) => lf.as_bytes()?,
+			FrameValue::Comment(lf) | FrameValue::UnSyncText(lf) => lf.as_bytes(),
 			FrameValue::Text { encoding, value } => {
 				let mut content = encode_text(value, *encoding, false);
 
 				content.insert(0, *encoding as u8);
 				content
 			},
-			FrameValue::UserText(content) | FrameValue::UserURL(content) => content.as_bytes(),
-			FrameValue::URL(link) => link.as_bytes().to_vec(),
+			FrameValue::UserText(content) => content.as_bytes(),
+			// Unlike the description, the URL is always Latin-1
+			FrameValue::UserURL(content) => {
+				let mut bytes = vec![content.encoding as u8];
+
+				bytes.extend(encode_text(&content.description, content.encoding, true));
+				bytes.extend(encode_text(&content.content, TextEncoding::Latin1, false));
+				bytes
+			},
+			FrameValue::URL(link) => encode_text(link, TextEncoding::Latin1, false),
 			FrameValue::Picture { encoding, picture } => {
 				picture.as_apic_bytes(ID3v2Version::V4, *encoding)?
 			},
 			FrameValue::Popularimeter(popularimeter) => popularimeter.as_bytes(),
+			FrameValue::SynchronizedText(sync_text) => sync_text.as_bytes()?,
+			FrameValue::Chapter(chapter) => chapter.create_bytes(version)?,
+			FrameValue::TableOfContents(toc) => toc.create_bytes(version)?,
+			FrameValue::GeneralEncapsulatedObject(geob) => geob.as_bytes(),
+			FrameValue::Private { owner, data } => {
+				let mut content = encode_text(owner, TextEncoding::Latin1, true);
+				content.extend_from_slice(data);
+				content
+			},
 			FrameValue::Binary(binary) => binary.clone(),
 		})
 	}
+
+	// Replaces the encoding of the value if `new_encoding` returns `Some`
+	//
+	// `new_encoding` is given the current encoding, and whether all of the value's text can be represented in Latin-1
+	pub(in crate::id3::v2) fn with_encoding<F>(&self, new_encoding: F) -> Option<Self>
+	where
+		F: Fn(TextEncoding, bool) -> Option<TextEncoding>,
+	{
+		let reencode = |encoding: TextEncoding, text: &[&str]| {
+			new_encoding(encoding, text.iter().all(|t| is_latin1(t))).filter(|new| *new != encoding)
+		};
+
+		match self {
+			FrameValue::Comment(lf) => {
+				reencode(lf.encoding, &[&lf.description, &lf.content]).map(|encoding| {
+					FrameValue::Comment(LanguageFrame {
+						encoding,
+						..lf.clone()
+					})
+				})
+			},
+			FrameValue::UnSyncText(lf) => reencode(lf.encoding, &[&lf.description, &lf.content])
+				.map(|encoding| {
+					FrameValue::UnSyncText(LanguageFrame {
+						encoding,
+						..lf.clone()
+					})
+				}),
+			FrameValue::Text { encoding, value } => {
+				reencode(*encoding, &[value]).map(|encoding| FrameValue::Text {
+					encoding,
+					value: value.clone(),
+				})
+			},
+			FrameValue::UserText(content) => {
+				reencode(content.encoding, &[&content.description, &content.content]).map(
+					|encoding| {
+						FrameValue::UserText(EncodedTextFrame {
+							encoding,
+							..content.clone()
+						})
+					},
+				)
+			},
+			FrameValue::UserURL(content) => reencode(content.encoding, &[&content.description])
+				.map(|encoding| {
+					FrameValue::UserURL(EncodedTextFrame {
+						encoding,
+						..content.clone()
+					})
+				}),
+			FrameValue::Picture { encoding, picture } => {
+				reencode(*encoding, &[picture.description().unwrap_or_default()]).map(|encoding| {
+					FrameValue::Picture {
+						encoding,
+						picture: picture.clone(),
+					}
+				})
+			},
+			FrameValue::SynchronizedText(sync_text) => {
+				let information = &sync_text.information;
+
+				let mut text = vec![information.description.as_deref().unwrap_or_default()];
+				text.extend(sync_text.content.iter().map(|(_, t)| t.as_str()));
+
+				reencode(information.encoding, &text).map(|encoding| {
+					let mut sync_text = sync_text.clone();
+					sync_text.information.encoding = encoding;
+					FrameValue::SynchronizedText(sync_text)
+				})
+			},
+			FrameValue::GeneralEncapsulatedObject(geob) => {
+				let information = &geob.information;
+
+				let text = [
+					information.file_name.as_deref().unwrap_or_default(),
+					information.descriptor.as_deref().unwrap_or_default(),
+				];
+
+				reencode(information.encoding, &text).map(|encoding| {
+					let mut geob = geob.clone();
+					geob.information.encoding = encoding;
+					FrameValue::GeneralEncapsulatedObject(geob)
+				})
+			},
+			_ => None,
+		}
+	}
 }
 
 /// Various flags to describe the content of an item
@@ -247,9 +388,9 @@ pub struct FrameFlags {
 	/// with the MPEG frame header, which is often identified by its "frame sync" (11 set bits).
 	/// It is preferred an ID3v2 tag is either *completely* unsynchronised or not unsynchronised at all.
 	///
-	/// NOTE: While unsynchronized data is read, for the sake of simplicity, this flag has no effect when
-	/// writing. There isn't much reason to write unsynchronized data.
-	pub unsynchronisation: bool, /* TODO: Maybe? This doesn't seem very useful, and it is wasted effort if one forgets to make this false when writing. */
+	/// NOTE: This flag only has an effect when writing an `ID3v2.4` tag. To unsynchronise an entire tag,
+	/// see [`ID3v2TagFlags::unsynchronisation`](crate::id3::v2::ID3v2TagFlags::unsynchronisation).
+	pub unsynchronisation: bool,
 	/// Frame has a data length indicator
 	///
 	/// The data length indicator is the size of the frame if the flags were all zeroed out.
@@ -270,7 +411,7 @@ impl From<TagItem> for Option<Frame> {
 					(FrameID::Valid(ref s), ItemValue::Text(text)) if s == "COMM" => {
 						FrameValue::Comment(LanguageFrame {
 							encoding: TextEncoding::UTF8,
-							language: *b"eng",
+							language: Language::ENGLISH,
 							description: String::new(),
 							content: text,
 						})
@@ -278,7 +419,7 @@ impl From<TagItem> for Option<Frame> {
 					(FrameID::Valid(ref s), ItemValue::Text(text)) if s == "USLT" => {
 						FrameValue::UnSyncText(LanguageFrame {
 							encoding: TextEncoding::UTF8,
-							language: *b"eng",
+							language: Language::ENGLISH,
 							description: String::new(),
 							content: text,
 						})
@@ -299,6 +440,12 @@ impl From<TagItem> for Option<Frame> {
 							content: text,
 						})
 					},
+					(FrameID::Valid(ref s), ItemValue::Binary(comment)) if s == "COMM" => {
+						comment_from_item(&comment).unwrap_or(FrameValue::Binary(comment))
+					},
+					(FrameID::Valid(ref s), value) if s == "POPM" => {
+						popularimeter_from_item(&value).unwrap_or_else(|| value.into())
+					},
 					(_, value) => value.into(),
 				};
 
@@ -361,27 +508,64 @@ impl<'a> TryFrom<&'a TagItem> for FrameRef<'a> {
 
 	fn try_from(tag_item: &'a TagItem) -> std::result::Result<Self, Self::Error> {
 		let id = match tag_item.key() {
-			ItemKey::Unknown(unknown) if unknown.len() == 4 => {
-				id::FrameID::verify_id(unknown)?;
-				Ok(unknown.as_str())
-			},
+			ItemKey::Unknown(unknown) if FrameID::is_frame_id(unknown) => Some(unknown.as_str()),
+			// Some keys map to TXXX/WXXX descriptions, rather than frame IDs
 			k => k
 				.map_key(TagType::ID3v2, false)
-				.ok_or_else(|| ID3v2Error::new(ID3v2ErrorKind::BadFrameID)),
-		}?;
+				.filter(|id| FrameID::is_frame_id(id)),
+		};
+
+		let id = match id {
+			Some(id) => id,
+			// Anything that isn't a frame ID gets stored in a TXXX/WXXX frame by its description
+			None => {
+				let description = tag_item
+					.key()
+					.map_key(TagType::ID3v2, true)
+					.ok_or_else(|| ID3v2Error::new(ID3v2ErrorKind::BadFrameID))?;
+
+				let (id, value) = match tag_item.value() {
+					ItemValue::Text(text) => (
+						"TXXX",
+						FrameValue::UserText(EncodedTextFrame {
+							encoding: TextEncoding::UTF8,
+							description: String::from(description),
+							content: text.clone(),
+						}),
+					),
+					ItemValue::Locator(locator) => (
+						"WXXX",
+						FrameValue::UserURL(EncodedTextFrame {
+							encoding: TextEncoding::UTF8,
+							description: String::from(description),
+							content: locator.clone(),
+						}),
+					),
+					ItemValue::Binary(_) => {
+						return Err(ID3v2Error::new(ID3v2ErrorKind::BadFrameID).into())
+					},
+				};
+
+				return Ok(FrameRef {
+					id,
+					value: Cow::Owned(value),
+					flags: FrameFlags::default(),
+				});
+			},
+		};
 
 		Ok(FrameRef {
 			id,
 			value: Cow::Owned(match (id, tag_item.value()) {
 				("COMM", ItemValue::Text(text)) => FrameValue::Comment(LanguageFrame {
 					encoding: TextEncoding::UTF8,
-					language: *b"eng",
+					language: Language::ENGLISH,
 					description: String::new(),
 					content: text.clone(),
 				}),
 				("USLT", ItemValue::Text(text)) => FrameValue::UnSyncText(LanguageFrame {
 					encoding: TextEncoding::UTF8,
-					language: *b"eng",
+					language: Language::ENGLISH,
 					description: String::new(),
 					content: text.clone(),
 				}),
@@ -397,6 +581,13 @@ impl<'a> TryFrom<&'a TagItem> for FrameRef<'a> {
 					description: String::new(),
 					content: text.clone(),
 				}),
+				("COMM", ItemValue::Binary(comment)) => comment_from_item(comment)
+					.unwrap_or_else(|| FrameValue::Binary(comment.clone())),
+				("POPM", value) => popularimeter_from_item(value).unwrap_or_else(|| value.into()),
+				// WFED is the only "W" frame that holds text rather than a URL
+				(id, ItemValue::Text(text)) if id.starts_with('W') && id != "WFED" => {
+					FrameValue::URL(text.clone())
+				},
 				(_, value) => value.into(),
 			}),
 			flags: FrameFlags::default(),
@@ -404,6 +595,34 @@ impl<'a> TryFrom<&'a TagItem> for FrameRef<'a> {
 	}
 }
 
+// Binary "COMM" items are the raw content of a comment with a description
+fn comment_from_item(comment: &[u8]) -> Option<FrameValue> {
+	content::parse_content(&mut &comment[..], "COMM", ID3v2Version::V4)
+		.ok()
+		.flatten()
+}
+
+// "POPM" items are either the frame's raw bytes, or a 0-100 rating from another format
+fn popularimeter_from_item(value: &ItemValue) -> Option<FrameValue> {
+	let popularimeter = match value {
+		ItemValue::Binary(popm) => Popularimeter::parse(&mut &popm[..]).ok()?,
+		ItemValue::Text(text) => {
+			let rating = text.trim().parse::<u8>().ok().filter(|r| *r <= 100)?;
+
+			let mut popularimeter = Popularimeter {
+				email: String::new(),
+				rating: 0,
+				counter: 0,
+			};
+			popularimeter.set_star_rating((rating + 10) / 20);
+			popularimeter
+		},
+		ItemValue::Locator(_) => return None,
+	};
+
+	Some(FrameValue::Popularimeter(popularimeter))
+}
+
 impl<'a> Into<FrameValue> for &'a ItemValue {
 	fn into(self) -> FrameValue {
 		match self {
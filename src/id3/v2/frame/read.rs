@@ -10,10 +10,13 @@ use std::io::Read;
 use byteorder::{BigEndian, ReadBytesExt};
 
 impl Frame {
-	pub(crate) fn read<R>(reader: &mut R, version: ID3v2Version) -> Result<(Option<Self>, bool)>
-	where
-		R: Read,
-	{
+	// `unsynchronised` is for ID3v2.4 tags with the unsynchronisation flag set, where every frame is
+	// unsynchronised, regardless of its own flags.
+	pub(crate) fn read(
+		reader: &mut &[u8],
+		version: ID3v2Version,
+		unsynchronised: bool,
+	) -> Result<(Option<Self>, bool)> {
 		// The header will be upgraded to ID3v2.4 past this point, so they can all be treated the same
 		let (id, size, mut flags) = match match version {
 			ID3v2Version::V2 => parse_v2_header(reader)?,
@@ -27,14 +30,20 @@ impl Frame {
 		let mut content = try_vec![0; size as usize];
 		reader.read_exact(&mut content)?;
 
-		if flags.unsynchronisation {
+		if flags.unsynchronisation || (unsynchronised && version == ID3v2Version::V4) {
 			content = crate::id3::v2::util::unsynch_content(content.as_slice())?;
 		}
 
 		if flags.compression {
+			// ID3v2.3 compressed frames are preceded by their decompressed size
+			let compressed = match version {
+				ID3v2Version::V3 => content.get(4..).unwrap_or_default(),
+				_ => &content,
+			};
+
 			let mut decompressed = Vec::new();
-			flate2::Decompress::new(true)
-				.decompress_vec(&content, &mut decompressed, flate2::FlushDecompress::None)
+			flate2::read::ZlibDecoder::new(compressed)
+				.read_to_end(&mut decompressed)
 				.map_err(|_| {
 					ID3v2Error::new(ID3v2ErrorKind::Other(
 						"Encountered a compressed frame, failed to decompress",
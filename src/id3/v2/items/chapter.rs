@@ -0,0 +1,229 @@
+use crate::error::{ID3v2Error, ID3v2ErrorKind, Result};
+use crate::id3::v2::frame::{Frame, FrameValue};
+use crate::id3::v2::util::text_utils::{decode_text, encode_text, TextEncoding};
+use crate::id3::v2::write::frame::create_items;
+use crate::id3::v2::ID3v2Version;
+
+use std::hash::{Hash, Hasher};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+/// The contents of a chapter ("CHAP") frame
+///
+/// A tag can contain multiple "CHAP" frames, but there must only be
+/// one with the same element ID.
+#[derive(Clone, Debug, Eq)]
+pub struct Chapter {
+	/// A unique identifier for the chapter, referenced by [`TableOfContents::child_element_ids`]
+	pub element_id: String,
+	/// The start of the chapter, in milliseconds
+	pub start_time: u32,
+	/// The end of the chapter, in milliseconds
+	pub end_time: u32,
+	/// The byte offset of the first audio frame in the chapter
+	///
+	/// If all bytes are set (`0xFFFFFFFF`), `start_time` should be used instead.
+	pub start_offset: u32,
+	/// The byte offset of the first audio frame following the chapter
+	///
+	/// If all bytes are set (`0xFFFFFFFF`), `end_time` should be used instead.
+	pub end_offset: u32,
+	/// Frames describing the chapter, usually a `TIT2` with its title
+	pub sub_frames: Vec<Frame>,
+}
+
+impl Chapter {
+	/// Get the chapter's title from its `TIT2` sub-frame
+	pub fn title(&self) -> Option<&str> {
+		sub_frame_title(&self.sub_frames)
+	}
+
+	pub(in crate::id3::v2) fn parse(content: &mut &[u8], version: ID3v2Version) -> Result<Self> {
+		let element_id = decode_element_id(content)?;
+
+		let start_time = content.read_u32::<BigEndian>()?;
+		let end_time = content.read_u32::<BigEndian>()?;
+		let start_offset = content.read_u32::<BigEndian>()?;
+		let end_offset = content.read_u32::<BigEndian>()?;
+
+		Ok(Self {
+			element_id,
+			start_time,
+			end_time,
+			start_offset,
+			end_offset,
+			sub_frames: parse_sub_frames(content, version)?,
+		})
+	}
+
+	/// Convert a [`Chapter`] into an ID3v2 CHAP frame byte Vec
+	///
+	/// NOTE: This does not include a frame header
+	///
+	/// # Errors
+	///
+	/// * One of the sub-frames is invalid, see [`ID3v2Tag`](crate::id3::v2::ID3v2Tag)
+	pub fn as_bytes(&self) -> Result<Vec<u8>> {
+		self.create_bytes(ID3v2Version::V4)
+	}
+
+	pub(in crate::id3::v2) fn create_bytes(&self, version: ID3v2Version) -> Result<Vec<u8>> {
+		let mut content = encode_text(&self.element_id, TextEncoding::Latin1, true);
+
+		content.write_u32::<BigEndian>(self.start_time)?;
+		content.write_u32::<BigEndian>(self.end_time)?;
+		content.write_u32::<BigEndian>(self.start_offset)?;
+		content.write_u32::<BigEndian>(self.end_offset)?;
+
+		create_items(
+			&mut content,
+			&mut self.sub_frames.iter().filter_map(Frame::as_opt_ref),
+			version,
+		)?;
+
+		Ok(content)
+	}
+}
+
+impl PartialEq for Chapter {
+	fn eq(&self, other: &Self) -> bool {
+		self.element_id == other.element_id
+	}
+}
+
+impl Hash for Chapter {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.element_id.hash(state);
+	}
+}
+
+/// The contents of a table of contents ("CTOC") frame
+///
+/// A tag can contain multiple "CTOC" frames, but there must only be
+/// one with the same element ID, and only one may be marked as top level.
+#[derive(Clone, Debug, Eq)]
+pub struct TableOfContents {
+	/// A unique identifier for the table of contents
+	pub element_id: String,
+	/// Whether this is the root of the table of contents tree
+	pub top_level: bool,
+	/// Whether the entries in `child_element_ids` are ordered
+	pub ordered: bool,
+	/// The element IDs of the [`Chapter`]s and [`TableOfContents`] within this table of contents
+	pub child_element_ids: Vec<String>,
+	/// Frames describing the table of contents, usually a `TIT2` with its title
+	pub sub_frames: Vec<Frame>,
+}
+
+impl TableOfContents {
+	/// Get the table of contents' title from its `TIT2` sub-frame
+	pub fn title(&self) -> Option<&str> {
+		sub_frame_title(&self.sub_frames)
+	}
+
+	pub(in crate::id3::v2) fn parse(content: &mut &[u8], version: ID3v2Version) -> Result<Self> {
+		let element_id = decode_element_id(content)?;
+
+		let flags = content.read_u8()?;
+		let entry_count = content.read_u8()?;
+
+		let mut child_element_ids = Vec::with_capacity(entry_count as usize);
+		for _ in 0..entry_count {
+			child_element_ids.push(decode_element_id(content)?);
+		}
+
+		Ok(Self {
+			element_id,
+			top_level: flags & 0x02 == 0x02,
+			ordered: flags & 0x01 == 0x01,
+			child_element_ids,
+			sub_frames: parse_sub_frames(content, version)?,
+		})
+	}
+
+	/// Convert a [`TableOfContents`] into an ID3v2 CTOC frame byte Vec
+	///
+	/// NOTE: This does not include a frame header
+	///
+	/// # Errors
+	///
+	/// * There are more than 255 entries in `child_element_ids`
+	/// * One of the sub-frames is invalid, see [`ID3v2Tag`](crate::id3::v2::ID3v2Tag)
+	pub fn as_bytes(&self) -> Result<Vec<u8>> {
+		self.create_bytes(ID3v2Version::V4)
+	}
+
+	pub(in crate::id3::v2) fn create_bytes(&self, version: ID3v2Version) -> Result<Vec<u8>> {
+		let entry_count = u8::try_from(self.child_element_ids.len()).map_err(|_| {
+			ID3v2Error::new(ID3v2ErrorKind::Other(
+				"Table of contents has more than 255 entries",
+			))
+		})?;
+
+		let mut content = encode_text(&self.element_id, TextEncoding::Latin1, true);
+
+		let mut flags = 0;
+		if self.top_level {
+			flags |= 0x02;
+		}
+
+		if self.ordered {
+			flags |= 0x01;
+		}
+
+		content.push(flags);
+		content.push(entry_count);
+
+		for child_element_id in &self.child_element_ids {
+			content.extend(encode_text(child_element_id, TextEncoding::Latin1, true));
+		}
+
+		create_items(
+			&mut content,
+			&mut self.sub_frames.iter().filter_map(Frame::as_opt_ref),
+			version,
+		)?;
+
+		Ok(content)
+	}
+}
+
+impl PartialEq for TableOfContents {
+	fn eq(&self, other: &Self) -> bool {
+		self.element_id == other.element_id
+	}
+}
+
+impl Hash for TableOfContents {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.element_id.hash(state);
+	}
+}
+
+fn decode_element_id(content: &mut &[u8]) -> Result<String> {
+	match decode_text(content, TextEncoding::Latin1, true)? {
+		Some(element_id) => Ok(element_id),
+		None => Err(ID3v2Error::new(ID3v2ErrorKind::Other("Found an empty element ID")).into()),
+	}
+}
+
+fn parse_sub_frames(content: &mut &[u8], version: ID3v2Version) -> Result<Vec<Frame>> {
+	let mut sub_frames = Vec::new();
+
+	loop {
+		match Frame::read(content, version, false)? {
+			(None, true) => break,
+			(Some(frame), false) => sub_frames.push(frame),
+			_ => {},
+		}
+	}
+
+	Ok(sub_frames)
+}
+
+fn sub_frame_title(sub_frames: &[Frame]) -> Option<&str> {
+	sub_frames.iter().find_map(|f| match f.content() {
+		FrameValue::Text { value, .. } if f.id_str() == "TIT2" => Some(value.as_str()),
+		_ => None,
+	})
+}
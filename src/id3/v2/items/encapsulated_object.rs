@@ -69,18 +69,22 @@ impl GeneralEncapsulatedObject {
 
 		let mut bytes = vec![encoding as u8];
 
-		if let Some(ref mime_type) = self.information.mime_type {
-			bytes.extend(mime_type.as_bytes())
+		let mime_type = self.information.mime_type.as_deref();
+		bytes.extend(encode_text(
+			mime_type.unwrap_or_default(),
+			TextEncoding::Latin1,
+			true,
+		));
+
+		// Missing strings are only written as a terminator, as an empty UTF-16 string would include a BOM
+		for text in [&self.information.file_name, &self.information.descriptor] {
+			match (text, encoding) {
+				(Some(text), _) => bytes.extend(encode_text(text, encoding, true)),
+				(None, TextEncoding::Latin1 | TextEncoding::UTF8) => bytes.push(0),
+				(None, TextEncoding::UTF16 | TextEncoding::UTF16BE) => bytes.extend([0, 0]),
+			}
 		}
 
-		bytes.push(0);
-
-		let file_name = self.information.file_name.as_deref();
-		bytes.extend(&*encode_text(file_name.unwrap_or(""), encoding, true));
-
-		let descriptor = self.information.descriptor.as_deref();
-		bytes.extend(&*encode_text(descriptor.unwrap_or(""), encoding, true));
-
 		bytes.extend(&self.data);
 
 		bytes
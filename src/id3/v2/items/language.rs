@@ -0,0 +1,236 @@
+use std::fmt::{Debug, Display, Formatter};
+
+// ISO-639-1 codes, and their ISO-639-2/T equivalents
+const TWO_LETTER_CODES: &[(&str, &[u8; 3])] = &[
+	("af", b"afr"),
+	("am", b"amh"),
+	("ar", b"ara"),
+	("az", b"aze"),
+	("be", b"bel"),
+	("bg", b"bul"),
+	("bn", b"ben"),
+	("bo", b"bod"),
+	("bs", b"bos"),
+	("ca", b"cat"),
+	("cs", b"ces"),
+	("cy", b"cym"),
+	("da", b"dan"),
+	("de", b"deu"),
+	("el", b"ell"),
+	("en", b"eng"),
+	("eo", b"epo"),
+	("es", b"spa"),
+	("et", b"est"),
+	("eu", b"eus"),
+	("fa", b"fas"),
+	("fi", b"fin"),
+	("fo", b"fao"),
+	("fr", b"fra"),
+	("ga", b"gle"),
+	("gd", b"gla"),
+	("gl", b"glg"),
+	("gu", b"guj"),
+	("he", b"heb"),
+	("hi", b"hin"),
+	("hr", b"hrv"),
+	("hu", b"hun"),
+	("hy", b"hye"),
+	("id", b"ind"),
+	("is", b"isl"),
+	("it", b"ita"),
+	("ja", b"jpn"),
+	("jv", b"jav"),
+	("ka", b"kat"),
+	("kk", b"kaz"),
+	("km", b"khm"),
+	("kn", b"kan"),
+	("ko", b"kor"),
+	("ku", b"kur"),
+	("ky", b"kir"),
+	("la", b"lat"),
+	("lb", b"ltz"),
+	("lo", b"lao"),
+	("lt", b"lit"),
+	("lv", b"lav"),
+	("mi", b"mri"),
+	("mk", b"mkd"),
+	("ml", b"mal"),
+	("mn", b"mon"),
+	("mr", b"mar"),
+	("ms", b"msa"),
+	("mt", b"mlt"),
+	("my", b"mya"),
+	("nb", b"nob"),
+	("ne", b"nep"),
+	("nl", b"nld"),
+	("nn", b"nno"),
+	("no", b"nor"),
+	("pa", b"pan"),
+	("pl", b"pol"),
+	("ps", b"pus"),
+	("pt", b"por"),
+	("ro", b"ron"),
+	("ru", b"rus"),
+	("sa", b"san"),
+	("sk", b"slk"),
+	("sl", b"slv"),
+	("so", b"som"),
+	("sq", b"sqi"),
+	("sr", b"srp"),
+	("sv", b"swe"),
+	("sw", b"swa"),
+	("ta", b"tam"),
+	("te", b"tel"),
+	("tg", b"tgk"),
+	("th", b"tha"),
+	("tk", b"tuk"),
+	("tl", b"tgl"),
+	("tr", b"tur"),
+	("uk", b"ukr"),
+	("ur", b"urd"),
+	("uz", b"uzb"),
+	("vi", b"vie"),
+	("yi", b"yid"),
+	("yo", b"yor"),
+	("zh", b"zho"),
+	("zu", b"zul"),
+];
+
+/// An ISO-639-2 language code
+///
+/// This is used by the `COMM`, `USLT`, and `SYLT` frames.
+///
+/// Codes read from a file are kept as-is, even if they're invalid, so they can be checked
+/// with [`Language::is_valid`]. When writing, an invalid code will be normalized if possible
+/// (Ex. `ENG` or `en` to `eng`), otherwise [`Language::UNDETERMINED`] is written instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::id3::v2::Language;
+///
+/// assert_eq!(Language::new("eng"), Some(Language::ENGLISH));
+///
+/// // ISO-639-1 codes are converted
+/// assert_eq!(Language::new("en"), Some(Language::ENGLISH));
+///
+/// assert_eq!(Language::new("e1g"), None);
+/// assert!(!Language::from_bytes(*b"e1g").is_valid());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Language([u8; 3]);
+
+impl Language {
+	/// An undetermined language (`und`)
+	pub const UNDETERMINED: Self = Self(*b"und");
+	/// An unknown language (`XXX`), as defined by the ID3v2 specification
+	pub const UNKNOWN: Self = Self(*b"XXX");
+	/// English (`eng`)
+	pub const ENGLISH: Self = Self(*b"eng");
+
+	/// Create a [`Language`] from a code
+	///
+	/// This accepts ISO-639-2 codes, as well as the two letter ISO-639-1 codes that have an
+	/// equivalent. The codes are case-insensitive, and are always stored in lowercase.
+	///
+	/// This will return `None` if `code` isn't a valid code.
+	pub fn new(code: &str) -> Option<Self> {
+		if code == "XXX" {
+			return Some(Self::UNKNOWN);
+		}
+
+		let code = code.to_ascii_lowercase();
+
+		match code.as_bytes() {
+			&[a, b, c] if code.as_bytes().iter().all(u8::is_ascii_lowercase) => {
+				Some(Self([a, b, c]))
+			},
+			[_, _] => TWO_LETTER_CODES
+				.iter()
+				.find(|(two_letter_code, _)| *two_letter_code == code)
+				.map(|(_, three_letter_code)| Self(**three_letter_code)),
+			_ => None,
+		}
+	}
+
+	/// Create a [`Language`] from 3 bytes, without any validation
+	///
+	/// This is how codes are read from a file, see [`Language::is_valid`].
+	pub const fn from_bytes(bytes: [u8; 3]) -> Self {
+		Self(bytes)
+	}
+
+	/// Returns the code's bytes
+	pub fn as_bytes(&self) -> [u8; 3] {
+		self.0
+	}
+
+	/// Returns the code as a string, if it's valid
+	pub fn as_str(&self) -> Option<&str> {
+		if self.is_valid() {
+			return std::str::from_utf8(&self.0).ok();
+		}
+
+		None
+	}
+
+	/// Whether the code is 3 lowercase ASCII letters, or [`Language::UNKNOWN`]
+	///
+	/// NOTE: This does not check if the code is assigned to a language.
+	pub fn is_valid(&self) -> bool {
+		*self == Self::UNKNOWN || self.0.iter().all(u8::is_ascii_lowercase)
+	}
+
+	// The code to write, normalizing it if it's invalid
+	pub(crate) fn normalized(self) -> Self {
+		if self.is_valid() {
+			return self;
+		}
+
+		std::str::from_utf8(&self.0)
+			.ok()
+			.and_then(|code| Self::new(code.trim_end_matches(|c| c == '\0' || c == ' ')))
+			.unwrap_or(Self::UNDETERMINED)
+	}
+}
+
+impl Default for Language {
+	fn default() -> Self {
+		Self::UNDETERMINED
+	}
+}
+
+impl Debug for Language {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("Language")
+			.field(&String::from_utf8_lossy(&self.0))
+			.finish()
+	}
+}
+
+impl Display for Language {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", String::from_utf8_lossy(&self.0))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Language;
+
+	#[test]
+	fn normalize_language() {
+		for (code, expected) in [
+			(*b"eng", Language::ENGLISH),
+			(*b"XXX", Language::UNKNOWN),
+			(*b"ENG", Language::ENGLISH),
+			(*b"en\0", Language::ENGLISH),
+			(*b"de ", Language::from_bytes(*b"deu")),
+			(*b"e1g", Language::UNDETERMINED),
+			(*b"zz\0", Language::UNDETERMINED),
+			([0xFF; 3], Language::UNDETERMINED),
+		] {
+			assert_eq!(Language::from_bytes(code).normalized(), expected);
+		}
+	}
+}
@@ -1,17 +1,20 @@
-use crate::error::{ID3v2Error, ID3v2ErrorKind, Result};
+use super::language::Language;
 use crate::id3::v2::util::text_utils::{encode_text, TextEncoding};
 
 use std::hash::{Hash, Hasher};
 
 /// Information about an `ID3v2` frame that requires a language
 ///
+/// A tag can contain multiple frames of the same ID, but there must only be one with the same
+/// language and description.
+///
 /// See [`EncodedTextFrame`](crate::id3::v2::EncodedTextFrame)
 #[derive(Clone, Debug, Eq)]
 pub struct LanguageFrame {
 	/// The encoding of the description and comment text
 	pub encoding: TextEncoding,
-	/// ISO-639-2 language code (3 bytes)
-	pub language: [u8; 3],
+	/// ISO-639-2 language code
+	pub language: Language,
 	/// Unique content description
 	pub description: String,
 	/// The actual frame content
@@ -20,12 +23,13 @@ pub struct LanguageFrame {
 
 impl PartialEq for LanguageFrame {
 	fn eq(&self, other: &Self) -> bool {
-		self.description == other.description
+		self.language == other.language && self.description == other.description
 	}
 }
 
 impl Hash for LanguageFrame {
 	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.language.hash(state);
 		self.description.hash(state);
 	}
 }
@@ -33,26 +37,17 @@ impl Hash for LanguageFrame {
 impl LanguageFrame {
 	/// Convert a [`LanguageFrame`] to a byte vec
 	///
-	/// NOTE: This does not include a frame header
-	///
-	/// # Errors
+	/// NOTES:
 	///
-	/// * `language` is not exactly 3 bytes
-	/// * `language` contains invalid characters `('a'..'z')`
-	pub fn as_bytes(&self) -> Result<Vec<u8>> {
+	/// * This does not include a frame header
+	/// * An invalid `language` is normalized, see [`Language`]
+	pub fn as_bytes(&self) -> Vec<u8> {
 		let mut bytes = vec![self.encoding as u8];
 
-		if self.language.len() != 3 || self.language.iter().any(|c| !(b'a'..=b'z').contains(&c)) {
-			return Err(ID3v2Error::new(ID3v2ErrorKind::Other(
-				"Invalid frame language found (expected 3 ascii characters)",
-			))
-			.into());
-		}
-
-		bytes.extend(self.language);
+		bytes.extend(self.language.normalized().as_bytes());
 		bytes.extend(encode_text(&self.description, self.encoding, true).iter());
 		bytes.extend(encode_text(&self.content, self.encoding, false));
 
-		Ok(bytes)
+		bytes
 	}
 }
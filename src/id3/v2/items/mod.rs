@@ -1,5 +1,7 @@
+pub(super) mod chapter;
 pub(super) mod encapsulated_object;
 pub(super) mod encoded_text_frame;
+pub(super) mod language;
 pub(super) mod language_frame;
 pub(super) mod popularimeter;
 pub(super) mod sync_text;
@@ -1,7 +1,11 @@
-use crate::id3::v2::util::text_utils::encode_text;
+use crate::error::Result;
+use crate::id3::v2::util::text_utils::{decode_text, encode_text};
 use crate::id3::v2::TextEncoding;
 
 use std::hash::{Hash, Hasher};
+use std::io::Read;
+
+use byteorder::ReadBytesExt;
 
 /// The contents of a popularimeter ("POPM") frame
 ///
@@ -18,11 +22,77 @@ pub struct Popularimeter {
 	pub rating: u8,
 	/// A play counter for the user. It is to be incremented each time the file is played.
 	///
-	/// This is a `u64` for simplicity. It may change if it becomes an issue.
+	/// The counter is stored as a variable-width integer, and is written using the fewest bytes
+	/// possible (with a minimum of 4). Counters wider than a `u64` are saturated to [`u64::MAX`] when read.
 	pub counter: u64,
 }
 
 impl Popularimeter {
+	/// Create a new [`Popularimeter`]
+	pub fn new(email: String, rating: u8, counter: u64) -> Self {
+		Self {
+			email,
+			rating,
+			counter,
+		}
+	}
+
+	pub(crate) fn parse(content: &mut &[u8]) -> Result<Self> {
+		let email = decode_text(content, TextEncoding::Latin1, true)?;
+		let rating = content.read_u8()?;
+
+		// The counter can be any width, so leading zeros are skipped before checking if it fits
+		let mut c = Vec::new();
+		content.read_to_end(&mut c)?;
+
+		let start = c.iter().position(|b| *b != 0).unwrap_or(c.len());
+		let counter_bytes = &c[start..];
+
+		let counter = if counter_bytes.len() > 8 {
+			// Anything wider than a `u64` is saturated
+			u64::MAX
+		} else {
+			let mut counter = [0; 8];
+			counter[8 - counter_bytes.len()..].copy_from_slice(counter_bytes);
+			u64::from_be_bytes(counter)
+		};
+
+		Ok(Self {
+			email: email.unwrap_or_default(),
+			rating,
+			counter,
+		})
+	}
+
+	/// Get the rating as a number of stars (0-5)
+	///
+	/// This uses the same mapping as Windows Media Player, where 1, 64, 128, 196, and 255
+	/// are 1-5 stars respectively.
+	pub fn star_rating(&self) -> u8 {
+		match self.rating {
+			0 => 0,
+			1..=31 => 1,
+			32..=95 => 2,
+			96..=159 => 3,
+			160..=223 => 4,
+			_ => 5,
+		}
+	}
+
+	/// Set the rating from a number of stars (0-5)
+	///
+	/// Values greater than 5 will be treated as 5 stars. See [`Popularimeter::star_rating`].
+	pub fn set_star_rating(&mut self, stars: u8) {
+		self.rating = match stars {
+			0 => 0,
+			1 => 1,
+			2 => 64,
+			3 => 128,
+			4 => 196,
+			_ => 255,
+		}
+	}
+
 	/// Convert a [`Popularimeter`] into an ID3v2 POPM frame byte Vec
 	///
 	/// NOTE: This does not include a frame header
@@ -102,4 +172,35 @@ mod tests {
 		test_popm(&popm_u32_boundary);
 		test_popm(&popm_u40);
 	}
+
+	#[test]
+	fn read_popm_counter() {
+		// A 4 byte counter is written back as-is
+		let popm_bytes = b"foo@bar.com\0\xC4\x00\x00\x01\x00";
+		let popm = Popularimeter::parse(&mut &popm_bytes[..]).unwrap();
+
+		assert_eq!(popm.email, "foo@bar.com");
+		assert_eq!(popm.rating, 196);
+		assert_eq!(popm.counter, 256);
+		assert_eq!(popm.as_bytes(), popm_bytes);
+
+		// Wider counters only keep their significant bytes
+		let popm_bytes = b"\0\xFF\x00\x00\x00\x00\x01\x00\x00\x00\x00\x01";
+		let popm = Popularimeter::parse(&mut &popm_bytes[..]).unwrap();
+
+		assert_eq!(popm.counter, 0x01_0000_0000_01);
+		assert_eq!(popm.as_bytes(), b"\0\xFF\x01\x00\x00\x00\x00\x01");
+
+		// Counters too large for a u64 are saturated
+		let popm_bytes = b"\0\xFF\x01\x00\x00\x00\x00\x00\x00\x00\x00";
+		let popm = Popularimeter::parse(&mut &popm_bytes[..]).unwrap();
+
+		assert_eq!(popm.counter, u64::MAX);
+
+		// The counter can be omitted
+		let popm = Popularimeter::parse(&mut &b"\0\x01"[..]).unwrap();
+
+		assert_eq!(popm.counter, 0);
+		assert_eq!(popm.as_bytes(), b"\0\x01\x00\x00\x00\x00");
+	}
 }
@@ -1,3 +1,4 @@
+use super::language::Language;
 use crate::error::{ErrorKind, ID3v2Error, ID3v2ErrorKind, LoftyError, Result};
 use crate::id3::v2::util::text_utils;
 use crate::id3::v2::util::text_utils::{
@@ -69,8 +70,8 @@ impl SyncTextContentType {
 pub struct SyncTextInformation {
 	/// The text encoding (description/text)
 	pub encoding: TextEncoding,
-	/// ISO-639-2 language code (3 bytes)
-	pub language: String,
+	/// ISO-639-2 language code
+	pub language: Language,
 	/// The format of the timestamps
 	pub timestamp_format: TimestampFormat,
 	/// The type of content stored
@@ -103,8 +104,7 @@ impl SynchronizedText {
 
 		let encoding = TextEncoding::from_u8(data[0])
 			.ok_or_else(|| LoftyError::new(ErrorKind::TextDecode("Found invalid encoding")))?;
-		let lang = std::str::from_utf8(&data[1..4])
-			.map_err(|_| ID3v2Error::new(ID3v2ErrorKind::BadSyncText))?;
+		let language = Language::from_bytes([data[1], data[2], data[3]]);
 		let timestamp_format = TimestampFormat::from_u8(data[4])
 			.ok_or_else(|| ID3v2Error::new(ID3v2ErrorKind::BadSyncText))?;
 		let content_type = SyncTextContentType::from_u8(data[5])
@@ -118,32 +118,25 @@ impl SynchronizedText {
 
 		// It's possible for the description to be the only string with a BOM
 		// To be safe, we change the encoding to the concrete variant determined from the description
-		if encoding == TextEncoding::UTF16 {
-			endianness = match [cursor.get_ref()[6], cursor.get_ref()[7]] {
-				[0xFF, 0xFE] => u16::from_le_bytes,
-				[0xFE, 0xFF] => u16::from_be_bytes,
-				// Since the description was already read, we can assume the BOM was valid
-				_ => unreachable!(),
-			};
+		if encoding == TextEncoding::UTF16 && data.len() >= 8 && data[6..8] == [0xFE, 0xFF] {
+			endianness = u16::from_be_bytes;
 		}
 
-		let mut pos = cursor.stream_position()? as u32;
-		let total = (data.len() - 6) as u32 - pos;
+		let total = (data.len() - 6) as u64;
 
 		let mut content = Vec::new();
 
-		while pos < total {
+		while cursor.position() < total {
 			let text = (|| -> Result<String> {
 				if encoding == TextEncoding::UTF16 {
 					let mut bom = [0; 2];
 					cursor
 						.read_exact(&mut bom)
 						.map_err(|_| ID3v2Error::new(ID3v2ErrorKind::BadSyncText))?;
+					cursor.seek(SeekFrom::Current(-2))?;
 
 					// Encountered text that doesn't include a BOM
 					if bom != [0xFF, 0xFE] && bom != [0xFE, 0xFF] {
-						cursor.seek(SeekFrom::Current(-2))?;
-
 						if let Some(raw_text) = read_to_terminator(&mut cursor, TextEncoding::UTF16)
 						{
 							return utf16_decode(&raw_text, endianness)
@@ -159,12 +152,9 @@ impl SynchronizedText {
 					.unwrap_or_default())
 			})()?;
 
-			pos += text.len() as u32;
-
 			let time = cursor
 				.read_u32::<BigEndian>()
 				.map_err(|_| ID3v2Error::new(ID3v2ErrorKind::BadSyncText))?;
-			pos += 4;
 
 			content.push((time, text));
 		}
@@ -172,7 +162,7 @@ impl SynchronizedText {
 		Ok(Self {
 			information: SyncTextInformation {
 				encoding,
-				language: lang.to_string(),
+				language,
 				timestamp_format,
 				content_type,
 				description,
@@ -183,53 +173,47 @@ impl SynchronizedText {
 
 	/// Convert a [`SynchronizedText`] to an ID3v2 SYLT frame byte Vec
 	///
-	/// NOTE: This does not include the frame header
+	/// NOTES:
+	///
+	/// * This does not include the frame header
+	/// * An invalid `language` is normalized, see [`Language`]
 	///
 	/// # Errors
 	///
 	/// * `content`'s length > [`u32::MAX`]
-	/// * See [`LanguageFrame`](crate::id3::v2::LanguageFrame)
 	pub fn as_bytes(&self) -> Result<Vec<u8>> {
 		let information = &self.information;
 
 		let mut data = vec![information.encoding as u8];
 
-		if information.language.len() == 3
-			&& information
-				.language
-				.chars()
-				.all(|c| ('a'..='z').contains(&c))
-		{
-			data.write_all(information.language.as_bytes())?;
-			data.write_u8(information.timestamp_format as u8)?;
-			data.write_u8(information.content_type as u8)?;
-
-			if let Some(description) = &information.description {
-				data.write_all(&encode_text(description, information.encoding, true))?;
-			} else {
-				data.write_u8(0)?;
-			}
-
-			for (time, ref text) in &self.content {
-				data.write_all(&encode_text(text, information.encoding, true))?;
-				data.write_u32::<BigEndian>(*time)?;
-			}
-
-			if data.len() as u64 > u64::from(u32::MAX) {
-				err!(TooMuchData);
-			}
-
-			return Ok(data);
+		data.write_all(&information.language.normalized().as_bytes())?;
+		data.write_u8(information.timestamp_format as u8)?;
+		data.write_u8(information.content_type as u8)?;
+
+		if let Some(description) = &information.description {
+			data.write_all(&encode_text(description, information.encoding, true))?;
+		} else {
+			data.write_u8(0)?;
+		}
+
+		for (time, ref text) in &self.content {
+			data.write_all(&encode_text(text, information.encoding, true))?;
+			data.write_u32::<BigEndian>(*time)?;
+		}
+
+		if data.len() as u64 > u64::from(u32::MAX) {
+			err!(TooMuchData);
 		}
 
-		Err(ID3v2Error::new(ID3v2ErrorKind::BadSyncText).into())
+		Ok(data)
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use crate::id3::v2::{
-		SyncTextContentType, SyncTextInformation, SynchronizedText, TextEncoding, TimestampFormat,
+		Language, SyncTextContentType, SyncTextInformation, SynchronizedText, TextEncoding,
+		TimestampFormat,
 	};
 
 	#[test]
@@ -237,7 +221,7 @@ mod tests {
 		let expected = SynchronizedText {
 			information: SyncTextInformation {
 				encoding: TextEncoding::Latin1,
-				language: String::from("eng"),
+				language: Language::ENGLISH,
 				timestamp_format: TimestampFormat::MS,
 				content_type: SyncTextContentType::Lyrics,
 				description: Some(String::from("Test Sync Text")),
@@ -263,7 +247,7 @@ mod tests {
 		let to_encode = SynchronizedText {
 			information: SyncTextInformation {
 				encoding: TextEncoding::Latin1,
-				language: String::from("eng"),
+				language: Language::ENGLISH,
 				timestamp_format: TimestampFormat::MS,
 				content_type: SyncTextContentType::Lyrics,
 				description: Some(String::from("Test Sync Text")),
@@ -284,4 +268,51 @@ mod tests {
 
 		assert_eq!(encoded, expected_bytes);
 	}
+
+	#[test]
+	fn sylt_utf16_frames_round_trip() {
+		let sylt = SynchronizedText {
+			information: SyncTextInformation {
+				encoding: TextEncoding::UTF16,
+				language: Language::from_bytes(*b"deu"),
+				timestamp_format: TimestampFormat::MPEG,
+				content_type: SyncTextContentType::Lyrics,
+				description: Some(String::from("Foo description")),
+			},
+			content: vec![
+				(0, String::from("Foo")),
+				(38, String::from("Bar")),
+				(76, String::from("Baz")),
+			],
+		};
+
+		let encoded = sylt.as_bytes().unwrap();
+		assert_eq!(SynchronizedText::parse(&encoded).unwrap(), sylt);
+	}
+
+	#[test]
+	fn sylt_invalid_language() {
+		for (language, expected) in [(*b"en\0", b"eng"), (*b"ENG", b"eng"), (*b"e1g", b"und")] {
+			let sylt = SynchronizedText {
+				information: SyncTextInformation {
+					encoding: TextEncoding::Latin1,
+					language: Language::from_bytes(language),
+					timestamp_format: TimestampFormat::MS,
+					content_type: SyncTextContentType::Lyrics,
+					description: None,
+				},
+				content: vec![(0, String::from("Foo"))],
+			};
+
+			let encoded = sylt.as_bytes().unwrap();
+			assert_eq!(&encoded[1..4], expected);
+
+			// Invalid languages are preserved when reading
+			let mut raw = encoded.clone();
+			raw[1..4].copy_from_slice(&language);
+			let parsed = SynchronizedText::parse(&raw).unwrap();
+			assert_eq!(parsed.information.language, Language::from_bytes(language));
+			assert!(!parsed.information.language.is_valid());
+		}
+	}
 }
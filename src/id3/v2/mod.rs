@@ -28,9 +28,12 @@ cfg_if::cfg_if! {
 		pub use tag::ID3v2Tag;
 
 		mod items;
+		pub use items::chapter::{Chapter, TableOfContents};
 		pub use items::encoded_text_frame::EncodedTextFrame;
+		pub use items::language::Language;
 		pub use items::language_frame::LanguageFrame;
 		pub use items::encapsulated_object::{GEOBInformation, GeneralEncapsulatedObject};
+		pub use items::popularimeter::Popularimeter;
 		pub use items::sync_text::{SyncTextContentType, SyncTextInformation, SynchronizedText, TimestampFormat};
 
 		mod frame;
@@ -1,8 +1,11 @@
-use super::frame::Frame;
+use super::frame::header::frame_size;
+use super::frame::{Frame, FrameValue};
 use super::tag::ID3v2Tag;
-use super::ID3v2Header;
-use crate::error::Result;
+use super::util::unsynch_content;
+use super::{ID3v2Header, ID3v2Version};
+use crate::error::{ErrorLocation, Result};
 use crate::macros::try_vec;
+use crate::probe::{ParseMode, ParseOptions};
 
 use std::io::Read;
 
@@ -10,28 +13,175 @@ pub(crate) fn parse_id3v2<R>(bytes: &mut R, header: ID3v2Header) -> Result<ID3v2
 where
 	R: Read,
 {
+	parse_id3v2_with_options(bytes, header, &mut ParseOptions::new())
+}
+
+/// Parse an ID3v2 tag, following the provided [`ParseMode`]
+///
+/// With [`ParseMode::Relaxed`], any frames that fail to parse are skipped, and their
+/// errors are pushed to `parse_errors`, along with those of any pictures exceeding the `limits`.
+/// Exceeding the tag size limit is always an error.
+pub(crate) fn parse_id3v2_with_options<R>(
+	bytes: &mut R,
+	header: ID3v2Header,
+	options: &mut ParseOptions<'_>,
+) -> Result<ID3v2Tag>
+where
+	R: Read,
+{
+	let limits = options.limits;
+	limits.check_tag_size(u64::from(header.size))?;
+
 	let mut tag_bytes = try_vec![0; (header.size - header.extended_size) as usize];
 	bytes.read_exact(&mut tag_bytes)?;
 
+	// Prior to ID3v2.4, unsynchronisation is applied to the entire tag rather than each frame
+	let unsynchronised = header.flags.unsynchronisation;
+	if unsynchronised && header.version != ID3v2Version::V4 {
+		tag_bytes = unsynch_content(&tag_bytes)?;
+	}
+
 	let mut tag = ID3v2Tag::default();
 	tag.original_version = header.version;
 	tag.set_flags(header.flags);
 
 	let reader = &mut &*tag_bytes;
 
+	let mut picture_count = 0;
+
+	let id_len = if header.version == ID3v2Version::V2 {
+		3
+	} else {
+		4
+	};
+
 	loop {
-		match Frame::read(reader, header.version)? {
+		// The tag header (10) and extended header are not a part of `tag_bytes`
+		let offset = 10 + u64::from(header.extended_size) + (tag_bytes.len() - reader.len()) as u64;
+		let frame_start = *reader;
+		let id = &frame_start[..id_len.min(frame_start.len())];
+
+		let frame = match Frame::read(reader, header.version, unsynchronised) {
+			Ok(frame) => frame,
+			Err(e) => {
+				let e = e.with_location(ErrorLocation::new(String::from_utf8_lossy(id), offset));
+				if options.parse_mode == ParseMode::Strict {
+					return Err(e);
+				}
+
+				options.parse_errors.push(e);
+
+				// The reader may have stopped anywhere within the frame, so we have to
+				// go by the frame's header to find the next one. If the size is unusable,
+				// there's no way to recover the remaining frames.
+				match frame_len(frame_start, header.version) {
+					Some(len) if len <= frame_start.len() => {
+						*reader = &frame_start[len..];
+						continue;
+					},
+					_ => break,
+				}
+			},
+		};
+
+		match frame {
 			// No frame content found, and we can expect there are no more frames
 			(None, true) => break,
-			(Some(f), false) => drop(tag.insert(f)),
+			(Some(f), false) => {
+				if let FrameValue::Picture { picture, .. } = f.content() {
+					let check = limits
+						.check_picture_count(picture_count + 1)
+						.and_then(|()| limits.check_picture_size(picture.data().len() as u64));
+
+					if !options.keep_picture(check)? {
+						continue;
+					}
+
+					picture_count += 1;
+				}
+
+				drop(tag.insert(f))
+			},
 			// No frame content found, but we can expect more frames
 			_ => {},
 		}
 	}
 
+	if header.version == ID3v2Version::V3 {
+		merge_v3_timestamp(&mut tag);
+	}
+
 	Ok(tag)
 }
 
+// The length of a frame, including its header, going only by its header
+fn frame_len(frame: &[u8], version: ID3v2Version) -> Option<usize> {
+	let (header_len, size) = match version {
+		ID3v2Version::V2 if frame.len() >= 6 => {
+			(6, u32::from_be_bytes([0, frame[3], frame[4], frame[5]]))
+		},
+		ID3v2Version::V3 | ID3v2Version::V4 if frame.len() >= 10 => {
+			let size = u32::from_be_bytes([frame[4], frame[5], frame[6], frame[7]]);
+			(
+				10,
+				frame_size(size, version == ID3v2Version::V4, &frame[10..]),
+			)
+		},
+		_ => return None,
+	};
+
+	Some(header_len + size as usize)
+}
+
+// ID3v2.3 splits the recording timestamp into TYER (upgraded to TDRC), TDAT (DDMM), and TIME (HHMM)
+fn merge_v3_timestamp(tag: &mut ID3v2Tag) {
+	fn digits<'a>(tag: &'a ID3v2Tag, id: &str, len: usize) -> Option<&'a str> {
+		match tag.get(id).map(Frame::content) {
+			Some(FrameValue::Text { value, .. })
+				if value.len() == len && value.bytes().all(|b| b.is_ascii_digit()) =>
+			{
+				Some(value)
+			},
+			_ => None,
+		}
+	}
+
+	let (year, date) = match (digits(tag, "TDRC", 4), digits(tag, "TDAT", 4)) {
+		(Some(year), Some(date)) => (year, date),
+		_ => return,
+	};
+
+	let time = digits(tag, "TIME", 4);
+	let timestamp = match time {
+		Some(time) => format!(
+			"{}-{}-{}T{}:{}",
+			year,
+			&date[2..],
+			&date[..2],
+			&time[..2],
+			&time[2..]
+		),
+		None => format!("{}-{}-{}", year, &date[2..], &date[..2]),
+	};
+
+	let has_time = time.is_some();
+
+	let mut frame = match tag.get("TDRC") {
+		Some(frame) => frame.clone(),
+		None => return,
+	};
+
+	if let FrameValue::Text { ref mut value, .. } = frame.value {
+		*value = timestamp;
+	}
+
+	tag.insert(frame);
+	tag.remove("TDAT");
+	if has_time {
+		tag.remove("TIME");
+	}
+}
+
 #[test]
 fn zero_size_id3v2() {
 	use crate::id3::v2::read_id3v2_header;
@@ -41,3 +191,71 @@ fn zero_size_id3v2() {
 	let header = read_id3v2_header(&mut f).unwrap();
 	assert!(parse_id3v2(&mut f, header).is_ok());
 }
+
+#[test]
+#[allow(clippy::non_ascii_literal)]
+fn unsynchronised_id3v2() {
+	use crate::id3::v2::read_id3v2_header;
+	use crate::Accessor;
+
+	// "ÿÿA", where the first 0xFF is followed by a zero
+	let frame_content = [0x00, 0xFF, 0x00, 0xFF, b'A'];
+
+	// ID3v2.3, where the entire tag is unsynchronised
+	let mut tag_bytes = b"ID3\x03\x00\x80\x00\x00\x00\x0FTIT2\x00\x00\x00\x04\x00\x00".to_vec();
+	tag_bytes.extend(frame_content);
+
+	let reader = &mut &tag_bytes[..];
+	let header = read_id3v2_header(reader).unwrap();
+	let tag = parse_id3v2(reader, header).unwrap();
+	assert_eq!(tag.title(), Some("ÿÿA"));
+
+	// ID3v2.4, where the frame is unsynchronised
+	let mut tag_bytes = b"ID3\x04\x00\x00\x00\x00\x00\x0FTIT2\x00\x00\x00\x05\x00\x02".to_vec();
+	tag_bytes.extend(frame_content);
+
+	let reader = &mut &tag_bytes[..];
+	let header = read_id3v2_header(reader).unwrap();
+	let tag = parse_id3v2(reader, header).unwrap();
+	assert_eq!(tag.title(), Some("ÿÿA"));
+}
+
+#[test]
+fn bad_frame_location() {
+	use crate::id3::v2::read_id3v2_header;
+
+	// A valid "TIT2" frame, followed by a "TALB" frame claiming to be larger than the tag
+	let tag_bytes = b"ID3\x04\x00\x00\x00\x00\x00\x16\
+		TIT2\x00\x00\x00\x02\x00\x00\x03A\
+		TALB\x00\x00\x00\x7F\x00\x00";
+
+	let reader = &mut &tag_bytes[..];
+	let header = read_id3v2_header(reader).unwrap();
+	let err = parse_id3v2(reader, header).unwrap_err();
+
+	let location = err.location().unwrap();
+	assert_eq!(location.id(), "TALB");
+	assert_eq!(location.offset(), 0x16);
+}
+
+#[test]
+fn relaxed_unrecoverable_frame() {
+	use crate::id3::v2::read_id3v2_header;
+	use crate::Accessor;
+
+	// The "TALB" frame claims to be larger than the tag, so there's no way to find any frames after it
+	let tag_bytes = b"ID3\x04\x00\x00\x00\x00\x00\x16\
+		TIT2\x00\x00\x00\x02\x00\x00\x03A\
+		TALB\x00\x00\x00\x7F\x00\x00";
+
+	let reader = &mut &tag_bytes[..];
+	let header = read_id3v2_header(reader).unwrap();
+
+	let mut options = ParseOptions::new();
+	options.parse_mode = ParseMode::Relaxed;
+
+	let tag = parse_id3v2_with_options(reader, header, &mut options).unwrap();
+
+	assert_eq!(tag.title().as_deref(), Some("A"));
+	assert_eq!(options.parse_errors.len(), 1);
+}
@@ -1,21 +1,31 @@
 use super::flags::ID3v2TagFlags;
 use super::frame::id::FrameID;
 use super::frame::{Frame, FrameFlags, FrameValue};
-use super::util::text_utils::TextEncoding;
+use super::util::text_utils::{encode_text, TextEncoding};
 use super::ID3v2Version;
-use crate::error::{LoftyError, Result};
+use crate::error::{FileEncodingError, LoftyError, Result};
+use crate::file::FileType;
 use crate::id3::v2::frame::FrameRef;
+use crate::id3::v2::items::chapter::{Chapter, TableOfContents};
+use crate::id3::v2::items::encapsulated_object::GeneralEncapsulatedObject;
 use crate::id3::v2::items::encoded_text_frame::EncodedTextFrame;
+use crate::id3::v2::items::language::Language;
 use crate::id3::v2::items::language_frame::LanguageFrame;
+use crate::id3::v2::items::popularimeter::Popularimeter;
+use crate::id3::v2::items::sync_text::SynchronizedText;
+use crate::mpeg::MPEGWriteOptions;
 use crate::picture::{Picture, PictureType};
+use crate::probe::Probe;
+use crate::tag::gapless::GaplessInfo;
 use crate::tag::item::{ItemKey, ItemValue, TagItem};
+use crate::tag::utils::{is_num_pair_key, join_num_pair, num_pair, split_num_pair};
 use crate::tag::{Tag, TagType};
 use crate::traits::{Accessor, TagExt};
 
 use std::borrow::Cow;
 use std::convert::TryInto;
 use std::fs::{File, OpenOptions};
-use std::io::Write;
+use std::io::{Seek, Write};
 use std::path::Path;
 
 macro_rules! impl_accessor {
@@ -64,7 +74,9 @@ macro_rules! impl_accessor {
 ///
 /// ## Supported file types
 ///
+/// * [`FileType::AAC`](crate::FileType::AAC)
 /// * [`FileType::MPEG`](crate::FileType::MPEG)
+/// * [`FileType::TTA`](crate::FileType::TTA)
 /// * [`FileType::WAV`](crate::FileType::WAV)
 /// * [`FileType::AIFF`](crate::FileType::AIFF)
 /// * [`FileType::APE`](crate::FileType::APE) **(READ ONLY)**
@@ -88,18 +100,25 @@ macro_rules! impl_accessor {
 ///
 /// * TXXX/WXXX - These frames will be stored as an [`ItemKey`] by their description. Some variants exist for these descriptions, such as the one for `ReplayGain`,
 /// otherwise [`ItemKey::Unknown`] will be used.
-/// * Any [`LanguageFrame`] - With ID3v2 being the only format that allows for language-specific items, this information is not retained. These frames **will** be discarded.
+/// * Any [`LanguageFrame`] - With ID3v2 being the only format that allows for language-specific items, the language and description
+/// are not retained. Multiple frames, such as lyrics in different languages, will be stored as multiple items of the same [`ItemKey`].
+///    * `COMM` frames with a description (Ex. iTunes' `iTunNORM` and `iTunSMPB`) are the exception, they're stored as binary
+///    [`ItemKey::Unknown("COMM")`](crate::ItemKey::Unknown) items so they can be written back as-is. These items are only
+///    written to ID3v2 tags. Only comments without a description are stored as [`ItemKey::Comment`](crate::ItemKey::Comment).
 ///
 /// ## Special Frames
 ///
-/// ID3v2 has `GEOB` and `SYLT` frames, which are not parsed by default, instead storing them as [`FrameValue::Binary`].
-/// They can easily be parsed with [`GeneralEncapsulatedObject::parse`](crate::id3::v2::GeneralEncapsulatedObject::parse)
-/// and [`SynchronizedText::parse`](crate::id3::v2::SynchronizedText::parse) respectively, and converted back to binary with
-/// [`GeneralEncapsulatedObject::as_bytes`](crate::id3::v2::GeneralEncapsulatedObject::as_bytes) and
-/// [`SynchronizedText::as_bytes`](crate::id3::v2::SynchronizedText::as_bytes) for writing.
+/// `GEOB` frames are parsed into [`GeneralEncapsulatedObject`]. See [`ID3v2Tag::general_encapsulated_objects`].
+///
+/// `SYLT` frames are parsed into [`SynchronizedText`]. See [`ID3v2Tag::synchronized_text`].
+///
+/// `CHAP` and `CTOC` frames are parsed into [`Chapter`] and [`TableOfContents`] respectively. See [`ID3v2Tag::chapters`]
+/// and [`ID3v2Tag::tables_of_contents`].
 pub struct ID3v2Tag {
 	flags: ID3v2TagFlags,
 	pub(super) original_version: ID3v2Version,
+	version: ID3v2Version,
+	preferred_encoding: Option<TextEncoding>,
 	frames: Vec<Frame>,
 }
 
@@ -117,6 +136,8 @@ impl Default for ID3v2Tag {
 		Self {
 			flags: ID3v2TagFlags::default(),
 			original_version: ID3v2Version::V4,
+			version: ID3v2Version::V4,
+			preferred_encoding: None,
 			frames: Vec::new(),
 		}
 	}
@@ -140,6 +161,77 @@ impl ID3v2Tag {
 	pub fn original_version(&self) -> ID3v2Version {
 		self.original_version
 	}
+
+	/// The version the tag will be written as
+	///
+	/// This is [`ID3v2Version::V4`] unless changed with [`ID3v2Tag::set_version`],
+	/// regardless of [`ID3v2Tag::original_version`].
+	pub fn version(&self) -> ID3v2Version {
+		self.version
+	}
+
+	/// Set the version the tag will be written as
+	///
+	/// When writing an `ID3v2.3` tag:
+	///
+	/// * `TDRC` is split into `TYER`, `TDAT`, and `TIME`, `TDOR` becomes `TORY`, and `TIPL` becomes `IPLS`
+	/// * Text encoded as [`TextEncoding::UTF8`] or [`TextEncoding::UTF16BE`] will be written as [`TextEncoding::UTF16`]
+	/// * Footers and extended headers are not written
+	///
+	/// NOTE: Writing `ID3v2.2` tags is not supported, and will result in an error.
+	pub fn set_version(&mut self, version: ID3v2Version) {
+		self.version = version
+	}
+
+	/// The [`TextEncoding`] used for all frames when writing, if set
+	///
+	/// See [`ID3v2Tag::set_preferred_encoding`].
+	pub fn preferred_encoding(&self) -> Option<TextEncoding> {
+		self.preferred_encoding
+	}
+
+	/// Set the [`TextEncoding`] to use for all frames when writing
+	///
+	/// By default (`None`), each frame is written with its own encoding.
+	///
+	/// Regardless of this setting:
+	///
+	/// * Text that can't be represented in Latin-1 will be written as [`TextEncoding::UTF8`]
+	/// (or [`TextEncoding::UTF16`] for `ID3v2.3`), rather than losing characters
+	/// * The encoding may be replaced when writing an `ID3v2.3` tag (See [`ID3v2Tag::set_version`])
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{ID3v2Tag, TextEncoding};
+	///
+	/// let mut tag = ID3v2Tag::default();
+	/// tag.set_preferred_encoding(Some(TextEncoding::UTF16));
+	///
+	/// assert_eq!(tag.preferred_encoding(), Some(TextEncoding::UTF16));
+	/// ```
+	pub fn set_preferred_encoding(&mut self, encoding: Option<TextEncoding>) {
+		self.preferred_encoding = encoding
+	}
+
+	// The frames to write, with the preferred encoding applied
+	fn frame_refs(&self) -> impl Iterator<Item = FrameRef<'_>> {
+		let preferred_encoding = self.preferred_encoding;
+
+		self.frames
+			.iter()
+			.filter_map(Frame::as_opt_ref)
+			.map(move |mut frame| {
+				if let Some(preferred_encoding) = preferred_encoding {
+					if let Some(value) = frame.value.with_encoding(|_, _| Some(preferred_encoding))
+					{
+						frame.value = Cow::Owned(value);
+					}
+				}
+
+				frame
+			})
+	}
 }
 
 impl ID3v2Tag {
@@ -148,6 +240,14 @@ impl ID3v2Tag {
 		self.frames.iter()
 	}
 
+	/// Returns the tag's frames as a slice
+	///
+	/// The frames are in the order they were read or inserted. Frames replaced through
+	/// [`ID3v2Tag::insert`] keep their original position.
+	pub fn frames(&self) -> &[Frame] {
+		&self.frames
+	}
+
 	/// Returns the number of frames in the tag
 	pub fn len(&self) -> usize {
 		self.frames.len()
@@ -166,14 +266,13 @@ impl ID3v2Tag {
 	///
 	/// This will replace any frame of the same id (**or description!** See [`EncodedTextFrame`])
 	pub fn insert(&mut self, frame: Frame) -> Option<Frame> {
-		let replaced = self
-			.frames
-			.iter()
-			.position(|f| f == &frame)
-			.map(|pos| self.frames.remove(pos));
-
-		self.frames.push(frame);
-		replaced
+		match self.frames.iter().position(|f| f == &frame) {
+			Some(pos) => Some(std::mem::replace(&mut self.frames[pos], frame)),
+			None => {
+				self.frames.push(frame);
+				None
+			},
+		}
 	}
 
 	/// Removes a [`Frame`] by id
@@ -181,6 +280,76 @@ impl ID3v2Tag {
 		self.frames.retain(|f| f.id_str() != id)
 	}
 
+	/// Gets the content of the `TXXX` frame with the specified description
+	///
+	/// NOTE: The description is case-sensitive
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::ID3v2Tag;
+	///
+	/// let mut tag = ID3v2Tag::default();
+	/// tag.insert_user_text(String::from("MusicBrainz Album Id"), String::from("foo"));
+	///
+	/// assert_eq!(tag.get_user_text("MusicBrainz Album Id"), Some("foo"));
+	/// assert_eq!(tag.get_user_text("MUSICBRAINZ ALBUM ID"), None);
+	/// ```
+	pub fn get_user_text(&self, description: &str) -> Option<&str> {
+		self.user_text_position(description)
+			.and_then(|pos| match self.frames[pos].content() {
+				FrameValue::UserText(content) => Some(content.content.as_str()),
+				_ => None,
+			})
+	}
+
+	/// Inserts a `TXXX` frame with the specified description
+	///
+	/// This will replace any `TXXX` frame with the same description, keeping its position and [`TextEncoding`].
+	/// Otherwise, the frame will be [`TextEncoding::UTF8`].
+	pub fn insert_user_text(&mut self, description: String, content: String) -> Option<Frame> {
+		let (pos, encoding) = match self.user_text_position(&description) {
+			Some(pos) => match self.frames[pos].content() {
+				FrameValue::UserText(existing) => (Some(pos), existing.encoding),
+				_ => (None, TextEncoding::UTF8),
+			},
+			None => (None, TextEncoding::UTF8),
+		};
+
+		let frame = Frame {
+			id: FrameID::Valid(String::from("TXXX")),
+			value: FrameValue::UserText(EncodedTextFrame {
+				encoding,
+				description,
+				content,
+			}),
+			flags: FrameFlags::default(),
+		};
+
+		match pos {
+			Some(pos) => Some(std::mem::replace(&mut self.frames[pos], frame)),
+			None => {
+				self.frames.push(frame);
+				None
+			},
+		}
+	}
+
+	/// Removes the `TXXX` frame with the specified description
+	pub fn remove_user_text(&mut self, description: &str) -> Option<Frame> {
+		let pos = self.user_text_position(description)?;
+		Some(self.frames.remove(pos))
+	}
+
+	fn user_text_position(&self, description: &str) -> Option<usize> {
+		self.frames.iter().position(|f| match f.content() {
+			FrameValue::UserText(content) => {
+				f.id_str() == "TXXX" && content.description == description
+			},
+			_ => false,
+		})
+	}
+
 	/// Inserts a [`Picture`]
 	///
 	/// According to spec, there can only be one picture of type [`PictureType::Icon`] and [`PictureType::OtherIcon`].
@@ -256,8 +425,61 @@ impl ID3v2Tag {
 		})
 	}
 
-	/// Returns all `COMM` frames
-	pub fn comments(&self) -> impl Iterator<Item = &LanguageFrame> {
+	/// Returns all `COMM` frames as `(language, description, text)`
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{Frame, FrameFlags, FrameValue, ID3v2Tag, Language, LanguageFrame, TextEncoding};
+	/// use lofty::Accessor;
+	///
+	/// # fn main() -> lofty::Result<()> {
+	/// let mut tag = ID3v2Tag::default();
+	/// tag.set_comment(String::from("Foo comment"));
+	/// tag.insert(Frame::new(
+	/// 	"COMM",
+	/// 	FrameValue::Comment(LanguageFrame {
+	/// 		encoding: TextEncoding::Latin1,
+	/// 		language: Language::ENGLISH,
+	/// 		description: String::from("iTunNORM"),
+	/// 		content: String::from(" 00000A2B 00000A2B"),
+	/// 	}),
+	/// 	FrameFlags::default(),
+	/// )?);
+	///
+	/// assert_eq!(
+	/// 	tag.comments().collect::<Vec<_>>(),
+	/// 	[
+	/// 		(Language::ENGLISH, "", "Foo comment"),
+	/// 		(Language::ENGLISH, "iTunNORM", " 00000A2B 00000A2B")
+	/// 	]
+	/// );
+	///
+	/// // Only the comment without a description is used by `Accessor::comment`
+	/// assert_eq!(tag.comment(), Some("Foo comment"));
+	/// # Ok(()) }
+	/// ```
+	pub fn comments(&self) -> impl Iterator<Item = (Language, &str, &str)> {
+		self.comment_frames()
+			.map(|c| (c.language, c.description.as_str(), c.content.as_str()))
+	}
+
+	/// Returns the first `COMM` frame with an empty description
+	///
+	/// This is the comment used by [`Accessor::comment`]. Comments with descriptions, such as iTunes'
+	/// `iTunNORM`, are usually not meant to be displayed.
+	pub fn default_comment(&self) -> Option<&LanguageFrame> {
+		self.comment_frames().find(|c| c.description.is_empty())
+	}
+
+	/// Returns the encoder delay and padding, according to the `iTunSMPB` comment
+	pub fn gapless_info(&self) -> Option<GaplessInfo> {
+		self.comments()
+			.find(|(_, description, _)| *description == "iTunSMPB")
+			.and_then(|(_, _, content)| GaplessInfo::from_itunsmpb(content))
+	}
+
+	fn comment_frames(&self) -> impl Iterator<Item = &LanguageFrame> {
 		self.frames.iter().filter_map(|f| match f {
 			Frame {
 				id: FrameID::Valid(id),
@@ -268,26 +490,319 @@ impl ID3v2Tag {
 		})
 	}
 
+	fn is_default_comment(frame: &Frame) -> bool {
+		matches!(frame, Frame {
+			id: FrameID::Valid(id),
+			value: FrameValue::Comment(LanguageFrame { description, .. }),
+			..
+		} if id == "COMM" && description.is_empty())
+	}
+
+	/// Returns all `SYLT` frames
+	pub fn synchronized_text(&self) -> impl Iterator<Item = &SynchronizedText> {
+		self.frames.iter().filter_map(|f| match f {
+			Frame {
+				id: FrameID::Valid(id),
+				value: FrameValue::SynchronizedText(val),
+				..
+			} if id == "SYLT" => Some(val),
+			_ => None,
+		})
+	}
+
+	/// Inserts a [`SynchronizedText`]
+	///
+	/// This will replace any `SYLT` frame with the same language and description.
+	pub fn insert_synchronized_text(&mut self, sync_text: SynchronizedText) -> Option<Frame> {
+		let replaced = self
+			.frames
+			.iter()
+			.position(|f| match f.content() {
+				FrameValue::SynchronizedText(existing) => {
+					existing.information.language == sync_text.information.language
+						&& existing.information.description == sync_text.information.description
+				},
+				_ => false,
+			})
+			.map(|pos| self.frames.remove(pos));
+
+		self.frames.push(Frame {
+			id: FrameID::Valid(String::from("SYLT")),
+			value: FrameValue::SynchronizedText(sync_text),
+			flags: FrameFlags::default(),
+		});
+
+		replaced
+	}
+
+	/// Returns all `POPM` frames
+	pub fn popularimeters(&self) -> impl Iterator<Item = &Popularimeter> {
+		self.frames.iter().filter_map(|f| match f {
+			Frame {
+				id: FrameID::Valid(id),
+				value: FrameValue::Popularimeter(val),
+				..
+			} if id == "POPM" => Some(val),
+			_ => None,
+		})
+	}
+
+	/// Gets the `POPM` frame with the specified email
+	pub fn popularimeter(&self, email: &str) -> Option<&Popularimeter> {
+		self.popularimeters().find(|popm| popm.email == email)
+	}
+
+	/// Gets a mutable reference to the `POPM` frame with the specified email
+	///
+	/// This can be used to edit the rating or play counter in place.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{ID3v2Tag, Popularimeter};
+	///
+	/// let mut tag = ID3v2Tag::default();
+	/// tag.insert_popularimeter(Popularimeter::new(String::from("foo@bar.com"), 196, 0));
+	///
+	/// if let Some(popm) = tag.popularimeter_mut("foo@bar.com") {
+	/// 	popm.counter += 1;
+	/// }
+	///
+	/// assert_eq!(tag.popularimeter("foo@bar.com").unwrap().counter, 1);
+	/// ```
+	pub fn popularimeter_mut(&mut self, email: &str) -> Option<&mut Popularimeter> {
+		self.frames.iter_mut().find_map(|f| match f {
+			Frame {
+				id: FrameID::Valid(id),
+				value: FrameValue::Popularimeter(val),
+				..
+			} if id == "POPM" && val.email == email => Some(val),
+			_ => None,
+		})
+	}
+
+	/// Inserts a [`Popularimeter`]
+	///
+	/// This will replace any `POPM` frame with the same email, keeping its position.
+	pub fn insert_popularimeter(&mut self, popularimeter: Popularimeter) -> Option<Frame> {
+		self.insert(Frame {
+			id: FrameID::Valid(String::from("POPM")),
+			value: FrameValue::Popularimeter(popularimeter),
+			flags: FrameFlags::default(),
+		})
+	}
+
+	/// Removes the `POPM` frame with the specified email
+	pub fn remove_popularimeter(&mut self, email: &str) -> Option<Frame> {
+		let pos = self.frames.iter().position(|f| match f.content() {
+			FrameValue::Popularimeter(popm) => f.id_str() == "POPM" && popm.email == email,
+			_ => false,
+		})?;
+
+		Some(self.frames.remove(pos))
+	}
+
+	/// Returns the data of all `PRIV` frames with the specified owner identifier
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{Frame, FrameFlags, FrameValue, ID3v2Tag};
+	///
+	/// # fn main() -> lofty::Result<()> {
+	/// let mut tag = ID3v2Tag::default();
+	///
+	/// for data in [vec![1], vec![2]] {
+	/// 	tag.insert(Frame::new(
+	/// 		"PRIV",
+	/// 		FrameValue::Private {
+	/// 			owner: String::from("WM/MediaClassPrimaryID"),
+	/// 			data,
+	/// 		},
+	/// 		FrameFlags::default(),
+	/// 	)?);
+	/// }
+	///
+	/// let mut private_frames = tag.private_frames("WM/MediaClassPrimaryID");
+	/// assert_eq!(private_frames.next(), Some(&[1][..]));
+	/// assert_eq!(private_frames.next(), Some(&[2][..]));
+	/// # Ok(()) }
+	/// ```
+	pub fn private_frames<'a>(&'a self, owner: &'a str) -> impl Iterator<Item = &'a [u8]> + 'a {
+		self.frames.iter().filter_map(move |f| match f {
+			Frame {
+				id: FrameID::Valid(id),
+				value: FrameValue::Private { owner: o, data },
+				..
+			} if id == "PRIV" && o == owner => Some(data.as_slice()),
+			_ => None,
+		})
+	}
+
+	/// Returns all `GEOB` frames
+	pub fn general_encapsulated_objects(&self) -> impl Iterator<Item = &GeneralEncapsulatedObject> {
+		self.frames.iter().filter_map(|f| match f {
+			Frame {
+				id: FrameID::Valid(id),
+				value: FrameValue::GeneralEncapsulatedObject(val),
+				..
+			} if id == "GEOB" => Some(val),
+			_ => None,
+		})
+	}
+
+	/// Gets the `GEOB` frame with the specified content descriptor
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{GEOBInformation, GeneralEncapsulatedObject, ID3v2Tag, TextEncoding};
+	///
+	/// let mut tag = ID3v2Tag::default();
+	/// tag.insert_general_encapsulated_object(GeneralEncapsulatedObject {
+	/// 	information: GEOBInformation {
+	/// 		encoding: TextEncoding::Latin1,
+	/// 		mime_type: Some(String::from("application/octet-stream")),
+	/// 		file_name: Some(String::from("project.bin")),
+	/// 		descriptor: Some(String::from("Project")),
+	/// 	},
+	/// 	data: vec![1, 2, 3],
+	/// });
+	///
+	/// let geob = tag.general_encapsulated_object("Project").unwrap();
+	/// assert_eq!(geob.data, &[1, 2, 3]);
+	/// ```
+	pub fn general_encapsulated_object(
+		&self,
+		descriptor: &str,
+	) -> Option<&GeneralEncapsulatedObject> {
+		self.general_encapsulated_objects()
+			.find(|geob| geob.information.descriptor.as_deref().unwrap_or_default() == descriptor)
+	}
+
+	/// Inserts a [`GeneralEncapsulatedObject`]
+	///
+	/// This will replace any `GEOB` frame with the same content descriptor, keeping its position.
+	pub fn insert_general_encapsulated_object(
+		&mut self,
+		geob: GeneralEncapsulatedObject,
+	) -> Option<Frame> {
+		let pos = self.geob_position(geob.information.descriptor.as_deref().unwrap_or_default());
+
+		let frame = Frame {
+			id: FrameID::Valid(String::from("GEOB")),
+			value: FrameValue::GeneralEncapsulatedObject(geob),
+			flags: FrameFlags::default(),
+		};
+
+		match pos {
+			Some(pos) => Some(std::mem::replace(&mut self.frames[pos], frame)),
+			None => {
+				self.frames.push(frame);
+				None
+			},
+		}
+	}
+
+	/// Removes the `GEOB` frame with the specified content descriptor
+	pub fn remove_general_encapsulated_object(&mut self, descriptor: &str) -> Option<Frame> {
+		let pos = self.geob_position(descriptor)?;
+		Some(self.frames.remove(pos))
+	}
+
+	// A missing content descriptor is treated as an empty one
+	fn geob_position(&self, descriptor: &str) -> Option<usize> {
+		self.frames.iter().position(|f| match f.content() {
+			FrameValue::GeneralEncapsulatedObject(geob) => {
+				f.id_str() == "GEOB"
+					&& geob.information.descriptor.as_deref().unwrap_or_default() == descriptor
+			},
+			_ => false,
+		})
+	}
+
+	/// Returns all `CHAP` frames
+	pub fn chapters(&self) -> impl Iterator<Item = &Chapter> {
+		self.frames.iter().filter_map(|f| match f {
+			Frame {
+				id: FrameID::Valid(id),
+				value: FrameValue::Chapter(val),
+				..
+			} if id == "CHAP" => Some(val),
+			_ => None,
+		})
+	}
+
+	/// Inserts a [`Chapter`]
+	///
+	/// This will replace any chapter with the same element ID.
+	pub fn insert_chapter(&mut self, chapter: Chapter) -> Option<Frame> {
+		self.insert(Frame {
+			id: FrameID::Valid(String::from("CHAP")),
+			value: FrameValue::Chapter(chapter),
+			flags: FrameFlags::default(),
+		})
+	}
+
+	/// Returns all `CTOC` frames
+	pub fn tables_of_contents(&self) -> impl Iterator<Item = &TableOfContents> {
+		self.frames.iter().filter_map(|f| match f {
+			Frame {
+				id: FrameID::Valid(id),
+				value: FrameValue::TableOfContents(val),
+				..
+			} if id == "CTOC" => Some(val),
+			_ => None,
+		})
+	}
+
+	/// Inserts a [`TableOfContents`]
+	///
+	/// This will replace any table of contents with the same element ID.
+	pub fn insert_table_of_contents(&mut self, toc: TableOfContents) -> Option<Frame> {
+		self.insert(Frame {
+			id: FrameID::Valid(String::from("CTOC")),
+			value: FrameValue::TableOfContents(toc),
+			flags: FrameFlags::default(),
+		})
+	}
+
 	fn split_num_pair(&self, id: &str) -> (Option<u32>, Option<u32>) {
 		if let Some(Frame {
 			value: FrameValue::Text { ref value, .. },
 			..
 		}) = self.get(id)
 		{
-			let mut split = value.split(&['\0', '/'][..]).flat_map(str::parse::<u32>);
-			return (split.next(), split.next());
+			return split_num_pair(value);
 		}
 
 		(None, None)
 	}
+
+	fn set_num_pair(&mut self, id: &str, current: Option<u32>, total: Option<u32>) {
+		match num_pair(current, total) {
+			Some(value) => {
+				self.insert(Frame::text(id, value));
+			},
+			None => self.remove(id),
+		}
+	}
 }
 
 impl Accessor for ID3v2Tag {
 	impl_accessor!(
-		title  => "TIT2";
-		artist => "TPE1";
-		album  => "TALB";
-		genre  => "TCON";
+		title        => "TIT2";
+		artist       => "TPE1";
+		album        => "TALB";
+		genre        => "TCON";
+		album_artist => "TPE2";
+		composer     => "TCOM";
+		initial_key  => "TKEY";
+		encoder      => "TSSE";
+		title_sort => "TSOT";
+		artist_sort => "TSOP";
+		album_sort => "TSOA";
+		album_artist_sort => "TSO2";
 	);
 
 	fn track(&self) -> Option<u32> {
@@ -295,11 +810,11 @@ impl Accessor for ID3v2Tag {
 	}
 
 	fn set_track(&mut self, value: u32) {
-		self.insert(Frame::text("TRCK", value.to_string()));
+		self.set_num_pair("TRCK", Some(value), self.track_total());
 	}
 
 	fn remove_track(&mut self) {
-		self.remove("TRCK");
+		self.set_num_pair("TRCK", None, self.track_total());
 	}
 
 	fn track_total(&self) -> Option<u32> {
@@ -307,18 +822,11 @@ impl Accessor for ID3v2Tag {
 	}
 
 	fn set_track_total(&mut self, value: u32) {
-		let current_track = self.split_num_pair("TRCK").0.unwrap_or(1);
-
-		self.insert(Frame::text("TRCK", format!("{}/{}", current_track, value)));
+		self.set_num_pair("TRCK", self.track(), Some(value));
 	}
 
 	fn remove_track_total(&mut self) {
-		let existing_track_number = self.track();
-		self.remove("TRCK");
-
-		if let Some(track) = existing_track_number {
-			self.insert(Frame::text("TRCK", track.to_string()));
-		}
+		self.set_num_pair("TRCK", self.track(), None);
 	}
 
 	fn disk(&self) -> Option<u32> {
@@ -326,11 +834,11 @@ impl Accessor for ID3v2Tag {
 	}
 
 	fn set_disk(&mut self, value: u32) {
-		self.insert(Frame::text("TPOS", value.to_string()));
+		self.set_num_pair("TPOS", Some(value), self.disk_total());
 	}
 
 	fn remove_disk(&mut self) {
-		self.remove("TPOS");
+		self.set_num_pair("TPOS", None, self.disk_total());
 	}
 
 	fn disk_total(&self) -> Option<u32> {
@@ -338,18 +846,31 @@ impl Accessor for ID3v2Tag {
 	}
 
 	fn set_disk_total(&mut self, value: u32) {
-		let current_disk = self.split_num_pair("TPOS").0.unwrap_or(1);
-
-		self.insert(Frame::text("TPOS", format!("{}/{}", current_disk, value)));
+		self.set_num_pair("TPOS", self.disk(), Some(value));
 	}
 
 	fn remove_disk_total(&mut self) {
-		let existing_track_number = self.track();
-		self.remove("TPOS");
+		self.set_num_pair("TPOS", self.disk(), None);
+	}
 
-		if let Some(track) = existing_track_number {
-			self.insert(Frame::text("TPOS", track.to_string()));
+	fn bpm(&self) -> Option<u16> {
+		if let Some(Frame {
+			value: FrameValue::Text { value, .. },
+			..
+		}) = self.get("TBPM")
+		{
+			return crate::tag::utils::parse_bpm(value);
 		}
+
+		None
+	}
+
+	fn set_bpm(&mut self, value: u16) {
+		self.insert(Frame::text("TBPM", value.to_string()));
+	}
+
+	fn remove_bpm(&mut self) {
+		self.remove("TBPM");
 	}
 
 	fn year(&self) -> Option<u32> {
@@ -378,20 +899,12 @@ impl Accessor for ID3v2Tag {
 	}
 
 	fn comment(&self) -> Option<&str> {
-		if let Some(Frame {
-			value: FrameValue::Comment(LanguageFrame { content, .. }),
-			..
-		}) = self.get("COMM")
-		{
-			return Some(content);
-		}
-
-		None
+		self.default_comment().map(|c| c.content.as_str())
 	}
 
 	fn set_comment(&mut self, value: String) {
 		// We'll just replace the first comment's content if it exists, otherwise create a new one
-		let first_comment = self.frames.iter_mut().find(|f| f.id_str() == "COMM");
+		let first_comment = self.frames.iter_mut().find(|f| Self::is_default_comment(f));
 		if let Some(Frame {
 			value: FrameValue::Comment(LanguageFrame { content, .. }),
 			..
@@ -406,7 +919,7 @@ impl Accessor for ID3v2Tag {
 				id: FrameID::Valid(String::from("COMM")),
 				value: FrameValue::Comment(LanguageFrame {
 					encoding: TextEncoding::UTF8,
-					language: *b"eng",
+					language: Language::ENGLISH,
 					description: String::new(),
 					content: value,
 				}),
@@ -416,7 +929,37 @@ impl Accessor for ID3v2Tag {
 	}
 
 	fn remove_comment(&mut self) {
-		self.remove("COMM");
+		// Comments with descriptions are left alone
+		self.frames.retain(|f| !Self::is_default_comment(f));
+	}
+}
+
+impl ID3v2Tag {
+	/// Writes the tag to an MPEG file, using the provided [`MPEGWriteOptions`]
+	///
+	/// This returns `true` if the file was modified. See [`TagExt::save_to`] to write the tag with
+	/// the default options.
+	///
+	/// # Errors
+	///
+	/// * `file` is not an MPEG file
+	/// * The ID3v1 policy is not [`ID3v1Policy::Keep`](crate::mpeg::ID3v1Policy::Keep), and the
+	///   `id3v1` feature is disabled
+	/// * See [`TagExt::save_to`]
+	pub fn save_to_mpeg(&self, file: &mut File, options: MPEGWriteOptions) -> Result<bool> {
+		let probe = Probe::new(file).guess_file_type()?;
+		if probe.file_type() != Some(FileType::MPEG) {
+			return Err(FileEncodingError::new(FileType::MPEG, "File is not an MPEG file").into());
+		}
+
+		let file = probe.into_inner();
+
+		let mut changed = self.save_to(file)?;
+
+		file.rewind()?;
+		changed |= crate::mpeg::write::write_id3v1_policy(file, self, options.id3v1)?;
+
+		Ok(changed)
 	}
 }
 
@@ -427,7 +970,7 @@ impl TagExt for ID3v2Tag {
 		self.frames.is_empty()
 	}
 
-	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err> {
 		self.save_to(&mut OpenOptions::new().read(true).write(true).open(path)?)
 	}
 
@@ -438,10 +981,11 @@ impl TagExt for ID3v2Tag {
 	/// * Attempting to write the tag to a format that does not support it
 	/// * Attempting to write an encrypted frame without a valid method symbol or data length indicator
 	/// * Attempting to write an invalid [`FrameID`]/[`FrameValue`] pairing
-	fn save_to(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err> {
 		Id3v2TagRef {
 			flags: self.flags,
-			frames: self.frames.iter().filter_map(Frame::as_opt_ref),
+			version: self.version,
+			frames: self.frame_refs(),
 		}
 		.write_to(file)
 	}
@@ -455,7 +999,8 @@ impl TagExt for ID3v2Tag {
 	fn dump_to<W: Write>(&self, writer: &mut W) -> std::result::Result<(), Self::Err> {
 		Id3v2TagRef {
 			flags: self.flags,
-			frames: self.frames.iter().filter_map(Frame::as_opt_ref),
+			version: self.version,
+			frames: self.frame_refs(),
 		}
 		.dump_to(writer)
 	}
@@ -483,8 +1028,12 @@ impl From<ID3v2Tag> for Tag {
 		) -> Option<()> {
 			let mut split = content.splitn(2, &['\0', '/'][..]);
 			let current = split.next()?.to_string();
-			tag.items
-				.push(TagItem::new(current_key, ItemValue::Text(current)));
+
+			// A missing number is stored as 0 when there is a total
+			if !current.is_empty() && current != "0" {
+				tag.items
+					.push(TagItem::new(current_key, ItemValue::Text(current)));
+			}
 
 			if let Some(total) = split.next() {
 				tag.items
@@ -496,6 +1045,9 @@ impl From<ID3v2Tag> for Tag {
 
 		let mut tag = Self::new(TagType::ID3v2);
 
+		// Both `TIT1` and `GRP1` are used for the grouping, `TIT1` is preferred if both exist
+		let has_tit1 = input.frames.iter().any(|f| f.id_str() == "TIT1");
+
 		for frame in input.frames {
 			let id = frame.id;
 
@@ -518,6 +1070,17 @@ impl From<ID3v2Tag> for Tag {
 				{
 					continue
 				},
+				("MVIN", FrameValue::Text { value: content, .. })
+					if split_pair(
+						&content,
+						&mut tag,
+						ItemKey::MovementNumber,
+						ItemKey::MovementCount,
+					)
+					.is_some() =>
+				{
+					continue
+				},
 				// Store TXXX/WXXX frames by their descriptions, rather than their IDs
 				(
 					"TXXX",
@@ -535,6 +1098,14 @@ impl From<ID3v2Tag> for Tag {
 						));
 					}
 				},
+				// Comments with descriptions are stored as-is, so they aren't mixed with the
+				// displayable comments
+				("COMM", FrameValue::Comment(ref comment)) if !comment.description.is_empty() => {
+					tag.items.push(TagItem::new(
+						ItemKey::Unknown(String::from("COMM")),
+						ItemValue::Binary(comment.as_bytes()),
+					));
+				},
 				(
 					"WXXX",
 					FrameValue::UserURL(EncodedTextFrame {
@@ -543,20 +1114,24 @@ impl From<ID3v2Tag> for Tag {
 						..
 					}),
 				) => {
+					// URLs can contain slashes, so they aren't split like TXXX frames
 					let item_key = ItemKey::from_key(TagType::ID3v2, description);
-					for c in content.split(&['\0', '/'][..]) {
-						tag.items.push(TagItem::new(
-							item_key.clone(),
-							ItemValue::Locator(c.to_string()),
-						));
-					}
+					tag.items
+						.push(TagItem::new(item_key, ItemValue::Locator(content.clone())));
 				},
 				(id, value) => {
-					let item_key = ItemKey::from_key(TagType::ID3v2, id);
+					let item_key = match id {
+						// Kept as-is, so it isn't lost when writing the tag back
+						"GRP1" if has_tit1 => ItemKey::Unknown(String::from("GRP1")),
+						_ => ItemKey::from_key(TagType::ID3v2, id),
+					};
 
 					let item_value = match value {
+						// Lyrics are stored as-is, they may contain any of the separators
+						FrameValue::UnSyncText(LanguageFrame { content, .. }) => {
+							ItemValue::Text(content)
+						},
 						FrameValue::Comment(LanguageFrame { content, .. })
-						| FrameValue::UnSyncText(LanguageFrame { content, .. })
 						| FrameValue::Text { value: content, .. }
 						| FrameValue::UserText(EncodedTextFrame { content, .. }) => {
 							for c in content.split(&['\0', '/'][..]) {
@@ -574,7 +1149,26 @@ impl From<ID3v2Tag> for Tag {
 							tag.push_picture(picture);
 							continue;
 						},
-						FrameValue::Popularimeter(_) => continue,
+						// Stored as binary, so it can be written back as-is
+						FrameValue::SynchronizedText(sync_text) => match sync_text.as_bytes() {
+							Ok(bytes) => ItemValue::Binary(bytes),
+							Err(_) => continue,
+						},
+						// Stored as binary, so the email and play counter aren't lost
+						FrameValue::Popularimeter(popularimeter) => {
+							ItemValue::Binary(popularimeter.as_bytes())
+						},
+						// Stored as binary, so it can be written back as-is
+						FrameValue::GeneralEncapsulatedObject(geob) => {
+							ItemValue::Binary(geob.as_bytes())
+						},
+						FrameValue::Private { owner, mut data } => {
+							let mut content = encode_text(&owner, TextEncoding::Latin1, true);
+							content.append(&mut data);
+
+							ItemValue::Binary(content)
+						},
+						FrameValue::Chapter(_) | FrameValue::TableOfContents(_) => continue,
 						FrameValue::Binary(binary) => ItemValue::Binary(binary),
 					};
 
@@ -615,6 +1209,20 @@ impl From<Tag> for ID3v2Tag {
 		let artists = join_items(&mut input, &ItemKey::TrackArtist);
 		id3v2_tag.set_artist(artists);
 
+		// Track, disc, and movement numbers are stored together as "current/total"
+		for (id, current_key, total_key) in [
+			("TRCK", ItemKey::TrackNumber, ItemKey::TrackTotal),
+			("TPOS", ItemKey::DiscNumber, ItemKey::DiscTotal),
+			("MVIN", ItemKey::MovementNumber, ItemKey::MovementCount),
+		] {
+			if let Some(value) = join_num_pair(&input, &current_key, &total_key) {
+				id3v2_tag.insert(Frame::text(id, value));
+			}
+
+			input.remove_key(&current_key);
+			input.remove_key(&total_key);
+		}
+
 		for item in input.items {
 			let frame: Frame = match item.into() {
 				Some(frame) => frame,
@@ -641,6 +1249,7 @@ impl From<Tag> for ID3v2Tag {
 
 pub(crate) struct Id3v2TagRef<'a, I: Iterator<Item = FrameRef<'a>> + 'a> {
 	pub(crate) flags: ID3v2TagFlags,
+	pub(crate) version: ID3v2Version,
 	pub(crate) frames: I,
 }
 
@@ -648,6 +1257,7 @@ impl<'a> Id3v2TagRef<'a, std::iter::Empty<FrameRef<'a>>> {
 	pub(crate) fn empty() -> Self {
 		Self {
 			flags: ID3v2TagFlags::default(),
+			version: ID3v2Version::V4,
 			frames: std::iter::empty(),
 		}
 	}
@@ -655,11 +1265,38 @@ impl<'a> Id3v2TagRef<'a, std::iter::Empty<FrameRef<'a>>> {
 
 // Create an iterator of FrameRef from a Tag's items for Id3v2TagRef::new
 pub(crate) fn tag_frames(tag: &Tag) -> impl Iterator<Item = FrameRef<'_>> + '_ {
+	// Track, disc, and movement numbers are stored together as "current/total"
+	let num_pairs = [
+		(
+			"TRCK",
+			join_num_pair(tag, &ItemKey::TrackNumber, &ItemKey::TrackTotal),
+		),
+		(
+			"TPOS",
+			join_num_pair(tag, &ItemKey::DiscNumber, &ItemKey::DiscTotal),
+		),
+		(
+			"MVIN",
+			join_num_pair(tag, &ItemKey::MovementNumber, &ItemKey::MovementCount),
+		),
+	];
+
 	let items = tag
 		.items()
 		.iter()
+		.filter(|i| !is_num_pair_key(i.key()))
 		.map(TryInto::<FrameRef<'_>>::try_into)
-		.filter_map(Result::ok);
+		.filter_map(Result::ok)
+		.chain(num_pairs.into_iter().filter_map(|(id, value)| {
+			value.map(|value| FrameRef {
+				id,
+				value: Cow::Owned(FrameValue::Text {
+					encoding: TextEncoding::UTF8,
+					value,
+				}),
+				flags: FrameFlags::default(),
+			})
+		}));
 
 	let pictures = tag.pictures().iter().map(|p| FrameRef {
 		id: "APIC",
@@ -674,7 +1311,7 @@ pub(crate) fn tag_frames(tag: &Tag) -> impl Iterator<Item = FrameRef<'_>> + '_ {
 }
 
 impl<'a, I: Iterator<Item = FrameRef<'a>> + 'a> Id3v2TagRef<'a, I> {
-	pub(crate) fn write_to(&mut self, file: &mut File) -> Result<()> {
+	pub(crate) fn write_to(&mut self, file: &mut File) -> Result<bool> {
 		super::write::write_id3v2(file, self)
 	}
 
@@ -690,12 +1327,14 @@ impl<'a, I: Iterator<Item = FrameRef<'a>> + 'a> Id3v2TagRef<'a, I> {
 mod tests {
 	use crate::id3::v2::items::popularimeter::Popularimeter;
 	use crate::id3::v2::{
-		read_id3v2_header, EncodedTextFrame, Frame, FrameFlags, FrameID, FrameValue, ID3v2Tag,
-		ID3v2Version, LanguageFrame, TextEncoding,
+		read_id3v2_header, Chapter, EncodedTextFrame, Frame, FrameFlags, FrameID, FrameValue,
+		GEOBInformation, GeneralEncapsulatedObject, ID3v2Tag, ID3v2Version, Language,
+		LanguageFrame, SyncTextContentType, SyncTextInformation, SynchronizedText, TableOfContents,
+		TextEncoding, TimestampFormat,
 	};
 	use crate::tag::utils::test_utils::read_path;
 	use crate::{
-		ItemKey, ItemValue, MimeType, Picture, PictureType, Tag, TagExt, TagItem, TagType,
+		Accessor, ItemKey, ItemValue, MimeType, Picture, PictureType, Tag, TagExt, TagItem, TagType,
 	};
 
 	fn read_tag(path: &str) -> ID3v2Tag {
@@ -755,7 +1394,7 @@ mod tests {
 				"COMM",
 				FrameValue::Comment(LanguageFrame {
 					encoding,
-					language: *b"eng",
+					language: Language::ENGLISH,
 					description: String::new(),
 					content: String::from("Qux comment"),
 				}),
@@ -820,6 +1459,194 @@ mod tests {
 		assert_eq!(parsed_tag, temp_parsed_tag);
 	}
 
+	#[test]
+	fn url_frames_round_trip() {
+		let mut tag = ID3v2Tag::default();
+		tag.insert(
+			Frame::new(
+				"WOAR",
+				FrameValue::URL(String::from("https://example.com/caf\u{e9}")),
+				FrameFlags::default(),
+			)
+			.unwrap(),
+		);
+		tag.insert(
+			Frame::new(
+				"WOAF",
+				FrameValue::URL(String::new()),
+				FrameFlags::default(),
+			)
+			.unwrap(),
+		);
+		tag.insert(
+			Frame::new(
+				"WXXX",
+				FrameValue::UserURL(EncodedTextFrame {
+					encoding: TextEncoding::UTF16,
+					description: String::from("Podcast feed"),
+					content: String::from("https://example.com/feed.xml"),
+				}),
+				FrameFlags::default(),
+			)
+			.unwrap(),
+		);
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		let reader = &mut &*writer;
+		let header = read_id3v2_header(reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(reader, header).unwrap();
+
+		// Empty and non-ASCII URLs are kept
+		assert_eq!(parsed_tag.frames(), tag.frames());
+
+		let generic_tag: Tag = parsed_tag.into();
+		assert_eq!(
+			generic_tag.get_locators(&ItemKey::TrackArtistURL).next(),
+			Some("https://example.com/caf\u{e9}")
+		);
+		assert_eq!(
+			generic_tag.get_locators(&ItemKey::AudioFileURL).next(),
+			Some("")
+		);
+		assert_eq!(
+			generic_tag
+				.get_locators(&ItemKey::Unknown(String::from("Podcast feed")))
+				.next(),
+			Some("https://example.com/feed.xml")
+		);
+
+		let id3v2: ID3v2Tag = generic_tag.into();
+		assert_eq!(
+			id3v2.get("WOAR").map(Frame::content),
+			Some(&FrameValue::URL(String::from(
+				"https://example.com/caf\u{e9}"
+			)))
+		);
+		assert_eq!(
+			id3v2.get("WOAF").map(Frame::content),
+			Some(&FrameValue::URL(String::new()))
+		);
+	}
+
+	#[test]
+	fn private_frames_round_trip() {
+		let owner = "http://www.example.com/podcast";
+
+		let mut tag = ID3v2Tag::default();
+		for data in [vec![1, 2], vec![3, 4], vec![]] {
+			tag.insert(
+				Frame::new(
+					"PRIV",
+					FrameValue::Private {
+						owner: String::from(owner),
+						data,
+					},
+					FrameFlags::default(),
+				)
+				.unwrap(),
+			);
+		}
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		// The owner is a null terminated Latin-1 string, followed by the data
+		let mut frame_content = owner.as_bytes().to_vec();
+		frame_content.extend([0, 1, 2]);
+		assert_eq!(
+			&writer[20..20 + frame_content.len()],
+			frame_content.as_slice()
+		);
+
+		let reader = &mut &*writer;
+		let header = read_id3v2_header(reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(reader, header).unwrap();
+
+		assert_eq!(
+			parsed_tag.private_frames(owner).collect::<Vec<_>>(),
+			vec![&[1, 2][..], &[3, 4], &[]]
+		);
+		assert_eq!(parsed_tag.private_frames("foo").count(), 0);
+	}
+
+	#[test]
+	fn geob_round_trip() {
+		let project = GeneralEncapsulatedObject {
+			information: GEOBInformation {
+				encoding: TextEncoding::UTF16,
+				mime_type: Some(String::from("application/octet-stream")),
+				file_name: Some(String::from("project.bin")),
+				descriptor: Some(String::from("Project")),
+			},
+			data: vec![0xFF, 0x00, 0x01, 0xFE],
+		};
+
+		let undescribed = GeneralEncapsulatedObject {
+			information: GEOBInformation {
+				encoding: TextEncoding::Latin1,
+				mime_type: None,
+				file_name: None,
+				descriptor: None,
+			},
+			data: vec![0; 16],
+		};
+
+		let mut tag = ID3v2Tag::default();
+		assert!(tag
+			.insert_general_encapsulated_object(project.clone())
+			.is_none());
+		assert!(tag
+			.insert_general_encapsulated_object(undescribed.clone())
+			.is_none());
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		// The frame content is written back exactly
+		let reader = &mut &*writer;
+		let header = read_id3v2_header(reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(reader, header).unwrap();
+
+		assert_eq!(
+			parsed_tag
+				.general_encapsulated_objects()
+				.collect::<Vec<_>>(),
+			vec![&project, &undescribed]
+		);
+		assert_eq!(
+			undescribed.as_bytes(),
+			[&[0, 0, 0, 0][..], &[0; 16]].concat()
+		);
+
+		assert_eq!(
+			parsed_tag.general_encapsulated_object("Project"),
+			Some(&project)
+		);
+		assert_eq!(
+			parsed_tag.general_encapsulated_object(""),
+			Some(&undescribed)
+		);
+
+		// An object with the same descriptor is replaced
+		let mut new_project = project.clone();
+		new_project.data = vec![1, 2, 3];
+
+		let replaced = tag.insert_general_encapsulated_object(new_project.clone());
+		assert_eq!(
+			replaced.map(|f| f.value),
+			Some(FrameValue::GeneralEncapsulatedObject(project))
+		);
+		assert_eq!(
+			tag.general_encapsulated_objects().next(),
+			Some(&new_project)
+		);
+
+		assert!(tag.remove_general_encapsulated_object("").is_some());
+		assert_eq!(tag.general_encapsulated_objects().count(), 1);
+	}
+
 	#[test]
 	fn id3v2_to_tag() {
 		let id3v2 = read_tag("tests/tags/assets/id3v2/test.id3v24");
@@ -880,7 +1707,7 @@ mod tests {
 			frame.content(),
 			&FrameValue::Comment(LanguageFrame {
 				encoding: TextEncoding::Latin1,
-				language: *b"eng",
+				language: Language::ENGLISH,
 				description: String::new(),
 				content: String::from("Qux comment")
 			})
@@ -970,6 +1797,7 @@ mod tests {
 					mime_type: MimeType::Png,
 					description: None,
 					data: read_path("tests/tags/assets/id3v2/test_full_cover.png").into(),
+					data_range: None,
 				},
 			},
 			flags,
@@ -1132,10 +1960,88 @@ mod tests {
 		assert_eq!(tag.item_count(), 1);
 		assert_eq!(
 			tag.items[0],
-			TagItem {
-				item_key: ItemKey::ReplayGainAlbumGain,
-				item_value: ItemValue::Text(String::from("-10.43 dB"))
-			}
+			TagItem::new(
+				ItemKey::ReplayGainAlbumGain,
+				ItemValue::Text(String::from("-10.43 dB"))
+			)
+		);
+	}
+
+	#[test]
+	fn txxx_items() {
+		let mut tag = ID3v2Tag::default();
+		tag.insert_user_text(String::from("MusicBrainz Album Id"), String::from("foo"));
+		tag.insert_user_text(String::from("ACOUSTID_ID"), String::from("bar"));
+		tag.insert_user_text(String::from("Custom Key"), String::from("baz"));
+
+		// The encoding of an existing frame is kept
+		if let FrameValue::UserText(ref mut content) = tag.frames[2].value {
+			content.encoding = TextEncoding::UTF16;
+		}
+		tag.insert_user_text(String::from("Custom Key"), String::from("qux"));
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		let reader = &mut &*writer;
+		let header = read_id3v2_header(reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(reader, header).unwrap();
+
+		assert_eq!(parsed_tag.len(), 3);
+		assert_eq!(
+			parsed_tag.get_user_text("MusicBrainz Album Id"),
+			Some("foo")
+		);
+		assert_eq!(parsed_tag.get_user_text("ACOUSTID_ID"), Some("bar"));
+		assert_eq!(
+			parsed_tag.frames[2].content(),
+			&FrameValue::UserText(EncodedTextFrame {
+				encoding: TextEncoding::UTF16,
+				description: String::from("Custom Key"),
+				content: String::from("qux"),
+			})
+		);
+
+		let generic_tag: Tag = parsed_tag.into();
+		assert_eq!(
+			generic_tag.get_string(&ItemKey::MusicBrainzReleaseId),
+			Some("foo")
+		);
+		// Only the canonical description is mapped, so the casing of others is kept
+		assert_eq!(
+			generic_tag.get_string(&ItemKey::Unknown(String::from("ACOUSTID_ID"))),
+			Some("bar")
+		);
+		assert_eq!(
+			ID3v2Tag::from(generic_tag.clone()).get_user_text("ACOUSTID_ID"),
+			Some("bar")
+		);
+		assert_eq!(
+			generic_tag.get_string(&ItemKey::Unknown(String::from("Custom Key"))),
+			Some("qux")
+		);
+
+		// Keys that map to a description are written as TXXX frames
+		let mut generic_tag = Tag::new(TagType::ID3v2);
+		generic_tag.insert_text(ItemKey::MusicBrainzReleaseId, String::from("foo"));
+		generic_tag.insert_text(ItemKey::ReplayGainTrackGain, String::from("-1.00 dB"));
+
+		let id3v2: ID3v2Tag = generic_tag.clone().into();
+		assert_eq!(id3v2.get_user_text("MusicBrainz Album Id"), Some("foo"));
+		assert_eq!(
+			id3v2.get_user_text("REPLAYGAIN_TRACK_GAIN"),
+			Some("-1.00 dB")
+		);
+
+		let mut writer = Vec::new();
+		generic_tag.dump_to(&mut writer).unwrap();
+
+		let reader = &mut &*writer;
+		let header = read_id3v2_header(reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(reader, header).unwrap();
+		assert_eq!(
+			parsed_tag.get_user_text("MusicBrainz Album Id"),
+			Some("foo")
 		);
 	}
 
@@ -1190,4 +2096,429 @@ mod tests {
 		assert_eq!(tag.frames.len(), 2);
 		assert_eq!(&tag.frames, &[txxx_frame, wxxx_frame])
 	}
+
+	#[test]
+	fn chapters_round_trip() {
+		let mut tag = ID3v2Tag::default();
+
+		tag.insert_table_of_contents(TableOfContents {
+			element_id: String::from("toc"),
+			top_level: true,
+			ordered: true,
+			child_element_ids: vec![
+				String::from("chp0"),
+				String::from("chp1"),
+				String::from("chp2"),
+			],
+			sub_frames: vec![Frame::text("TIT2", String::from("Foo toc"))],
+		});
+
+		for (i, title) in ["Foo", "Bar", "Baz"].into_iter().enumerate() {
+			let i = i as u32;
+			tag.insert_chapter(Chapter {
+				element_id: format!("chp{i}"),
+				start_time: i * 1000,
+				end_time: (i + 1) * 1000,
+				start_offset: u32::MAX,
+				end_offset: u32::MAX,
+				sub_frames: vec![Frame::text("TIT2", format!("{title} chapter"))],
+			});
+		}
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		let mut reader = &mut &writer[..];
+		let header = read_id3v2_header(&mut reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+		let tocs = parsed_tag.tables_of_contents().collect::<Vec<_>>();
+		assert_eq!(tocs.len(), 1);
+
+		let toc = tocs[0];
+		assert_eq!(toc.element_id, "toc");
+		assert!(toc.top_level);
+		assert!(toc.ordered);
+		assert_eq!(toc.child_element_ids, ["chp0", "chp1", "chp2"]);
+		assert_eq!(toc.title(), Some("Foo toc"));
+
+		let chapters = parsed_tag.chapters().collect::<Vec<_>>();
+		assert_eq!(chapters.len(), 3);
+
+		for (i, (chapter, title)) in chapters
+			.into_iter()
+			.zip(["Foo chapter", "Bar chapter", "Baz chapter"])
+			.enumerate()
+		{
+			let i = i as u32;
+			assert_eq!(chapter.element_id, format!("chp{i}"));
+			assert_eq!(chapter.start_time, i * 1000);
+			assert_eq!(chapter.end_time, (i + 1) * 1000);
+			assert_eq!(chapter.start_offset, u32::MAX);
+			assert_eq!(chapter.end_offset, u32::MAX);
+			assert_eq!(chapter.title(), Some(title));
+		}
+	}
+
+	#[test]
+	fn table_of_contents_flags() {
+		let toc = TableOfContents {
+			element_id: String::from("toc"),
+			top_level: false,
+			ordered: true,
+			child_element_ids: vec![String::from("chp0")],
+			sub_frames: Vec::new(),
+		};
+
+		let bytes = toc.as_bytes().unwrap();
+		assert_eq!(bytes, b"toc\0\x01\x01chp0\0");
+
+		let parsed = TableOfContents::parse(&mut &bytes[..], ID3v2Version::V4).unwrap();
+		assert!(!parsed.top_level);
+		assert!(parsed.ordered);
+		assert_eq!(parsed.child_element_ids, ["chp0"]);
+	}
+
+	#[test]
+	fn sylt_round_trip() {
+		let mut tag = ID3v2Tag::default();
+
+		for (language, timestamp_format) in [
+			(*b"eng", TimestampFormat::MS),
+			(*b"deu", TimestampFormat::MPEG),
+		] {
+			tag.insert_synchronized_text(SynchronizedText {
+				information: SyncTextInformation {
+					encoding: TextEncoding::UTF8,
+					language: Language::from_bytes(language),
+					timestamp_format,
+					content_type: SyncTextContentType::Lyrics,
+					description: Some(String::from("Foo description")),
+				},
+				content: vec![(0, String::from("Foo")), (1000, String::from("Bar"))],
+			});
+		}
+
+		// Same language and description, this replaces the first frame
+		let replaced = tag.insert_synchronized_text(SynchronizedText {
+			information: SyncTextInformation {
+				encoding: TextEncoding::UTF8,
+				language: Language::ENGLISH,
+				timestamp_format: TimestampFormat::MS,
+				content_type: SyncTextContentType::Lyrics,
+				description: Some(String::from("Foo description")),
+			},
+			content: vec![(500, String::from("Baz"))],
+		});
+		assert!(replaced.is_some());
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		let mut reader = &mut &writer[..];
+		let header = read_id3v2_header(&mut reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+		let sync_text = parsed_tag.synchronized_text().collect::<Vec<_>>();
+		assert_eq!(sync_text.len(), 2);
+
+		assert_eq!(
+			sync_text[0].information.language,
+			Language::from_bytes(*b"deu")
+		);
+		assert_eq!(
+			sync_text[0].information.timestamp_format,
+			TimestampFormat::MPEG
+		);
+		assert_eq!(
+			sync_text[0].content,
+			[(0, String::from("Foo")), (1000, String::from("Bar"))]
+		);
+
+		assert_eq!(sync_text[1].information.language, Language::ENGLISH);
+		assert_eq!(
+			sync_text[1].information.timestamp_format,
+			TimestampFormat::MS
+		);
+		assert_eq!(sync_text[1].content, [(500, String::from("Baz"))]);
+	}
+
+	#[test]
+	fn multiple_uslt_frames() {
+		let mut tag = ID3v2Tag::default();
+
+		for (language, content) in [(*b"eng", "Foo/Bar lyrics"), (*b"deu", "Baz lyrics")] {
+			tag.insert(
+				Frame::new(
+					"USLT",
+					FrameValue::UnSyncText(LanguageFrame {
+						encoding: TextEncoding::UTF8,
+						language: Language::from_bytes(language),
+						description: String::new(),
+						content: String::from(content),
+					}),
+					FrameFlags::default(),
+				)
+				.unwrap(),
+			);
+		}
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		let mut reader = &mut &writer[..];
+		let header = read_id3v2_header(&mut reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+		let lyrics = parsed_tag.unsync_text().collect::<Vec<_>>();
+		assert_eq!(lyrics.len(), 2);
+		assert_eq!(lyrics[0].language, Language::ENGLISH);
+		assert_eq!(lyrics[1].language, Language::from_bytes(*b"deu"));
+
+		let tag: Tag = parsed_tag.into();
+		assert_eq!(tag.get_string(&ItemKey::Lyrics), Some("Foo/Bar lyrics"));
+		assert_eq!(
+			tag.get_strings(&ItemKey::Lyrics).collect::<Vec<_>>(),
+			["Foo/Bar lyrics", "Baz lyrics"]
+		);
+	}
+
+	#[test]
+	fn frames_keep_order() {
+		let mut tag = ID3v2Tag::default();
+
+		for (id, content) in [
+			("TIT2", "Foo title"),
+			("TPE1", "Bar artist"),
+			("TALB", "Baz album"),
+		] {
+			tag.insert(
+				Frame::new(
+					id,
+					FrameValue::Text {
+						encoding: TextEncoding::UTF8,
+						value: String::from(content),
+					},
+					FrameFlags::default(),
+				)
+				.unwrap(),
+			);
+		}
+
+		let replaced = tag.insert(
+			Frame::new(
+				"TPE1",
+				FrameValue::Text {
+					encoding: TextEncoding::UTF8,
+					value: String::from("Qux artist"),
+				},
+				FrameFlags::default(),
+			)
+			.unwrap(),
+		);
+		assert!(replaced.is_some());
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		let mut reader = &mut &writer[..];
+		let header = read_id3v2_header(&mut reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+		let ids = parsed_tag
+			.frames()
+			.iter()
+			.map(Frame::id_str)
+			.collect::<Vec<_>>();
+		assert_eq!(ids, ["TIT2", "TPE1", "TALB"]);
+		assert_eq!(parsed_tag.frames(), tag.frames());
+	}
+
+	#[test]
+	fn id3v22_frame_upgrade() {
+		fn v2_frame(id: &[u8; 3], content: &[u8]) -> Vec<u8> {
+			let mut frame = id.to_vec();
+			frame.extend(&(content.len() as u32).to_be_bytes()[1..]);
+			frame.extend(content);
+			frame
+		}
+
+		let gif = read_path("tests/picture/assets/gif_640x628.gif");
+
+		let mut pic_content = b"\0GIF\x03\0".to_vec();
+		pic_content.extend(&gif);
+
+		let mut frames = Vec::new();
+		frames.extend(v2_frame(b"TT2", b"\0Foo title"));
+		frames.extend(v2_frame(b"TP1", b"\0Bar artist"));
+		frames.extend(v2_frame(b"TOA", b"\0Baz artist"));
+		frames.extend(v2_frame(b"TXT", b"\0Qux writer"));
+		frames.extend(v2_frame(b"XYZ", b"\0Unknown"));
+		frames.extend(v2_frame(b"PIC", &pic_content));
+
+		let mut tag_bytes = b"ID3\x02\x00\x00".to_vec();
+		tag_bytes.extend(
+			crate::id3::v2::util::synch_u32(frames.len() as u32)
+				.unwrap()
+				.to_be_bytes(),
+		);
+		tag_bytes.extend(frames);
+
+		let mut reader = &mut &tag_bytes[..];
+		let header = read_id3v2_header(&mut reader).unwrap();
+		assert_eq!(header.version, ID3v2Version::V2);
+
+		let tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+		assert_eq!(tag.original_version(), ID3v2Version::V2);
+
+		assert_eq!(
+			tag.frames().iter().map(Frame::id_str).collect::<Vec<_>>(),
+			["TIT2", "TPE1", "TOPE", "TEXT", "XYZ", "APIC"]
+		);
+		assert_eq!(tag.title(), Some("Foo title"));
+		assert_eq!(tag.artist(), Some("Bar artist"));
+
+		let picture = match tag.get("APIC").map(Frame::content) {
+			Some(FrameValue::Picture { picture, .. }) => picture,
+			_ => unreachable!(),
+		};
+		assert_eq!(picture.mime_type(), &MimeType::Gif);
+		assert_eq!(picture.pic_type(), PictureType::CoverFront);
+		assert_eq!(picture.data(), &gif);
+
+		// Always written as ID3v2.4, without the outdated frame
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+		assert_eq!(&writer[..5], b"ID3\x04\x00");
+
+		let mut reader = &mut &writer[..];
+		let header = read_id3v2_header(&mut reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+		assert_eq!(
+			parsed_tag
+				.frames()
+				.iter()
+				.map(Frame::id_str)
+				.collect::<Vec<_>>(),
+			["TIT2", "TPE1", "TOPE", "TEXT", "APIC"]
+		);
+	}
+
+	fn frame_size_tag(major_version: u8, size: [u8; 4], title: &str) -> Vec<u8> {
+		let mut frames = Vec::new();
+
+		frames.extend(b"TIT2");
+		frames.extend(size);
+		frames.extend([0, 0, 0]);
+		frames.extend(title.as_bytes());
+
+		frames.extend(b"TPE1");
+		frames.extend(if major_version == 4 {
+			[0, 0, 0, 11]
+		} else {
+			11_u32.to_be_bytes()
+		});
+		frames.extend([0, 0, 0]);
+		frames.extend(b"Foo artist");
+
+		let mut tag_bytes = vec![b'I', b'D', b'3', major_version, 0, 0];
+		tag_bytes.extend(
+			crate::id3::v2::util::synch_u32(frames.len() as u32)
+				.unwrap()
+				.to_be_bytes(),
+		);
+		tag_bytes.extend(frames);
+		tag_bytes
+	}
+
+	fn parse_tag_bytes(tag_bytes: &[u8]) -> ID3v2Tag {
+		let mut reader = &mut &tag_bytes[..];
+		let header = read_id3v2_header(&mut reader).unwrap();
+		crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap()
+	}
+
+	#[test]
+	fn grouping_and_movement() {
+		fn text<'a>(tag: &'a ID3v2Tag, id: &str) -> Option<&'a str> {
+			match tag.get(id).map(Frame::content) {
+				Some(FrameValue::Text { value, .. }) => Some(value),
+				_ => None,
+			}
+		}
+
+		let mut id3v2 = ID3v2Tag::default();
+		id3v2.insert(Frame::text("GRP1", String::from("Bar grouping")));
+		id3v2.insert(Frame::text("TIT1", String::from("Foo grouping")));
+		id3v2.insert(Frame::text("MVNM", String::from("Allegro con brio")));
+		id3v2.insert(Frame::text("MVIN", String::from("1/4")));
+
+		let tag: Tag = id3v2.into();
+		assert_eq!(tag.get_string(&ItemKey::ContentGroup), Some("Foo grouping"));
+		assert_eq!(
+			tag.get_string(&ItemKey::MovementName),
+			Some("Allegro con brio")
+		);
+		assert_eq!(tag.get_string(&ItemKey::MovementNumber), Some("1"));
+		assert_eq!(tag.get_string(&ItemKey::MovementCount), Some("4"));
+
+		// `GRP1` is kept around, since it isn't used for `ItemKey::ContentGroup`
+		let id3v2: ID3v2Tag = tag.into();
+		assert_eq!(text(&id3v2, "TIT1"), Some("Foo grouping"));
+		assert_eq!(text(&id3v2, "GRP1"), Some("Bar grouping"));
+		assert_eq!(text(&id3v2, "MVIN"), Some("1/4"));
+
+		// `GRP1` is used when it's the only grouping frame
+		let mut id3v2 = ID3v2Tag::default();
+		id3v2.insert(Frame::text("GRP1", String::from("Bar grouping")));
+
+		let tag: Tag = id3v2.into();
+		assert_eq!(tag.get_string(&ItemKey::ContentGroup), Some("Bar grouping"));
+
+		// The work is stored in a TXXX frame
+		let mut tag = Tag::new(TagType::ID3v2);
+		tag.insert_text(ItemKey::Work, String::from("Symphony No. 5"));
+
+		let id3v2: ID3v2Tag = tag.into();
+		assert_eq!(
+			id3v2.get("TXXX").map(Frame::content),
+			Some(&FrameValue::UserText(EncodedTextFrame {
+				encoding: TextEncoding::UTF8,
+				description: String::from("Work"),
+				content: String::from("Symphony No. 5"),
+			}))
+		);
+	}
+
+	#[test]
+	fn id3v23_large_frame_size() {
+		// 200 bytes (0xC9 with the encoding), which would be mangled if read as a synchsafe integer
+		let title = "a".repeat(200);
+		let tag = parse_tag_bytes(&frame_size_tag(3, 201_u32.to_be_bytes(), &title));
+
+		assert_eq!(tag.original_version(), ID3v2Version::V3);
+		assert_eq!(tag.title(), Some(title.as_str()));
+		assert_eq!(tag.artist(), Some("Foo artist"));
+	}
+
+	#[test]
+	fn id3v24_non_synchsafe_frame_size() {
+		let title = "a".repeat(200);
+
+		let synchsafe = crate::id3::v2::util::synch_u32(201).unwrap().to_be_bytes();
+		let tag = parse_tag_bytes(&frame_size_tag(4, synchsafe, &title));
+		assert_eq!(tag.title(), Some(title.as_str()));
+		assert_eq!(tag.artist(), Some("Foo artist"));
+
+		// Some encoders write plain integers in ID3v2.4
+		let tag = parse_tag_bytes(&frame_size_tag(4, 201_u32.to_be_bytes(), &title));
+		assert_eq!(tag.title(), Some(title.as_str()));
+		assert_eq!(tag.artist(), Some("Foo artist"));
+
+		// 256 is also a valid synchsafe integer (128), but that doesn't lead to the next frame
+		let title = "a".repeat(255);
+		let tag = parse_tag_bytes(&frame_size_tag(4, 256_u32.to_be_bytes(), &title));
+		assert_eq!(tag.title(), Some(title.as_str()));
+		assert_eq!(tag.artist(), Some("Foo artist"));
+	}
 }
@@ -52,6 +52,27 @@ cfg_if::cfg_if! {
 			Ok(unsynch_content)
 		}
 
+		// Inserts a zero after every 0xFF byte followed by 0x00 or a byte >= 0xE0 (0b111xxxxx)
+		//
+		// A trailing 0xFF is also followed by a zero, so it can't be mistaken for a frame sync
+		// with whatever comes after the content.
+		pub(in crate::id3::v2) fn synch_content(content: &[u8]) -> Vec<u8> {
+			let mut synch_content = Vec::with_capacity(content.len());
+
+			for (i, byte) in content.iter().enumerate() {
+				synch_content.push(*byte);
+
+				if *byte == 0xFF {
+					match content.get(i + 1) {
+						Some(next) if *next != 0 && *next < 0xE0 => {},
+						_ => synch_content.push(0),
+					}
+				}
+			}
+
+			synch_content
+		}
+
 		/// Create a synchsafe integer
 		///
 		/// See [`FrameFlags::unsynchronisation`](crate::id3::v2::FrameFlags::unsynchronisation) for an explanation.
@@ -98,4 +119,16 @@ mod tests {
 
 		assert!(super::unsynch_content(invalid_unsynch.as_slice()).is_err());
 	}
+
+	#[test]
+	fn synchronisation() {
+		let content = [0xFF, 0x00, 0xFF, 0xE0, 0xFF, 0x12, 0xFF];
+		let synch_content = super::synch_content(&content);
+
+		assert_eq!(
+			synch_content,
+			[0xFF, 0x00, 0x00, 0xFF, 0x00, 0xE0, 0xFF, 0x12, 0xFF, 0x00]
+		);
+		assert_eq!(super::unsynch_content(&synch_content).unwrap(), content);
+	}
 }
@@ -137,6 +137,11 @@ pub(crate) fn utf16_decode(reader: &[u8], endianness: fn([u8; 2]) -> u16) -> Res
 		.map_err(|_| LoftyError::new(ErrorKind::TextDecode("Given an invalid UTF-16 string")))
 }
 
+// Whether the text can be encoded as Latin-1 without any loss
+pub(crate) fn is_latin1(text: &str) -> bool {
+	text.chars().all(|c| u32::from(c) <= 0xFF)
+}
+
 pub(crate) fn encode_text(text: &str, text_encoding: TextEncoding, terminated: bool) -> Vec<u8> {
 	match text_encoding {
 		TextEncoding::Latin1 => {
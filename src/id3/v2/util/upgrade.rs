@@ -47,6 +47,7 @@ gen_upgrades!(
 		"ETC" => "ETCO",
 		"GEO" => "GEOB",
 		"IPL" => "TIPL",
+		"LNK" => "LINK",
 		"MCI" => "MCDI",
 		"MLL" => "MLLT",
 		"PIC" => "APIC",
@@ -67,7 +68,7 @@ gen_upgrades!(
 		"TLA" => "TLAN",
 		"TLE" => "TLEN",
 		"TMT" => "TMED",
-		"TOA" => "TOAL",
+		"TOA" => "TOPE",
 		"TOF" => "TOFN",
 		"TOL" => "TOLY",
 		"TOR" => "TDOR",
@@ -90,7 +91,7 @@ gen_upgrades!(
 		"TT1" => "TIT1",
 		"TT2" => "TIT2",
 		"TT3" => "TIT3",
-		"TXT" => "TOLY",
+		"TXT" => "TEXT",
 		"TXX" => "TXXX",
 		"TYE" => "TDRC",
 		"UFI" => "UFID",
@@ -1,17 +1,34 @@
-use crate::error::Result;
-use crate::iff::chunk::Chunks;
+use crate::error::{FileEncodingError, Result};
+use crate::file::FileType;
+use crate::iff::chunk::{is_id3_chunk, Chunks};
+use crate::macros::try_vec;
 
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom, Write};
 
 use byteorder::{ByteOrder, WriteBytesExt};
 
-pub(in crate::id3::v2) fn write_to_chunk_file<B>(data: &mut File, tag: &[u8]) -> Result<()>
+/// Replaces (or removes, if `tag` is empty) the ID3v2 chunk, writing a new one with the ID `fourcc`
+pub(in crate::id3::v2) fn write_to_chunk_file<B>(
+	data: &mut File,
+	tag: &[u8],
+	fourcc: [u8; 4],
+) -> Result<bool>
 where
 	B: ByteOrder,
 {
 	// RIFF....WAVE
-	data.seek(SeekFrom::Current(12))?;
+	let mut header = [0; 12];
+	data.read_exact(&mut header)?;
+
+	// The sizes of RF64 files are stored in the `ds64` chunk, which we don't update
+	if &header[..4] == b"RF64" || &header[..4] == b"BW64" {
+		return Err(FileEncodingError::new(
+			FileType::WAV,
+			"Writing to RF64 files is not supported",
+		)
+		.into());
+	}
 
 	let file_len = data.metadata()?.len().saturating_sub(12);
 
@@ -20,16 +37,34 @@ where
 	let mut chunks = Chunks::<B>::new(file_len);
 
 	while chunks.next(data).is_ok() {
-		if &chunks.fourcc == b"ID3 " || &chunks.fourcc == b"id3 " {
+		if is_id3_chunk(chunks.fourcc) {
 			id3v2_chunk = (Some(data.stream_position()? - 8), Some(chunks.size));
 			break;
 		}
 
-		data.seek(SeekFrom::Current(i64::from(chunks.size)))?;
+		data.seek(SeekFrom::Current(chunks.size as i64))?;
 
 		chunks.correct_position(data)?;
 	}
 
+	match id3v2_chunk {
+		(None, _) if tag.is_empty() => return Ok(false),
+		// Nothing to do if the chunk is identical to the new one
+		(Some(chunk_start), Some(chunk_size)) if chunk_size == tag.len() as u64 => {
+			let mut existing = try_vec![0; tag.len() + 4];
+
+			data.seek(SeekFrom::Start(chunk_start))?;
+			data.read_exact(&mut existing[..4])?;
+			data.seek(SeekFrom::Current(4))?;
+			data.read_exact(&mut existing[4..])?;
+
+			if existing[..4] == fourcc && &existing[4..] == tag {
+				return Ok(false);
+			}
+		},
+		_ => {},
+	}
+
 	if let (Some(chunk_start), Some(mut chunk_size)) = id3v2_chunk {
 		data.rewind()?;
 
@@ -42,7 +77,7 @@ where
 		data.read_to_end(&mut file_bytes)?;
 
 		file_bytes.splice(
-			chunk_start as usize..(chunk_start + u64::from(chunk_size) + 8) as usize,
+			chunk_start as usize..(chunk_start + chunk_size + 8) as usize,
 			[],
 		);
 
@@ -53,7 +88,7 @@ where
 
 	if !tag.is_empty() {
 		data.seek(SeekFrom::End(0))?;
-		data.write_all(b"ID3 ")?;
+		data.write_all(&fourcc)?;
 		data.write_u32::<B>(tag.len() as u32)?;
 		data.write_all(tag)?;
 
@@ -62,13 +97,14 @@ where
 		if tag.len() % 2 != 0 {
 			data.write_u8(0)?;
 		}
+	}
 
-		let total_size = data.stream_position()? - 8;
+	// The outer chunk size needs to be updated whether the tag was added, replaced, or removed
+	let total_size = data.seek(SeekFrom::End(0))? - 8;
 
-		data.seek(SeekFrom::Start(4))?;
+	data.seek(SeekFrom::Start(4))?;
 
-		data.write_u32::<B>(total_size as u32)?;
-	}
+	data.write_u32::<B>(total_size as u32)?;
 
-	Ok(())
+	Ok(true)
 }
@@ -1,28 +1,113 @@
 use crate::error::{ID3v2Error, ID3v2ErrorKind, Result};
 use crate::id3::v2::frame::{FrameFlags, FrameRef, FrameValue};
-use crate::id3::v2::util::synch_u32;
+use crate::id3::v2::util::text_utils::TextEncoding;
+use crate::id3::v2::util::{synch_content, synch_u32};
+use crate::id3::v2::ID3v2Version;
 
 use std::io::Write;
 
 use byteorder::{BigEndian, WriteBytesExt};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
 
 pub(in crate::id3::v2) fn create_items<'a, W>(
 	writer: &mut W,
 	frames: &mut dyn Iterator<Item = FrameRef<'a>>,
+	version: ID3v2Version,
 ) -> Result<()>
 where
 	W: Write,
 {
 	for frame in frames {
 		verify_frame(&frame)?;
-		let value = frame.value.as_bytes()?;
 
-		write_frame(writer, frame.id, frame.flags, &value)?;
+		if version == ID3v2Version::V3 {
+			if let Some(v3_frames) = downgrade_frame(&frame) {
+				for (id, value) in v3_frames {
+					let value = value.as_bytes(version)?;
+					write_frame(writer, id, frame.flags, &value, version)?;
+				}
+
+				continue;
+			}
+		}
+
+		let value = frame.value.as_bytes(version)?;
+
+		// Unsynchronisation is only applied per frame in ID3v2.4, ID3v2.3 tags are unsynchronised as a whole
+		if version == ID3v2Version::V4 && frame.flags.unsynchronisation {
+			let mut frame_bytes = Vec::new();
+			write_frame(&mut frame_bytes, frame.id, frame.flags, &value, version)?;
+			write_unsynchronised(writer, frame_bytes)?;
+
+			continue;
+		}
+
+		write_frame(writer, frame.id, frame.flags, &value, version)?;
 	}
 
 	Ok(())
 }
 
+// Converts frames that were introduced in ID3v2.4 to their ID3v2.3 equivalents
+//
+// Frames without an equivalent (or with a value that can't be converted) are dropped
+fn downgrade_frame(frame: &FrameRef<'_>) -> Option<Vec<(&'static str, FrameValue)>> {
+	fn digits(value: &str, range: std::ops::Range<usize>) -> Option<&str> {
+		value
+			.get(range)
+			.filter(|s| s.bytes().all(|b| b.is_ascii_digit()))
+	}
+
+	fn separator(value: &str, pos: usize, sep: u8) -> bool {
+		value.as_bytes().get(pos) == Some(&sep)
+	}
+
+	let text = |encoding: TextEncoding, value: String| FrameValue::Text { encoding, value };
+
+	match (frame.id, frame.value.as_ref()) {
+		// Timestamps are stored as "yyyy-MM-ddTHH:mm:ss", with any number of the trailing fields missing
+		("TDRC", FrameValue::Text { encoding, value }) => {
+			let year = match digits(value, 0..4) {
+				Some(year) => year,
+				None => return Some(Vec::new()),
+			};
+
+			let mut frames = vec![("TYER", text(*encoding, year.to_string()))];
+
+			let month = digits(value, 5..7).filter(|_| separator(value, 4, b'-'));
+			let day = digits(value, 8..10).filter(|_| separator(value, 7, b'-'));
+
+			if let (Some(month), Some(day)) = (month, day) {
+				frames.push(("TDAT", text(*encoding, format!("{}{}", day, month))));
+
+				let hour = digits(value, 11..13).filter(|_| separator(value, 10, b'T'));
+				let minute = digits(value, 14..16).filter(|_| separator(value, 13, b':'));
+
+				if let (Some(hour), Some(minute)) = (hour, minute) {
+					frames.push(("TIME", text(*encoding, format!("{}{}", hour, minute))));
+				}
+			}
+
+			Some(frames)
+		},
+		("TDOR", FrameValue::Text { encoding, value }) => Some(
+			digits(value, 0..4)
+				.map(|year| ("TORY", text(*encoding, year.to_string())))
+				.into_iter()
+				.collect(),
+		),
+		("TIPL", value) => Some(vec![("IPLS", value.clone())]),
+		// These have no ID3v2.3 equivalent
+		(
+			"ASPI" | "EQU2" | "RVA2" | "SEEK" | "SIGN" | "TDEN" | "TDRL" | "TDTG" | "TMCL" | "TMOO"
+			| "TPRO" | "TSST" | "TDRC" | "TDOR",
+			_,
+		) => Some(Vec::new()),
+		_ => None,
+	}
+}
+
 fn verify_frame(frame: &FrameRef<'_>) -> Result<()> {
 	match (frame.id, frame.value.as_ref()) {
 		("APIC", FrameValue::Picture { .. })
@@ -30,6 +115,12 @@ fn verify_frame(frame: &FrameRef<'_>) -> Result<()> {
 		| ("COMM", FrameValue::Comment(_))
 		| ("TXXX", FrameValue::UserText(_))
 		| ("WXXX", FrameValue::UserURL(_))
+		| ("POPM", FrameValue::Popularimeter(_))
+		| ("SYLT", FrameValue::SynchronizedText(_))
+		| ("CHAP", FrameValue::Chapter(_))
+		| ("CTOC", FrameValue::TableOfContents(_))
+		| ("GEOB", FrameValue::GeneralEncapsulatedObject(_))
+		| ("PRIV", FrameValue::Private { .. })
 		| (_, FrameValue::Binary(_))
 		| ("WFED" | "GRP1" | "MVNM" | "MVIN", FrameValue::Text { .. }) => Ok(()),
 		(id, FrameValue::Text { .. }) if id.starts_with('T') => Ok(()),
@@ -45,6 +136,11 @@ fn verify_frame(frame: &FrameRef<'_>) -> Result<()> {
 				FrameValue::UserURL(_) => "UserURL",
 				FrameValue::Picture { .. } => "Picture",
 				FrameValue::Popularimeter(_) => "Popularimeter",
+				FrameValue::SynchronizedText(_) => "SynchronizedText",
+				FrameValue::Chapter(_) => "Chapter",
+				FrameValue::TableOfContents(_) => "TableOfContents",
+				FrameValue::GeneralEncapsulatedObject(_) => "GeneralEncapsulatedObject",
+				FrameValue::Private { .. } => "Private",
 				FrameValue::Binary(_) => "Binary",
 			},
 		))
@@ -52,16 +148,39 @@ fn verify_frame(frame: &FrameRef<'_>) -> Result<()> {
 	}
 }
 
-fn write_frame<W>(writer: &mut W, name: &str, flags: FrameFlags, value: &[u8]) -> Result<()>
+fn write_frame<W>(
+	writer: &mut W,
+	name: &str,
+	flags: FrameFlags,
+	value: &[u8],
+	version: ID3v2Version,
+) -> Result<()>
 where
 	W: Write,
 {
 	if flags.encryption.0 {
-		write_encrypted(writer, name, value, flags)?;
+		write_encrypted(writer, name, value, flags, version)?;
 		return Ok(());
 	}
 
-	let len = value.len() as u32;
+	// ID3v2.3 compressed frames are preceded by their decompressed size
+	let compressed;
+	let mut value = value;
+	let mut decompressed_size = None;
+	if version == ID3v2Version::V3 && flags.compression {
+		let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+		encoder.write_all(value)?;
+
+		compressed = encoder.finish()?;
+		decompressed_size = Some(value.len() as u32);
+		value = &compressed;
+	}
+
+	let mut len = value.len() as u32;
+	if decompressed_size.is_some() {
+		len += 4;
+	}
+
 	let is_grouping_identity = flags.grouping_identity.0;
 
 	write_frame_header(
@@ -69,8 +188,13 @@ where
 		name,
 		if is_grouping_identity { len + 1 } else { len },
 		flags,
+		version,
 	)?;
 
+	if let Some(decompressed_size) = decompressed_size {
+		writer.write_u32::<BigEndian>(decompressed_size)?;
+	}
+
 	if is_grouping_identity {
 		writer.write_u8(flags.grouping_identity.1)?;
 	}
@@ -80,7 +204,28 @@ where
 	Ok(())
 }
 
-fn write_encrypted<W>(writer: &mut W, name: &str, value: &[u8], flags: FrameFlags) -> Result<()>
+// Unsynchronises everything after the frame header, which only requires the size to be updated
+fn write_unsynchronised<W>(writer: &mut W, mut frame_bytes: Vec<u8>) -> Result<()>
+where
+	W: Write,
+{
+	let content = synch_content(&frame_bytes[10..]);
+	frame_bytes.truncate(10);
+	frame_bytes[4..8].copy_from_slice(&synch_u32(content.len() as u32)?.to_be_bytes());
+
+	writer.write_all(&frame_bytes)?;
+	writer.write_all(&content)?;
+
+	Ok(())
+}
+
+fn write_encrypted<W>(
+	writer: &mut W,
+	name: &str,
+	value: &[u8],
+	flags: FrameFlags,
+	version: ID3v2Version,
+) -> Result<()>
 where
 	W: Write,
 {
@@ -95,8 +240,8 @@ where
 	}
 
 	if data_length_indicator.0 && data_length_indicator.1 > 0 {
-		write_frame_header(writer, name, (value.len() + 1) as u32, flags)?;
-		writer.write_u32::<BigEndian>(synch_u32(data_length_indicator.1)?)?;
+		write_frame_header(writer, name, (value.len() + 1) as u32, flags, version)?;
+		writer.write_u32::<BigEndian>(frame_size(data_length_indicator.1, version)?)?;
 		writer.write_u8(method_symbol)?;
 		writer.write_all(value)?;
 
@@ -109,17 +254,68 @@ where
 	.into())
 }
 
-fn write_frame_header<W>(writer: &mut W, name: &str, len: u32, flags: FrameFlags) -> Result<()>
+fn write_frame_header<W>(
+	writer: &mut W,
+	name: &str,
+	len: u32,
+	flags: FrameFlags,
+	version: ID3v2Version,
+) -> Result<()>
 where
 	W: Write,
 {
 	writer.write_all(name.as_bytes())?;
-	writer.write_u32::<BigEndian>(synch_u32(len)?)?;
-	writer.write_u16::<BigEndian>(get_flags(flags))?;
+	writer.write_u32::<BigEndian>(frame_size(len, version)?)?;
+	writer.write_u16::<BigEndian>(match version {
+		ID3v2Version::V3 => get_v3_flags(flags),
+		_ => get_flags(flags),
+	})?;
 
 	Ok(())
 }
 
+// ID3v2.3 frame sizes are plain integers, rather than synchsafe
+fn frame_size(len: u32, version: ID3v2Version) -> Result<u32> {
+	match version {
+		ID3v2Version::V3 => Ok(len),
+		_ => synch_u32(len),
+	}
+}
+
+fn get_v3_flags(tag_flags: FrameFlags) -> u16 {
+	let mut flags = 0;
+
+	if tag_flags == FrameFlags::default() {
+		return flags;
+	}
+
+	if tag_flags.tag_alter_preservation {
+		flags |= 0x8000
+	}
+
+	if tag_flags.file_alter_preservation {
+		flags |= 0x4000
+	}
+
+	if tag_flags.read_only {
+		flags |= 0x2000
+	}
+
+	if tag_flags.compression {
+		flags |= 0x0080
+	}
+
+	if tag_flags.encryption.0 {
+		flags |= 0x0040
+	}
+
+	if tag_flags.grouping_identity.0 {
+		flags |= 0x0020
+	}
+
+	flags
+}
+
 fn get_flags(tag_flags: FrameFlags) -> u16 {
 	let mut flags = 0;
 
@@ -1,14 +1,14 @@
 mod chunk_file;
-mod frame;
+pub(super) mod frame;
 
-use super::ID3v2TagFlags;
-use crate::error::Result;
+use super::{ID3v2TagFlags, ID3v2Version};
+use crate::error::{ID3v2Error, ID3v2ErrorKind, Result};
 use crate::file::FileType;
 use crate::id3::find_id3v2;
 use crate::id3::v2::frame::FrameRef;
 use crate::id3::v2::tag::Id3v2TagRef;
-use crate::id3::v2::util::synch_u32;
-use crate::macros::err;
+use crate::id3::v2::util::{synch_content, synch_u32};
+use crate::macros::{err, try_vec};
 use crate::probe::Probe;
 
 use std::fs::File;
@@ -35,22 +35,28 @@ static CRC_32_TABLE: once_cell::sync::Lazy<[u32; 256]> = once_cell::sync::Lazy::
 pub(crate) fn write_id3v2<'a, I: Iterator<Item = FrameRef<'a>> + 'a>(
 	data: &mut File,
 	tag: &mut Id3v2TagRef<'a, I>,
-) -> Result<()> {
+) -> Result<bool> {
 	let probe = Probe::new(data).guess_file_type()?;
 	let file_type = probe.file_type();
 
 	let data = probe.into_inner();
 
 	match file_type {
-		Some(FileType::APE | FileType::MPEG | FileType::FLAC) => {},
+		Some(FileType::AAC | FileType::APE | FileType::MPEG | FileType::FLAC | FileType::TTA) => {},
 		// Formats such as WAV and AIFF store the ID3v2 tag in an 'ID3 ' chunk rather than at the beginning of the file
+		//
+		// The chunk ID is lowercase in WAV files, by convention
 		Some(FileType::WAV) => {
 			tag.flags.footer = false;
-			return chunk_file::write_to_chunk_file::<LittleEndian>(data, &create_tag(tag)?);
+			return chunk_file::write_to_chunk_file::<LittleEndian>(
+				data,
+				&create_tag(tag)?,
+				*b"id3 ",
+			);
 		},
 		Some(FileType::AIFF) => {
 			tag.flags.footer = false;
-			return chunk_file::write_to_chunk_file::<BigEndian>(data, &create_tag(tag)?);
+			return chunk_file::write_to_chunk_file::<BigEndian>(data, &create_tag(tag)?, *b"ID3 ");
 		},
 		_ => err!(UnsupportedTag),
 	}
@@ -59,6 +65,19 @@ pub(crate) fn write_id3v2<'a, I: Iterator<Item = FrameRef<'a>> + 'a>(
 
 	// find_id3v2 will seek us to the end of the tag
 	find_id3v2(data, false)?;
+	let tag_end = data.stream_position()?;
+
+	// Nothing to do if the tag is identical to the existing one
+	if id3v2.len() as u64 == tag_end {
+		let mut existing = try_vec![0; id3v2.len()];
+
+		data.rewind()?;
+		data.read_exact(&mut existing)?;
+
+		if existing == id3v2 {
+			return Ok(false);
+		}
+	}
 
 	let mut file_bytes = Vec::new();
 	data.read_to_end(&mut file_bytes)?;
@@ -69,7 +88,7 @@ pub(crate) fn write_id3v2<'a, I: Iterator<Item = FrameRef<'a>> + 'a>(
 	data.set_len(0)?;
 	data.write_all(&file_bytes)?;
 
-	Ok(())
+	Ok(true)
 }
 
 pub(super) fn create_tag<'a, I: Iterator<Item = FrameRef<'a>> + 'a>(
@@ -83,16 +102,39 @@ pub(super) fn create_tag<'a, I: Iterator<Item = FrameRef<'a>> + 'a>(
 		return Ok(Vec::new());
 	}
 
-	let has_footer = tag.flags.footer;
-	let needs_crc = tag.flags.crc;
+	let version = tag.version;
+	if version == ID3v2Version::V2 {
+		return Err(ID3v2Error::new(ID3v2ErrorKind::Other(
+			"Writing ID3v2.2 tags is not supported",
+		))
+		.into());
+	}
+
+	// Footers and the extended header are only written for ID3v2.4
+	let is_v4 = version == ID3v2Version::V4;
+	let has_footer = tag.flags.footer && is_v4;
+	let needs_crc = tag.flags.crc && is_v4;
 	#[cfg(feature = "id3v2_restrictions")]
 	let has_restrictions = tag.flags.restrictions.0;
 
-	let (mut id3v2, extended_header_len) = create_tag_header(tag.flags)?;
+	let (mut id3v2, extended_header_len) = create_tag_header(tag.flags, version)?;
 	let header_len = id3v2.get_ref().len();
 
+	// In ID3v2.4 every frame is unsynchronised individually
+	let unsynchronise = tag.flags.unsynchronisation;
+	let mut frames = peek.map(|mut frame| {
+		frame.flags.unsynchronisation |= unsynchronise && is_v4;
+		frame
+	});
+
 	// Write the items
-	frame::create_items(&mut id3v2, &mut peek)?;
+	frame::create_items(&mut id3v2, &mut frames, version)?;
+
+	// Prior to ID3v2.4, the entire tag is unsynchronised
+	if unsynchronise && !is_v4 {
+		let frames = id3v2.get_mut().split_off(header_len);
+		id3v2.get_mut().extend(synch_content(&frames));
+	}
 
 	let len = id3v2.get_ref().len() - header_len;
 
@@ -139,26 +181,35 @@ pub(super) fn create_tag<'a, I: Iterator<Item = FrameRef<'a>> + 'a>(
 	Ok(id3v2.into_inner())
 }
 
-fn create_tag_header(flags: ID3v2TagFlags) -> Result<(Cursor<Vec<u8>>, u32)> {
+fn create_tag_header(
+	flags: ID3v2TagFlags,
+	version: ID3v2Version,
+) -> Result<(Cursor<Vec<u8>>, u32)> {
 	let mut header = Cursor::new(Vec::new());
 
 	header.write_all(&[b'I', b'D', b'3'])?;
 
 	let mut tag_flags = 0;
 
-	// Version 4, rev 0
-	header.write_all(&[4, 0])?;
+	let is_v4 = version == ID3v2Version::V4;
+
+	// Version 4 or 3, rev 0
+	header.write_all(&[if is_v4 { 4 } else { 3 }, 0])?;
 
 	#[cfg(not(feature = "id3v2_restrictions"))]
-	let extended_header = flags.crc;
+	let extended_header = flags.crc && is_v4;
 
 	#[cfg(feature = "id3v2_restrictions")]
-	let extended_header = flags.crc || flags.restrictions.0;
+	let extended_header = (flags.crc || flags.restrictions.0) && is_v4;
 
-	if flags.footer {
+	if flags.footer && is_v4 {
 		tag_flags |= 0x10
 	}
 
+	if flags.unsynchronisation {
+		tag_flags |= 0x80
+	}
+
 	if flags.experimental {
 		tag_flags |= 0x20
 	}
@@ -240,7 +291,10 @@ fn calculate_crc(content: &[u8]) -> [u8; 5] {
 
 #[cfg(test)]
 mod tests {
-	use crate::id3::v2::{ID3v2Tag, ID3v2TagFlags};
+	use crate::id3::v2::{
+		read_id3v2_header, Frame, FrameFlags, FrameValue, ID3v2Tag, ID3v2TagFlags, ID3v2Version,
+		TextEncoding,
+	};
 	use crate::{Accessor, TagExt};
 
 	#[test]
@@ -273,4 +327,216 @@ mod tests {
 
 		assert_eq!(unsynch_crc, 0x66BA_7E94);
 	}
+
+	#[test]
+	fn id3v23_write() {
+		let long_artist = "Foo artist ".repeat(20);
+
+		let mut tag = ID3v2Tag::default();
+		tag.set_version(ID3v2Version::V3);
+		tag.set_artist(long_artist.clone());
+		tag.set_title(String::from("Foo title"));
+		tag.insert(
+			Frame::new(
+				"TDRC",
+				FrameValue::Text {
+					encoding: TextEncoding::Latin1,
+					value: String::from("2022-07-25T13:45:10"),
+				},
+				FrameFlags::default(),
+			)
+			.unwrap(),
+		);
+
+		// These have no ID3v2.3 equivalent
+		for id in ["TDRL", "TSST"] {
+			tag.insert(
+				Frame::new(
+					id,
+					FrameValue::Text {
+						encoding: TextEncoding::Latin1,
+						value: String::from("Foo"),
+					},
+					FrameFlags::default(),
+				)
+				.unwrap(),
+			);
+		}
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		// ID3v2.3, rev 0
+		assert_eq!(&writer[3..5], &[3, 0]);
+
+		// TPE1 is the first frame, and its size isn't synchsafe.
+		// The content was converted from UTF-8 to UTF-16 (encoding byte + BOM + 2 bytes per character)
+		let artist_size = 1 + 2 + long_artist.len() * 2;
+		assert_eq!(&writer[10..14], b"TPE1");
+		assert_eq!(&writer[14..18], &(artist_size as u32).to_be_bytes());
+		assert_eq!(writer[20], TextEncoding::UTF16 as u8);
+
+		for frame in [&b"TYER"[..], b"TDAT", b"TIME"] {
+			assert!(writer.windows(4).any(|w| w == frame));
+		}
+		for frame in [&b"TDRC"[..], b"TDRL", b"TSST"] {
+			assert!(!writer.windows(4).any(|w| w == frame));
+		}
+
+		// The timestamp is merged back together when reading
+		let mut reader = &mut &writer[..];
+		let header = read_id3v2_header(&mut reader).unwrap();
+		assert_eq!(header.version, ID3v2Version::V3);
+
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+		assert_eq!(parsed_tag.artist(), Some(long_artist.as_str()));
+		assert_eq!(parsed_tag.title(), Some("Foo title"));
+		assert_eq!(
+			parsed_tag.get("TDRC").map(Frame::content),
+			Some(&FrameValue::Text {
+				encoding: TextEncoding::Latin1,
+				value: String::from("2022-07-25T13:45"),
+			})
+		);
+		assert!(parsed_tag.get("TDAT").is_none());
+		assert!(parsed_tag.get("TIME").is_none());
+	}
+
+	#[test]
+	fn id3v23_write_compressed() {
+		let mut tag = ID3v2Tag::default();
+		tag.set_version(ID3v2Version::V3);
+		tag.insert(
+			Frame::new(
+				"TIT2",
+				FrameValue::Text {
+					encoding: TextEncoding::Latin1,
+					value: String::from("Foo title"),
+				},
+				FrameFlags {
+					compression: true,
+					..FrameFlags::default()
+				},
+			)
+			.unwrap(),
+		);
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		// The compression flag is followed by the decompressed size (encoding byte + text)
+		assert_eq!(&writer[10..14], b"TIT2");
+		assert_eq!(&writer[18..20], &[0x00, 0x80]);
+		assert_eq!(&writer[20..24], &10_u32.to_be_bytes());
+
+		let mut reader = &mut &writer[..];
+		let header = read_id3v2_header(&mut reader).unwrap();
+		let parsed_tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+		assert_eq!(parsed_tag.title(), Some("Foo title"));
+	}
+
+	#[test]
+	#[allow(clippy::non_ascii_literal)]
+	fn write_non_latin1_text() {
+		fn round_trip(tag: &ID3v2Tag) -> (u8, ID3v2Tag) {
+			let mut writer = Vec::new();
+			tag.dump_to(&mut writer).unwrap();
+
+			let mut reader = &mut &writer[..];
+			let header = read_id3v2_header(&mut reader).unwrap();
+			let parsed_tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+			// The encoding of the first frame
+			(writer[20], parsed_tag)
+		}
+
+		let title = "日本語";
+
+		let mut tag = ID3v2Tag::default();
+		tag.insert(
+			Frame::new(
+				"TIT2",
+				FrameValue::Text {
+					encoding: TextEncoding::Latin1,
+					value: String::from(title),
+				},
+				FrameFlags::default(),
+			)
+			.unwrap(),
+		);
+
+		// Latin-1 can't represent the title, so UTF-8 is used instead
+		let (encoding, parsed_tag) = round_trip(&tag);
+		assert_eq!(encoding, TextEncoding::UTF8 as u8);
+		assert_eq!(parsed_tag.title(), Some(title));
+
+		// ID3v2.3 has to use UTF-16
+		tag.set_version(ID3v2Version::V3);
+		let (encoding, parsed_tag) = round_trip(&tag);
+		assert_eq!(encoding, TextEncoding::UTF16 as u8);
+		assert_eq!(parsed_tag.title(), Some(title));
+
+		// The preferred encoding is used over the frame's encoding
+		tag.set_version(ID3v2Version::V4);
+		tag.set_preferred_encoding(Some(TextEncoding::UTF16BE));
+		let (encoding, parsed_tag) = round_trip(&tag);
+		assert_eq!(encoding, TextEncoding::UTF16BE as u8);
+		assert_eq!(parsed_tag.title(), Some(title));
+	}
+
+	#[test]
+	fn write_unsynchronised() {
+		let value = vec![0xFF, 0xE0, 0xFF, 0x00, 0xFF];
+
+		let mut tag = ID3v2Tag::default();
+		tag.set_flags(ID3v2TagFlags {
+			unsynchronisation: true,
+			..ID3v2TagFlags::default()
+		});
+		tag.insert(
+			Frame::new(
+				"PRIV",
+				FrameValue::Private {
+					owner: String::from("foo@bar.com"),
+					data: value.clone(),
+				},
+				FrameFlags::default(),
+			)
+			.unwrap(),
+		);
+
+		for version in [ID3v2Version::V4, ID3v2Version::V3] {
+			tag.set_version(version);
+
+			let mut writer = Vec::new();
+			tag.dump_to(&mut writer).unwrap();
+
+			// The unsynchronisation flag
+			assert_eq!(writer[5] & 0x80, 0x80);
+
+			// There are no false syncs in the written frame
+			let frame = &writer[10..];
+			assert!(!frame.windows(2).any(|w| w[0] == 0xFF && w[1] >= 0xE0));
+
+			let mut reader = &mut &writer[..];
+			let header = read_id3v2_header(&mut reader).unwrap();
+			let parsed_tag = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+			assert_eq!(
+				parsed_tag.private_frames("foo@bar.com").collect::<Vec<_>>(),
+				vec![value.as_slice()]
+			);
+		}
+	}
+
+	#[test]
+	fn id3v22_write_unsupported() {
+		let mut tag = ID3v2Tag::default();
+		tag.set_version(ID3v2Version::V2);
+		tag.set_artist(String::from("Foo artist"));
+
+		assert!(tag.dump_to(&mut Vec::new()).is_err());
+	}
 }
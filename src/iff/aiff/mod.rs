@@ -1,10 +1,12 @@
-mod properties;
-mod read;
+pub(crate) mod properties;
+pub(crate) mod read;
 pub(crate) mod write;
 
 #[cfg(feature = "id3v2")]
 use crate::id3::v2::tag::ID3v2Tag;
-use crate::properties::FileProperties;
+use properties::AiffProperties;
+
+use std::ops::Range;
 
 use lofty_attr::LoftyFile;
 
@@ -28,5 +30,7 @@ pub struct AiffFile {
 	#[lofty(tag_type = "ID3v2")]
 	pub(crate) id3v2_tag: Option<ID3v2Tag>,
 	/// The file's audio properties
-	pub(crate) properties: FileProperties,
+	pub(crate) properties: AiffProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
 }
@@ -7,11 +7,99 @@ use std::time::Duration;
 
 use byteorder::{BigEndian, ReadBytesExt};
 
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+/// An AIFF file's audio properties
+pub struct AiffProperties {
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) bit_depth: u8,
+	pub(crate) channels: u8,
+	pub(crate) sample_frames: u32,
+	pub(crate) aiff_c: bool,
+	pub(crate) compression_type: Option<[u8; 4]>,
+	pub(crate) compression_name: Option<String>,
+}
+
+impl From<AiffProperties> for FileProperties {
+	fn from(input: AiffProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: Some(input.bit_depth),
+			channels: Some(input.channels),
+		}
+	}
+}
+
+impl AiffProperties {
+	/// Duration
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Bits per sample
+	pub fn bit_depth(&self) -> u8 {
+		self.bit_depth
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+
+	/// The number of sample frames
+	///
+	/// A sample frame contains one sample for each channel.
+	pub fn sample_frames(&self) -> u32 {
+		self.sample_frames
+	}
+
+	/// Whether the file is AIFF-C (form type `AIFC`)
+	pub fn is_aiff_c(&self) -> bool {
+		self.aiff_c
+	}
+
+	/// The AIFF-C compression type, such as `NONE`, `sowt`, or `alaw`
+	///
+	/// This will be `None` for plain AIFF files.
+	pub fn compression_type(&self) -> Option<[u8; 4]> {
+		self.compression_type
+	}
+
+	/// The AIFF-C compression name, such as "Not compressed" or "ALaw 2:1"
+	///
+	/// This will be `None` for plain AIFF files.
+	pub fn compression_name(&self) -> Option<&str> {
+		self.compression_name.as_deref()
+	}
+}
+
 pub(super) fn read_properties(
 	comm: &mut &[u8],
-	stream_len: u32,
+	aiff_c: bool,
+	stream_len: u64,
 	file_length: u64,
-) -> Result<FileProperties> {
+) -> Result<AiffProperties> {
 	let channels = comm.read_u16::<BigEndian>()? as u8;
 
 	if channels == 0 {
@@ -24,50 +112,99 @@ pub(super) fn read_properties(
 	let mut sample_rate_bytes = [0; 10];
 	comm.read_exact(&mut sample_rate_bytes)?;
 
-	let sign = u64::from(sample_rate_bytes[0] & 0x80);
+	let sample_rate = extended_to_u32(sample_rate_bytes);
+
+	let mut properties = AiffProperties {
+		sample_rate,
+		bit_depth: sample_size as u8,
+		channels,
+		sample_frames,
+		aiff_c,
+		..AiffProperties::default()
+	};
+
+	// AIFF-C extends the COMM chunk with the compression type, followed by a pascal string
+	if aiff_c {
+		let mut compression_type = [0; 4];
+		if comm.read_exact(&mut compression_type).is_ok() {
+			properties.compression_type = Some(compression_type);
+			properties.compression_name = read_pstring(comm);
+		}
+	}
+
+	if sample_rate > 0 && sample_frames > 0 {
+		let length = (f64::from(sample_frames) * 1000.0) / f64::from(sample_rate);
 
-	sample_rate_bytes[0] &= 0x7F;
+		properties.duration = Duration::from_millis((length + 0.5) as u64);
+		properties.overall_bitrate = ((file_length as f64) * 8.0 / length + 0.5) as u32;
+		properties.audio_bitrate = (stream_len as f64 * 8.0 / length + 0.5) as u32;
+	}
 
-	let mut exponent = u16::from(sample_rate_bytes[0]) << 8 | u16::from(sample_rate_bytes[1]);
-	exponent = exponent - 16383 + 1023;
+	Ok(properties)
+}
 
-	let fraction = &mut sample_rate_bytes[2..];
-	fraction[0] &= 0x7F;
+// Converts an 80-bit IEEE 754 extended precision float to a `u32`, rounding to the nearest integer
+//
+// The float is made up of a sign bit, a 15 bit exponent (biased by 16383), and a 64 bit mantissa.
+// Unlike other IEEE 754 formats, the integer bit of the mantissa is stored explicitly.
+//
+// Negative numbers, infinity, and NaN are all treated as 0, and values too large are saturated.
+fn extended_to_u32(bytes: [u8; 10]) -> u32 {
+	const EXPONENT_BIAS: i32 = 16383;
 
-	let fraction: Vec<u64> = fraction.iter_mut().map(|v| u64::from(*v)).collect();
+	let sign = bytes[0] & 0x80 != 0;
+	let exponent = i32::from(u16::from_be_bytes([bytes[0] & 0x7F, bytes[1]]));
 
-	let fraction = fraction[0] << 56
-		| fraction[1] << 48
-		| fraction[2] << 40
-		| fraction[3] << 32
-		| fraction[4] << 24
-		| fraction[5] << 16
-		| fraction[6] << 8
-		| fraction[7];
+	let mut mantissa = [0; 8];
+	mantissa.copy_from_slice(&bytes[2..]);
+	let mantissa = u64::from_be_bytes(mantissa);
 
-	let f64_bytes = sign << 56 | u64::from(exponent) << 52 | fraction >> 11;
-	let float = f64::from_be_bytes(f64_bytes.to_be_bytes());
+	if sign || exponent == 0x7FFF || mantissa == 0 {
+		return 0;
+	}
 
-	let sample_rate = float.round() as u32;
+	// The mantissa is a fixed point number, with the binary point after the integer bit
+	let value = (mantissa as f64) * 2_f64.powi(exponent - EXPONENT_BIAS - 63);
 
-	let (duration, overall_bitrate, audio_bitrate) = if sample_rate > 0 && sample_frames > 0 {
-		let length = (f64::from(sample_frames) * 1000.0) / f64::from(sample_rate);
+	// Float to int casts saturate
+	value.round() as u32
+}
 
-		(
-			Duration::from_millis(length as u64),
-			Some(((file_length as f64) * 8.0 / length + 0.5) as u32),
-			Some((f64::from(stream_len) * 8.0 / length + 0.5) as u32),
-		)
-	} else {
-		(Duration::ZERO, None, None)
-	};
+fn read_pstring(reader: &mut &[u8]) -> Option<String> {
+	let len = reader.read_u8().ok()? as usize;
+	let text = reader.get(..len)?;
 
-	Ok(FileProperties {
-		duration,
-		overall_bitrate,
-		audio_bitrate,
-		sample_rate: Some(sample_rate),
-		bit_depth: Some(sample_size as u8),
-		channels: Some(channels),
-	})
+	Some(String::from_utf8_lossy(text).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::extended_to_u32;
+
+	#[test]
+	fn extended_sample_rates() {
+		assert_eq!(
+			extended_to_u32([0x40, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0]),
+			44100
+		);
+		assert_eq!(
+			extended_to_u32([0x40, 0x0E, 0xBB, 0x80, 0, 0, 0, 0, 0, 0]),
+			48000
+		);
+		assert_eq!(
+			extended_to_u32([0x40, 0x0F, 0xBB, 0x80, 0, 0, 0, 0, 0, 0]),
+			96000
+		);
+
+		// 0.5, which would underflow a naive exponent conversion
+		assert_eq!(extended_to_u32([0x3F, 0xFE, 0x80, 0, 0, 0, 0, 0, 0, 0]), 1);
+
+		// Zero, negative numbers, and infinity
+		assert_eq!(extended_to_u32([0; 10]), 0);
+		assert_eq!(
+			extended_to_u32([0xC0, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0]),
+			0
+		);
+		assert_eq!(extended_to_u32([0x7F, 0xFF, 0x80, 0, 0, 0, 0, 0, 0, 0]), 0);
+	}
 }
@@ -1,13 +1,16 @@
+use super::properties::AiffProperties;
 #[cfg(feature = "aiff_text_chunks")]
 use super::tag::{AIFFTextChunks, Comment};
 use super::AiffFile;
-use crate::error::{FileDecodingError, Result};
+use crate::error::{FileDecodingError, LoftyError, Result};
 use crate::file::FileType;
 #[cfg(feature = "id3v2")]
 use crate::id3::v2::tag::ID3v2Tag;
+#[cfg(feature = "id3v2")]
+use crate::iff::chunk::is_id3_chunk;
 use crate::iff::chunk::Chunks;
 use crate::macros::err;
-use crate::properties::FileProperties;
+use crate::probe::ParseOptions;
 
 use std::io::{Read, Seek, SeekFrom};
 
@@ -15,7 +18,8 @@ use byteorder::BigEndian;
 #[cfg(feature = "aiff_text_chunks")]
 use byteorder::ReadBytesExt;
 
-pub(in crate::iff) fn verify_aiff<R>(data: &mut R) -> Result<()>
+// Returns the form type, which is either `AIFF` or `AIFC`
+pub(in crate::iff) fn verify_aiff<R>(data: &mut R) -> Result<[u8; 4]>
 where
 	R: Read + Seek,
 {
@@ -26,16 +30,31 @@ where
 		err!(UnknownFormat);
 	}
 
-	Ok(())
+	let mut form_type = [0; 4];
+	form_type.copy_from_slice(&id[8..]);
+
+	Ok(form_type)
 }
 
 pub(crate) fn read_from<R>(data: &mut R, read_properties: bool) -> Result<AiffFile>
+where
+	R: Read + Seek,
+{
+	read_from_with_options(data, read_properties, &mut ParseOptions::new())
+}
+
+#[allow(unused_variables)]
+pub(crate) fn read_from_with_options<R>(
+	data: &mut R,
+	read_properties: bool,
+	options: &mut ParseOptions<'_>,
+) -> Result<AiffFile>
 where
 	R: Read + Seek,
 {
 	// TODO: Maybe one day the `Seek` bound can be removed?
 	// let file_size = verify_aiff(data)?;
-	verify_aiff(data)?;
+	let aiff_c = &verify_aiff(data)? == b"AIFC";
 
 	let current_pos = data.stream_position()?;
 	let file_len = data.seek(SeekFrom::End(0))?;
@@ -44,6 +63,7 @@ where
 
 	let mut comm = None;
 	let mut stream_len = 0;
+	let mut audio_data_range = 0..0;
 
 	#[cfg(feature = "aiff_text_chunks")]
 	let mut text_chunks = AIFFTextChunks::default();
@@ -55,26 +75,44 @@ where
 	#[cfg(feature = "id3v2")]
 	let mut id3v2_tag: Option<ID3v2Tag> = None;
 
-	let mut chunks = Chunks::<BigEndian>::new(file_len);
+	let mut chunks = Chunks::<BigEndian>::new(file_len - current_pos);
 
 	while chunks.next(data).is_ok() {
 		match &chunks.fourcc {
 			#[cfg(feature = "id3v2")]
-			b"ID3 " | b"id3 " => id3v2_tag = Some(chunks.id3_chunk(data)?),
+			fourcc if is_id3_chunk(*fourcc) => id3v2_tag = Some(chunks.id3_chunk(data, options)?),
 			b"COMM" if read_properties && comm.is_none() => {
 				if chunks.size < 18 {
-					return Err(FileDecodingError::new(
+					return Err(LoftyError::from(FileDecodingError::new(
 						FileType::AIFF,
 						"File has an invalid \"COMM\" chunk size (< 18)",
-					)
-					.into());
+					))
+					.with_location(chunks.location()));
 				}
 
 				comm = Some(chunks.content(data)?);
 				chunks.correct_position(data)?;
 			},
-			b"SSND" if read_properties => {
+			b"SSND" => {
 				stream_len = chunks.size;
+
+				let start = data.stream_position()?;
+				let end = start + chunks.size;
+
+				// The sound data is preceded by an offset and block size, with the
+				// offset being the number of bytes to skip before the first sample frame
+				let mut data_start = start;
+				if chunks.size >= 8 {
+					let mut header = [0; 8];
+					data.read_exact(&mut header)?;
+					data.seek(SeekFrom::Start(start))?;
+
+					let offset = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+					data_start = start + 8 + u64::from(offset);
+				}
+
+				audio_data_range = data_start.min(end)..end;
+
 				chunks.skip(data)?;
 			},
 			#[cfg(feature = "aiff_text_chunks")]
@@ -148,6 +186,7 @@ where
 
 				properties = super::properties::read_properties(
 					&mut &*comm,
+					aiff_c,
 					stream_len,
 					data.stream_position()?,
 				)?;
@@ -161,7 +200,7 @@ where
 			},
 		}
 	} else {
-		properties = FileProperties::default();
+		properties = AiffProperties::default();
 	};
 
 	Ok(AiffFile {
@@ -179,5 +218,6 @@ where
 		},
 		#[cfg(feature = "id3v2")]
 		id3v2_tag,
+		audio_data_range,
 	})
 }
@@ -161,11 +161,11 @@ impl TagExt for AIFFTextChunks {
 	///
 	/// * `path` does not exist
 	/// * See [`AIFFTextChunks::save_to`]
-	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err> {
 		self.save_to(&mut OpenOptions::new().read(true).write(true).open(path)?)
 	}
 
-	fn save_to(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err> {
 		AiffTextChunksRef {
 			name: self.name.as_deref(),
 			author: self.author.as_deref(),
@@ -173,7 +173,9 @@ impl TagExt for AIFFTextChunks {
 			annotations: self.annotations.as_deref(),
 			comments: self.comments.as_deref(),
 		}
-		.write_to(file)
+		.write_to(file)?;
+
+		Ok(true)
 	}
 
 	fn dump_to<W: Write>(&self, writer: &mut W) -> std::result::Result<(), Self::Err> {
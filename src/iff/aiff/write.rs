@@ -8,7 +8,7 @@ use crate::tag::{Tag, TagType};
 use std::fs::File;
 
 #[allow(unused_variables)]
-pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<()> {
+pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<bool> {
 	match tag.tag_type() {
 		#[cfg(feature = "aiff_text_chunks")]
 		TagType::AIFFText => {
@@ -22,10 +22,12 @@ pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<()> {
 				comments: None,
 			}
 		}
-		.write_to(data),
+		.write_to(data)
+		.map(|()| true),
 		#[cfg(feature = "id3v2")]
 		TagType::ID3v2 => v2::tag::Id3v2TagRef {
 			flags: v2::ID3v2TagFlags::default(),
+			version: v2::ID3v2Version::V4,
 			frames: v2::tag::tag_frames(tag),
 		}
 		.write_to(data),
@@ -1,39 +1,56 @@
-use crate::error::Result;
+use crate::error::{ErrorKind, ErrorLocation, LoftyError, Result};
 #[cfg(feature = "id3v2")]
 use crate::id3::v2::tag::ID3v2Tag;
 use crate::macros::{err, try_vec};
+#[cfg(feature = "id3v2")]
+use crate::probe::ParseOptions;
 
 use std::io::{Read, Seek, SeekFrom};
 use std::marker::PhantomData;
 
-use byteorder::{ByteOrder, ReadBytesExt};
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
+
+/// Whether a chunk is an ID3v2 chunk
+///
+/// Writers disagree on the casing of the ID (`ID3 ` and `id3 ` are the most common), so this
+/// is case-insensitive.
+pub(crate) fn is_id3_chunk(fourcc: [u8; 4]) -> bool {
+	fourcc.eq_ignore_ascii_case(b"ID3 ")
+}
 
 pub(crate) struct Chunks<B>
 where
 	B: ByteOrder,
 {
 	pub fourcc: [u8; 4],
-	pub size: u32,
+	// Chunk sizes are 32 bits, but they can be replaced by the 64 bit sizes of an RF64 file's `ds64` chunk
+	pub size: u64,
+	// The offset of the current chunk's header, used to locate errors
+	pub offset: u64,
+	// The amount of data left in the stream, used to reject chunk sizes that can't possibly be valid
 	remaining_size: u64,
 	_phantom: PhantomData<B>,
 }
 
 impl<B: ByteOrder> Chunks<B> {
-	pub fn new(file_size: u64) -> Self {
+	/// Create a new chunk reader, where `remaining_size` is the length of the stream after the current position
+	pub fn new(remaining_size: u64) -> Self {
 		Self {
 			fourcc: [0; 4],
 			size: 0,
-			remaining_size: file_size,
+			offset: 0,
+			remaining_size,
 			_phantom: PhantomData,
 		}
 	}
 
 	pub fn next<R>(&mut self, data: &mut R) -> Result<()>
 	where
-		R: Read,
+		R: Read + Seek,
 	{
+		self.offset = data.stream_position()?;
 		data.read_exact(&mut self.fourcc)?;
-		self.size = data.read_u32::<B>()?;
+		self.size = u64::from(data.read_u32::<B>()?);
 
 		self.remaining_size = self.remaining_size.saturating_sub(8);
 
@@ -47,7 +64,8 @@ impl<B: ByteOrder> Chunks<B> {
 		let cont = self.content(data)?;
 		self.correct_position(data)?;
 
-		let value_str = std::str::from_utf8(&cont)?;
+		let value_str = std::str::from_utf8(&cont)
+			.map_err(|e| LoftyError::from(e).with_location(self.location()))?;
 
 		Ok(value_str.trim_end_matches('\0').to_string())
 	}
@@ -65,47 +83,57 @@ impl<B: ByteOrder> Chunks<B> {
 
 		if cont.len() % 2 != 0 {
 			data.seek(SeekFrom::Current(1))?;
+			self.remaining_size = self.remaining_size.saturating_sub(1);
 		}
 
-		Ok(String::from_utf8(cont)?)
+		String::from_utf8(cont).map_err(|e| LoftyError::from(e).with_location(self.location()))
 	}
 
 	pub fn content<R>(&mut self, data: &mut R) -> Result<Vec<u8>>
 	where
 		R: Read,
 	{
-		self.read(data, u64::from(self.size))
+		self.read(data, self.size)
+	}
+
+	/// The location of the current chunk, to attach to any errors that arise while reading it
+	pub fn location(&self) -> ErrorLocation {
+		ErrorLocation::new(String::from_utf8_lossy(&self.fourcc), self.offset)
 	}
 
 	fn read<R>(&mut self, data: &mut R, size: u64) -> Result<Vec<u8>>
 	where
 		R: Read,
 	{
+		// Check the declared size against what's left in the stream before allocating,
+		// so a bogus size can't cause a huge allocation
 		if size > self.remaining_size {
-			err!(TooMuchData);
+			return Err(LoftyError::new(ErrorKind::TooMuchData).with_location(self.location()));
 		}
 
 		let mut content = try_vec![0; size as usize];
-		data.read_exact(&mut content)?;
+		data.read_exact(&mut content)
+			.map_err(|e| LoftyError::from(e).with_location(self.location()))?;
 
 		self.remaining_size = self.remaining_size.saturating_sub(size);
 		Ok(content)
 	}
 
 	#[cfg(feature = "id3v2")]
-	pub fn id3_chunk<R>(&mut self, data: &mut R) -> Result<ID3v2Tag>
+	pub fn id3_chunk<R>(&mut self, data: &mut R, options: &mut ParseOptions<'_>) -> Result<ID3v2Tag>
 	where
 		R: Read + Seek,
 	{
-		use crate::id3::v2::read::parse_id3v2;
+		use crate::id3::v2::read::parse_id3v2_with_options;
 		use crate::id3::v2::read_id3v2_header;
 
 		let content = self.content(data)?;
 
 		let reader = &mut &*content;
 
-		let header = read_id3v2_header(reader)?;
-		let id3v2 = parse_id3v2(reader, header)?;
+		let id3v2 = read_id3v2_header(reader)
+			.and_then(|header| parse_id3v2_with_options(reader, header, options))
+			.map_err(|e| e.with_location(self.location()))?;
 
 		// Skip over the footer
 		if id3v2.flags().footer {
@@ -121,10 +149,10 @@ impl<B: ByteOrder> Chunks<B> {
 	where
 		R: Read + Seek,
 	{
-		data.seek(SeekFrom::Current(i64::from(self.size)))?;
+		data.seek(SeekFrom::Current(self.size as i64))?;
 		self.correct_position(data)?;
 
-		self.remaining_size = self.remaining_size.saturating_sub(u64::from(self.size));
+		self.remaining_size = self.remaining_size.saturating_sub(self.size);
 
 		Ok(())
 	}
@@ -144,3 +172,241 @@ impl<B: ByteOrder> Chunks<B> {
 		Ok(())
 	}
 }
+
+/// Information about a top-level chunk in a WAV or AIFF file
+///
+/// See [`chunks`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChunkInfo {
+	/// The chunk's identifier, such as `fmt ` or `COMM`
+	pub fourcc: [u8; 4],
+	/// The size of the chunk's content
+	///
+	/// This does not include the 8 byte chunk header, or the padding byte that follows odd sized chunks.
+	/// For RF64 files, the size of the `data` chunk is taken from the `ds64` chunk.
+	pub size: u64,
+	/// The offset of the chunk header from the start of the stream
+	pub offset: u64,
+}
+
+/// An iterator over the top-level chunks of a WAV or AIFF file
+///
+/// See [`chunks`]
+pub struct ChunkIter<'a, R> {
+	reader: &'a mut R,
+	big_endian: bool,
+	rf64_data_size: Option<u64>,
+	position: u64,
+	end: u64,
+	done: bool,
+}
+
+/// Iterate over the top-level chunks of a WAV (RIFF/RF64) or AIFF (FORM) file
+///
+/// This only reads the chunk headers, skipping over their content, so it can be used to inspect
+/// the structure of a file without parsing any tags.
+///
+/// Iteration stops at the end of the stream, or after the first error.
+///
+/// # Errors
+///
+/// * `reader` does not start with a RIFF, RF64, BW64, or FORM header
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::iff::chunks;
+///
+/// # fn main() -> lofty::Result<()> {
+/// # let path = "tests/files/assets/minimal/wav_format_pcm.wav";
+/// let mut file = std::fs::File::open(path)?;
+///
+/// for chunk in chunks(&mut file)? {
+/// 	let chunk = chunk?;
+/// 	println!("{:?} ({} bytes)", chunk.fourcc, chunk.size);
+/// }
+/// # Ok(()) }
+/// ```
+pub fn chunks<R>(reader: &mut R) -> Result<ChunkIter<'_, R>>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+
+	let mut header = [0; 12];
+	reader.read_exact(&mut header)?;
+
+	let (big_endian, rf64) = match &header[..4] {
+		b"RIFF" => (false, false),
+		b"RF64" | b"BW64" => (false, true),
+		b"FORM" => (true, false),
+		_ => err!(UnknownFormat),
+	};
+
+	let end = reader.seek(SeekFrom::End(0))?;
+
+	let mut iter = ChunkIter {
+		reader,
+		big_endian,
+		rf64_data_size: None,
+		position: start + 12,
+		end,
+		done: false,
+	};
+
+	// The `ds64` chunk must be the first chunk of an RF64 file
+	if rf64 {
+		iter.reader.seek(SeekFrom::Start(iter.position))?;
+
+		// Chunk header (8), riffSize (8), dataSize (8)
+		let mut ds64 = [0; 24];
+		if iter.reader.read_exact(&mut ds64).is_ok() && &ds64[..4] == b"ds64" {
+			iter.rf64_data_size = Some(LittleEndian::read_u64(&ds64[16..]));
+		}
+	}
+
+	Ok(iter)
+}
+
+impl<R> ChunkIter<'_, R>
+where
+	R: Read + Seek,
+{
+	fn read_chunk(&mut self) -> Result<ChunkInfo> {
+		let offset = self.position;
+		self.reader.seek(SeekFrom::Start(offset))?;
+
+		let mut fourcc = [0; 4];
+		self.reader.read_exact(&mut fourcc)?;
+
+		let mut size = u64::from(if self.big_endian {
+			self.reader.read_u32::<BigEndian>()?
+		} else {
+			self.reader.read_u32::<LittleEndian>()?
+		});
+
+		if &fourcc == b"data" && size == 0xFFFF_FFFF {
+			size = self.rf64_data_size.unwrap_or(size);
+		}
+
+		// Chunks are expected to start on even boundaries, see `Chunks::correct_position`
+		self.position = size
+			.checked_add(8 + size % 2)
+			.and_then(|len| offset.checked_add(len))
+			.ok_or_else(|| LoftyError::new(ErrorKind::TooMuchData))?;
+
+		Ok(ChunkInfo {
+			fourcc,
+			size,
+			offset,
+		})
+	}
+}
+
+impl<R> Iterator for ChunkIter<'_, R>
+where
+	R: Read + Seek,
+{
+	type Item = Result<ChunkInfo>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		// There needs to be room for at least a chunk header
+		if self.done || self.position.saturating_add(8) > self.end {
+			return None;
+		}
+
+		let chunk = self.read_chunk();
+		self.done = chunk.is_err();
+
+		Some(chunk)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{chunks, ChunkInfo, Chunks};
+	use crate::error::{ErrorKind, ErrorLocation};
+
+	use std::io::Cursor;
+
+	use byteorder::LittleEndian;
+
+	#[test]
+	fn chunk_size_exceeds_stream() {
+		// A chunk claiming to be ~4GB, with only 4 bytes of content
+		let chunk = b"LIST\xF0\xFF\xFF\xFFINFO";
+		let reader = &mut Cursor::new(&chunk[..]);
+
+		let mut chunks = Chunks::<LittleEndian>::new(chunk.len() as u64);
+		chunks.next(reader).unwrap();
+
+		assert_eq!(chunks.size, 0xFFFF_FFF0);
+
+		let err = chunks.content(reader).unwrap_err();
+		assert!(matches!(err.kind(), ErrorKind::TooMuchData));
+		assert_eq!(err.location(), Some(&ErrorLocation::new("LIST", 0)));
+		assert_eq!(
+			err.to_string(),
+			"An abnormally large amount of data was provided, and an overflow occurred (in \"LIST\" at offset 0x0)"
+		);
+	}
+
+	#[test]
+	fn chunk_iter_padding() {
+		// The odd sized "NAME" chunk is followed by a padding byte
+		let file = b"FORM\0\0\0\x1CAIFFNAME\0\0\0\x03Foo\0ANNO\0\0\0\x04Bar!";
+
+		let infos = chunks(&mut Cursor::new(&file[..]))
+			.unwrap()
+			.collect::<crate::error::Result<Vec<_>>>()
+			.unwrap();
+
+		assert_eq!(
+			infos,
+			vec![
+				ChunkInfo {
+					fourcc: *b"NAME",
+					size: 3,
+					offset: 12,
+				},
+				ChunkInfo {
+					fourcc: *b"ANNO",
+					size: 4,
+					offset: 24,
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn chunk_iter_wav() {
+		let mut file =
+			std::fs::File::open("tests/files/assets/minimal/wav_format_pcm.wav").unwrap();
+
+		let fourccs = chunks(&mut file)
+			.unwrap()
+			.map(|chunk| chunk.unwrap().fourcc)
+			.collect::<Vec<_>>();
+
+		assert_eq!(&fourccs[..2], &[*b"fmt ", *b"data"]);
+
+		assert!(chunks(&mut Cursor::new(b"OggS\0\0\0\0\0\0\0\0")).is_err());
+	}
+
+	#[test]
+	fn chunk_iter_rf64_size_overflow() {
+		// A `ds64` chunk with a `data` size that can't be skipped over
+		let mut file = b"RF64\xFF\xFF\xFF\xFFWAVEds64\x10\0\0\0".to_vec();
+		file.extend([0; 8]);
+		file.extend(u64::MAX.to_le_bytes());
+		file.extend(b"data\xFF\xFF\xFF\xFF");
+
+		let reader = &mut Cursor::new(&file[..]);
+		let mut iter = chunks(reader).unwrap();
+		assert_eq!(iter.next().unwrap().unwrap().fourcc, *b"ds64");
+
+		let err = iter.next().unwrap().unwrap_err();
+		assert!(matches!(err.kind(), ErrorKind::TooMuchData));
+		assert!(iter.next().is_none());
+	}
+}
@@ -5,8 +5,10 @@ pub(crate) mod wav;
 
 // Exports
 
+pub use aiff::properties::AiffProperties;
 pub use aiff::AiffFile;
-pub use wav::{WavFile, WavFormat, WavProperties};
+pub use chunk::{chunks, ChunkInfo, ChunkIter};
+pub use wav::{ChannelMask, WavFile, WavFormat, WavProperties};
 
 cfg_if::cfg_if! {
 	if #[cfg(feature = "aiff_text_chunks")] {
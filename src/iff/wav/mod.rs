@@ -1,10 +1,12 @@
 mod properties;
-mod read;
+pub(crate) mod read;
 pub(crate) mod write;
 
 #[cfg(feature = "id3v2")]
 use crate::id3::v2::tag::ID3v2Tag;
 
+use std::ops::Range;
+
 use lofty_attr::LoftyFile;
 
 cfg_if::cfg_if! {
@@ -15,7 +17,7 @@ cfg_if::cfg_if! {
 }
 
 // Exports
-pub use crate::iff::wav::properties::{WavFormat, WavProperties};
+pub use crate::iff::wav::properties::{ChannelMask, WavFormat, WavProperties};
 
 /// A WAV file
 #[derive(LoftyFile)]
@@ -31,4 +33,6 @@ pub struct WavFile {
 	pub(crate) id3v2_tag: Option<ID3v2Tag>,
 	/// The file's audio properties
 	pub(crate) properties: WavProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
 }
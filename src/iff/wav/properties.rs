@@ -2,20 +2,31 @@ use crate::error::{FileDecodingError, Result};
 use crate::file::FileType;
 use crate::properties::FileProperties;
 
+use std::io::Read;
 use std::time::Duration;
 
 use byteorder::{LittleEndian, ReadBytesExt};
 
 const PCM: u16 = 0x0001;
 const IEEE_FLOAT: u16 = 0x0003;
+const IMA_ADPCM: u16 = 0x0011;
 const EXTENSIBLE: u16 = 0xFFFE;
 
+// The `SubFormat` GUIDs of `WAVE_FORMAT_EXTENSIBLE` are the format tag, followed by
+// the bytes of `-0000-0010-8000-00AA00389B71`
+const SUBFORMAT_GUID_SUFFIX: [u8; 14] = [
+	0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71,
+];
+
 #[allow(missing_docs, non_camel_case_types)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 /// A WAV file's format
+///
+/// For `WAVE_FORMAT_EXTENSIBLE` files, this is the format from the `SubFormat` GUID.
 pub enum WavFormat {
 	PCM,
 	IEEE_FLOAT,
+	IMA_ADPCM,
 	Other(u16),
 }
 
@@ -25,6 +36,85 @@ impl Default for WavFormat {
 	}
 }
 
+/// The speaker positions of a `WAVE_FORMAT_EXTENSIBLE` file's channels
+///
+/// This is the `dwChannelMask` field of the `fmt ` chunk. The channels in the stream
+/// are in the same order as the bits, starting from [`ChannelMask::FRONT_LEFT`].
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::iff::ChannelMask;
+///
+/// let stereo = ChannelMask::FRONT_LEFT | ChannelMask::FRONT_RIGHT;
+///
+/// assert!(stereo.contains(ChannelMask::FRONT_LEFT));
+/// assert!(!stereo.contains(ChannelMask::LOW_FREQUENCY));
+/// assert_eq!(stereo.bits(), 0b11);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ChannelMask(u32);
+
+#[allow(missing_docs)]
+impl ChannelMask {
+	pub const FRONT_LEFT: Self = Self(0x1);
+	pub const FRONT_RIGHT: Self = Self(0x2);
+	pub const FRONT_CENTER: Self = Self(0x4);
+	pub const LOW_FREQUENCY: Self = Self(0x8);
+	pub const BACK_LEFT: Self = Self(0x10);
+	pub const BACK_RIGHT: Self = Self(0x20);
+	pub const FRONT_LEFT_OF_CENTER: Self = Self(0x40);
+	pub const FRONT_RIGHT_OF_CENTER: Self = Self(0x80);
+	pub const BACK_CENTER: Self = Self(0x100);
+	pub const SIDE_LEFT: Self = Self(0x200);
+	pub const SIDE_RIGHT: Self = Self(0x400);
+	pub const TOP_CENTER: Self = Self(0x800);
+	pub const TOP_FRONT_LEFT: Self = Self(0x1000);
+	pub const TOP_FRONT_CENTER: Self = Self(0x2000);
+	pub const TOP_FRONT_RIGHT: Self = Self(0x4000);
+	pub const TOP_BACK_LEFT: Self = Self(0x8000);
+	pub const TOP_BACK_CENTER: Self = Self(0x1_0000);
+	pub const TOP_BACK_RIGHT: Self = Self(0x2_0000);
+}
+
+impl ChannelMask {
+	/// Create a `ChannelMask` from its raw bits
+	pub fn from_bits(bits: u32) -> Self {
+		Self(bits)
+	}
+
+	/// Returns the raw bits
+	pub fn bits(self) -> u32 {
+		self.0
+	}
+
+	/// Whether all of the positions in `other` are set
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// The number of speaker positions that are set
+	pub fn count(self) -> u32 {
+		self.0.count_ones()
+	}
+}
+
+impl std::ops::BitOr for ChannelMask {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self::Output {
+		Self(self.0 | rhs.0)
+	}
+}
+
+impl std::ops::BitAnd for ChannelMask {
+	type Output = Self;
+
+	fn bitand(self, rhs: Self) -> Self::Output {
+		Self(self.0 & rhs.0)
+	}
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 #[non_exhaustive]
 /// A WAV file's audio properties
@@ -35,7 +125,9 @@ pub struct WavProperties {
 	pub(crate) audio_bitrate: u32,
 	pub(crate) sample_rate: u32,
 	pub(crate) bit_depth: u8,
+	pub(crate) valid_bits_per_sample: Option<u8>,
 	pub(crate) channels: u8,
+	pub(crate) channel_mask: Option<ChannelMask>,
 }
 
 impl From<WavProperties> for FileProperties {
@@ -45,7 +137,7 @@ impl From<WavProperties> for FileProperties {
 			overall_bitrate: Some(input.overall_bitrate),
 			audio_bitrate: Some(input.audio_bitrate),
 			sample_rate: Some(input.sample_rate),
-			bit_depth: Some(input.bit_depth),
+			bit_depth: Some(input.valid_bits_per_sample.unwrap_or(input.bit_depth)),
 			channels: Some(input.channels),
 		}
 	}
@@ -73,15 +165,32 @@ impl WavProperties {
 	}
 
 	/// Bits per sample
+	///
+	/// This is the size of the sample container, which may be larger than the actual sample
+	/// size for `WAVE_FORMAT_EXTENSIBLE` files. See [`WavProperties::valid_bits_per_sample`].
 	pub fn bit_depth(&self) -> u8 {
 		self.bit_depth
 	}
 
+	/// The number of bits per sample that are actually used
+	///
+	/// This is only available for `WAVE_FORMAT_EXTENSIBLE` files.
+	pub fn valid_bits_per_sample(&self) -> Option<u8> {
+		self.valid_bits_per_sample
+	}
+
 	/// Channel count
 	pub fn channels(&self) -> u8 {
 		self.channels
 	}
 
+	/// The speaker positions of the channels
+	///
+	/// This is only available for `WAVE_FORMAT_EXTENSIBLE` files.
+	pub fn channel_mask(&self) -> Option<ChannelMask> {
+		self.channel_mask
+	}
+
 	/// WAV format
 	pub fn format(&self) -> &WavFormat {
 		&self.format
@@ -90,8 +199,8 @@ impl WavProperties {
 
 pub(super) fn read_properties(
 	fmt: &mut &[u8],
-	mut total_samples: u32,
-	stream_len: u32,
+	mut total_samples: u64,
+	stream_len: u64,
 	file_length: u64,
 ) -> Result<WavProperties> {
 	let mut format_tag = fmt.read_u16::<LittleEndian>()?;
@@ -109,12 +218,15 @@ pub(super) fn read_properties(
 	let bits_per_sample = fmt.read_u16::<LittleEndian>()?;
 	let bytes_per_sample = block_align / u16::from(channels);
 
-	let mut bit_depth = if bits_per_sample > 0 {
+	let bit_depth = if bits_per_sample > 0 {
 		bits_per_sample as u8
 	} else {
 		(bytes_per_sample * 8) as u8
 	};
 
+	let mut valid_bits = None;
+	let mut channel_mask = None;
+
 	if format_tag == EXTENSIBLE {
 		if fmt.len() + 16 < 40 {
 			return Err(FileDecodingError::new(
@@ -129,37 +241,53 @@ pub(super) fn read_properties(
 		// Valid bits per sample (2)
 		let valid_bits_per_sample = fmt.read_u16::<LittleEndian>()?;
 		// Channel mask (4)
-		let _channel_mask = fmt.read_u32::<LittleEndian>()?;
+		channel_mask = Some(ChannelMask(fmt.read_u32::<LittleEndian>()?));
 
 		if valid_bits_per_sample > 0 {
-			bit_depth = valid_bits_per_sample as u8;
+			valid_bits = Some(valid_bits_per_sample as u8);
+		}
+
+		// SubFormat (16)
+		let mut sub_format = [0; 16];
+		fmt.read_exact(&mut sub_format)?;
+
+		if sub_format[2..] == SUBFORMAT_GUID_SUFFIX {
+			format_tag = u16::from_le_bytes([sub_format[0], sub_format[1]]);
 		}
-		format_tag = fmt.read_u16::<LittleEndian>()?;
 	}
 
-	let non_pcm = format_tag != PCM && format_tag != IEEE_FLOAT;
+	let format = match format_tag {
+		PCM => WavFormat::PCM,
+		IEEE_FLOAT => WavFormat::IEEE_FLOAT,
+		IMA_ADPCM => WavFormat::IMA_ADPCM,
+		other => WavFormat::Other(other),
+	};
 
-	if non_pcm && total_samples == 0 {
+	let pcm = matches!(format, WavFormat::PCM | WavFormat::IEEE_FLOAT);
+
+	// Compressed formats can't have their sample count derived from the stream length,
+	// so we have to rely on the "fact" chunk, or the byte rate
+	if pcm {
+		total_samples = if bits_per_sample > 0 {
+			stream_len / u64::from(u16::from(channels) * ((bits_per_sample + 7) / 8))
+		} else {
+			0
+		};
+	} else if total_samples == 0 && bytes_per_second == 0 {
 		return Err(FileDecodingError::new(
 			FileType::WAV,
-			"Non-PCM format identified, no \"fact\" chunk found",
+			"Non-PCM format identified, no \"fact\" chunk or byte rate found",
 		)
 		.into());
 	}
 
-	if bits_per_sample > 0 {
-		total_samples = stream_len / u32::from(u16::from(channels) * ((bits_per_sample + 7) / 8))
-	} else if !non_pcm {
-		total_samples = 0
-	}
-
 	let (duration, overall_bitrate, audio_bitrate) = if sample_rate > 0 && total_samples > 0 {
-		let length = (u64::from(total_samples) * 1000) / u64::from(sample_rate);
+		let length = (total_samples * 1000) / u64::from(sample_rate);
 		if length == 0 {
 			(Duration::ZERO, 0, 0)
 		} else {
 			let overall_bitrate = ((file_length * 8) / length) as u32;
-			let audio_bitrate = (u64::from(stream_len * 8) / length) as u32;
+			let audio_bitrate = ((stream_len * 8) / length) as u32;
 
 			(
 				Duration::from_millis(length),
@@ -167,8 +295,8 @@ pub(super) fn read_properties(
 				audio_bitrate,
 			)
 		}
-	} else if bytes_per_second > 0 {
-		let length = (u64::from(stream_len) * 1000) / u64::from(bytes_per_second);
+	} else if bytes_per_second > 0 && (stream_len * 1000) >= u64::from(bytes_per_second) {
+		let length = (stream_len * 1000) / u64::from(bytes_per_second);
 
 		let overall_bitrate = ((file_length * 8) / length) as u32;
 		let audio_bitrate = (bytes_per_second * 8) / 1000;
@@ -183,16 +311,89 @@ pub(super) fn read_properties(
 	};
 
 	Ok(WavProperties {
-		format: match format_tag {
-			PCM => WavFormat::PCM,
-			IEEE_FLOAT => WavFormat::IEEE_FLOAT,
-			other => WavFormat::Other(other),
-		},
+		format,
 		duration,
 		overall_bitrate,
 		audio_bitrate,
 		sample_rate,
 		bit_depth,
+		valid_bits_per_sample: valid_bits,
 		channels,
+		channel_mask,
 	})
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{read_properties, ChannelMask, WavFormat};
+	use crate::properties::FileProperties;
+
+	use std::time::Duration;
+
+	// A stereo 44.1kHz "fmt " chunk, without any extra format information
+	fn fmt(
+		format_tag: u16,
+		bytes_per_second: u32,
+		block_align: u16,
+		bits_per_sample: u16,
+	) -> Vec<u8> {
+		let mut fmt = Vec::new();
+		fmt.extend(format_tag.to_le_bytes());
+		fmt.extend(2_u16.to_le_bytes());
+		fmt.extend(44100_u32.to_le_bytes());
+		fmt.extend(bytes_per_second.to_le_bytes());
+		fmt.extend(block_align.to_le_bytes());
+		fmt.extend(bits_per_sample.to_le_bytes());
+		fmt
+	}
+
+	#[test]
+	fn ima_adpcm_without_fact_chunk() {
+		// 4 bits per sample, with 2048 byte blocks holding 2041 samples
+		let fmt = fmt(0x0011, 88_837, 2048, 4);
+
+		// 10 seconds worth of blocks
+		let properties = read_properties(&mut &fmt[..], 0, 888_370, 888_414).unwrap();
+
+		assert_eq!(properties.format(), &WavFormat::IMA_ADPCM);
+		assert_eq!(properties.channel_mask(), None);
+		assert_eq!(properties.duration(), Duration::from_secs(10));
+		assert_eq!(properties.bitrate(), 710);
+	}
+
+	#[test]
+	fn ima_adpcm_with_fact_chunk() {
+		let fmt = fmt(0x0011, 88_837, 2048, 4);
+
+		// The sample count from the "fact" chunk takes priority
+		let properties = read_properties(&mut &fmt[..], 220_500, 888_370, 888_414).unwrap();
+		assert_eq!(properties.duration(), Duration::from_secs(5));
+	}
+
+	#[test]
+	fn extensible_sub_format() {
+		// 24-bit samples, padded to 32 bits
+		let mut fmt = fmt(0xFFFE, 352_800, 8, 32);
+		// cbSize, valid bits per sample, channel mask
+		fmt.extend([22, 0, 24, 0, 3, 0, 0, 0]);
+		// KSDATAFORMAT_SUBTYPE_PCM
+		fmt.extend([
+			0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38,
+			0x9B, 0x71,
+		]);
+
+		let properties = read_properties(&mut &fmt[..], 0, 352_800, 352_880).unwrap();
+
+		assert_eq!(properties.format(), &WavFormat::PCM);
+		assert_eq!(properties.bit_depth(), 32);
+		assert_eq!(properties.valid_bits_per_sample(), Some(24));
+		assert_eq!(
+			properties.channel_mask(),
+			Some(ChannelMask::FRONT_LEFT | ChannelMask::FRONT_RIGHT)
+		);
+		assert_eq!(properties.duration(), Duration::from_secs(1));
+
+		// The generic properties only report the valid bits
+		assert_eq!(FileProperties::from(properties).bit_depth(), Some(24));
+	}
+}
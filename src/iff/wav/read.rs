@@ -2,28 +2,42 @@ use super::properties::WavProperties;
 #[cfg(feature = "riff_info_list")]
 use super::tag::RIFFInfoList;
 use super::WavFile;
-use crate::error::{FileDecodingError, Result};
+use crate::error::{FileDecodingError, LoftyError, Result};
 use crate::file::FileType;
 #[cfg(feature = "id3v2")]
 use crate::id3::v2::tag::ID3v2Tag;
+#[cfg(feature = "id3v2")]
+use crate::iff::chunk::is_id3_chunk;
 use crate::iff::chunk::Chunks;
+use crate::probe::ParseOptions;
 
 use std::io::{Read, Seek, SeekFrom};
 
 use byteorder::{LittleEndian, ReadBytesExt};
 
-pub(in crate::iff) fn verify_wav<T>(data: &mut T) -> Result<()>
+// The sizes of RF64 (and BW64) files that don't fit in 32 bits are stored in a `ds64` chunk,
+// with the original size fields set to `0xFFFFFFFF`
+const RF64_SIZE_PLACEHOLDER: u64 = 0xFFFF_FFFF;
+
+/// Verifies the RIFF header, returning whether the file is an RF64 file
+pub(in crate::iff) fn verify_wav<T>(data: &mut T) -> Result<bool>
 where
 	T: Read + Seek,
 {
 	let mut id = [0; 12];
 	data.read_exact(&mut id)?;
 
-	if &id[..4] != b"RIFF" {
-		return Err(
-			FileDecodingError::new(FileType::WAV, "WAV file doesn't contain a RIFF chunk").into(),
-		);
-	}
+	let rf64 = match &id[..4] {
+		b"RIFF" => false,
+		b"RF64" | b"BW64" => true,
+		_ => {
+			return Err(FileDecodingError::new(
+				FileType::WAV,
+				"WAV file doesn't contain a RIFF chunk",
+			)
+			.into())
+		},
+	};
 
 	if &id[8..] != b"WAVE" {
 		return Err(
@@ -31,33 +45,66 @@ where
 		);
 	}
 
-	Ok(())
+	Ok(rf64)
 }
 
 pub(crate) fn read_from<R>(data: &mut R, read_properties: bool) -> Result<WavFile>
 where
 	R: Read + Seek,
 {
-	verify_wav(data)?;
+	read_from_with_options(data, read_properties, &mut ParseOptions::new())
+}
+
+#[allow(unused_variables)]
+pub(crate) fn read_from_with_options<R>(
+	data: &mut R,
+	read_properties: bool,
+	options: &mut ParseOptions<'_>,
+) -> Result<WavFile>
+where
+	R: Read + Seek,
+{
+	let rf64 = verify_wav(data)?;
 
 	let current_pos = data.stream_position()?;
 	let file_len = data.seek(SeekFrom::End(0))?;
 
 	data.seek(SeekFrom::Start(current_pos))?;
 
-	let mut stream_len = 0_u32;
-	let mut total_samples = 0_u32;
+	let mut stream_len = 0_u64;
+	let mut total_samples = 0_u64;
 	let mut fmt = Vec::new();
 
+	// The 64 bit data size and sample count from the `ds64` chunk
+	let mut ds64_data_size = None;
+	let mut ds64_sample_count = None;
+
+	let mut audio_data_range = 0..0;
+
 	#[cfg(feature = "riff_info_list")]
 	let mut riff_info = RIFFInfoList::default();
 	#[cfg(feature = "id3v2")]
 	let mut id3v2_tag: Option<ID3v2Tag> = None;
 
-	let mut chunks = Chunks::<LittleEndian>::new(file_len);
+	let mut chunks = Chunks::<LittleEndian>::new(file_len - current_pos);
 
 	while chunks.next(data).is_ok() {
 		match &chunks.fourcc {
+			b"ds64" if rf64 => {
+				// riffSize (8), dataSize (8), sampleCount (8), tableLength (4), table
+				let content = chunks.content(data)?;
+				if content.len() < 24 {
+					return Err(LoftyError::from(FileDecodingError::new(
+						FileType::WAV,
+						"RF64 file contains an invalid \"ds64\" chunk",
+					))
+					.with_location(chunks.location()));
+				}
+
+				let content = &mut &content[8..];
+				ds64_data_size = Some(content.read_u64::<LittleEndian>()?);
+				ds64_sample_count = Some(content.read_u64::<LittleEndian>()?);
+			},
 			b"fmt " if read_properties => {
 				if fmt.is_empty() {
 					fmt = chunks.content(data)?;
@@ -67,14 +114,35 @@ where
 			},
 			b"fact" if read_properties => {
 				if total_samples == 0 {
-					total_samples = data.read_u32::<LittleEndian>()?;
+					total_samples = u64::from(data.read_u32::<LittleEndian>()?);
+
+					if rf64 && total_samples == RF64_SIZE_PLACEHOLDER {
+						total_samples = ds64_sample_count.unwrap_or(0);
+					}
 				} else {
 					data.seek(SeekFrom::Current(4))?;
 				}
 			},
-			b"data" if read_properties => {
+			b"data" => {
+				if rf64 && chunks.size == RF64_SIZE_PLACEHOLDER {
+					match ds64_data_size {
+						Some(size) => chunks.size = size,
+						None if read_properties => {
+							return Err(FileDecodingError::new(
+								FileType::WAV,
+								"RF64 file is missing a \"ds64\" chunk",
+							)
+							.into())
+						},
+						None => {},
+					}
+				}
+
 				if stream_len == 0 {
-					stream_len += chunks.size
+					stream_len += chunks.size;
+
+					let start = data.stream_position()?;
+					audio_data_range = start..start + chunks.size;
 				}
 
 				chunks.skip(data)?;
@@ -86,7 +154,14 @@ where
 				match &list_type {
 					#[cfg(feature = "riff_info_list")]
 					b"INFO" => {
-						let end = data.stream_position()? + u64::from(chunks.size - 4);
+						let list_size = chunks.size.checked_sub(4).ok_or_else(|| {
+							FileDecodingError::new(
+								FileType::WAV,
+								"Found an invalid \"LIST\" chunk size",
+							)
+						})?;
+
+						let end = data.stream_position()? + list_size;
 						super::tag::read::parse_riff_info(data, &mut chunks, end, &mut riff_info)?;
 					},
 					_ => {
@@ -96,7 +171,7 @@ where
 				}
 			},
 			#[cfg(feature = "id3v2")]
-			b"ID3 " | b"id3 " => id3v2_tag = Some(chunks.id3_chunk(data)?),
+			fourcc if is_id3_chunk(*fourcc) => id3v2_tag = Some(chunks.id3_chunk(data, options)?),
 			_ => chunks.skip(data)?,
 		}
 	}
@@ -131,5 +206,6 @@ where
 		riff_info_tag: (!riff_info.items.is_empty()).then(|| riff_info),
 		#[cfg(feature = "id3v2")]
 		id3v2_tag,
+		audio_data_range,
 	})
 }
@@ -92,11 +92,13 @@ impl RIFFInfoList {
 
 impl Accessor for RIFFInfoList {
 	impl_accessor!(
-		artist  => "IART";
-		title   => "INAM";
-		album   => "IPRD";
-		genre   => "IGNR";
-		comment => "ICMT";
+		artist   => "IART";
+		title    => "INAM";
+		album    => "IPRD";
+		genre    => "IGNR";
+		comment  => "ICMT";
+		composer => "IMUS";
+		encoder  => "ISFT";
 	);
 
 	fn track(&self) -> Option<u32> {
@@ -139,13 +141,15 @@ impl TagExt for RIFFInfoList {
 		self.items.is_empty()
 	}
 
-	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err> {
 		self.save_to(&mut OpenOptions::new().read(true).write(true).open(path)?)
 	}
 
-	fn save_to(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err> {
 		RIFFInfoListRef::new(self.items.iter().map(|(k, v)| (k.as_str(), v.as_str())))
-			.write_to(file)
+			.write_to(file)?;
+
+		Ok(true)
 	}
 
 	fn dump_to<W: Write>(&self, writer: &mut W) -> std::result::Result<(), Self::Err> {
@@ -255,7 +259,7 @@ pub(crate) fn tagitems_into_riff(items: &[TagItem]) -> impl Iterator<Item = (&st
 #[cfg(test)]
 mod tests {
 	use crate::iff::RIFFInfoList;
-	use crate::{Tag, TagExt, TagType};
+	use crate::{ItemKey, Tag, TagExt, TagType};
 
 	use crate::iff::chunk::Chunks;
 	use byteorder::LittleEndian;
@@ -336,6 +340,44 @@ mod tests {
 		crate::tag::utils::test_utils::verify_tag(&tag, true, false);
 	}
 
+	#[test]
+	fn riff_info_unknown_key_round_trip() {
+		let mut riff_info = RIFFInfoList::default();
+		riff_info.insert(String::from("INAM"), String::from("Foo title"));
+		riff_info.insert(String::from("IXYZ"), String::from("Odd"));
+
+		let tag: Tag = riff_info.clone().into();
+
+		assert_eq!(tag.get_string(&ItemKey::TrackTitle), Some("Foo title"));
+		assert_eq!(
+			tag.get_string(&ItemKey::Unknown(String::from("IXYZ"))),
+			Some("Odd")
+		);
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		// Every item, and thus the entire chunk, must have an even length
+		assert_eq!(writer.len() % 2, 0);
+		assert_eq!(&writer[..4], b"LIST");
+		assert_eq!(
+			u32::from_le_bytes(writer[4..8].try_into().unwrap()) as usize,
+			writer.len() - 8
+		);
+		assert_eq!(&writer[8..12], b"INFO");
+
+		let mut re_read = RIFFInfoList::default();
+		super::read::parse_riff_info(
+			&mut Cursor::new(&writer[12..]),
+			&mut Chunks::<LittleEndian>::new(writer.len() as u64 - 12),
+			writer.len() as u64 - 12,
+			&mut re_read,
+		)
+		.unwrap();
+
+		assert_eq!(riff_info, re_read);
+	}
+
 	#[test]
 	fn tag_to_riff_info() {
 		let tag = crate::tag::utils::test_utils::create_tag(TagType::RIFFInfo);
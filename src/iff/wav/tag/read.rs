@@ -1,5 +1,5 @@
 use super::RIFFInfoList;
-use crate::error::{FileDecodingError, Result};
+use crate::error::{FileDecodingError, LoftyError, Result};
 use crate::file::FileType;
 use crate::iff::chunk::Chunks;
 
@@ -18,23 +18,30 @@ where
 {
 	while data.stream_position()? != end && chunks.next(data).is_ok() {
 		let key_str = String::from_utf8(chunks.fourcc.to_vec()).map_err(|_| {
-			FileDecodingError::new(FileType::WAV, "Non UTF-8 item key found in RIFF INFO")
+			LoftyError::from(FileDecodingError::new(
+				FileType::WAV,
+				"Non UTF-8 item key found in RIFF INFO",
+			))
+			.with_location(chunks.location())
 		})?;
 
 		if !verify_key(&key_str) {
-			return Err(FileDecodingError::new(
+			return Err(LoftyError::from(FileDecodingError::new(
 				FileType::WAV,
 				"RIFF INFO item key contains invalid characters",
-			)
-			.into());
+			))
+			.with_location(chunks.location()));
 		}
 
-		tag.items.push((
-			key_str,
-			chunks.read_cstring(data).map_err(|_| {
-				FileDecodingError::new(FileType::WAV, "Failed to read RIFF INFO item value")
-			})?,
-		));
+		let value = chunks.read_cstring(data).map_err(|_| {
+			LoftyError::from(FileDecodingError::new(
+				FileType::WAV,
+				"Failed to read RIFF INFO item value",
+			))
+			.with_location(chunks.location())
+		})?;
+
+		tag.items.push((key_str, value));
 	}
 
 	Ok(())
@@ -1,5 +1,6 @@
 use super::RIFFInfoListRef;
-use crate::error::Result;
+use crate::error::{FileEncodingError, Result};
+use crate::file::FileType;
 use crate::iff::chunk::Chunks;
 use crate::iff::wav::read::verify_wav;
 use crate::macros::err;
@@ -16,7 +17,15 @@ pub(in crate::iff::wav) fn write_riff_info<'a, I>(
 where
 	I: Iterator<Item = (&'a str, &'a str)>,
 {
-	verify_wav(data)?;
+	if verify_wav(data)? {
+		// The sizes of RF64 files are stored in the `ds64` chunk, which we don't update
+		return Err(FileEncodingError::new(
+			FileType::WAV,
+			"Writing to RF64 files is not supported",
+		)
+		.into());
+	}
+
 	let file_len = data.metadata()?.len().saturating_sub(12);
 
 	let mut riff_info_bytes = Vec::new();
@@ -24,7 +33,9 @@ where
 
 	if let Some(info_list_size) = find_info_list(data, file_len)? {
 		let info_list_start = data.seek(SeekFrom::Current(-12))? as usize;
-		let info_list_end = info_list_start + 8 + info_list_size as usize;
+		// The existing chunk may be followed by a padding byte, which has to go as well
+		let info_list_end =
+			info_list_start + 8 + info_list_size as usize + (info_list_size % 2) as usize;
 
 		data.rewind()?;
 
@@ -53,7 +64,7 @@ where
 	Ok(())
 }
 
-fn find_info_list<R>(data: &mut R, file_size: u64) -> Result<Option<u32>>
+fn find_info_list<R>(data: &mut R, file_size: u64) -> Result<Option<u64>>
 where
 	R: Read + Seek,
 {
@@ -71,7 +82,7 @@ where
 				break;
 			}
 
-			data.seek(SeekFrom::Current(-8))?;
+			data.seek(SeekFrom::Current(-4))?;
 		}
 
 		chunks.skip(data)?;
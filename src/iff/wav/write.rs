@@ -8,16 +8,18 @@ use crate::tag::{Tag, TagType};
 use std::fs::File;
 
 #[allow(unused_variables)]
-pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<()> {
+pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<bool> {
 	match tag.tag_type() {
 		#[cfg(feature = "riff_info_list")]
 		TagType::RIFFInfo => {
 			super::tag::RIFFInfoListRef::new(super::tag::tagitems_into_riff(tag.items()))
 				.write_to(data)
+				.map(|()| true)
 		},
 		#[cfg(feature = "id3v2")]
 		TagType::ID3v2 => v2::tag::Id3v2TagRef {
 			flags: v2::ID3v2TagFlags::default(),
+			version: v2::ID3v2Version::V4,
 			frames: v2::tag::tag_frames(tag),
 		}
 		.write_to(data),
@@ -8,14 +8,20 @@
 //!
 //! | File Format | Metadata Format(s)                   |
 //! |-------------|--------------------------------------|
+//! | AAC (ADTS)  | `ID3v2`, `ID3v1`, `APEv2`, `APEv1`   |
 //! | Ape         | `APEv2`, `APEv1`, `ID3v2`\*, `ID3v1` |
 //! | AIFF        | `ID3v2`, `Text Chunks`               |
+//! | DSF         | `ID3v2`\*                            |
 //! | FLAC        | `Vorbis Comments`, `ID3v2`\*         |
 //! | MP3         | `ID3v2`, `ID3v1`, `APEv2`, `APEv1`   |
+//! | Matroska    | `Matroska Tags`\*                    |
 //! | MP4         | `iTunes-style ilst`                  |
+//! | Musepack    | `APEv2`, `APEv1`, `ID3v1`            |
+//! | Ogg FLAC    | `Vorbis Comments`                    |
 //! | Opus        | `Vorbis Comments`                    |
 //! | Ogg Vorbis  | `Vorbis Comments`                    |
 //! | Speex       | `Vorbis Comments`                    |
+//! | TrueAudio   | `ID3v2`, `ID3v1`, `APEv2`, `APEv1`   |
 //! | WAV         | `ID3v2`, `RIFF INFO`                 |
 //! | WavPack     | `APEv2`, `APEv1`, `ID3v1`            |
 //!
@@ -119,12 +125,16 @@
 //! * `ape`
 //! * `id3v1`
 //! * `id3v2`
+//! * `lyrics3v2`
 //! * `mp4_ilst`
 //! * `riff_info_list`
 //! * `vorbis_comments`
 //!
 //! ## Utilities
 //! * `id3v2_restrictions` - Parses ID3v2 extended headers and exposes flags for fine grained control
+//! * `picture-tools` - Enables `Picture::thumbnail`, for creating resized copies of pictures
+//! * `rayon` - Enables `read_dir_parallel`, for reading every file in a directory across multiple threads
+//! * `serde` - Implements `Serialize` and `Deserialize` for [`Tag`] and the types it contains
 //!
 //! # Important format-specific notes
 //!
@@ -177,15 +187,19 @@
 // proc macro hacks
 extern crate self as lofty;
 
+pub mod aac;
 pub mod ape;
+pub mod dsf;
 pub mod error;
 pub(crate) mod file;
 pub mod flac;
 pub mod id3;
 pub mod iff;
 pub(crate) mod macros;
+pub mod mka;
 pub mod mp4;
 pub mod mpeg;
+pub mod musepack;
 pub mod ogg;
 pub(crate) mod picture;
 mod probe;
@@ -193,16 +207,24 @@ pub(crate) mod properties;
 pub mod resolve;
 pub(crate) mod tag;
 mod traits;
+pub mod tta;
 pub mod wavpack;
 
 pub use crate::error::{LoftyError, Result};
 
-pub use crate::probe::{read_from, read_from_path, Probe};
+#[cfg(feature = "rayon")]
+pub use crate::probe::read_dir_parallel;
+pub use crate::probe::{
+	read_from, read_from_path, read_from_reader, ParseMode, Probe, ProbeOptions,
+};
 
 pub use crate::file::{AudioFile, FileType, TaggedFile};
 pub use crate::picture::{MimeType, Picture, PictureType};
 pub use crate::properties::FileProperties;
-pub use crate::tag::{Tag, TagType};
+pub use crate::tag::builder::TagBuilder;
+pub use crate::tag::gapless::GaplessInfo;
+pub use crate::tag::replay_gain::ReplayGain;
+pub use crate::tag::{remove_all_tags, remove_all_tags_from_path, MergePolicy, Tag, TagType};
 pub use tag::item::{ItemKey, ItemValue, TagItem};
 
 pub use crate::traits::{Accessor, TagExt};
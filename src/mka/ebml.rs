@@ -0,0 +1,231 @@
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+use crate::macros::try_vec;
+
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+// EBML header
+pub(super) const EBML_HEADER: u32 = 0x1A45_DFA3;
+pub(super) const DOC_TYPE: u32 = 0x4282;
+
+// Top level
+pub(super) const SEGMENT: u32 = 0x1853_8067;
+
+// \Segment
+pub(super) const INFO: u32 = 0x1549_A966;
+pub(super) const TRACKS: u32 = 0x1654_AE6B;
+pub(super) const CLUSTER: u32 = 0x1F43_B675;
+#[cfg(feature = "matroska_tags")]
+pub(super) const TAGS: u32 = 0x1254_C367;
+
+// \Segment\Info
+pub(super) const TIMECODE_SCALE: u32 = 0x2A_D7B1;
+pub(super) const DURATION: u32 = 0x4489;
+
+// \Segment\Tracks
+pub(super) const TRACK_ENTRY: u32 = 0xAE;
+pub(super) const TRACK_TYPE: u32 = 0x83;
+pub(super) const AUDIO: u32 = 0xE1;
+pub(super) const SAMPLING_FREQUENCY: u32 = 0xB5;
+pub(super) const OUTPUT_SAMPLING_FREQUENCY: u32 = 0x78B5;
+pub(super) const CHANNELS: u32 = 0x9F;
+pub(super) const BIT_DEPTH: u32 = 0x6264;
+
+// \Segment\Tags
+#[cfg(feature = "matroska_tags")]
+pub(super) const TAG: u32 = 0x7373;
+#[cfg(feature = "matroska_tags")]
+pub(super) const TARGETS: u32 = 0x63C0;
+#[cfg(feature = "matroska_tags")]
+pub(super) const TARGET_TYPE_VALUE: u32 = 0x68CA;
+#[cfg(feature = "matroska_tags")]
+pub(super) const SIMPLE_TAG: u32 = 0x67C8;
+#[cfg(feature = "matroska_tags")]
+pub(super) const TAG_NAME: u32 = 0x45A3;
+#[cfg(feature = "matroska_tags")]
+pub(super) const TAG_LANGUAGE: u32 = 0x447A;
+#[cfg(feature = "matroska_tags")]
+pub(super) const TAG_STRING: u32 = 0x4487;
+
+// The largest size we'll read into memory for a single (non-master) element
+const MAX_VALUE_SIZE: u64 = 16_777_216;
+
+pub(super) struct ElementHeader {
+	pub(super) id: u32,
+	// `None` if the element has an unknown size
+	pub(super) size: Option<u64>,
+}
+
+fn bad_element(message: &'static str) -> FileDecodingError {
+	FileDecodingError::new(FileType::Mka, message)
+}
+
+// IDs and sizes are stored as variable length integers, with the number of leading zeros
+// in the first byte determining the length
+pub(super) fn read_element_header<R>(reader: &mut R) -> Result<ElementHeader>
+where
+	R: Read,
+{
+	let first = reader.read_u8()?;
+	let id_len = first.leading_zeros() + 1;
+
+	// IDs are limited to 4 bytes (EBMLMaxIDLength)
+	if id_len > 4 {
+		return Err(bad_element("Found an invalid element ID").into());
+	}
+
+	// The marker bit is kept as part of the ID
+	let mut id = u32::from(first);
+	for _ in 1..id_len {
+		id = (id << 8) | u32::from(reader.read_u8()?);
+	}
+
+	let first = reader.read_u8()?;
+	let size_len = first.leading_zeros() + 1;
+
+	// Sizes are limited to 8 bytes (EBMLMaxSizeLength)
+	if size_len > 8 {
+		return Err(bad_element("Found an invalid element size").into());
+	}
+
+	// The marker bit is removed from sizes
+	let mut size = u64::from(first) & (0xFF >> size_len);
+	for _ in 1..size_len {
+		size = (size << 8) | u64::from(reader.read_u8()?);
+	}
+
+	// A size with all of its bits set is reserved to mean "unknown"
+	let unknown = size == (1 << (7 * size_len)) - 1;
+
+	Ok(ElementHeader {
+		id,
+		size: (!unknown).then(|| size),
+	})
+}
+
+/// Calls `f` for every child of a master element ending at `end`
+///
+/// The reader is moved to the end of each child after `f` returns.
+pub(super) fn read_children<R, F>(reader: &mut R, end: u64, mut f: F) -> Result<()>
+where
+	R: Read + Seek,
+	F: FnMut(&mut R, u32, u64) -> Result<()>,
+{
+	while reader.stream_position()? < end {
+		let header = read_element_header(reader)?;
+
+		let size = match header.size {
+			Some(size) => size,
+			None => return Err(bad_element("Found a child element with an unknown size").into()),
+		};
+
+		let child_end = reader.stream_position()? + size;
+		if child_end > end {
+			return Err(bad_element("Found a child element that extends past its parent").into());
+		}
+
+		f(reader, header.id, child_end)?;
+		reader.seek(SeekFrom::Start(child_end))?;
+	}
+
+	Ok(())
+}
+
+pub(super) fn read_uint<R>(reader: &mut R, size: u64) -> Result<u64>
+where
+	R: Read,
+{
+	if size > 8 {
+		return Err(bad_element("Found an unsigned integer larger than 8 bytes").into());
+	}
+
+	let mut value = 0;
+	for _ in 0..size {
+		value = (value << 8) | u64::from(reader.read_u8()?);
+	}
+
+	Ok(value)
+}
+
+pub(super) fn read_float<R>(reader: &mut R, size: u64) -> Result<f64>
+where
+	R: Read,
+{
+	match size {
+		0 => Ok(0.0),
+		4 => Ok(f64::from(reader.read_f32::<BigEndian>()?)),
+		8 => Ok(reader.read_f64::<BigEndian>()?),
+		_ => Err(bad_element("Found a float that isn't 4 or 8 bytes").into()),
+	}
+}
+
+// Strings may be padded with trailing null bytes
+pub(super) fn read_string<R>(reader: &mut R, size: u64) -> Result<String>
+where
+	R: Read,
+{
+	if size > MAX_VALUE_SIZE {
+		return Err(bad_element("Found a string element that is too large").into());
+	}
+
+	let mut content = try_vec![0; size as usize];
+	reader.read_exact(&mut content)?;
+
+	if let Some(end) = content.iter().position(|b| *b == 0) {
+		content.truncate(end);
+	}
+
+	Ok(String::from_utf8_lossy(&content).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{read_element_header, read_float, read_uint};
+
+	#[test]
+	fn element_header() {
+		// A 4 byte ID with a 1 byte size
+		let header = read_element_header(&mut &[0x1A, 0x45, 0xDF, 0xA3, 0x9F][..]).unwrap();
+		assert_eq!(header.id, 0x1A45_DFA3);
+		assert_eq!(header.size, Some(31));
+
+		// A 1 byte ID with a 2 byte size
+		let header = read_element_header(&mut &[0xAE, 0x41, 0x00][..]).unwrap();
+		assert_eq!(header.id, 0xAE);
+		assert_eq!(header.size, Some(256));
+
+		// An unknown size
+		let header = read_element_header(
+			&mut &[
+				0x18, 0x53, 0x80, 0x67, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+			][..],
+		)
+		.unwrap();
+		assert_eq!(header.id, 0x1853_8067);
+		assert_eq!(header.size, None);
+
+		// IDs can't be longer than 4 bytes
+		assert!(read_element_header(&mut &[0x08, 0, 0, 0, 0, 0x81][..]).is_err());
+	}
+
+	#[test]
+	fn values() {
+		assert_eq!(
+			read_uint(&mut &[0x0F, 0x42, 0x40][..], 3).unwrap(),
+			1_000_000
+		);
+		assert_eq!(read_uint(&mut &[][..], 0).unwrap(), 0);
+
+		assert_eq!(
+			read_float(&mut &44100.0_f32.to_be_bytes()[..], 4).unwrap(),
+			44100.0
+		);
+		assert_eq!(
+			read_float(&mut &1000.5_f64.to_be_bytes()[..], 8).unwrap(),
+			1000.5
+		);
+		assert!(read_float(&mut &[0; 3][..], 3).is_err());
+	}
+}
@@ -0,0 +1,37 @@
+//! Matroska specific items
+//!
+//! ## File notes
+//!
+//! This covers both Matroska (`.mka`, `.mkv`) and WebM files.
+//!
+//! Reading is limited to the elements needed for the audio properties and tags:
+//!
+//! * `\Segment\Info` for the duration
+//! * `\Segment\Tracks` for the sample rate, channels, and bit depth of the first audio track
+//! * `\Segment\Tags` for the tags
+//!
+//! Matroska tags are **read only**.
+mod ebml;
+mod properties;
+mod read;
+#[cfg(feature = "matroska_tags")]
+pub(crate) mod tag;
+
+pub use properties::MkaProperties;
+#[cfg(feature = "matroska_tags")]
+pub use tag::{MatroskaTag, SimpleTag, TargetType};
+
+use lofty_attr::LoftyFile;
+
+/// A Matroska file
+#[derive(LoftyFile, Default)]
+#[lofty(read_fn = "read::read_from")]
+#[lofty(file_type = "Mka")]
+pub struct MkaFile {
+	/// A Matroska tag, made up of every `\Segment\Tags\Tag`
+	#[cfg(feature = "matroska_tags")]
+	#[lofty(tag_type = "Matroska")]
+	pub(crate) matroska_tag: Option<MatroskaTag>,
+	/// The file's audio properties
+	pub(crate) properties: MkaProperties,
+}
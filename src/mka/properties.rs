@@ -0,0 +1,225 @@
+use super::ebml::{
+	read_children, read_float, read_uint, AUDIO, BIT_DEPTH, CHANNELS, DURATION,
+	OUTPUT_SAMPLING_FREQUENCY, SAMPLING_FREQUENCY, TIMECODE_SCALE, TRACK_ENTRY, TRACK_TYPE,
+};
+use crate::error::Result;
+use crate::properties::FileProperties;
+
+use std::io::{Read, Seek};
+use std::time::Duration;
+
+// The `TrackType` of audio tracks
+const TRACK_TYPE_AUDIO: u64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+/// A Matroska file's audio properties
+pub struct MkaProperties {
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) bit_depth: Option<u8>,
+	pub(crate) channels: u8,
+}
+
+impl From<MkaProperties> for FileProperties {
+	fn from(input: MkaProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: input.bit_depth,
+			channels: Some(input.channels),
+		}
+	}
+}
+
+impl MkaProperties {
+	/// Duration
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	///
+	/// This is calculated from the size of every cluster in the segment, so it will
+	/// include any non-audio tracks.
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Bits per sample, if specified
+	pub fn bit_depth(&self) -> Option<u8> {
+		self.bit_depth
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+}
+
+// The relevant parts of \Segment\Info
+pub(super) struct SegmentInfo {
+	timecode_scale: u64,
+	// In units of `timecode_scale` nanoseconds
+	duration: Option<f64>,
+}
+
+impl Default for SegmentInfo {
+	fn default() -> Self {
+		Self {
+			timecode_scale: 1_000_000,
+			duration: None,
+		}
+	}
+}
+
+impl SegmentInfo {
+	pub(super) fn read<R>(reader: &mut R, end: u64) -> Result<Self>
+	where
+		R: Read + Seek,
+	{
+		let mut info = Self::default();
+
+		read_children(reader, end, |reader, id, child_end| {
+			let size = child_end - reader.stream_position()?;
+
+			match id {
+				TIMECODE_SCALE => info.timecode_scale = read_uint(reader, size)?,
+				DURATION => info.duration = Some(read_float(reader, size)?),
+				_ => {},
+			}
+
+			Ok(())
+		})?;
+
+		Ok(info)
+	}
+}
+
+// The relevant parts of an audio \Segment\Tracks\TrackEntry
+#[derive(Default)]
+pub(super) struct AudioTrack {
+	sample_rate: f64,
+	output_sample_rate: Option<f64>,
+	channels: u64,
+	bit_depth: Option<u64>,
+}
+
+impl AudioTrack {
+	// Finds the first audio track in \Segment\Tracks
+	pub(super) fn read<R>(reader: &mut R, end: u64) -> Result<Option<Self>>
+	where
+		R: Read + Seek,
+	{
+		let mut audio_track = None;
+
+		read_children(reader, end, |reader, id, child_end| {
+			if id == TRACK_ENTRY && audio_track.is_none() {
+				audio_track = Self::read_track_entry(reader, child_end)?;
+			}
+
+			Ok(())
+		})?;
+
+		Ok(audio_track)
+	}
+
+	fn read_track_entry<R>(reader: &mut R, end: u64) -> Result<Option<Self>>
+	where
+		R: Read + Seek,
+	{
+		let mut track_type = None;
+		let mut track = Self {
+			sample_rate: 8000.0,
+			channels: 1,
+			..Self::default()
+		};
+
+		read_children(reader, end, |reader, id, child_end| {
+			let size = child_end - reader.stream_position()?;
+
+			match id {
+				TRACK_TYPE => track_type = Some(read_uint(reader, size)?),
+				AUDIO => {
+					read_children(reader, child_end, |reader, id, child_end| {
+						let size = child_end - reader.stream_position()?;
+
+						match id {
+							SAMPLING_FREQUENCY => track.sample_rate = read_float(reader, size)?,
+							OUTPUT_SAMPLING_FREQUENCY => {
+								track.output_sample_rate = Some(read_float(reader, size)?)
+							},
+							CHANNELS => track.channels = read_uint(reader, size)?,
+							BIT_DEPTH => track.bit_depth = Some(read_uint(reader, size)?),
+							_ => {},
+						}
+
+						Ok(())
+					})?;
+				},
+				_ => {},
+			}
+
+			Ok(())
+		})?;
+
+		Ok((track_type == Some(TRACK_TYPE_AUDIO)).then(|| track))
+	}
+}
+
+#[allow(
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss,
+	clippy::cast_precision_loss
+)]
+pub(super) fn read_properties(
+	info: &SegmentInfo,
+	audio_track: Option<&AudioTrack>,
+	cluster_size: u64,
+	file_length: u64,
+) -> MkaProperties {
+	let mut properties = MkaProperties::default();
+
+	if let Some(duration) = info.duration {
+		let duration_ns = duration * info.timecode_scale as f64;
+
+		if duration_ns.is_finite() && duration_ns > 0.0 {
+			properties.duration = Duration::from_nanos(duration_ns as u64);
+		}
+	}
+
+	let length = properties.duration.as_millis() as u64;
+	if length > 0 {
+		properties.overall_bitrate = ((file_length * 8) / length) as u32;
+		properties.audio_bitrate = ((cluster_size * 8) / length) as u32;
+	}
+
+	if let Some(track) = audio_track {
+		// The output sample rate differs in cases such as HE-AAC, where it's double the sampling frequency
+		let sample_rate = track.output_sample_rate.unwrap_or(track.sample_rate);
+		if sample_rate.is_finite() && sample_rate > 0.0 {
+			properties.sample_rate = sample_rate.round() as u32;
+		}
+
+		properties.channels = u8::try_from(track.channels).unwrap_or(u8::MAX);
+		properties.bit_depth = track
+			.bit_depth
+			.map(|bit_depth| u8::try_from(bit_depth).unwrap_or(u8::MAX));
+	}
+
+	properties
+}
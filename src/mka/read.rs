@@ -0,0 +1,205 @@
+use super::ebml::{
+	read_children, read_element_header, read_string, CLUSTER, DOC_TYPE, EBML_HEADER, INFO, SEGMENT,
+	TRACKS,
+};
+#[cfg(feature = "matroska_tags")]
+use super::ebml::{
+	read_uint, SIMPLE_TAG, TAG, TAGS, TAG_LANGUAGE, TAG_NAME, TAG_STRING, TARGETS,
+	TARGET_TYPE_VALUE,
+};
+use super::properties::{AudioTrack, MkaProperties, SegmentInfo};
+#[cfg(feature = "matroska_tags")]
+use super::tag::{MatroskaTag, SimpleTag, TargetType};
+use super::MkaFile;
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+use crate::macros::err;
+
+use std::io::{Read, Seek, SeekFrom};
+
+pub(super) fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<MkaFile>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+	let file_end = reader.seek(SeekFrom::End(0))?;
+	reader.seek(SeekFrom::Start(start))?;
+
+	let ebml_header = read_element_header(reader)?;
+	if ebml_header.id != EBML_HEADER {
+		err!(UnknownFormat);
+	}
+
+	let ebml_header_end = match ebml_header.size {
+		Some(size) => reader.stream_position()? + size,
+		None => {
+			return Err(
+				FileDecodingError::new(FileType::Mka, "EBML header has an unknown size").into(),
+			)
+		},
+	};
+
+	let mut doc_type = None;
+	read_children(reader, ebml_header_end, |reader, id, child_end| {
+		if id == DOC_TYPE {
+			let size = child_end - reader.stream_position()?;
+			doc_type = Some(read_string(reader, size)?);
+		}
+
+		Ok(())
+	})?;
+
+	if !matches!(doc_type.as_deref(), Some("matroska" | "webm")) {
+		return Err(FileDecodingError::new(FileType::Mka, "Unsupported EBML document type").into());
+	}
+
+	let segment = read_element_header(reader)?;
+	if segment.id != SEGMENT {
+		return Err(FileDecodingError::new(FileType::Mka, "Expected a \"Segment\" element").into());
+	}
+
+	// An unknown size means the segment continues to the end of the file
+	let segment_end = segment
+		.size
+		.map_or(file_end, |size| {
+			reader.stream_position().map_or(file_end, |pos| pos + size)
+		})
+		.min(file_end);
+
+	let mut file = MkaFile::default();
+
+	let mut info = SegmentInfo::default();
+	let mut audio_track = None;
+	let mut cluster_size = 0;
+	#[cfg(feature = "matroska_tags")]
+	let mut matroska_tag = MatroskaTag::default();
+
+	// The segment's children are read individually, since clusters may have an unknown size
+	while reader.stream_position()? < segment_end {
+		let element = read_element_header(reader)?;
+
+		// Unknown sized elements (usually clusters in live streams) can only be skipped
+		// by parsing their children, so we stop here
+		let size = match element.size {
+			Some(size) => size,
+			None => break,
+		};
+
+		let element_end = reader.stream_position()? + size;
+
+		match element.id {
+			INFO if read_properties => info = SegmentInfo::read(reader, element_end)?,
+			TRACKS if read_properties && audio_track.is_none() => {
+				audio_track = AudioTrack::read(reader, element_end)?
+			},
+			CLUSTER => cluster_size += size,
+			#[cfg(feature = "matroska_tags")]
+			TAGS => read_tags(reader, element_end, &mut matroska_tag)?,
+			_ => {},
+		}
+
+		reader.seek(SeekFrom::Start(element_end))?;
+	}
+
+	#[cfg(feature = "matroska_tags")]
+	if !matroska_tag.simple_tags.is_empty() {
+		file.matroska_tag = Some(matroska_tag);
+	}
+
+	file.properties = if read_properties {
+		super::properties::read_properties(
+			&info,
+			audio_track.as_ref(),
+			cluster_size,
+			file_end - start,
+		)
+	} else {
+		MkaProperties::default()
+	};
+
+	Ok(file)
+}
+
+// Reads every `Tag` in \Segment\Tags
+#[cfg(feature = "matroska_tags")]
+fn read_tags<R>(reader: &mut R, end: u64, matroska_tag: &mut MatroskaTag) -> Result<()>
+where
+	R: Read + Seek,
+{
+	read_children(reader, end, |reader, id, tag_end| {
+		if id != TAG {
+			return Ok(());
+		}
+
+		let mut target_type_value = TargetType::Album as u64;
+		let mut simple_tags = Vec::new();
+
+		read_children(reader, tag_end, |reader, id, child_end| {
+			match id {
+				TARGETS => {
+					read_children(reader, child_end, |reader, id, child_end| {
+						if id == TARGET_TYPE_VALUE {
+							let size = child_end - reader.stream_position()?;
+							target_type_value = read_uint(reader, size)?;
+						}
+
+						Ok(())
+					})?;
+				},
+				SIMPLE_TAG => {
+					if let Some(simple_tag) = read_simple_tag(reader, child_end)? {
+						simple_tags.push(simple_tag);
+					}
+				},
+				_ => {},
+			}
+
+			Ok(())
+		})?;
+
+		// The `Targets` element isn't required to come first
+		if let Some(target_type) = TargetType::from_value(target_type_value) {
+			for (name, language, value) in simple_tags {
+				matroska_tag.simple_tags.push(SimpleTag {
+					target_type,
+					name,
+					language,
+					value,
+				});
+			}
+		}
+
+		Ok(())
+	})
+}
+
+// Returns the name, language, and value of a `SimpleTag`
+//
+// This will return `None` for binary and empty tags
+#[cfg(feature = "matroska_tags")]
+fn read_simple_tag<R>(reader: &mut R, end: u64) -> Result<Option<(String, String, String)>>
+where
+	R: Read + Seek,
+{
+	let mut name = None;
+	let mut language = String::from("und");
+	let mut value = None;
+
+	read_children(reader, end, |reader, id, child_end| {
+		let size = child_end - reader.stream_position()?;
+
+		match id {
+			TAG_NAME => name = Some(read_string(reader, size)?),
+			TAG_LANGUAGE => language = read_string(reader, size)?,
+			TAG_STRING => value = Some(read_string(reader, size)?),
+			_ => {},
+		}
+
+		Ok(())
+	})?;
+
+	match (name, value) {
+		(Some(name), Some(value)) if !name.is_empty() => Ok(Some((name, language, value))),
+		_ => Ok(None),
+	}
+}
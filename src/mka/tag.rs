@@ -0,0 +1,363 @@
+use crate::error::LoftyError;
+use crate::macros::err;
+use crate::tag::item::{ItemKey, ItemValue, TagItem};
+use crate::tag::{Tag, TagType};
+use crate::traits::{Accessor, TagExt};
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+macro_rules! impl_accessor {
+	($($name:ident => ($target_type:ident, $key:literal);)+) => {
+		paste::paste! {
+			$(
+				fn $name(&self) -> Option<&str> {
+					self.get(TargetType::$target_type, $key)
+				}
+
+				fn [<set_ $name>](&mut self, value: String) {
+					self.insert(SimpleTag::new(TargetType::$target_type, String::from($key), value))
+				}
+
+				fn [<remove_ $name>](&mut self) {
+					self.remove(TargetType::$target_type, $key)
+				}
+			)+
+		}
+	}
+}
+
+/// The logical level a [`SimpleTag`] applies to
+///
+/// This is stored in the file as a `TargetTypeValue`. When it isn't specified, the
+/// default is [`TargetType::Album`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+#[allow(missing_docs)]
+pub enum TargetType {
+	Shot = 10,
+	Subtrack = 20,
+	Track = 30,
+	Part = 40,
+	Album = 50,
+	Edition = 60,
+	Collection = 70,
+}
+
+impl TargetType {
+	/// Get a `TargetType` from a `TargetTypeValue`
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::mka::TargetType;
+	///
+	/// assert_eq!(TargetType::from_value(30), Some(TargetType::Track));
+	/// assert_eq!(TargetType::from_value(31), None);
+	/// ```
+	pub fn from_value(value: u64) -> Option<Self> {
+		match value {
+			10 => Some(Self::Shot),
+			20 => Some(Self::Subtrack),
+			30 => Some(Self::Track),
+			40 => Some(Self::Part),
+			50 => Some(Self::Album),
+			60 => Some(Self::Edition),
+			70 => Some(Self::Collection),
+			_ => None,
+		}
+	}
+}
+
+/// A single Matroska tag, stored as a `SimpleTag` element
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SimpleTag {
+	pub(crate) target_type: TargetType,
+	pub(crate) name: String,
+	pub(crate) language: String,
+	pub(crate) value: String,
+}
+
+impl SimpleTag {
+	/// Create a new `SimpleTag`
+	///
+	/// The language will be `und` (undetermined).
+	pub fn new(target_type: TargetType, name: String, value: String) -> Self {
+		Self {
+			target_type,
+			name,
+			language: String::from("und"),
+			value,
+		}
+	}
+
+	/// Returns the [`TargetType`]
+	pub fn target_type(&self) -> TargetType {
+		self.target_type
+	}
+
+	/// Returns the tag name (Ex. `TITLE`)
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Returns the language of the value
+	pub fn language(&self) -> &str {
+		&self.language
+	}
+
+	/// Returns the value
+	pub fn value(&self) -> &str {
+		&self.value
+	}
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone)]
+/// A Matroska tag
+///
+/// This is a flattened view of every `SimpleTag` in `\Segment\Tags`, along with the
+/// [`TargetType`] of the `Tag` containing it. Nested `SimpleTag`s and binary values are not
+/// supported.
+///
+/// NOTE: This tag is **read only**
+///
+/// ## Supported file types
+///
+/// * [`FileType::Mka`](crate::FileType::Mka)
+///
+/// ## Conversions
+///
+/// ### To `Tag`
+///
+/// The meaning of `TITLE` and `ARTIST` depends on the target type. At [`TargetType::Album`]
+/// and above, they are converted to [`ItemKey::AlbumTitle`] and [`ItemKey::AlbumArtist`].
+/// Below that, they are converted to [`ItemKey::TrackTitle`] and [`ItemKey::TrackArtist`].
+///
+/// Similarly, `PART_NUMBER` is the [`ItemKey::TrackNumber`] at [`TargetType::Track`], and the
+/// [`ItemKey::DiscNumber`] at [`TargetType::Part`]. `TOTAL_PARTS` is the [`ItemKey::TrackTotal`]
+/// at [`TargetType::Album`].
+///
+/// All other names are converted regardless of their target type.
+pub struct MatroskaTag {
+	pub(crate) simple_tags: Vec<SimpleTag>,
+}
+
+impl MatroskaTag {
+	/// Returns all of the [`SimpleTag`]s
+	pub fn simple_tags(&self) -> &[SimpleTag] {
+		&self.simple_tags
+	}
+
+	/// Gets the value of the first [`SimpleTag`] with a matching target type and name
+	///
+	/// NOTE: The name is case-insensitive
+	pub fn get(&self, target_type: TargetType, name: &str) -> Option<&str> {
+		self.simple_tags
+			.iter()
+			.find(|tag| tag.target_type == target_type && tag.name.eq_ignore_ascii_case(name))
+			.map(SimpleTag::value)
+	}
+
+	/// Inserts a [`SimpleTag`]
+	///
+	/// This will replace any [`SimpleTag`] with the same target type and name.
+	pub fn insert(&mut self, simple_tag: SimpleTag) {
+		self.remove(simple_tag.target_type, &simple_tag.name);
+		self.simple_tags.push(simple_tag);
+	}
+
+	/// Removes all [`SimpleTag`]s with a matching target type and name
+	///
+	/// NOTE: The name is case-insensitive
+	pub fn remove(&mut self, target_type: TargetType, name: &str) {
+		self.simple_tags
+			.retain(|tag| tag.target_type != target_type || !tag.name.eq_ignore_ascii_case(name));
+	}
+}
+
+impl Accessor for MatroskaTag {
+	impl_accessor!(
+		artist       => (Track, "ARTIST");
+		title        => (Track, "TITLE");
+		album        => (Album, "TITLE");
+		album_artist => (Album, "ARTIST");
+		genre        => (Track, "GENRE");
+		comment      => (Track, "COMMENT");
+		composer     => (Track, "COMPOSER");
+		encoder      => (Track, "ENCODER");
+	);
+}
+
+impl TagExt for MatroskaTag {
+	type Err = LoftyError;
+
+	fn is_empty(&self) -> bool {
+		self.simple_tags.is_empty()
+	}
+
+	/// Writing Matroska tags is not supported
+	///
+	/// # Errors
+	///
+	/// This will always error with [`ErrorKind::UnsupportedTag`](crate::error::ErrorKind::UnsupportedTag)
+	fn save_to_path<P: AsRef<Path>>(&self, _path: P) -> std::result::Result<bool, Self::Err> {
+		err!(UnsupportedTag)
+	}
+
+	/// Writing Matroska tags is not supported
+	///
+	/// # Errors
+	///
+	/// This will always error with [`ErrorKind::UnsupportedTag`](crate::error::ErrorKind::UnsupportedTag)
+	fn save_to(&self, _file: &mut File) -> std::result::Result<bool, Self::Err> {
+		err!(UnsupportedTag)
+	}
+
+	/// Writing Matroska tags is not supported
+	///
+	/// # Errors
+	///
+	/// This will always error with [`ErrorKind::UnsupportedTag`](crate::error::ErrorKind::UnsupportedTag)
+	fn dump_to<W: Write>(&self, _writer: &mut W) -> std::result::Result<(), Self::Err> {
+		err!(UnsupportedTag)
+	}
+
+	fn remove_from_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+		TagType::Matroska.remove_from_path(path)
+	}
+
+	fn remove_from(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+		TagType::Matroska.remove_from(file)
+	}
+
+	fn clear(&mut self) {
+		self.simple_tags.clear();
+	}
+}
+
+// Items that aren't stored at `TargetType::Track`, so they can't be in `MATROSKA_MAP`
+pub(crate) const TARGETED_ITEMKEYS: [ItemKey; 4] = [
+	ItemKey::AlbumTitle,
+	ItemKey::AlbumArtist,
+	ItemKey::TrackTotal,
+	ItemKey::DiscNumber,
+];
+
+impl From<MatroskaTag> for Tag {
+	fn from(input: MatroskaTag) -> Self {
+		let mut tag = Tag::new(TagType::Matroska);
+
+		for simple_tag in input.simple_tags {
+			let name = simple_tag.name.to_ascii_uppercase();
+			let item_key = match (simple_tag.target_type, name.as_str()) {
+				(TargetType::Album, "TITLE") => ItemKey::AlbumTitle,
+				(TargetType::Album, "ARTIST") => ItemKey::AlbumArtist,
+				(TargetType::Album, "TOTAL_PARTS") => ItemKey::TrackTotal,
+				(TargetType::Part, "PART_NUMBER") => ItemKey::DiscNumber,
+				(TargetType::Track, _) => ItemKey::from_key(TagType::Matroska, &simple_tag.name),
+				// At any other level, these describe something other than the track, album, or disc,
+				// and there are no keys for them
+				(_, "TITLE" | "ARTIST" | "PART_NUMBER" | "TOTAL_PARTS") => continue,
+				_ => ItemKey::from_key(TagType::Matroska, &simple_tag.name),
+			};
+
+			tag.items
+				.push(TagItem::new(item_key, ItemValue::Text(simple_tag.value)));
+		}
+
+		tag
+	}
+}
+
+impl From<Tag> for MatroskaTag {
+	fn from(input: Tag) -> Self {
+		let mut matroska_tag = Self::default();
+
+		for item in input.items {
+			let value = match item.item_value {
+				ItemValue::Text(value) | ItemValue::Locator(value) => value,
+				ItemValue::Binary(_) => continue,
+			};
+
+			let (target_type, name) = match item.item_key {
+				ItemKey::AlbumTitle => (TargetType::Album, "TITLE"),
+				ItemKey::AlbumArtist => (TargetType::Album, "ARTIST"),
+				ItemKey::TrackTotal => (TargetType::Album, "TOTAL_PARTS"),
+				ItemKey::DiscNumber => (TargetType::Part, "PART_NUMBER"),
+				ref key => match key.map_key(TagType::Matroska, true) {
+					Some(name) => (TargetType::Track, name),
+					None => continue,
+				},
+			};
+
+			matroska_tag
+				.simple_tags
+				.push(SimpleTag::new(target_type, name.to_string(), value));
+		}
+
+		matroska_tag
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{MatroskaTag, SimpleTag, TargetType};
+	use crate::{Accessor, ItemKey, Tag, TagType};
+
+	fn simple_tag(target_type: TargetType, name: &str, value: &str) -> SimpleTag {
+		SimpleTag::new(target_type, name.to_string(), value.to_string())
+	}
+
+	#[test]
+	fn matroska_to_tag() {
+		let matroska_tag = MatroskaTag {
+			simple_tags: vec![
+				simple_tag(TargetType::Collection, "TITLE", "Foo collection"),
+				simple_tag(TargetType::Edition, "PART_NUMBER", "4"),
+				simple_tag(TargetType::Album, "TITLE", "Foo album"),
+				simple_tag(TargetType::Album, "ARTIST", "Bar artist"),
+				simple_tag(TargetType::Album, "TOTAL_PARTS", "12"),
+				simple_tag(TargetType::Part, "PART_NUMBER", "2"),
+				simple_tag(TargetType::Track, "TITLE", "Foo title"),
+				simple_tag(TargetType::Track, "ARTIST", "Baz artist"),
+				simple_tag(TargetType::Track, "PART_NUMBER", "3"),
+				simple_tag(TargetType::Album, "GENRE", "Classical"),
+				simple_tag(TargetType::Track, "NARRATED_BY", "Qux"),
+			],
+		};
+
+		let tag: Tag = matroska_tag.into();
+
+		assert_eq!(tag.get_string(&ItemKey::AlbumTitle), Some("Foo album"));
+		assert_eq!(tag.get_string(&ItemKey::AlbumArtist), Some("Bar artist"));
+		assert_eq!(tag.get_string(&ItemKey::TrackTotal), Some("12"));
+		assert_eq!(tag.get_string(&ItemKey::DiscNumber), Some("2"));
+		assert_eq!(tag.get_string(&ItemKey::TrackTitle), Some("Foo title"));
+		assert_eq!(tag.get_string(&ItemKey::TrackArtist), Some("Baz artist"));
+		assert_eq!(tag.get_string(&ItemKey::TrackNumber), Some("3"));
+		assert_eq!(tag.get_string(&ItemKey::Genre), Some("Classical"));
+		assert_eq!(
+			tag.get_string(&ItemKey::Unknown(String::from("NARRATED_BY"))),
+			Some("Qux")
+		);
+
+		// The collection and edition level items have no equivalent
+		assert_eq!(tag.item_count(), 9);
+	}
+
+	#[test]
+	fn tag_to_matroska() {
+		let mut tag = Tag::new(TagType::Matroska);
+		tag.set_title(String::from("Foo title"));
+		tag.set_album(String::from("Foo album"));
+		tag.set_genre(String::from("Classical"));
+
+		let matroska_tag: MatroskaTag = tag.into();
+
+		assert_eq!(matroska_tag.title(), Some("Foo title"));
+		assert_eq!(matroska_tag.album(), Some("Foo album"));
+		assert_eq!(matroska_tag.genre(), Some("Classical"));
+		assert_eq!(matroska_tag.get(TargetType::Album, "GENRE"), None);
+	}
+}
@@ -232,3 +232,71 @@ impl From<u8> for AdvisoryRating {
 		}
 	}
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// The iTunes media type, stored in the `stik` atom
+pub enum MediaType {
+	/// A media type of 0
+	Movie,
+	/// A media type of 1, used for music
+	Normal,
+	/// A media type of 2
+	Audiobook,
+	/// A media type of 5
+	WhackedBookmark,
+	/// A media type of 6
+	MusicVideo,
+	/// A media type of 9
+	ShortFilm,
+	/// A media type of 10
+	TvShow,
+	/// A media type of 11
+	Booklet,
+	/// A media type of 14
+	Ringtone,
+	/// A media type of 21
+	Podcast,
+	/// A media type of 23
+	ITunesU,
+	/// Any other media type
+	Other(u8),
+}
+
+impl MediaType {
+	/// Returns the media type as it appears in the `stik` atom
+	pub fn as_u8(&self) -> u8 {
+		match self {
+			MediaType::Movie => 0,
+			MediaType::Normal => 1,
+			MediaType::Audiobook => 2,
+			MediaType::WhackedBookmark => 5,
+			MediaType::MusicVideo => 6,
+			MediaType::ShortFilm => 9,
+			MediaType::TvShow => 10,
+			MediaType::Booklet => 11,
+			MediaType::Ringtone => 14,
+			MediaType::Podcast => 21,
+			MediaType::ITunesU => 23,
+			MediaType::Other(other) => *other,
+		}
+	}
+}
+
+impl From<u8> for MediaType {
+	fn from(input: u8) -> Self {
+		match input {
+			0 => Self::Movie,
+			1 => Self::Normal,
+			2 => Self::Audiobook,
+			5 => Self::WhackedBookmark,
+			6 => Self::MusicVideo,
+			9 => Self::ShortFilm,
+			10 => Self::TvShow,
+			11 => Self::Booklet,
+			14 => Self::Ringtone,
+			21 => Self::Podcast,
+			23 => Self::ITunesU,
+			other => Self::Other(other),
+		}
+	}
+}
@@ -6,12 +6,16 @@ pub(crate) mod write;
 
 use super::AtomIdent;
 use crate::error::LoftyError;
+use crate::id3::v1::constants::GENRES;
 use crate::mp4::ilst::atom::AtomDataStorage;
 use crate::picture::{Picture, PictureType};
+use crate::tag::gapless::GaplessInfo;
 use crate::tag::item::{ItemKey, ItemValue, TagItem};
+use crate::tag::utils::parse_bpm;
 use crate::tag::{Tag, TagType};
 use crate::traits::{Accessor, TagExt};
-use atom::{AdvisoryRating, Atom, AtomData};
+use atom::{AdvisoryRating, Atom, AtomData, MediaType};
+use constants::{BE_SIGNED_INTEGER, RESERVED};
 use r#ref::AtomIdentRef;
 
 use std::fs::{File, OpenOptions};
@@ -22,7 +26,28 @@ const ARTIST: AtomIdent = AtomIdent::Fourcc(*b"\xa9ART");
 const TITLE: AtomIdent = AtomIdent::Fourcc(*b"\xa9nam");
 const ALBUM: AtomIdent = AtomIdent::Fourcc(*b"\xa9alb");
 const GENRE: AtomIdent = AtomIdent::Fourcc(*b"\xa9gen");
+const GENRE_ID: AtomIdent = AtomIdent::Fourcc(*b"gnre");
 const COMMENT: AtomIdent = AtomIdent::Fourcc(*b"\xa9cmt");
+const ALBUM_ARTIST: AtomIdent = AtomIdent::Fourcc(*b"aART");
+const COMPOSER: AtomIdent = AtomIdent::Fourcc(*b"\xa9wrt");
+const BPM: AtomIdent = AtomIdent::Fourcc(*b"tmpo");
+const ENCODER: AtomIdent = AtomIdent::Fourcc(*b"\xa9too");
+const TITLE_SORT: AtomIdent = AtomIdent::Fourcc(*b"sonm");
+const ARTIST_SORT: AtomIdent = AtomIdent::Fourcc(*b"soar");
+const ALBUM_SORT: AtomIdent = AtomIdent::Fourcc(*b"soal");
+const ALBUM_ARTIST_SORT: AtomIdent = AtomIdent::Fourcc(*b"soaa");
+const COMPILATION: AtomIdent = AtomIdent::Fourcc(*b"cpil");
+const PODCAST: AtomIdent = AtomIdent::Fourcc(*b"pcst");
+const GAPLESS_PLAYBACK: AtomIdent = AtomIdent::Fourcc(*b"pgap");
+const MEDIA_TYPE: AtomIdent = AtomIdent::Fourcc(*b"stik");
+const MOVEMENT_NUMBER: AtomIdent = AtomIdent::Fourcc(*b"\xa9mvi");
+const MOVEMENT_COUNT: AtomIdent = AtomIdent::Fourcc(*b"\xa9mvc");
+const DESCRIPTION: AtomIdent = AtomIdent::Fourcc(*b"desc");
+const LONG_DESCRIPTION: AtomIdent = AtomIdent::Fourcc(*b"ldes");
+const KEYWORDS: AtomIdent = AtomIdent::Fourcc(*b"keyw");
+const CATEGORY: AtomIdent = AtomIdent::Fourcc(*b"catg");
+const PODCAST_URL: AtomIdent = AtomIdent::Fourcc(*b"purl");
+const EPISODE_GUID: AtomIdent = AtomIdent::Fourcc(*b"egid");
 
 macro_rules! impl_accessor {
 	($($name:ident => $const:ident;)+) => {
@@ -53,6 +78,30 @@ macro_rules! impl_accessor {
 	}
 }
 
+// Getters, setters, and removers for text atoms outside of `Accessor`
+macro_rules! impl_text_atoms {
+	($($name:ident => $const:ident, $fourcc:literal, $what:literal, $new_atom:path;)+) => {
+		paste::paste! {
+			$(
+				#[doc = "Returns the " $what ", according to the `" $fourcc "` atom"]
+				pub fn $name(&self) -> Option<&str> {
+					self.text_atom(&$const)
+				}
+
+				#[doc = "Sets the `" $fourcc "` atom"]
+				pub fn [<set_ $name>](&mut self, value: String) {
+					self.replace_atom($new_atom($const, value))
+				}
+
+				#[doc = "Removes the `" $fourcc "` atom"]
+				pub fn [<remove_ $name>](&mut self) {
+					self.remove_atom(&$const)
+				}
+			)+
+		}
+	}
+}
+
 #[derive(Default, PartialEq, Debug, Clone)]
 /// An MP4 ilst atom
 ///
@@ -125,30 +174,29 @@ impl Ilst {
 	}
 
 	/// Returns all pictures
+	///
+	/// A single `covr` atom can contain multiple pictures, which are all returned in order.
 	pub fn pictures(&self) -> impl Iterator<Item = &Picture> {
 		const COVR: AtomIdent = AtomIdent::Fourcc(*b"covr");
 
-		self.atoms.iter().filter_map(|a| match a.ident {
-			COVR => {
-				if let Some(AtomData::Picture(pic)) = a.data().next() {
-					Some(pic)
-				} else {
-					None
-				}
-			},
-			_ => None,
-		})
+		self.atoms
+			.iter()
+			.filter(|a| a.ident == COVR)
+			.flat_map(Atom::data)
+			.filter_map(|data| match data {
+				AtomData::Picture(pic) => Some(pic),
+				_ => None,
+			})
 	}
 
 	/// Inserts a picture
+	///
+	/// The picture is appended to the existing `covr` atom, if there is one.
 	pub fn insert_picture(&mut self, mut picture: Picture) {
 		// This is just for correctness, it doesn't really matter.
 		picture.pic_type = PictureType::Other;
 
-		self.atoms.push(Atom {
-			ident: AtomIdent::Fourcc(*b"covr"),
-			data: AtomDataStorage::Single(AtomData::Picture(picture)),
-		})
+		insert_covr(&mut self.atoms, picture);
 	}
 
 	/// Removes all pictures
@@ -182,6 +230,138 @@ impl Ilst {
 		})
 	}
 
+	/// Returns the iTunes media type according to the `stik` atom
+	pub fn media_type(&self) -> Option<MediaType> {
+		let media_type = match self.atom(&MEDIA_TYPE)?.data().next()? {
+			AtomData::SignedInteger(si) => *si as u8,
+			AtomData::UnsignedInteger(ui) => *ui as u8,
+			AtomData::Unknown { data: c, .. } if !c.is_empty() => c[c.len() - 1],
+			_ => return None,
+		};
+
+		Some(MediaType::from(media_type))
+	}
+
+	/// Sets the `stik` atom
+	pub fn set_media_type(&mut self, media_type: MediaType) {
+		self.replace_atom(Atom {
+			ident: MEDIA_TYPE,
+			data: AtomDataStorage::Single(AtomData::Unknown {
+				code: BE_SIGNED_INTEGER,
+				data: vec![media_type.as_u8()],
+			}),
+		})
+	}
+
+	/// Removes the `stik` atom
+	pub fn remove_media_type(&mut self) {
+		self.remove_atom(&MEDIA_TYPE)
+	}
+
+	/// Returns the numeric genre from the `gnre` atom
+	///
+	/// This is an ID3v1 genre index, plus one. To get the genre as a string, use [`Accessor::genre`],
+	/// which will fall back to this if there is no `©gen` atom.
+	pub fn genre_id(&self) -> Option<u16> {
+		match self.atom(&GENRE_ID)?.data().next()? {
+			AtomData::SignedInteger(si) => u16::try_from(*si).ok(),
+			AtomData::UnsignedInteger(ui) => u16::try_from(*ui).ok(),
+			AtomData::Unknown { data, .. } if data.len() == 2 => {
+				Some(u16::from_be_bytes([data[0], data[1]]))
+			},
+			_ => None,
+		}
+	}
+
+	/// Returns whether the track is part of a compilation, according to the `cpil` atom
+	pub fn compilation(&self) -> Option<bool> {
+		self.flag(&COMPILATION)
+	}
+
+	/// Sets the `cpil` atom
+	///
+	/// NOTE: Setting this to `false` will still write the atom, to remove it use [`Ilst::remove_compilation`].
+	pub fn set_compilation(&mut self, compilation: bool) {
+		self.replace_atom(flag_atom(COMPILATION, compilation))
+	}
+
+	/// Removes the `cpil` atom
+	pub fn remove_compilation(&mut self) {
+		self.remove_atom(&COMPILATION)
+	}
+
+	/// Returns whether the track is part of a gapless album, according to the `pgap` atom
+	pub fn gapless_playback(&self) -> Option<bool> {
+		self.flag(&GAPLESS_PLAYBACK)
+	}
+
+	/// Sets the `pgap` atom
+	///
+	/// NOTE: Setting this to `false` will still write the atom, to remove it use [`Ilst::remove_gapless_playback`].
+	pub fn set_gapless_playback(&mut self, gapless_playback: bool) {
+		self.replace_atom(flag_atom(GAPLESS_PLAYBACK, gapless_playback))
+	}
+
+	/// Removes the `pgap` atom
+	pub fn remove_gapless_playback(&mut self) {
+		self.remove_atom(&GAPLESS_PLAYBACK)
+	}
+
+	/// Returns the encoder delay and padding, according to the `----:com.apple.iTunes:iTunSMPB` atom
+	pub fn gapless_info(&self) -> Option<GaplessInfo> {
+		let ident = AtomIdent::Freeform {
+			mean: String::from("com.apple.iTunes"),
+			name: String::from("iTunSMPB"),
+		};
+
+		self.text_atom(&ident).and_then(GaplessInfo::from_itunsmpb)
+	}
+
+	/// Returns whether the track is a podcast, according to the `pcst` atom
+	pub fn podcast(&self) -> Option<bool> {
+		self.flag(&PODCAST)
+	}
+
+	/// Sets the `pcst` atom
+	///
+	/// NOTE: Setting this to `false` will still write the atom, to remove it use [`Ilst::remove_podcast`].
+	pub fn set_podcast(&mut self, podcast: bool) {
+		self.replace_atom(flag_atom(PODCAST, podcast))
+	}
+
+	/// Removes the `pcst` atom
+	pub fn remove_podcast(&mut self) {
+		self.remove_atom(&PODCAST)
+	}
+
+	impl_text_atoms! {
+		description      => DESCRIPTION, "desc", "short description", Atom::text;
+		long_description => LONG_DESCRIPTION, "ldes", "long description", Atom::text;
+		keywords         => KEYWORDS, "keyw", "podcast keywords", Atom::text;
+		category         => CATEGORY, "catg", "podcast category", Atom::text;
+		podcast_url      => PODCAST_URL, "purl", "podcast feed URL", implicit_text_atom;
+		episode_guid     => EPISODE_GUID, "egid", "podcast episode GUID", implicit_text_atom;
+	}
+
+	// Reads a text atom, which may be stored as implicit data (`purl` and `egid` are written this way by iTunes)
+	fn text_atom(&self, ident: &AtomIdent) -> Option<&str> {
+		match self.atom(ident)?.data().next()? {
+			AtomData::UTF8(text) | AtomData::UTF16(text) => Some(text),
+			AtomData::Unknown { code: 0, data } => std::str::from_utf8(data).ok(),
+			_ => None,
+		}
+	}
+
+	// Reads a single byte boolean atom
+	fn flag(&self, ident: &AtomIdent) -> Option<bool> {
+		match self.atom(ident)?.data().next()? {
+			AtomData::SignedInteger(int) => Some(*int != 0),
+			AtomData::UnsignedInteger(uint) => Some(*uint != 0),
+			AtomData::Unknown { data, .. } => data.first().map(|b| *b != 0),
+			_ => None,
+		}
+	}
+
 	/// Returns the total number of tracks
 	pub fn track_total(&self) -> Option<u16> {
 		self.extract_number(*b"trkn", 6)
@@ -201,11 +381,11 @@ impl Ilst {
 	fn extract_number(&self, fourcc: [u8; 4], expected_size: usize) -> Option<u16> {
 		if let Some(atom) = self.atom(&AtomIdent::Fourcc(fourcc)) {
 			match atom.data().next() {
+				// A value of 0 means that side of the pair is missing
 				Some(AtomData::Unknown { code: 0, data }) if data.len() >= expected_size => {
-					return Some(u16::from_be_bytes([
-						data[expected_size - 2],
-						data[expected_size - 1],
-					]))
+					let number =
+						u16::from_be_bytes([data[expected_size - 2], data[expected_size - 1]]);
+					return (number != 0).then(|| number);
 				},
 				_ => {},
 			}
@@ -217,13 +397,40 @@ impl Ilst {
 
 impl Accessor for Ilst {
 	impl_accessor!(
-		artist  => ARTIST;
-		title   => TITLE;
-		album   => ALBUM;
-		genre   => GENRE;
-		comment => COMMENT;
+		artist       => ARTIST;
+		title        => TITLE;
+		album        => ALBUM;
+		comment      => COMMENT;
+		album_artist => ALBUM_ARTIST;
+		composer     => COMPOSER;
+		encoder      => ENCODER;
+		title_sort => TITLE_SORT;
+		artist_sort => ARTIST_SORT;
+		album_sort => ALBUM_SORT;
+		album_artist_sort => ALBUM_ARTIST_SORT;
 	);
 
+	fn genre(&self) -> Option<&str> {
+		if let Some(atom) = self.atom(&GENRE) {
+			if let Some(AtomData::UTF8(val) | AtomData::UTF16(val)) = atom.data().next() {
+				return Some(val);
+			}
+		}
+
+		genre_from_id(self.genre_id()?)
+	}
+
+	fn set_genre(&mut self, value: String) {
+		// A stale `gnre` atom would conflict with the new genre
+		self.remove_atom(&GENRE_ID);
+		self.replace_atom(Atom::text(GENRE, value))
+	}
+
+	fn remove_genre(&mut self) {
+		self.remove_atom(&GENRE);
+		self.remove_atom(&GENRE_ID);
+	}
+
 	fn track(&self) -> Option<u32> {
 		self.extract_number(*b"trkn", 4).map(u32::from)
 	}
@@ -237,7 +444,12 @@ impl Accessor for Ilst {
 	}
 
 	fn remove_track(&mut self) {
+		let track_total = self.track_total();
 		self.remove_atom(&AtomIdent::Fourcc(*b"trkn"));
+
+		if let Some(track_total) = track_total {
+			self.set_track_total(u32::from(track_total));
+		}
 	}
 
 	fn track_total(&self) -> Option<u32> {
@@ -246,7 +458,7 @@ impl Accessor for Ilst {
 
 	fn set_track_total(&mut self, value: u32) {
 		let value = (value as u16).to_be_bytes();
-		let track = self.track().unwrap_or(1).to_be_bytes();
+		let track = (self.track().unwrap_or(0) as u16).to_be_bytes();
 
 		let data = vec![0, 0, track[0], track[1], value[0], value[1]];
 		self.replace_atom(Atom::unknown_implicit(AtomIdent::Fourcc(*b"trkn"), data));
@@ -270,14 +482,19 @@ impl Accessor for Ilst {
 
 	fn set_disk(&mut self, value: u32) {
 		let value = (value as u16).to_be_bytes();
-		let disk_total = self.disk_total().unwrap_or(0).to_be_bytes();
+		let disk_total = (self.disk_total().unwrap_or(0) as u16).to_be_bytes();
 
 		let data = vec![0, 0, value[0], value[1], disk_total[0], disk_total[1]];
 		self.replace_atom(Atom::unknown_implicit(AtomIdent::Fourcc(*b"disk"), data));
 	}
 
 	fn remove_disk(&mut self) {
+		let disk_total = self.disk_total();
 		self.remove_atom(&AtomIdent::Fourcc(*b"disk"));
+
+		if let Some(disk_total) = disk_total {
+			self.set_disk_total(disk_total);
+		}
 	}
 
 	fn disk_total(&self) -> Option<u32> {
@@ -286,7 +503,7 @@ impl Accessor for Ilst {
 
 	fn set_disk_total(&mut self, value: u32) {
 		let value = (value as u16).to_be_bytes();
-		let disk = self.disk().unwrap_or(1).to_be_bytes();
+		let disk = (self.disk().unwrap_or(0) as u16).to_be_bytes();
 
 		let data = vec![0, 0, disk[0], disk[1], value[0], value[1]];
 		self.replace_atom(Atom::unknown_implicit(AtomIdent::Fourcc(*b"disk"), data));
@@ -304,6 +521,43 @@ impl Accessor for Ilst {
 		}
 	}
 
+	fn bpm(&self) -> Option<u16> {
+		if let Some(atom) = self.atom(&BPM) {
+			match atom.data().next() {
+				Some(AtomData::SignedInteger(bpm)) => return u16::try_from(*bpm).ok(),
+				Some(AtomData::UnsignedInteger(bpm)) => return u16::try_from(*bpm).ok(),
+				Some(AtomData::Unknown {
+					code: RESERVED | BE_SIGNED_INTEGER,
+					data,
+				}) if data.len() == 2 => return Some(u16::from_be_bytes([data[0], data[1]])),
+				Some(AtomData::UTF8(text)) => return parse_bpm(text),
+				_ => {},
+			}
+		}
+
+		None
+	}
+
+	fn set_bpm(&mut self, value: u16) {
+		self.replace_atom(int_atom(BPM, value))
+	}
+
+	fn remove_bpm(&mut self) {
+		self.remove_atom(&BPM)
+	}
+
+	fn initial_key(&self) -> Option<&str> {
+		self.text_atom(&initial_key_ident())
+	}
+
+	fn set_initial_key(&mut self, value: String) {
+		self.replace_atom(Atom::text(initial_key_ident(), value))
+	}
+
+	fn remove_initial_key(&mut self) {
+		self.remove_atom(&initial_key_ident())
+	}
+
 	fn year(&self) -> Option<u32> {
 		if let Some(atom) = self.atom(&AtomIdent::Fourcc(*b"\xa9day")) {
 			if let Some(AtomData::UTF8(text)) = atom.data().next() {
@@ -333,12 +587,12 @@ impl TagExt for Ilst {
 		self.atoms.is_empty()
 	}
 
-	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err> {
 		let mut f = OpenOptions::new().read(true).write(true).open(path)?;
 		self.save_to(&mut f)
 	}
 
-	fn save_to(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err> {
 		self.as_ref().write_to(file)
 	}
 
@@ -363,54 +617,105 @@ impl From<Ilst> for Tag {
 	fn from(input: Ilst) -> Self {
 		let mut tag = Self::new(TagType::MP4ilst);
 
+		// The `gnre` atom is only used when there is no `©gen` atom
+		if input.atom(&GENRE).is_none() {
+			if let Some(genre) = input.genre_id().and_then(genre_from_id) {
+				tag.insert_text(ItemKey::Genre, String::from(genre));
+			}
+		}
+
 		for atom in input.atoms {
 			let Atom { ident, data } = atom;
+
+			// Atoms with multiple text values (such as freeform atoms) get an item for each value
+			if let AtomDataStorage::Multiple(ref values) = data {
+				// A `covr` atom can hold multiple pictures
+				if values.iter().all(|v| matches!(v, AtomData::Picture(_))) {
+					for value in values {
+						if let AtomData::Picture(pic) = value {
+							tag.pictures.push(pic.clone());
+						}
+					}
+
+					continue;
+				}
+
+				if values
+					.iter()
+					.all(|v| matches!(v, AtomData::UTF8(_) | AtomData::UTF16(_)))
+				{
+					let key = ident_to_item_key(&ident);
+					for value in values {
+						if let AtomData::UTF8(text) | AtomData::UTF16(text) = value {
+							tag.items
+								.push(TagItem::new(key.clone(), ItemValue::Text(text.clone())));
+						}
+					}
+
+					continue;
+				}
+			}
+
 			let value = match data.take_first() {
 				AtomData::UTF8(text) | AtomData::UTF16(text) => ItemValue::Text(text),
 				AtomData::Picture(pic) => {
 					tag.pictures.push(pic);
 					continue;
 				},
+				AtomData::Unknown { code: 0, data } if is_implicit_text(&ident) => {
+					match String::from_utf8(data) {
+						Ok(text) => ItemValue::Text(text),
+						Err(_) => continue,
+					}
+				},
 				// We have to special case track/disc numbers since they are stored together
 				AtomData::Unknown { code: 0, data } if data.len() >= 6 => {
 					if let AtomIdent::Fourcc(ref fourcc) = ident {
 						match fourcc {
-							b"trkn" => {
-								let current = u16::from_be_bytes([data[2], data[3]]);
-								let total = u16::from_be_bytes([data[4], data[5]]);
-
-								tag.insert_text(ItemKey::TrackNumber, current.to_string());
-								tag.insert_text(ItemKey::TrackTotal, total.to_string());
-							},
-							b"disk" => {
-								let current = u16::from_be_bytes([data[2], data[3]]);
-								let total = u16::from_be_bytes([data[4], data[5]]);
-
-								tag.insert_text(ItemKey::DiscNumber, current.to_string());
-								tag.insert_text(ItemKey::DiscTotal, total.to_string());
-							},
+							b"trkn" => insert_int_pair(
+								&mut tag,
+								&data,
+								ItemKey::TrackNumber,
+								ItemKey::TrackTotal,
+							),
+							b"disk" => insert_int_pair(
+								&mut tag,
+								&data,
+								ItemKey::DiscNumber,
+								ItemKey::DiscTotal,
+							),
 							_ => {},
 						}
 					}
 
 					continue;
 				},
+				// Flags are stored as a single byte
+				AtomData::SignedInteger(int) if is_flag(&ident) => {
+					ItemValue::Text(u8::from(int != 0).to_string())
+				},
+				AtomData::UnsignedInteger(uint) if is_flag(&ident) => {
+					ItemValue::Text(u8::from(uint != 0).to_string())
+				},
+				AtomData::Unknown { data, .. } if is_flag(&ident) && !data.is_empty() => {
+					ItemValue::Text(u8::from(data[0] != 0).to_string())
+				},
+				// The BPM and movement numbers are stored as 16-bit integers
+				AtomData::SignedInteger(int) if is_int(&ident) => ItemValue::Text(int.to_string()),
+				AtomData::UnsignedInteger(uint) if is_int(&ident) => {
+					ItemValue::Text(uint.to_string())
+				},
+				AtomData::Unknown {
+					code: RESERVED | BE_SIGNED_INTEGER,
+					data,
+				} if is_int(&ident) && data.len() == 2 => {
+					ItemValue::Text(u16::from_be_bytes([data[0], data[1]]).to_string())
+				},
 				_ => continue,
 			};
 
-			let key = ItemKey::from_key(
-				TagType::MP4ilst,
-				&match ident {
-					AtomIdent::Fourcc(fourcc) => {
-						fourcc.iter().map(|b| *b as char).collect::<String>()
-					},
-					AtomIdent::Freeform { mean, name } => {
-						format!("----:{}:{}", mean, name)
-					},
-				},
-			);
-
-			tag.items.push(TagItem::new(key, value));
+			tag.items
+				.push(TagItem::new(ident_to_item_key(&ident), value));
 		}
 
 		tag
@@ -463,10 +768,34 @@ impl From<Tag> for Ilst {
 					ItemKey::TrackTotal => convert_to_uint(&mut tracks.1, data.as_str()),
 					ItemKey::DiscNumber => convert_to_uint(&mut discs.0, data.as_str()),
 					ItemKey::DiscTotal => convert_to_uint(&mut discs.1, data.as_str()),
-					_ => ilst.atoms.push(Atom {
-						ident,
-						data: AtomDataStorage::Single(AtomData::UTF8(data)),
-					}),
+					ItemKey::BPM => {
+						if let Some(bpm) = parse_bpm(&data) {
+							ilst.atoms.push(int_atom(ident, bpm))
+						}
+					},
+					ItemKey::PodcastURL | ItemKey::PodcastGlobalUniqueID => {
+						ilst.replace_atom(implicit_text_atom(ident, data))
+					},
+					ItemKey::MovementNumber | ItemKey::MovementCount => {
+						if let Ok(num) = data.trim().parse::<u16>() {
+							ilst.replace_atom(int_atom(ident, num))
+						}
+					},
+					ItemKey::FlagCompilation
+					| ItemKey::FlagPodcast
+					| ItemKey::FlagGaplessPlayback => {
+						if let Some(flag) = parse_flag(&data) {
+							ilst.replace_atom(flag_atom(ident, flag))
+						}
+					},
+					// Multiple values are stored in the same atom
+					_ => match ilst.atoms.iter_mut().find(|a| a.ident == ident) {
+						Some(atom) => atom.push_data(AtomData::UTF8(data)),
+						None => ilst.atoms.push(Atom {
+							ident,
+							data: AtomDataStorage::Single(AtomData::UTF8(data)),
+						}),
+					},
 				}
 			}
 		}
@@ -476,10 +805,7 @@ impl From<Tag> for Ilst {
 			// assign a picture type in this format
 			picture.pic_type = PictureType::Other;
 
-			ilst.atoms.push(Atom {
-				ident: AtomIdent::Fourcc([b'c', b'o', b'v', b'r']),
-				data: AtomDataStorage::Single(AtomData::Picture(picture)),
-			})
+			insert_covr(&mut ilst.atoms, picture);
 		}
 
 		create_int_pair(&mut ilst, *b"trkn", tracks);
@@ -489,10 +815,111 @@ impl From<Tag> for Ilst {
 	}
 }
 
+// A value of 0 means that side of the pair is missing
+fn insert_int_pair(tag: &mut Tag, data: &[u8], current_key: ItemKey, total_key: ItemKey) {
+	let current = u16::from_be_bytes([data[2], data[3]]);
+	let total = u16::from_be_bytes([data[4], data[5]]);
+
+	if current != 0 {
+		tag.insert_text(current_key, current.to_string());
+	}
+
+	if total != 0 {
+		tag.insert_text(total_key, total.to_string());
+	}
+}
+
+// The `gnre` atom stores an ID3v1 genre index, plus one
+fn genre_from_id(id: u16) -> Option<&'static str> {
+	GENRES.get(usize::from(id).checked_sub(1)?).copied()
+}
+
+// All pictures are stored in a single `covr` atom, each in their own `data` atom
+fn insert_covr(atoms: &mut Vec<Atom>, picture: Picture) {
+	const COVR: AtomIdent = AtomIdent::Fourcc(*b"covr");
+
+	match atoms.iter_mut().find(|a| a.ident == COVR) {
+		Some(atom) => atom.push_data(AtomData::Picture(picture)),
+		None => atoms.push(Atom {
+			ident: COVR,
+			data: AtomDataStorage::Single(AtomData::Picture(picture)),
+		}),
+	}
+}
+
+fn is_implicit_text(ident: &AtomIdent) -> bool {
+	*ident == PODCAST_URL || *ident == EPISODE_GUID
+}
+
+// iTunes stores the podcast URL and episode GUID as implicit data, rather than UTF-8
+fn implicit_text_atom(ident: AtomIdent, text: String) -> Atom {
+	Atom::unknown_implicit(ident, text.into_bytes())
+}
+
+fn is_int(ident: &AtomIdent) -> bool {
+	*ident == BPM || *ident == MOVEMENT_NUMBER || *ident == MOVEMENT_COUNT
+}
+
+// iTunes expects these to be 16-bit integers, `AtomData::SignedInteger` would shrink them
+fn int_atom(ident: AtomIdent, value: u16) -> Atom {
+	Atom {
+		ident,
+		data: AtomDataStorage::Single(AtomData::Unknown {
+			code: BE_SIGNED_INTEGER,
+			data: value.to_be_bytes().to_vec(),
+		}),
+	}
+}
+
+fn is_flag(ident: &AtomIdent) -> bool {
+	*ident == COMPILATION || *ident == PODCAST || *ident == GAPLESS_PLAYBACK
+}
+
+fn parse_flag(text: &str) -> Option<bool> {
+	match text.trim() {
+		"1" => Some(true),
+		"0" => Some(false),
+		t if t.eq_ignore_ascii_case("true") => Some(true),
+		t if t.eq_ignore_ascii_case("false") => Some(false),
+		_ => None,
+	}
+}
+
+// The initial key is stored in a freeform atom, so it can't be a constant
+fn initial_key_ident() -> AtomIdent {
+	AtomIdent::Freeform {
+		mean: String::from("com.apple.iTunes"),
+		name: String::from("initialkey"),
+	}
+}
+
+// iTunes writes flags as a single byte, and keeps the atom around when it's `false`
+fn flag_atom(ident: AtomIdent, flag: bool) -> Atom {
+	Atom {
+		ident,
+		data: AtomDataStorage::Single(AtomData::Unknown {
+			code: BE_SIGNED_INTEGER,
+			data: vec![u8::from(flag)],
+		}),
+	}
+}
+
+// Freeform atoms are keyed as "----:mean:name"
+fn ident_to_item_key(ident: &AtomIdent) -> ItemKey {
+	ItemKey::from_key(
+		TagType::MP4ilst,
+		&match ident {
+			AtomIdent::Fourcc(fourcc) => fourcc.iter().map(|b| *b as char).collect::<String>(),
+			AtomIdent::Freeform { mean, name } => format!("----:{}:{}", mean, name),
+		},
+	)
+}
+
 fn item_key_to_ident(key: &ItemKey) -> Option<AtomIdentRef<'_>> {
 	key.map_key(TagType::MP4ilst, true).and_then(|ident| {
 		if ident.starts_with("----") {
-			let mut split = ident.split(':');
+			// The name may contain colons
+			let mut split = ident.splitn(3, ':');
 
 			split.next();
 
@@ -520,10 +947,13 @@ fn item_key_to_ident(key: &ItemKey) -> Option<AtomIdentRef<'_>> {
 mod tests {
 	use crate::mp4::ilst::atom::AtomDataStorage;
 	use crate::mp4::read::AtomReader;
-	use crate::mp4::{AdvisoryRating, Atom, AtomData, AtomIdent, Ilst, Mp4File};
+	use crate::mp4::{AdvisoryRating, Atom, AtomData, AtomIdent, Ilst, MediaType, Mp4File};
+	use crate::probe::ParseOptions;
 	use crate::tag::utils::test_utils;
 	use crate::tag::utils::test_utils::read_path;
-	use crate::{Accessor, AudioFile, ItemKey, Tag, TagExt, TagType};
+	use crate::{
+		Accessor, AudioFile, ItemKey, MimeType, Picture, PictureType, Tag, TagExt, TagType,
+	};
 	use std::io::{Cursor, Read, Seek, Write};
 
 	fn read_ilst(path: &str) -> Ilst {
@@ -533,7 +963,7 @@ mod tests {
 		let cursor = Cursor::new(tag);
 		let mut reader = AtomReader::new(cursor).unwrap();
 
-		super::read::parse_ilst(&mut reader, len as u64).unwrap()
+		super::read::parse_ilst(&mut reader, len as u64, &mut ParseOptions::new()).unwrap()
 	}
 
 	fn verify_atom(ilst: &Ilst, ident: [u8; 4], data: &AtomData) {
@@ -601,7 +1031,8 @@ mod tests {
 		let cursor = Cursor::new(tag);
 		let mut reader = AtomReader::new(cursor).unwrap();
 
-		let parsed_tag = super::read::parse_ilst(&mut reader, len as u64).unwrap();
+		let parsed_tag =
+			super::read::parse_ilst(&mut reader, len as u64, &mut ParseOptions::new()).unwrap();
 
 		assert_eq!(expected_tag, parsed_tag);
 	}
@@ -617,8 +1048,12 @@ mod tests {
 		let mut reader = AtomReader::new(cursor).unwrap();
 
 		// Remove the ilst identifier and size
-		let temp_parsed_tag =
-			super::read::parse_ilst(&mut reader, (writer.len() - 8) as u64).unwrap();
+		let temp_parsed_tag = super::read::parse_ilst(
+			&mut reader,
+			(writer.len() - 8) as u64,
+			&mut ParseOptions::new(),
+		)
+		.unwrap();
 
 		assert_eq!(parsed_tag, temp_parsed_tag);
 	}
@@ -631,7 +1066,8 @@ mod tests {
 		let cursor = Cursor::new(tag);
 		let mut reader = AtomReader::new(cursor).unwrap();
 
-		let ilst = super::read::parse_ilst(&mut reader, len as u64).unwrap();
+		let ilst =
+			super::read::parse_ilst(&mut reader, len as u64, &mut ParseOptions::new()).unwrap();
 
 		let tag: Tag = ilst.into();
 
@@ -746,7 +1182,12 @@ mod tests {
 			let cursor = Cursor::new(ilst_bytes);
 			let mut reader = AtomReader::new(cursor).unwrap();
 
-			ilst = super::read::parse_ilst(&mut reader, ilst_bytes.len() as u64).unwrap();
+			ilst = super::read::parse_ilst(
+				&mut reader,
+				ilst_bytes.len() as u64,
+				&mut ParseOptions::new(),
+			)
+			.unwrap();
 		}
 
 		let mut file = tempfile::tempfile().unwrap();
@@ -839,6 +1280,373 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn freeform_atom() {
+		let ident = AtomIdent::Freeform {
+			mean: String::from("com.apple.iTunes"),
+			name: String::from("MusicBrainz Track Id"),
+		};
+		let values = vec![
+			AtomData::UTF8(String::from("a1b2c3d4")),
+			AtomData::UTF8(String::from("e5f6a7b8")),
+		];
+
+		let mut ilst = Ilst::default();
+		ilst.insert_atom(Atom::from_collection(ident.clone(), values.clone()).unwrap());
+		ilst.insert_atom(Atom::new(
+			AtomIdent::Freeform {
+				mean: String::from("com.example"),
+				name: String::from("Foo:Bar"),
+			},
+			AtomData::UTF8(String::from("Baz")),
+		));
+
+		let mut writer = Vec::new();
+		ilst.dump_to(&mut writer).unwrap();
+
+		let cursor = Cursor::new(&writer[8..]);
+		let mut reader = AtomReader::new(cursor).unwrap();
+
+		let parsed_ilst = super::read::parse_ilst(
+			&mut reader,
+			(writer.len() - 8) as u64,
+			&mut ParseOptions::new(),
+		)
+		.unwrap();
+		assert_eq!(parsed_ilst, ilst);
+
+		// Each value gets its own item
+		let tag: Tag = parsed_ilst.into();
+
+		let key = ItemKey::Unknown(String::from("----:com.apple.iTunes:MusicBrainz Track Id"));
+		assert_eq!(
+			tag.get_strings(&key).collect::<Vec<_>>(),
+			vec!["a1b2c3d4", "e5f6a7b8"]
+		);
+		assert_eq!(
+			tag.get_string(&ItemKey::Unknown(String::from("----:com.example:Foo:Bar"))),
+			Some("Baz")
+		);
+
+		// And they're written back to the same atom
+		let ilst_from_tag: Ilst = tag.into();
+		assert_eq!(ilst_from_tag, ilst);
+	}
+
+	#[test]
+	fn flag_atoms() {
+		let mut ilst = Ilst::default();
+		ilst.set_compilation(true);
+		ilst.set_gapless_playback(false);
+
+		let mut writer = Vec::new();
+		ilst.dump_to(&mut writer).unwrap();
+
+		let cursor = Cursor::new(&writer[8..]);
+		let mut reader = AtomReader::new(cursor).unwrap();
+
+		let parsed_ilst = super::read::parse_ilst(
+			&mut reader,
+			(writer.len() - 8) as u64,
+			&mut ParseOptions::new(),
+		)
+		.unwrap();
+
+		// `false` flags are still written
+		assert_eq!(parsed_ilst.compilation(), Some(true));
+		assert_eq!(parsed_ilst.gapless_playback(), Some(false));
+
+		let tag: Tag = parsed_ilst.into();
+		assert_eq!(tag.get_string(&ItemKey::FlagCompilation), Some("1"));
+		assert_eq!(tag.get_string(&ItemKey::FlagGaplessPlayback), Some("0"));
+
+		let mut ilst: Ilst = tag.into();
+		verify_atom(
+			&ilst,
+			*b"cpil",
+			&AtomData::Unknown {
+				code: 21,
+				data: vec![1],
+			},
+		);
+		verify_atom(
+			&ilst,
+			*b"pgap",
+			&AtomData::Unknown {
+				code: 21,
+				data: vec![0],
+			},
+		);
+
+		ilst.remove_compilation();
+		ilst.remove_gapless_playback();
+		assert_eq!(ilst.compilation(), None);
+		assert_eq!(ilst.gapless_playback(), None);
+	}
+
+	#[test]
+	fn podcast_atoms() {
+		let mut ilst = Ilst::default();
+		ilst.set_podcast(true);
+		ilst.set_description(String::from("Foo description"));
+		ilst.set_long_description(String::from("Foo long description"));
+		ilst.set_keywords(String::from("foo,bar"));
+		ilst.set_category(String::from("Foo category"));
+		ilst.set_podcast_url(String::from("https://example.com/feed.xml"));
+		ilst.set_episode_guid(String::from("foo-episode-1"));
+
+		let mut writer = Vec::new();
+		ilst.dump_to(&mut writer).unwrap();
+
+		let cursor = Cursor::new(&writer[8..]);
+		let mut reader = AtomReader::new(cursor).unwrap();
+
+		let parsed_ilst = super::read::parse_ilst(
+			&mut reader,
+			(writer.len() - 8) as u64,
+			&mut ParseOptions::new(),
+		)
+		.unwrap();
+
+		assert_eq!(parsed_ilst.podcast(), Some(true));
+		assert_eq!(parsed_ilst.description(), Some("Foo description"));
+		assert_eq!(parsed_ilst.long_description(), Some("Foo long description"));
+		assert_eq!(parsed_ilst.keywords(), Some("foo,bar"));
+		assert_eq!(parsed_ilst.category(), Some("Foo category"));
+		assert_eq!(
+			parsed_ilst.podcast_url(),
+			Some("https://example.com/feed.xml")
+		);
+		assert_eq!(parsed_ilst.episode_guid(), Some("foo-episode-1"));
+
+		// The podcast flag is a single byte, and the URL/GUID are implicit data
+		verify_atom(
+			&ilst,
+			*b"pcst",
+			&AtomData::Unknown {
+				code: 21,
+				data: vec![1],
+			},
+		);
+		verify_atom(
+			&parsed_ilst,
+			*b"egid",
+			&AtomData::Unknown {
+				code: 0,
+				data: b"foo-episode-1".to_vec(),
+			},
+		);
+
+		let tag: Tag = parsed_ilst.into();
+		assert_eq!(tag.get_string(&ItemKey::FlagPodcast), Some("1"));
+		assert_eq!(
+			tag.get_string(&ItemKey::Description),
+			Some("Foo description")
+		);
+		assert_eq!(
+			tag.get_string(&ItemKey::PodcastDescription),
+			Some("Foo long description")
+		);
+		assert_eq!(tag.get_string(&ItemKey::PodcastKeywords), Some("foo,bar"));
+		assert_eq!(
+			tag.get_string(&ItemKey::PodcastSeriesCategory),
+			Some("Foo category")
+		);
+		assert_eq!(
+			tag.get_string(&ItemKey::PodcastURL),
+			Some("https://example.com/feed.xml")
+		);
+		assert_eq!(
+			tag.get_string(&ItemKey::PodcastGlobalUniqueID),
+			Some("foo-episode-1")
+		);
+
+		let mut ilst_from_tag: Ilst = tag.into();
+		verify_atom(
+			&ilst_from_tag,
+			*b"purl",
+			&AtomData::Unknown {
+				code: 0,
+				data: b"https://example.com/feed.xml".to_vec(),
+			},
+		);
+		assert_eq!(ilst_from_tag.podcast(), Some(true));
+
+		ilst_from_tag.remove_podcast();
+		ilst_from_tag.remove_episode_guid();
+		assert_eq!(ilst_from_tag.podcast(), None);
+		assert_eq!(ilst_from_tag.episode_guid(), None);
+	}
+
+	#[test]
+	fn movement_atoms() {
+		let mut tag = Tag::new(TagType::MP4ilst);
+		tag.insert_text(ItemKey::Work, String::from("Symphony No. 5"));
+		tag.insert_text(ItemKey::MovementName, String::from("Allegro con brio"));
+		tag.insert_text(ItemKey::MovementNumber, String::from("1"));
+		tag.insert_text(ItemKey::MovementCount, String::from("4"));
+
+		let ilst: Ilst = tag.into();
+		assert_eq!(
+			ilst.atom(&AtomIdent::Fourcc(*b"\xa9wrk"))
+				.unwrap()
+				.data()
+				.next(),
+			Some(&AtomData::UTF8(String::from("Symphony No. 5")))
+		);
+
+		// The movement number and count are integers
+		verify_atom(
+			&ilst,
+			*b"\xa9mvi",
+			&AtomData::Unknown {
+				code: 21,
+				data: vec![0, 1],
+			},
+		);
+		verify_atom(
+			&ilst,
+			*b"\xa9mvc",
+			&AtomData::Unknown {
+				code: 21,
+				data: vec![0, 4],
+			},
+		);
+
+		let tag: Tag = ilst.into();
+		assert_eq!(
+			tag.get_string(&ItemKey::MovementName),
+			Some("Allegro con brio")
+		);
+		assert_eq!(tag.get_string(&ItemKey::MovementNumber), Some("1"));
+		assert_eq!(tag.get_string(&ItemKey::MovementCount), Some("4"));
+	}
+
+	#[test]
+	fn multiple_pictures() {
+		let front = Picture::new_unchecked(
+			PictureType::CoverFront,
+			MimeType::Jpeg,
+			None,
+			vec![0xFF, 0xD8, 0xFF, 0xE0],
+		);
+		let back = Picture::new_unchecked(
+			PictureType::CoverBack,
+			MimeType::Png,
+			None,
+			b"\x89PNG\r\n\x1a\n".to_vec(),
+		);
+
+		let mut ilst = Ilst::default();
+		ilst.insert_picture(front);
+		ilst.insert_picture(back);
+
+		// Both pictures are stored in the same atom
+		assert_eq!(ilst.atoms().len(), 1);
+
+		let mut writer = Vec::new();
+		ilst.dump_to(&mut writer).unwrap();
+
+		// ilst (8) + covr (8) + data (8), followed by the version and flags
+		assert_eq!(&writer[20..24], b"data");
+		assert_eq!(&writer[24..28], &[0, 0, 0, 13]);
+
+		let png_data_atom = 24 + 4 + 4 + 4;
+		assert_eq!(&writer[png_data_atom + 4..png_data_atom + 8], b"data");
+		assert_eq!(
+			&writer[png_data_atom + 8..png_data_atom + 12],
+			&[0, 0, 0, 14]
+		);
+
+		let cursor = Cursor::new(&writer[8..]);
+		let mut reader = AtomReader::new(cursor).unwrap();
+
+		let parsed_ilst = super::read::parse_ilst(
+			&mut reader,
+			(writer.len() - 8) as u64,
+			&mut ParseOptions::new(),
+		)
+		.unwrap();
+
+		let pictures = parsed_ilst.pictures().collect::<Vec<_>>();
+		assert_eq!(pictures.len(), 2);
+		assert_eq!(pictures[0].mime_type(), &MimeType::Jpeg);
+		assert_eq!(pictures[1].mime_type(), &MimeType::Png);
+		assert!(pictures.iter().all(|p| p.pic_type() == PictureType::Other));
+
+		// Every picture makes it into the tag, and back into a single atom
+		let tag: Tag = parsed_ilst.into();
+		assert_eq!(tag.pictures().len(), 2);
+
+		let ilst_from_tag: Ilst = tag.into();
+		assert_eq!(ilst_from_tag, ilst);
+	}
+
+	#[test]
+	fn media_type() {
+		let mut ilst = Ilst::default();
+		assert_eq!(ilst.media_type(), None);
+
+		ilst.set_media_type(MediaType::Audiobook);
+		assert_eq!(ilst.media_type(), Some(MediaType::Audiobook));
+
+		let mut writer = Vec::new();
+		ilst.dump_to(&mut writer).unwrap();
+
+		let cursor = Cursor::new(&writer[8..]);
+		let mut reader = AtomReader::new(cursor).unwrap();
+
+		let parsed_ilst = super::read::parse_ilst(
+			&mut reader,
+			(writer.len() - 8) as u64,
+			&mut ParseOptions::new(),
+		)
+		.unwrap();
+		assert_eq!(parsed_ilst.media_type(), Some(MediaType::Audiobook));
+
+		assert_eq!(MediaType::from(21), MediaType::Podcast);
+		assert_eq!(MediaType::from(100), MediaType::Other(100));
+		assert_eq!(MediaType::Other(100).as_u8(), 100);
+
+		ilst.remove_media_type();
+		assert_eq!(ilst.media_type(), None);
+	}
+
+	#[test]
+	fn genre_id() {
+		let mut ilst = Ilst::default();
+
+		// "Classical" is index 32 in the ID3v1 genre list
+		ilst.insert_atom(Atom::unknown_implicit(
+			AtomIdent::Fourcc(*b"gnre"),
+			vec![0, 33],
+		));
+
+		assert_eq!(ilst.genre_id(), Some(33));
+		assert_eq!(ilst.genre(), Some("Classical"));
+
+		let tag: Tag = ilst.clone().into();
+		assert_eq!(tag.get_string(&ItemKey::Genre), Some("Classical"));
+
+		// The `©gen` atom takes precedence
+		ilst.insert_atom(Atom::text(
+			AtomIdent::Fourcc(*b"\xa9gen"),
+			String::from("Jazz"),
+		));
+		assert_eq!(ilst.genre(), Some("Jazz"));
+
+		let tag: Tag = ilst.clone().into();
+		assert_eq!(
+			tag.get_strings(&ItemKey::Genre).collect::<Vec<_>>(),
+			vec!["Jazz"]
+		);
+
+		// Setting the genre removes the `gnre` atom
+		ilst.set_genre(String::from("Rock"));
+		assert_eq!(ilst.genre_id(), None);
+		assert_eq!(ilst.genre(), Some("Rock"));
+	}
+
 	#[test]
 	fn zero_sized_ilst() {
 		let file = Mp4File::read_from(
@@ -4,21 +4,27 @@ use super::constants::{
 };
 use super::{Atom, AtomData, AtomIdent, Ilst};
 use crate::error::Result;
-use crate::id3::v1::constants::GENRES;
 use crate::id3::v2::util::text_utils::utf16_decode;
 use crate::macros::{err, try_vec};
 use crate::mp4::atom_info::AtomInfo;
 use crate::mp4::ilst::atom::AtomDataStorage;
 use crate::mp4::read::{skip_unneeded, AtomReader};
 use crate::picture::{MimeType, Picture, PictureType};
+use crate::probe::ParseOptions;
 
 use std::borrow::Cow;
 use std::io::{Cursor, Read, Seek, SeekFrom};
 
-pub(in crate::mp4) fn parse_ilst<R>(reader: &mut AtomReader<R>, len: u64) -> Result<Ilst>
+pub(in crate::mp4) fn parse_ilst<R>(
+	reader: &mut AtomReader<R>,
+	len: u64,
+	options: &mut ParseOptions<'_>,
+) -> Result<Ilst>
 where
 	R: Read + Seek,
 {
+	options.limits.check_tag_size(len)?;
+
 	let mut contents = try_vec![0; len as usize];
 	reader.read_exact(&mut contents)?;
 
@@ -27,6 +33,7 @@ where
 	let mut ilst_reader = AtomReader::new(&mut cursor)?;
 
 	let mut tag = Ilst::default();
+	let mut picture_count = 0;
 
 	while let Ok(atom) = ilst_reader.next() {
 		if let AtomIdent::Fourcc(ref fourcc) = atom.ident {
@@ -36,29 +43,13 @@ where
 					continue;
 				},
 				b"covr" => {
-					handle_covr(&mut ilst_reader, &mut tag, &atom)?;
-					continue;
-				},
-				// Upgrade this to a \xa9gen atom
-				b"gnre" => {
-					if let Some(atom_data) = parse_data_inner(&mut ilst_reader, &atom)? {
-						let mut data = Vec::new();
-
-						for (flags, content) in atom_data {
-							if (flags == BE_SIGNED_INTEGER || flags == 0) && content.len() >= 2 {
-								let index = content[1] as usize;
-								if index > 0 && index <= GENRES.len() {
-									data.push(AtomData::UTF8(String::from(GENRES[index - 1])));
-								}
-							}
-						}
-
-						tag.atoms.push(Atom {
-							ident: AtomIdent::Fourcc(*b"\xa9gen"),
-							data: AtomDataStorage::Multiple(data),
-						})
-					}
-
+					handle_covr(
+						&mut ilst_reader,
+						&mut tag,
+						&atom,
+						options,
+						&mut picture_count,
+					)?;
 					continue;
 				},
 				// Special case the "Album ID", as it has the code "BE signed integer" (21), but
@@ -159,16 +150,17 @@ where
 		// We don't care about the locale
 		reader.seek(SeekFrom::Current(4))?;
 
+		pos += data_atom.len;
+
 		let content_len = (data_atom.len - 16) as usize;
 		if content_len == 0 {
 			// We won't add empty atoms
-			return Ok(None);
+			continue;
 		}
 
 		let mut content = try_vec![0; content_len];
 		reader.read_exact(&mut content)?;
 
-		pos += data_atom.len;
 		ret.push((flags, content));
 	}
 
@@ -200,7 +192,14 @@ fn parse_int(bytes: &[u8]) -> Result<i32> {
 	})
 }
 
-fn handle_covr<R>(reader: &mut AtomReader<R>, tag: &mut Ilst, atom_info: &AtomInfo) -> Result<()>
+// `picture_count` is the number of pictures read from the `ilst` so far
+fn handle_covr<R>(
+	reader: &mut AtomReader<R>,
+	tag: &mut Ilst,
+	atom_info: &AtomInfo,
+	options: &mut ParseOptions<'_>,
+	picture_count: &mut usize,
+) -> Result<()>
 where
 	R: Read + Seek,
 {
@@ -209,6 +208,17 @@ where
 
 		let len = atom_data.len();
 		for (flags, value) in atom_data {
+			let limits = options.limits;
+			let check = limits
+				.check_picture_count(*picture_count + 1)
+				.and_then(|()| limits.check_picture_size(value.len() as u64));
+
+			if !options.keep_picture(check)? {
+				continue;
+			}
+
+			*picture_count += 1;
+
 			let mime_type = match flags {
 				// Type 0 is implicit
 				RESERVED => MimeType::None,
@@ -225,6 +235,7 @@ where
 				mime_type,
 				description: None,
 				data: Cow::from(value),
+				data_range: None,
 			});
 
 			if len == 1 {
@@ -14,7 +14,8 @@ use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
 use byteorder::{BigEndian, WriteBytesExt};
 
-pub(crate) fn write_to<'a, I: 'a>(data: &mut File, tag: &mut IlstRef<'a, I>) -> Result<()>
+// Returns `false` if the file didn't need to be modified
+pub(crate) fn write_to<'a, I: 'a>(data: &mut File, tag: &mut IlstRef<'a, I>) -> Result<bool>
 where
 	I: IntoIterator<Item = &'a AtomData>,
 {
@@ -40,7 +41,7 @@ where
 
 	// Nothing to do
 	if remove_tag && udta.is_none() {
-		return Ok(());
+		return Ok(false);
 	}
 
 	// Total size of new atoms
@@ -60,16 +61,20 @@ where
 				meta_is_full(&mut cursor)?;
 
 				// We can use the existing `udta` and `meta` atoms
-				save_to_existing(
+				let modified = save_to_existing(
 					&mut cursor,
 					(meta, udta),
 					&mut new_udta_size,
 					ilst,
 					remove_tag,
-				)?
+				)?;
+
+				if !modified {
+					return Ok(false);
+				}
 			},
 			// Nothing to do
-			None if remove_tag => return Ok(()),
+			None if remove_tag => return Ok(false),
 			// We have to create the `meta` atom
 			None => {
 				existing_udta_size = udta.len;
@@ -133,16 +138,17 @@ where
 	data.set_len(0)?;
 	data.write_all(&cursor.into_inner())?;
 
-	Ok(())
+	Ok(true)
 }
 
+// Returns `false` if the existing `ilst` atom is identical to `ilst`, or there was nothing to remove
 fn save_to_existing(
 	cursor: &mut Cursor<Vec<u8>>,
 	(meta, udta): (AtomInfo, AtomInfo),
 	new_udta_size: &mut u64,
 	ilst: Vec<u8>,
 	remove_tag: bool,
-) -> Result<()> {
+) -> Result<bool> {
 	let replacement;
 	let range;
 
@@ -151,7 +157,7 @@ fn save_to_existing(
 	if tree.is_empty() {
 		// Nothing to do
 		if remove_tag {
-			return Ok(());
+			return Ok(false);
 		}
 
 		let meta_end = (meta.start + meta.len) as usize;
@@ -165,6 +171,10 @@ fn save_to_existing(
 		let mut range_start = existing_ilst.start;
 		let range_end = existing_ilst.start + existing_ilst_size;
 
+		if cursor.get_ref()[range_start as usize..range_end as usize] == ilst[..] {
+			return Ok(false);
+		}
+
 		if remove_tag {
 			// We just need to strip out the `ilst` atom
 
@@ -214,7 +224,7 @@ fn save_to_existing(
 				cursor.write_all(b"free")?;
 				cursor.write_all(&try_vec![1; (remaining_space - 8) as usize])?;
 
-				return Ok(());
+				return Ok(true);
 			}
 
 			replacement = ilst;
@@ -239,7 +249,7 @@ fn save_to_existing(
 		write_size(udta.start, *new_udta_size, udta.extended, cursor)?;
 	}
 
-	Ok(())
+	Ok(true)
 }
 
 fn create_meta(cursor: &mut Cursor<Vec<u8>>, ilst: &[u8]) -> Result<()> {
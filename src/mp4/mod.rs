@@ -6,11 +6,16 @@
 mod atom_info;
 mod moov;
 mod properties;
-mod read;
+pub(crate) mod read;
 mod trak;
 
+use std::ops::Range;
+
 use lofty_attr::LoftyFile;
 
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::Chapter;
+
 // Exports
 
 cfg_if::cfg_if! {
@@ -18,7 +23,7 @@ cfg_if::cfg_if! {
 		pub(crate) mod ilst;
 
 		pub use atom_info::AtomIdent;
-		pub use ilst::atom::{Atom, AtomData, AdvisoryRating};
+		pub use ilst::atom::{Atom, AtomData, AdvisoryRating, MediaType};
 		pub use ilst::Ilst;
 
 		/// This module contains the codes for all of the [Well-known data types]
@@ -42,8 +47,13 @@ pub struct Mp4File {
 	#[lofty(tag_type = "MP4ilst")]
 	/// The parsed `ilst` (metadata) atom, if it exists
 	pub(crate) ilst_tag: Option<Ilst>,
+	#[cfg(feature = "id3v2")]
+	/// The chapters from the Nero `chpl` atom
+	pub(crate) chapters: Vec<Chapter>,
 	/// The file's audio properties
 	pub(crate) properties: Mp4Properties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
 }
 
 impl Mp4File {
@@ -65,4 +75,31 @@ impl Mp4File {
 	pub fn ftyp(&self) -> &str {
 		self.ftyp.as_ref()
 	}
+
+	/// Returns the file's chapters
+	///
+	/// These are read from the Nero `chpl` atom, with each chapter's title stored in a `TIT2` sub-frame.
+	/// A chapter ends where the next one begins, and the last chapter ends with the file.
+	///
+	/// If the file has no chapters, this will be empty.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::mp4::Mp4File;
+	/// use lofty::AudioFile;
+	///
+	/// # fn main() -> lofty::Result<()> {
+	/// # let mut m4b_reader = std::io::Cursor::new(&[]);
+	/// let m4b_file = Mp4File::read_from(&mut m4b_reader, false)?;
+	///
+	/// for chapter in m4b_file.chapters() {
+	/// 	println!("{}ms: {:?}", chapter.start_time, chapter.title());
+	/// }
+	/// # Ok(()) }
+	/// ```
+	#[cfg(feature = "id3v2")]
+	pub fn chapters(&self) -> &[Chapter] {
+		&self.chapters
+	}
 }
@@ -1,14 +1,21 @@
 use super::atom_info::{AtomIdent, AtomInfo};
-use super::read::skip_unneeded;
+use super::read::{skip_unneeded, AtomReader};
 use super::trak::Trak;
 #[cfg(feature = "mp4_ilst")]
 use super::{
 	ilst::{read::parse_ilst, Ilst},
-	read::{meta_is_full, AtomReader},
+	read::meta_is_full,
 };
 use crate::error::{FileDecodingError, Result};
 use crate::file::FileType;
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::{Chapter, Frame, FrameFlags, FrameValue, TextEncoding};
+#[cfg(feature = "id3v2")]
+use crate::probe::ParseMode;
+use crate::probe::ParseOptions;
 
+#[cfg(any(feature = "mp4_ilst", feature = "id3v2"))]
+use std::io::SeekFrom;
 use std::io::{Read, Seek};
 
 pub(crate) struct Moov {
@@ -16,6 +23,9 @@ pub(crate) struct Moov {
 	#[cfg(feature = "mp4_ilst")]
 	// Represents a parsed moov.udta.meta.ilst since we don't need anything else
 	pub(crate) meta: Option<Ilst>,
+	#[cfg(feature = "id3v2")]
+	// Represents a parsed moov.udta.chpl (Nero chapters)
+	pub(crate) chapters: Vec<Chapter>,
 }
 
 impl Moov {
@@ -41,22 +51,42 @@ impl Moov {
 		}
 	}
 
-	pub(super) fn parse<R>(reader: &mut AtomReader<R>, read_properties: bool) -> Result<Self>
+	// `options` is only needed for the `udta` atom
+	#[cfg_attr(
+		not(any(feature = "mp4_ilst", feature = "id3v2")),
+		allow(unused_variables)
+	)]
+	pub(super) fn parse<R>(
+		reader: &mut AtomReader<R>,
+		read_properties: bool,
+		options: &mut ParseOptions<'_>,
+	) -> Result<Self>
 	where
 		R: Read + Seek,
 	{
-		let mut traks = Vec::new();
-		#[cfg(feature = "mp4_ilst")]
-		let mut meta = None;
+		let mut moov = Self {
+			traks: Vec::new(),
+			#[cfg(feature = "mp4_ilst")]
+			meta: None,
+			#[cfg(feature = "id3v2")]
+			chapters: Vec::new(),
+		};
+
+		// The duration of the movie in milliseconds, used to end the last chapter
+		#[cfg(feature = "id3v2")]
+		let mut duration = None;
 
 		while let Ok(atom) = reader.next() {
 			if let AtomIdent::Fourcc(fourcc) = atom.ident {
 				match &fourcc {
-					b"trak" if read_properties => traks.push(Trak::parse(reader, &atom)?),
-					#[cfg(feature = "mp4_ilst")]
-					b"udta" => {
-						meta = meta_from_udta(reader, atom.len - 8)?;
+					b"trak" if read_properties => moov.traks.push(Trak::parse(reader, &atom)?),
+					#[cfg(feature = "id3v2")]
+					b"mvhd" => {
+						duration = mvhd_duration(reader)?;
+						reader.seek(SeekFrom::Start(atom.start + atom.len))?;
 					},
+					#[cfg(any(feature = "mp4_ilst", feature = "id3v2"))]
+					b"udta" => moov.parse_udta(reader, &atom, options)?,
 					_ => skip_unneeded(reader, atom.extended, atom.len)?,
 				}
 
@@ -66,27 +96,78 @@ impl Moov {
 			skip_unneeded(reader, atom.extended, atom.len)?
 		}
 
-		Ok(Self {
-			traks,
-			#[cfg(feature = "mp4_ilst")]
-			meta,
-		})
+		#[cfg(feature = "id3v2")]
+		if let (Some(last), Some(duration)) = (moov.chapters.last_mut(), duration) {
+			last.end_time = last.end_time.max(duration);
+		}
+
+		Ok(moov)
+	}
+
+	#[cfg(any(feature = "mp4_ilst", feature = "id3v2"))]
+	fn parse_udta<R>(
+		&mut self,
+		reader: &mut AtomReader<R>,
+		udta: &AtomInfo,
+		options: &mut ParseOptions<'_>,
+	) -> Result<()>
+	where
+		R: Read + Seek,
+	{
+		let udta_end = udta.start + udta.len;
+
+		while reader.position()? < udta_end {
+			let atom = reader.next()?;
+
+			if let AtomIdent::Fourcc(fourcc) = atom.ident {
+				match &fourcc {
+					#[cfg(feature = "mp4_ilst")]
+					b"meta" => {
+						if let Some(ilst) = ilst_from_meta(reader, &atom, options)? {
+							self.meta = Some(ilst);
+						}
+					},
+					#[cfg(feature = "id3v2")]
+					// Chapters aren't essential, an invalid `chpl` atom shouldn't prevent reading the file
+					b"chpl" => match parse_chpl(reader, &atom) {
+						Ok(chapters) => self.chapters = chapters,
+						Err(e) if options.parse_mode == ParseMode::Relaxed => {
+							options.parse_errors.push(e)
+						},
+						Err(_) => {},
+					},
+					_ => {},
+				}
+			}
+
+			reader.seek(SeekFrom::Start(atom.start + atom.len))?;
+		}
+
+		Ok(())
 	}
 }
 
 #[cfg(feature = "mp4_ilst")]
-fn meta_from_udta<R>(reader: &mut AtomReader<R>, len: u64) -> Result<Option<Ilst>>
+fn ilst_from_meta<R>(
+	reader: &mut AtomReader<R>,
+	meta: &AtomInfo,
+	options: &mut ParseOptions<'_>,
+) -> Result<Option<Ilst>>
 where
 	R: Read + Seek,
 {
-	let mut read = 8;
-	let mut meta = (false, 0_u64);
+	// It's possible for the `meta` atom to be non-full,
+	// so we have to check for that case
+	let full_meta_atom = meta_is_full(reader)?;
 
-	while read < len {
+	let mut read = if full_meta_atom { 12 } else { 8 };
+	let mut islt = (false, 0_u64);
+
+	while read < meta.len {
 		let atom = reader.next()?;
 
-		if atom.ident == AtomIdent::Fourcc(*b"meta") {
-			meta = (true, atom.len);
+		if atom.ident == AtomIdent::Fourcc(*b"ilst") {
+			islt = (true, atom.len);
 			break;
 		}
 
@@ -94,37 +175,195 @@ where
 		skip_unneeded(reader, atom.extended, atom.len)?;
 	}
 
-	if !meta.0 {
-		return Ok(None);
+	if islt.0 {
+		return parse_ilst(reader, islt.1 - 8, options).map(Some);
 	}
 
-	// It's possible for the `meta` atom to be non-full,
-	// so we have to check for that case
-	let full_meta_atom = meta_is_full(reader)?;
+	Ok(None)
+}
 
-	if full_meta_atom {
-		read = 12;
+#[cfg(feature = "id3v2")]
+fn mvhd_duration<R>(reader: &mut AtomReader<R>) -> Result<Option<u32>>
+where
+	R: Read + Seek,
+{
+	let version = reader.read_u8()?;
+
+	// Flags (3)
+	reader.seek(SeekFrom::Current(3))?;
+
+	// The creation and modification times come before the timescale and duration
+	let (timescale, duration) = if version == 0 {
+		reader.seek(SeekFrom::Current(8))?;
+		(reader.read_u32()?, u64::from(reader.read_u32()?))
 	} else {
-		read = 8;
+		reader.seek(SeekFrom::Current(16))?;
+		(reader.read_u32()?, reader.read_u64()?)
+	};
+
+	if timescale == 0 {
+		return Ok(None);
 	}
 
-	let mut islt = (false, 0_u64);
+	let duration_ms = u128::from(duration) * 1000 / u128::from(timescale);
+	Ok(Some(u32::try_from(duration_ms).unwrap_or(u32::MAX)))
+}
 
-	while read < meta.1 {
-		let atom = reader.next()?;
+// Nero chapters are stored as a list of start times (in 100ns units) and titles:
+//
+// Version (1)
+// Flags (3)
+// Reserved (4), only present in version 1
+// Chapter count (1)
+//
+// For each chapter:
+//
+// Start time (8)
+// Title length (1)
+// Title (UTF-8)
+#[cfg(feature = "id3v2")]
+fn parse_chpl<R>(reader: &mut AtomReader<R>, chpl: &AtomInfo) -> Result<Vec<Chapter>>
+where
+	R: Read + Seek,
+{
+	const CHAPTER_OFFSET_UNSET: u32 = 0xFFFF_FFFF;
 
-		if atom.ident == AtomIdent::Fourcc(*b"ilst") {
-			islt = (true, atom.len);
-			break;
+	let bad_chpl = || FileDecodingError::new(FileType::MP4, "Found an invalid \"chpl\" atom");
+
+	let chpl_end = chpl.start + chpl.len;
+
+	let version = reader.read_u8()?;
+	reader.seek(SeekFrom::Current(if version == 0 { 3 } else { 7 }))?;
+
+	let count = reader.read_u8()?;
+
+	let mut entries = Vec::with_capacity(count as usize);
+	for _ in 0..count {
+		if reader.position()? + 9 > chpl_end {
+			return Err(bad_chpl().into());
 		}
 
-		read += atom.len;
-		skip_unneeded(reader, atom.extended, atom.len)?;
+		let start = reader.read_u64()?;
+		let title_len = reader.read_u8()?;
+
+		if reader.position()? + u64::from(title_len) > chpl_end {
+			return Err(bad_chpl().into());
+		}
+
+		let mut title = vec![0; title_len as usize];
+		reader.read_exact(&mut title)?;
+
+		let start_ms = u32::try_from(start / 10_000).unwrap_or(u32::MAX);
+		entries.push((start_ms, String::from_utf8_lossy(&title).into_owned()));
 	}
 
-	if islt.0 {
-		return parse_ilst(reader, islt.1 - 8).map(Some);
+	let mut chapters = Vec::with_capacity(entries.len());
+	for (i, (start_time, title)) in entries.iter().enumerate() {
+		// Each chapter ends where the next one begins
+		let end_time = entries
+			.get(i + 1)
+			.map_or(*start_time, |(next_start, _)| *next_start);
+
+		let title_frame = Frame::new(
+			"TIT2",
+			FrameValue::Text {
+				encoding: TextEncoding::UTF8,
+				value: title.clone(),
+			},
+			FrameFlags::default(),
+		)?;
+
+		chapters.push(Chapter {
+			element_id: format!("chp{}", i),
+			start_time: *start_time,
+			end_time,
+			start_offset: CHAPTER_OFFSET_UNSET,
+			end_offset: CHAPTER_OFFSET_UNSET,
+			sub_frames: vec![title_frame],
+		});
 	}
 
-	Ok(None)
+	Ok(chapters)
+}
+
+#[cfg(test)]
+#[cfg(feature = "id3v2")]
+mod tests {
+	use super::Moov;
+	use crate::mp4::read::AtomReader;
+	use crate::mp4::Mp4File;
+	use crate::probe::ParseOptions;
+	use crate::tag::utils::test_utils::read_path;
+	use crate::AudioFile;
+
+	use std::io::Cursor;
+
+	fn atom(ident: &[u8; 4], content: &[u8]) -> Vec<u8> {
+		let mut atom = ((content.len() + 8) as u32).to_be_bytes().to_vec();
+		atom.extend(ident);
+		atom.extend(content);
+		atom
+	}
+
+	#[test]
+	fn nero_chapters() {
+		// Version 0, a timescale of 1000, and a duration of 90 seconds
+		let mut mvhd = vec![0; 12];
+		mvhd.extend(1000_u32.to_be_bytes());
+		mvhd.extend(90_000_u32.to_be_bytes());
+		mvhd.resize(100, 0);
+
+		// Version 1, with 2 chapters starting at 0 and 42.5 seconds
+		let mut chpl = vec![1, 0, 0, 0, 0, 0, 0, 0, 2];
+		chpl.extend(0_u64.to_be_bytes());
+		chpl.push(5);
+		chpl.extend(b"Intro");
+		chpl.extend(425_000_000_u64.to_be_bytes());
+		chpl.push(9);
+		chpl.extend(b"Chapter 1");
+
+		let mut udta = atom(b"free", &[0; 4]);
+		udta.extend(atom(b"chpl", &chpl));
+
+		let mut moov = atom(b"mvhd", &mvhd);
+		moov.extend(atom(b"udta", &udta));
+
+		let mut reader = AtomReader::new(Cursor::new(moov)).unwrap();
+		let moov = Moov::parse(&mut reader, false, &mut ParseOptions::new()).unwrap();
+
+		assert_eq!(moov.chapters.len(), 2);
+
+		assert_eq!(moov.chapters[0].title(), Some("Intro"));
+		assert_eq!(moov.chapters[0].start_time, 0);
+		assert_eq!(moov.chapters[0].end_time, 42_500);
+
+		assert_eq!(moov.chapters[1].title(), Some("Chapter 1"));
+		assert_eq!(moov.chapters[1].start_time, 42_500);
+		assert_eq!(moov.chapters[1].end_time, 90_000);
+		assert_eq!(moov.chapters[1].start_offset, 0xFFFF_FFFF);
+	}
+
+	#[test]
+	fn invalid_chapters() {
+		// The title is longer than the atom
+		let mut chpl = vec![1, 0, 0, 0, 0, 0, 0, 0, 1];
+		chpl.extend(0_u64.to_be_bytes());
+		chpl.push(200);
+		chpl.extend(b"Intro");
+
+		let moov = atom(b"udta", &atom(b"chpl", &chpl));
+
+		let mut reader = AtomReader::new(Cursor::new(moov)).unwrap();
+		let moov = Moov::parse(&mut reader, false, &mut ParseOptions::new()).unwrap();
+
+		assert!(moov.chapters.is_empty());
+	}
+
+	#[test]
+	fn no_chapters() {
+		let file_bytes = read_path("tests/files/assets/minimal/m4a_codec_aac.m4a");
+		let file = Mp4File::read_from(&mut Cursor::new(file_bytes), false).unwrap();
+
+		assert!(file.chapters().is_empty());
+	}
 }
@@ -5,8 +5,10 @@ use super::Mp4File;
 use crate::error::{ErrorKind, FileDecodingError, LoftyError, Result};
 use crate::file::FileType;
 use crate::macros::err;
+use crate::probe::ParseOptions;
 
 use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
 
 use byteorder::{BigEndian, ReadBytesExt};
 
@@ -149,30 +151,67 @@ where
 }
 
 pub(crate) fn read_from<R>(data: &mut R, read_properties: bool) -> Result<Mp4File>
+where
+	R: Read + Seek,
+{
+	read_from_with_options(data, read_properties, &mut ParseOptions::new())
+}
+
+pub(crate) fn read_from_with_options<R>(
+	data: &mut R,
+	read_properties: bool,
+	options: &mut ParseOptions<'_>,
+) -> Result<Mp4File>
 where
 	R: Read + Seek,
 {
 	let mut reader = AtomReader::new(data)?;
 
 	let ftyp = verify_mp4(&mut reader)?;
+	let after_ftyp = reader.position()?;
 
 	Moov::find(&mut reader)?;
-	let moov = Moov::parse(&mut reader, read_properties)?;
+	let moov = Moov::parse(&mut reader, read_properties, options)?;
 
 	let file_length = reader.seek(SeekFrom::End(0))?;
 
+	let properties = if read_properties {
+		super::properties::read_properties(&mut reader, &moov.traks, file_length)?
+	} else {
+		Mp4Properties::default()
+	};
+
+	reader.seek(SeekFrom::Start(after_ftyp))?;
+	let audio_data_range = find_mdat(&mut reader)?.unwrap_or(0..0);
+
 	Ok(Mp4File {
 		ftyp,
 		#[cfg(feature = "mp4_ilst")]
 		ilst_tag: moov.meta,
-		properties: if read_properties {
-			super::properties::read_properties(&mut reader, &moov.traks, file_length)?
-		} else {
-			Mp4Properties::default()
-		},
+		#[cfg(feature = "id3v2")]
+		chapters: moov.chapters,
+		properties,
+		audio_data_range,
 	})
 }
 
+// Finds the content of the first top-level `mdat` atom
+fn find_mdat<R>(reader: &mut AtomReader<R>) -> Result<Option<Range<u64>>>
+where
+	R: Read + Seek,
+{
+	while let Ok(atom) = reader.next() {
+		if atom.ident == AtomIdent::Fourcc(*b"mdat") {
+			let header_len = if atom.extended { 16 } else { 8 };
+			return Ok(Some(atom.start + header_len..atom.start + atom.len));
+		}
+
+		skip_unneeded(reader, atom.extended, atom.len)?;
+	}
+
+	Ok(None)
+}
+
 pub(super) fn skip_unneeded<R>(reader: &mut R, ext: bool, len: u64) -> Result<()>
 where
 	R: Read + Seek,
@@ -293,45 +293,161 @@ impl Header {
 	}
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum XingHeaderType {
+	Xing,
+	// The same as "Xing", but used for CBR streams
+	Info,
+	Vbri,
+}
+
 pub(super) struct XingHeader {
+	pub ty: XingHeaderType,
 	pub frames: u32,
-	pub size: u32,
+	pub size: Option<u32>,
+	pub lame: Option<LameHeader>,
+}
+
+// The LAME extension of a Xing header
+//
+// https://wiki.hydrogenaud.io/index.php?title=LAME#VBR_header_and_LAME_tag
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub(crate) struct LameHeader {
+	pub(crate) version: [u8; 9],
+	// Stored as a fixed point number, where 1.0 is 2^23
+	pub(crate) peak: u32,
+	pub(crate) radio_gain: u16,
+	pub(crate) audiophile_gain: u16,
+	pub(crate) delay: u16,
+	pub(crate) padding: u16,
+}
+
+impl LameHeader {
+	const SIZE: usize = 36;
+
+	fn read(reader: &mut &[u8]) -> Result<Option<Self>> {
+		if reader.len() < Self::SIZE {
+			return Ok(None);
+		}
+
+		let mut version = [0; 9];
+		reader.read_exact(&mut version)?;
+
+		// FFmpeg writes the same header, using its own encoder name
+		if !(version.starts_with(b"LAME")
+			|| version.starts_with(b"Lavf")
+			|| version.starts_with(b"Lavc"))
+		{
+			return Ok(None);
+		}
+
+		// Skip 2 bytes
+		// Info tag revision + VBR method (1)
+		// Lowpass filter value (1)
+		let _info = reader.read_u16::<BigEndian>()?;
+
+		let peak = reader.read_u32::<BigEndian>()?;
+		let radio_gain = reader.read_u16::<BigEndian>()?;
+		let audiophile_gain = reader.read_u16::<BigEndian>()?;
+
+		// Skip 2 bytes
+		// Encoding flags + ATH type (1)
+		// Bitrate (1)
+		let _info = reader.read_u16::<BigEndian>()?;
+
+		// The delay and padding are both 12 bits
+		let delay_padding = reader.read_u24::<BigEndian>()?;
+
+		Ok(Some(Self {
+			version,
+			peak,
+			radio_gain,
+			audiophile_gain,
+			delay: (delay_padding >> 12) as u16,
+			padding: (delay_padding & 0xFFF) as u16,
+		}))
+	}
 }
 
 impl XingHeader {
+	// The VBRI header is always 32 bytes after the frame header, rather than after the side information
+	pub(super) const VBRI_OFFSET: u64 = 36;
+	// A Xing header with all of its optional fields (120 bytes), followed by a LAME header (36 bytes)
+	pub(super) const MAX_SIZE: u64 = 156;
+
 	pub(super) fn read(reader: &mut &[u8]) -> Result<Option<Self>> {
+		const FRAMES_FLAG: u32 = 0x0001;
+		const BYTES_FLAG: u32 = 0x0002;
+		const TOC_FLAG: u32 = 0x0004;
+		const QUALITY_FLAG: u32 = 0x0008;
+
 		let reader_len = reader.len();
 
 		let mut header = [0; 4];
-		reader.read_exact(&mut header)?;
+		if reader.read_exact(&mut header).is_err() {
+			return Ok(None);
+		}
 
 		match &header {
 			b"Xing" | b"Info" => {
-				if reader_len < 16 {
+				if reader_len < 8 {
 					return Err(FileDecodingError::new(
 						FileType::MPEG,
-						"Xing header has an invalid size (< 16)",
+						"Xing header has an invalid size (< 8)",
 					)
 					.into());
 				}
 
-				let mut flags = [0; 4];
-				reader.read_exact(&mut flags)?;
-
-				if flags[3] & 0x03 != 0x03 {
-					return Ok(None);
-					// TODO: Debug message?
-					// 	return Err(FileDecodingError::new(
-					// 		FileType::MP3,
-					// 		"Xing header doesn't have required flags set (0x0001 and 0x0002)",
-					// 	)
-					// 	.into());
+				let ty = if &header == b"Xing" {
+					XingHeaderType::Xing
+				} else {
+					XingHeaderType::Info
+				};
+
+				let flags = reader.read_u32::<BigEndian>()?;
+
+				// Each of the fields is only present if its flag is set
+				let frames = if flags & FRAMES_FLAG == FRAMES_FLAG {
+					Some(reader.read_u32::<BigEndian>()?)
+				} else {
+					None
+				};
+
+				let size = if flags & BYTES_FLAG == BYTES_FLAG {
+					Some(reader.read_u32::<BigEndian>()?)
+				} else {
+					None
+				};
+
+				if flags & TOC_FLAG == TOC_FLAG {
+					// The TOC is only used for seeking, so a truncated one is ignored, along
+					// with everything after it
+					if reader.len() < 100 {
+						*reader = &[];
+						return Ok(frames.map(|frames| Self {
+							ty,
+							frames,
+							size,
+							lame: None,
+						}));
+					}
+
+					*reader = &reader[100..];
 				}
 
-				let frames = reader.read_u32::<BigEndian>()?;
-				let size = reader.read_u32::<BigEndian>()?;
+				if flags & QUALITY_FLAG == QUALITY_FLAG {
+					let _quality = reader.read_u32::<BigEndian>()?;
+				}
+
+				let lame = LameHeader::read(reader)?;
 
-				Ok(Some(Self { frames, size }))
+				// Without a frame count, the header is of no use
+				Ok(frames.map(|frames| Self {
+					ty,
+					frames,
+					size,
+					lame,
+				}))
 			},
 			b"VBRI" => {
 				if reader_len < 32 {
@@ -351,14 +467,19 @@ impl XingHeader {
 				let size = reader.read_u32::<BigEndian>()?;
 				let frames = reader.read_u32::<BigEndian>()?;
 
-				Ok(Some(Self { frames, size }))
+				Ok(Some(Self {
+					ty: XingHeaderType::Vbri,
+					frames,
+					size: Some(size),
+					lame: None,
+				}))
 			},
 			_ => Ok(None),
 		}
 	}
 
 	pub(super) fn is_valid(&self) -> bool {
-		self.frames > 0 && self.size > 0
+		self.frames > 0 && self.size != Some(0)
 	}
 }
 
@@ -397,4 +518,46 @@ mod tests {
 		let mut reader = Cursor::new(bytes);
 		test(&mut reader, Some(283));
 	}
+
+	#[test]
+	fn xing_header_flags() {
+		use super::{XingHeader, XingHeaderType};
+
+		// Frames and quality, without a byte count or TOC
+		let mut xing = b"Xing\x00\x00\x00\x09".to_vec();
+		xing.extend(1000_u32.to_be_bytes());
+		xing.extend(100_u32.to_be_bytes());
+
+		let header = XingHeader::read(&mut &xing[..]).unwrap().unwrap();
+		assert_eq!(header.ty, XingHeaderType::Xing);
+		assert_eq!(header.frames, 1000);
+		assert_eq!(header.size, None);
+		assert!(header.is_valid());
+
+		// All fields
+		let mut info = b"Info\x00\x00\x00\x0F".to_vec();
+		info.extend(1000_u32.to_be_bytes());
+		info.extend(50_000_u32.to_be_bytes());
+		info.extend([0; 100]);
+		info.extend(100_u32.to_be_bytes());
+
+		let reader = &mut &info[..];
+		let header = XingHeader::read(reader).unwrap().unwrap();
+		assert_eq!(header.ty, XingHeaderType::Info);
+		assert_eq!(header.frames, 1000);
+		assert_eq!(header.size, Some(50_000));
+		assert!(reader.is_empty());
+
+		// A truncated TOC is ignored
+		let reader = &mut &info[..50];
+		let header = XingHeader::read(reader).unwrap().unwrap();
+		assert_eq!(header.frames, 1000);
+		assert_eq!(header.size, Some(50_000));
+		assert!(header.lame.is_none());
+		assert!(reader.is_empty());
+
+		// No frame count
+		let xing = b"Xing\x00\x00\x00\x02\x00\x00\xC3\x50";
+		assert!(XingHeader::read(&mut &xing[..]).unwrap().is_none());
+	}
 }
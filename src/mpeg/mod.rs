@@ -2,18 +2,25 @@
 mod constants;
 pub(crate) mod header;
 mod properties;
-mod read;
+pub(crate) mod read;
 pub(crate) mod write;
 
 pub use header::{ChannelMode, Emphasis, Layer, MpegVersion};
 pub use properties::MPEGProperties;
+pub use write::{ID3v1Policy, MPEGWriteOptions};
 
 #[cfg(feature = "ape")]
 use crate::ape::tag::ApeTag;
+#[cfg(feature = "lyrics3v2")]
+use crate::id3::lyrics3v2::Lyrics3v2Tag;
 #[cfg(feature = "id3v1")]
 use crate::id3::v1::tag::ID3v1Tag;
 #[cfg(feature = "id3v2")]
 use crate::id3::v2::tag::ID3v2Tag;
+#[cfg(feature = "id3v2")]
+use crate::traits::Accessor;
+
+use std::ops::Range;
 
 use lofty_attr::LoftyFile;
 
@@ -33,6 +40,31 @@ pub struct MPEGFile {
 	#[cfg(feature = "ape")]
 	#[lofty(tag_type = "APE")]
 	pub(crate) ape_tag: Option<ApeTag>,
+	/// A Lyrics3v2 tag
+	#[cfg(feature = "lyrics3v2")]
+	#[lofty(tag_type = "Lyrics3v2")]
+	pub(crate) lyrics3v2_tag: Option<Lyrics3v2Tag>,
 	/// The file's audio properties
 	pub(crate) properties: MPEGProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
+}
+
+impl MPEGFile {
+	/// Returns the software used to encode the file
+	///
+	/// This is taken from the ID3v2 tag's `TSSE` frame, falling back to the
+	/// version string in the LAME header (see [`MPEGProperties::lame_version`]).
+	///
+	/// NOTE: The LAME header is only available if the properties were read.
+	pub fn encoder(&self) -> Option<&str> {
+		#[cfg(feature = "id3v2")]
+		if let Some(encoder) = self.id3v2_tag.as_ref().and_then(Accessor::encoder) {
+			return Some(encoder);
+		}
+
+		self.properties
+			.lame_version()
+			.filter(|version| !version.is_empty())
+	}
 }
@@ -1,7 +1,10 @@
-use super::header::{ChannelMode, Emphasis, Header, Layer, MpegVersion, XingHeader};
+use super::header::{
+	ChannelMode, Emphasis, Header, LameHeader, Layer, MpegVersion, XingHeader, XingHeaderType,
+};
 use crate::error::Result;
 use crate::mpeg::header::{cmp_header, rev_search_for_frame_sync, HeaderCmpResult};
 use crate::properties::FileProperties;
+use crate::ReplayGain;
 
 use std::io::{Read, Seek, SeekFrom};
 use std::time::Duration;
@@ -24,6 +27,8 @@ pub struct MPEGProperties {
 	pub(crate) copyright: bool,
 	pub(crate) original: bool,
 	pub(crate) emphasis: Emphasis,
+	pub(crate) vbr: bool,
+	pub(crate) lame: Option<LameHeader>,
 }
 
 impl From<MPEGProperties> for FileProperties {
@@ -99,6 +104,72 @@ impl MPEGProperties {
 	pub fn emphasis(&self) -> Emphasis {
 		self.emphasis
 	}
+
+	/// Whether the file has a VBR (Xing or VBRI) header
+	///
+	/// If so, the duration was calculated from the header's frame count.
+	///
+	/// NOTE: CBR files can also have a Xing (Info) header, which will be used for the duration in the same way,
+	/// but this will return `false`. Files without either header have their duration estimated from the
+	/// bitrate and stream size.
+	pub fn is_vbr(&self) -> bool {
+		self.vbr
+	}
+
+	/// The number of samples added to the start of the stream by the encoder
+	///
+	/// This is only available if the file has a LAME header.
+	pub fn encoder_delay(&self) -> Option<u16> {
+		self.lame.map(|lame| lame.delay)
+	}
+
+	/// The number of samples added to the end of the stream by the encoder
+	///
+	/// This is only available if the file has a LAME header.
+	pub fn encoder_padding(&self) -> Option<u16> {
+		self.lame.map(|lame| lame.padding)
+	}
+
+	/// The encoder version string from the LAME header, such as "LAME3.99r"
+	pub fn lame_version(&self) -> Option<&str> {
+		let version = &self.lame.as_ref()?.version;
+		let version = std::str::from_utf8(version).ok()?;
+
+		Some(version.trim_end_matches(|c| c == '\0' || c == ' '))
+	}
+
+	/// The ReplayGain values stored in the LAME header
+	///
+	/// The radio and audiophile gains are used as the track and album gains respectively. There is no album peak.
+	///
+	/// This will return `None` if the file doesn't have a LAME header, or if none of the values are set.
+	pub fn lame_replay_gain(&self) -> Option<ReplayGain> {
+		// The upper 3 bits are the name code (1 = radio, 2 = audiophile), followed by 3 bits for the
+		// originator, a sign bit, and the gain in tenths of a dB
+		fn gain(field: u16, name_code: u16) -> Option<f32> {
+			if field >> 13 != name_code {
+				return None;
+			}
+
+			let gain = f32::from(field & 0x1FF) / 10.0;
+			Some(if field & 0x200 == 0 { gain } else { -gain })
+		}
+
+		let lame = self.lame?;
+
+		let replay_gain = ReplayGain {
+			track_gain: gain(lame.radio_gain, 1),
+			track_peak: (lame.peak > 0).then(|| lame.peak as f32 / (1 << 23) as f32),
+			album_gain: gain(lame.audiophile_gain, 2),
+			album_peak: None,
+		};
+
+		if replay_gain == ReplayGain::default() {
+			return None;
+		}
+
+		Some(replay_gain)
+	}
 }
 
 pub(super) fn read_properties<R>(
@@ -131,13 +202,22 @@ where
 
 	match xing_header {
 		Some(xing_header) if first_frame_header.sample_rate > 0 && xing_header.is_valid() => {
-			let frame_time =
-				u32::from(first_frame_header.samples) * 1000 / first_frame_header.sample_rate;
-			let length = u64::from(frame_time) * u64::from(xing_header.frames);
+			// The length of a single frame isn't always a whole number of milliseconds,
+			// so the total sample count is used to avoid drift
+			let total_samples =
+				u64::from(xing_header.frames) * u64::from(first_frame_header.samples);
+			let length = total_samples * 1000 / u64::from(first_frame_header.sample_rate);
+
+			// Without a byte count, the rest of the file is assumed to be the stream
+			let stream_len = xing_header
+				.size
+				.map_or(file_length - first_frame_offset, u64::from);
 
 			properties.duration = Duration::from_millis(length);
 			properties.overall_bitrate = ((file_length * 8) / length) as u32;
-			properties.audio_bitrate = ((u64::from(xing_header.size) * 8) / length) as u32;
+			properties.audio_bitrate = ((stream_len * 8) / length) as u32;
+			properties.vbr = xing_header.ty != XingHeaderType::Info;
+			properties.lame = xing_header.lame;
 		},
 		_ if first_frame_header.bitrate > 0 => {
 			properties.audio_bitrate = first_frame_header.bitrate;
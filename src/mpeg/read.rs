@@ -7,10 +7,12 @@ use crate::ape::tag::read::read_ape_tag;
 use crate::error::{FileDecodingError, Result};
 use crate::file::FileType;
 #[cfg(feature = "id3v2")]
-use crate::id3::v2::read::parse_id3v2;
+use crate::id3::v2::read::parse_id3v2_with_options;
 use crate::id3::v2::read_id3v2_header;
 use crate::id3::{find_id3v1, find_lyrics3v2, ID3FindResults};
 use crate::macros::err;
+use crate::probe::{ParseMode, ParseOptions};
+use crate::tag::TagType;
 
 use std::io::{Read, Seek, SeekFrom};
 
@@ -20,10 +22,35 @@ pub(super) fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<MPEG
 where
 	R: Read + Seek,
 {
+	read_from_with_options(reader, read_properties, &mut ParseOptions::new())
+}
+
+// Only the tags in `options.tag_types` are read (or all of them if `None`).
+//
+// Without properties, the search for the first MPEG frame is skipped, and reading stops as soon
+// as all of the requested tags are found.
+pub(crate) fn read_from_with_options<R>(
+	reader: &mut R,
+	read_properties: bool,
+	options: &mut ParseOptions<'_>,
+) -> Result<MPEGFile>
+where
+	R: Read + Seek,
+{
+	let tag_types = options.tag_types;
+	let wanted = |tag_type: TagType| tag_types.map_or(true, |types| types.contains(&tag_type));
+	let done = |file: &MPEGFile| {
+		!read_properties
+			&& tag_types.map_or(false, |types| {
+				types.iter().all(|tag_type| has_tag(file, *tag_type))
+			})
+	};
+
 	let mut file = MPEGFile::default();
 
 	let mut first_frame_offset = 0;
 	let mut first_frame_header = None;
+	let mut audio_start = 0;
 
 	// Skip any invalid padding
 	while reader.read_u8()? == 0 {}
@@ -43,11 +70,20 @@ where
 				let skip_footer = header.flags.footer;
 
 				#[cfg(feature = "id3v2")]
-				{
-					let id3v2 = parse_id3v2(reader, header)?;
+				if wanted(TagType::ID3v2) {
+					let id3v2 = parse_id3v2_with_options(reader, header, options)?;
 					file.id3v2_tag = Some(id3v2);
+				} else {
+					reader.seek(SeekFrom::Current(i64::from(
+						header.size - header.extended_size,
+					)))?;
 				}
 
+				#[cfg(not(feature = "id3v2"))]
+				reader.seek(SeekFrom::Current(i64::from(
+					header.size - header.extended_size,
+				)))?;
+
 				// Skip over the footer
 				if skip_footer {
 					reader.seek(SeekFrom::Current(10))?;
@@ -62,18 +98,14 @@ where
 				if &header_remaining == b"AGEX" {
 					let ape_header = read_ape_header(reader, false)?;
 
-					#[cfg(not(feature = "ape"))]
-					{
-						let size = ape_header.size;
-						reader.seek(SeekFrom::Current(size as i64))?;
-					}
-
 					#[cfg(feature = "ape")]
-					{
-						file.ape_tag =
-							Some(crate::ape::tag::read::read_ape_tag(reader, ape_header)?);
+					if wanted(TagType::APE) {
+						file.ape_tag = Some(read_ape_tag(reader, ape_header)?);
+						continue;
 					}
 
+					// Skip the items and footer, the size includes the header we already read
+					reader.seek(SeekFrom::Current(i64::from(ape_header.size) - 32))?;
 					continue;
 				}
 
@@ -86,25 +118,60 @@ where
 				#[allow(clippy::neg_multiply)]
 				reader.seek(SeekFrom::Current(-1 * header.len() as i64))?;
 
+				// The first frame is only needed for the properties
+				if !read_properties {
+					audio_start = reader.stream_position()?;
+					break;
+				}
+
 				#[allow(clippy::used_underscore_binding)]
 				if let Some((_first_first_header, _first_frame_offset)) = find_next_frame(reader)? {
 					first_frame_offset = _first_frame_offset;
 					first_frame_header = Some(_first_first_header);
+					audio_start = first_frame_offset;
 					break;
 				}
 			},
 		}
 	}
 
+	if done(&file) {
+		// The trailing tags are never searched for, so they aren't excluded
+		file.audio_data_range = audio_start..reader.seek(SeekFrom::End(0))?;
+		return Ok(file);
+	}
+
 	#[allow(unused_variables)]
-	let ID3FindResults(header, id3v1) = find_id3v1(reader, true)?;
+	let ID3FindResults(header, id3v1) = find_id3v1(reader, wanted(TagType::ID3v1))?;
 
 	#[cfg(feature = "id3v1")]
 	if header.is_some() {
 		file.id3v1_tag = id3v1;
 	}
 
-	let _ = find_lyrics3v2(reader)?;
+	if done(&file) {
+		file.audio_data_range = audio_start..reader.stream_position()?;
+		return Ok(file);
+	}
+
+	#[allow(unused_variables)]
+	let ID3FindResults(header, size) = find_lyrics3v2(reader)?;
+
+	#[cfg(feature = "lyrics3v2")]
+	if header.is_some() && wanted(TagType::Lyrics3v2) {
+		let mut content = crate::macros::try_vec![0; size as usize];
+		reader.read_exact(&mut content)?;
+
+		// A malformed Lyrics3v2 tag doesn't affect the audio or the other tags, so it's skipped
+		match crate::id3::lyrics3v2::read::parse_lyrics3v2(&content) {
+			Ok(lyrics3v2) => file.lyrics3v2_tag = Some(lyrics3v2),
+			Err(e) if options.parse_mode == ParseMode::Relaxed => options.parse_errors.push(e),
+			Err(_) => {},
+		}
+
+		// Seek back to the start of the tag, where an APE tag may end
+		reader.seek(SeekFrom::Current(-i64::from(size)))?;
+	}
 
 	reader.seek(SeekFrom::Current(-32))?;
 
@@ -113,18 +180,20 @@ where
 
 	match &ape_preamble {
 		APE_PREAMBLE => {
+			// The preamble is the first 8 bytes of the 32 byte footer
+			let footer_end = reader.stream_position()? + 24;
+
 			let ape_header = read_ape_header(reader, true)?;
 			let size = ape_header.size;
 
 			#[cfg(feature = "ape")]
-			{
+			if wanted(TagType::APE) {
 				let ape = read_ape_tag(reader, ape_header)?;
 				file.ape_tag = Some(ape);
 			}
 
 			// Seek back to the start of the tag
-			let pos = reader.stream_position()?;
-			reader.seek(SeekFrom::Start(pos - u64::from(size)))?;
+			reader.seek(SeekFrom::Start(footer_end - u64::from(size)))?;
 		},
 		// Correct the position (APE header - Preamble)
 		_ => {
@@ -133,6 +202,7 @@ where
 	}
 
 	let last_frame_offset = reader.stream_position()?;
+	file.audio_data_range = audio_start.min(last_frame_offset)..last_frame_offset;
 	file.properties = MPEGProperties::default();
 
 	if read_properties {
@@ -154,14 +224,26 @@ where
 
 		let first_frame_offset = first_frame_offset;
 
-		// Try to read a Xing header
-		let xing_header_location = first_frame_offset + u64::from(first_frame_header.data_start);
-		reader.seek(SeekFrom::Start(xing_header_location))?;
-
-		let mut xing_reader = [0; 32];
-		reader.read_exact(&mut xing_reader)?;
-
-		let xing_header = XingHeader::read(&mut &xing_reader[..])?;
+		// Try to read a Xing/Info header, which follows the side information,
+		// falling back to a VBRI header
+		let mut xing_header = None;
+		for offset in [
+			u64::from(first_frame_header.data_start),
+			XingHeader::VBRI_OFFSET,
+		] {
+			reader.seek(SeekFrom::Start(first_frame_offset + offset))?;
+
+			let mut xing_reader = Vec::new();
+			reader
+				.by_ref()
+				.take(XingHeader::MAX_SIZE)
+				.read_to_end(&mut xing_reader)?;
+
+			xing_header = XingHeader::read(&mut &xing_reader[..])?;
+			if xing_header.is_some() {
+				break;
+			}
+		}
 
 		let file_length = reader.seek(SeekFrom::End(0))?;
 
@@ -178,6 +260,21 @@ where
 	Ok(file)
 }
 
+#[allow(unused_variables)]
+fn has_tag(file: &MPEGFile, tag_type: TagType) -> bool {
+	match tag_type {
+		#[cfg(feature = "id3v2")]
+		TagType::ID3v2 => file.id3v2_tag.is_some(),
+		#[cfg(feature = "id3v1")]
+		TagType::ID3v1 => file.id3v1_tag.is_some(),
+		#[cfg(feature = "ape")]
+		TagType::APE => file.ape_tag.is_some(),
+		#[cfg(feature = "lyrics3v2")]
+		TagType::Lyrics3v2 => file.lyrics3v2_tag.is_some(),
+		_ => false,
+	}
+}
+
 // Searches for the next frame, comparing it to the following one
 fn find_next_frame<R>(reader: &mut R) -> Result<Option<(Header, u64)>>
 where
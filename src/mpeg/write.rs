@@ -11,13 +11,82 @@ use crate::tag::{Tag, TagType};
 
 use std::fs::File;
 
+/// What to do with an existing ID3v1 tag when writing an ID3v2 tag to an MPEG file
+///
+/// See [`MPEGWriteOptions`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ID3v1Policy {
+	/// Leave the ID3v1 tag untouched
+	Keep,
+	/// Remove the ID3v1 tag
+	Remove,
+	/// Replace the ID3v1 tag with the fields of the ID3v2 tag
+	///
+	/// NOTE: The ID3v1 tag will be removed if none of the ID3v2 tag's fields can be represented in ID3v1.
+	Mirror,
+}
+
+/// Options to control how an ID3v2 tag is written to an MPEG file
+///
+/// This is used in [`ID3v2Tag::save_to_mpeg`](crate::id3::v2::ID3v2Tag::save_to_mpeg) and
+/// [`TaggedFile::save_to_mpeg`](crate::TaggedFile::save_to_mpeg).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lofty::id3::v2::ID3v2Tag;
+/// use lofty::mpeg::{ID3v1Policy, MPEGWriteOptions};
+///
+/// # fn main() -> lofty::Result<()> {
+/// # let mut mpeg_file = std::fs::OpenOptions::new().read(true).write(true).open("foo.mp3")?;
+/// let tag = ID3v2Tag::default();
+///
+/// // Some players prefer ID3v1 over ID3v2, so we don't want a stale ID3v1 tag left behind
+/// let options = MPEGWriteOptions::new().id3v1(ID3v1Policy::Remove);
+///
+/// tag.save_to_mpeg(&mut mpeg_file, options)?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct MPEGWriteOptions {
+	/// What to do with an existing ID3v1 tag, defaults to [`ID3v1Policy::Keep`]
+	pub id3v1: ID3v1Policy,
+}
+
+impl Default for MPEGWriteOptions {
+	fn default() -> Self {
+		Self {
+			id3v1: ID3v1Policy::Keep,
+		}
+	}
+}
+
+impl MPEGWriteOptions {
+	/// Creates a new `MPEGWriteOptions`, same as `Default`
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// What to do with an existing ID3v1 tag
+	///
+	/// NOTE: Anything other than [`ID3v1Policy::Keep`] requires the `id3v1` feature
+	#[must_use]
+	pub fn id3v1(mut self, policy: ID3v1Policy) -> Self {
+		self.id3v1 = policy;
+		self
+	}
+}
+
 #[allow(unused_variables)]
-pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<()> {
+pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<bool> {
 	match tag.tag_type() {
 		#[cfg(feature = "ape")]
 		TagType::APE => ape::tag::ApeTagRef {
 			read_only: false,
-			items: ape::tag::tagitems_into_ape(tag.items()),
+			version: ape::tag::ApeTagVersion::V2,
+			items: ape::tag::tagitems_into_ape(tag),
 		}
 		.write_to(data),
 		#[cfg(feature = "id3v1")]
@@ -25,9 +94,47 @@ pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<()> {
 		#[cfg(feature = "id3v2")]
 		TagType::ID3v2 => v2::tag::Id3v2TagRef {
 			flags: v2::ID3v2TagFlags::default(),
+			version: v2::ID3v2Version::V4,
 			frames: v2::tag::tag_frames(tag),
 		}
 		.write_to(data),
+		#[cfg(feature = "lyrics3v2")]
+		TagType::Lyrics3v2 => crate::id3::lyrics3v2::tag::Lyrics3v2TagRef {
+			fields: crate::id3::lyrics3v2::tag::tagitems_into_lyrics3v2(tag.items()),
+		}
+		.write_to(data)
+		.map(|()| true),
+		_ => err!(UnsupportedTag),
+	}
+}
+
+// Applies the ID3v1 policy after the ID3v2 tag has been written
+#[cfg(feature = "id3v2")]
+#[allow(unused_variables, unreachable_patterns)]
+pub(crate) fn write_id3v1_policy(
+	data: &mut File,
+	id3v2: &v2::ID3v2Tag,
+	policy: ID3v1Policy,
+) -> Result<bool> {
+	match policy {
+		ID3v1Policy::Keep => Ok(false),
+		#[cfg(feature = "id3v1")]
+		ID3v1Policy::Remove => {
+			Into::<v1::tag::Id3v1TagRef<'_>>::into(&Tag::new(TagType::ID3v1)).write_to(data)
+		},
+		#[cfg(feature = "id3v1")]
+		ID3v1Policy::Mirror => {
+			use crate::traits::Accessor;
+
+			let tag: Tag = id3v2.clone().into();
+			let year = id3v2.year().map(|year| year.to_string());
+
+			// ID3v2 only has a recording date, the year has to be taken from it
+			let mut id3v1 = Into::<v1::tag::Id3v1TagRef<'_>>::into(&tag);
+			id3v1.year = id3v1.year.or(year.as_deref());
+
+			id3v1.write_to(data)
+		},
 		_ => err!(UnsupportedTag),
 	}
 }
@@ -0,0 +1,10 @@
+pub(super) const SV8_MAGIC: &[u8; 4] = b"MPCK";
+pub(super) const SV7_MAGIC: &[u8; 3] = b"MP+";
+
+// Shared between SV7 and SV8
+pub(super) const FREQUENCY_TABLE: [u32; 4] = [44100, 48000, 37800, 32000];
+
+// SV7 streams are made up of frames of 1152 samples
+pub(super) const SV7_FRAME_SAMPLES: u64 = 1152;
+// The delay introduced by the decoder's synthesis filter, used for SV7 streams that aren't gapless
+pub(super) const SV7_SYNTH_DELAY: u64 = 481;
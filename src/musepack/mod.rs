@@ -0,0 +1,41 @@
+//! Musepack specific items
+//!
+//! ## File notes
+//!
+//! Both the SV7 and SV8 stream versions are supported. Older stream versions (SV4-SV6)
+//! will be rejected.
+mod constants;
+mod properties;
+mod read;
+pub(crate) mod write;
+
+#[cfg(feature = "ape")]
+use crate::ape::tag::ApeTag;
+#[cfg(feature = "id3v1")]
+use crate::id3::v1::tag::ID3v1Tag;
+
+use std::ops::Range;
+
+use lofty_attr::LoftyFile;
+
+// Exports
+pub use properties::MpcProperties;
+
+/// A Musepack file
+#[derive(Default, LoftyFile)]
+#[lofty(read_fn = "read::read_from")]
+#[lofty(file_type = "Musepack")]
+pub struct MpcFile {
+	/// An ID3v1 tag
+	#[cfg(feature = "id3v1")]
+	#[lofty(tag_type = "ID3v1")]
+	pub(crate) id3v1_tag: Option<ID3v1Tag>,
+	/// An APEv1/v2 tag
+	#[cfg(feature = "ape")]
+	#[lofty(tag_type = "APE")]
+	pub(crate) ape_tag: Option<ApeTag>,
+	/// The file's audio properties
+	pub(crate) properties: MpcProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
+}
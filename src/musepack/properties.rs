@@ -0,0 +1,267 @@
+use super::constants::{FREQUENCY_TABLE, SV7_FRAME_SAMPLES, SV7_SYNTH_DELAY};
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+use crate::macros::try_vec;
+use crate::properties::FileProperties;
+
+use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+/// A Musepack file's audio properties
+pub struct MpcProperties {
+	pub(crate) version: u8,
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) channels: u8,
+}
+
+impl From<MpcProperties> for FileProperties {
+	fn from(input: MpcProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: None,
+			channels: Some(input.channels),
+		}
+	}
+}
+
+impl MpcProperties {
+	/// Duration
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+
+	/// Stream version (7 or 8)
+	pub fn version(&self) -> u8 {
+		self.version
+	}
+}
+
+// http://trac.musepack.net/musepack/wiki/SV8Specification
+
+// The largest possible size field is 9 bytes
+const SV8_MAX_SIZE_FIELD_LEN: u8 = 9;
+
+pub(super) fn read_sv8_properties<R>(
+	reader: &mut R,
+	stream_length: u64,
+	file_length: u64,
+) -> Result<MpcProperties>
+where
+	R: Read + Seek,
+{
+	// The stream header should be the first packet, but we'll skip anything that comes before it
+	let stream_header = loop {
+		let mut key = [0; 2];
+		reader.read_exact(&mut key)?;
+
+		let (packet_size, size_len) = read_sv8_size(reader)?;
+
+		// The packet size includes the key and the size field itself
+		let content_size = packet_size
+			.checked_sub(2 + u64::from(size_len))
+			.ok_or_else(|| {
+				FileDecodingError::new(FileType::Musepack, "Packet has an invalid size")
+			})?;
+
+		match &key {
+			b"SH" => {
+				if content_size > stream_length {
+					return Err(FileDecodingError::new(
+						FileType::Musepack,
+						"Stream header packet is larger than the stream",
+					)
+					.into());
+				}
+
+				let mut content = try_vec![0; content_size as usize];
+				reader.read_exact(&mut content)?;
+
+				break content;
+			},
+			// We've hit the audio data without finding a stream header
+			b"AP" | b"SE" => {
+				return Err(FileDecodingError::new(
+					FileType::Musepack,
+					"File does not contain a stream header packet",
+				)
+				.into());
+			},
+			_ => {
+				reader.seek(SeekFrom::Current(content_size as i64))?;
+			},
+		}
+	};
+
+	let stream_header = &mut &*stream_header;
+
+	let _crc = stream_header.read_u32::<LittleEndian>()?;
+
+	let version = stream_header.read_u8()?;
+	if version != 8 {
+		return Err(FileDecodingError::new(
+			FileType::Musepack,
+			"Stream header has an unknown stream version",
+		)
+		.into());
+	}
+
+	let (sample_count, _) = read_sv8_size(stream_header)?;
+	let (beginning_silence, _) = read_sv8_size(stream_header)?;
+
+	// Sample frequency (3), Max used bands (5)
+	let sample_rate_idx = stream_header.read_u8()? >> 5;
+	let sample_rate = match FREQUENCY_TABLE.get(sample_rate_idx as usize) {
+		Some(sample_rate) => *sample_rate,
+		None => {
+			return Err(FileDecodingError::new(
+				FileType::Musepack,
+				"Stream header has an invalid sample rate index",
+			)
+			.into())
+		},
+	};
+
+	// Channel count - 1 (4), Mid side stereo (1), Audio block frames (3)
+	let channels = (stream_header.read_u8()? >> 4) + 1;
+
+	let mut properties = MpcProperties {
+		version,
+		sample_rate,
+		channels,
+		..MpcProperties::default()
+	};
+
+	calculate_length(
+		&mut properties,
+		sample_count.saturating_sub(beginning_silence),
+		stream_length,
+		file_length,
+	);
+
+	Ok(properties)
+}
+
+// Reads the variable length size field used in SV8 packets, returning the value and how many
+// bytes it took up
+fn read_sv8_size<R>(reader: &mut R) -> Result<(u64, u8)>
+where
+	R: Read,
+{
+	let mut size = 0_u64;
+	let mut len = 0_u8;
+
+	loop {
+		let byte = reader.read_u8()?;
+		len += 1;
+
+		size = (size << 7) | u64::from(byte & 0x7F);
+
+		if byte & 0x80 == 0 {
+			break;
+		}
+
+		if len == SV8_MAX_SIZE_FIELD_LEN {
+			return Err(FileDecodingError::new(
+				FileType::Musepack,
+				"Packet has an invalid size field",
+			)
+			.into());
+		}
+	}
+
+	Ok((size, len))
+}
+
+// http://trac.musepack.net/musepack/wiki/SV7Specification
+
+pub(super) fn read_sv7_properties<R>(
+	reader: &mut R,
+	stream_length: u64,
+	file_length: u64,
+) -> Result<MpcProperties>
+where
+	R: Read,
+{
+	let frame_count = reader.read_u32::<LittleEndian>()?;
+
+	// Max level (16), sample frequency (2), link (2), profile (4), MS (1), IS (1), max band (6)
+	let flags = reader.read_u32::<LittleEndian>()?;
+
+	// The sample frequency is an index into the frequency table, not the actual rate
+	let sample_rate = FREQUENCY_TABLE[((flags >> 16) & 0x03) as usize];
+
+	// Title gain/peak (4), album gain/peak (4)
+	let _replay_gain = reader.read_u64::<LittleEndian>()?;
+
+	// True gapless (1), last frame length (11), fast seeking (1), reserved
+	let gapless_info = reader.read_u32::<LittleEndian>()?;
+
+	let mut sample_count = u64::from(frame_count) * SV7_FRAME_SAMPLES;
+	if (gapless_info >> 31) & 1 == 1 {
+		let last_frame_samples = u64::from((gapless_info >> 20) & 0x7FF);
+		sample_count = sample_count.saturating_sub(SV7_FRAME_SAMPLES - last_frame_samples);
+	} else {
+		sample_count = sample_count.saturating_sub(SV7_SYNTH_DELAY);
+	}
+
+	let mut properties = MpcProperties {
+		version: 7,
+		sample_rate,
+		// SV7 only supports stereo
+		channels: 2,
+		..MpcProperties::default()
+	};
+
+	calculate_length(&mut properties, sample_count, stream_length, file_length);
+
+	Ok(properties)
+}
+
+fn calculate_length(
+	properties: &mut MpcProperties,
+	sample_count: u64,
+	stream_length: u64,
+	file_length: u64,
+) {
+	if sample_count == 0 || properties.sample_rate == 0 {
+		return;
+	}
+
+	let length = sample_count * 1000 / u64::from(properties.sample_rate);
+	properties.duration = Duration::from_millis(length);
+
+	if length > 0 {
+		properties.audio_bitrate = crate::div_ceil(stream_length * 8, length) as u32;
+		properties.overall_bitrate = crate::div_ceil(file_length * 8, length) as u32;
+	}
+}
@@ -0,0 +1,122 @@
+use super::constants::{SV7_MAGIC, SV8_MAGIC};
+use super::properties::MpcProperties;
+use super::MpcFile;
+use crate::ape::constants::APE_PREAMBLE;
+use crate::ape::header::read_ape_header;
+#[cfg(feature = "ape")]
+use crate::ape::tag::read::read_ape_tag;
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+use crate::id3::{find_id3v1, find_id3v2, find_lyrics3v2, ID3FindResults};
+use crate::macros::err;
+
+use std::io::{Read, Seek, SeekFrom};
+
+pub(super) fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<MpcFile>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+	let file_length = reader.seek(SeekFrom::End(0))? - start;
+	reader.seek(SeekFrom::Start(start))?;
+
+	let mut stream_length = file_length;
+
+	// ID3v2 tags are unsupported in Musepack files, but still possible
+	if let ID3FindResults(Some(header), _) = find_id3v2(reader, false)? {
+		stream_length -= u64::from(header.size) + 10;
+
+		// Exclude the footer
+		if header.flags.footer {
+			stream_length -= 10;
+		}
+	}
+
+	let stream_start = reader.stream_position()?;
+
+	let mut magic = [0; 4];
+	reader.read_exact(&mut magic)?;
+
+	let is_sv8 = match magic {
+		_ if &magic == SV8_MAGIC => true,
+		_ if &magic[..3] == SV7_MAGIC => {
+			if magic[3] & 0x0F != 7 {
+				return Err(FileDecodingError::new(
+					FileType::Musepack,
+					"Found an unsupported stream version (< SV7)",
+				)
+				.into());
+			}
+
+			false
+		},
+		_ => err!(UnknownFormat),
+	};
+
+	#[cfg(feature = "id3v1")]
+	let mut id3v1_tag = None;
+	#[cfg(feature = "ape")]
+	let mut ape_tag = None;
+
+	let ID3FindResults(id3v1_header, id3v1) = find_id3v1(reader, true)?;
+
+	if id3v1_header.is_some() {
+		stream_length -= 128;
+		#[cfg(feature = "id3v1")]
+		{
+			id3v1_tag = id3v1;
+		}
+	}
+
+	// Next, check for a Lyrics3v2 tag, and skip over it, as it's no use to us
+	let ID3FindResults(lyrics3_header, lyrics3v2_size) = find_lyrics3v2(reader)?;
+
+	if lyrics3_header.is_some() {
+		stream_length -= u64::from(lyrics3v2_size);
+	}
+
+	// Next, search for an APE tag footer
+	//
+	// Starts with ['A', 'P', 'E', 'T', 'A', 'G', 'E', 'X']
+	// Exactly 32 bytes long
+	// Strongly recommended to be at the end of the file
+	let mut stream_end = reader.stream_position()?;
+	reader.seek(SeekFrom::Current(-32))?;
+
+	let mut ape_preamble = [0; 8];
+	reader.read_exact(&mut ape_preamble)?;
+
+	if &ape_preamble == APE_PREAMBLE {
+		let ape_header = read_ape_header(reader, true)?;
+		stream_length -= u64::from(ape_header.size);
+		stream_end = stream_end.saturating_sub(u64::from(ape_header.size));
+
+		#[cfg(feature = "ape")]
+		{
+			let ape = read_ape_tag(reader, ape_header)?;
+			ape_tag = Some(ape)
+		}
+	}
+
+	let properties = if read_properties {
+		// Skip back over the magic signature
+		reader.seek(SeekFrom::Start(stream_start + 4))?;
+
+		if is_sv8 {
+			super::properties::read_sv8_properties(reader, stream_length, file_length)?
+		} else {
+			super::properties::read_sv7_properties(reader, stream_length, file_length)?
+		}
+	} else {
+		MpcProperties::default()
+	};
+
+	Ok(MpcFile {
+		#[cfg(feature = "id3v1")]
+		id3v1_tag,
+		#[cfg(feature = "ape")]
+		ape_tag,
+		properties,
+		audio_data_range: stream_start.min(stream_end)..stream_end,
+	})
+}
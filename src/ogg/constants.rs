@@ -1,3 +1,7 @@
+// The vendor string used when a file doesn't already have one
+#[cfg(feature = "vorbis_comments")]
+pub const DEFAULT_VENDOR: &str = concat!("lofty ", env!("CARGO_PKG_VERSION"));
+
 // https://xiph.org/vorbis/doc/Vorbis_I_spec.html#x1-620004.2.1
 pub const VORBIS_IDENT_HEAD: &[u8] = &[1, 118, 111, 114, 98, 105, 115];
 pub const VORBIS_COMMENT_HEAD: &[u8] = &[3, 118, 111, 114, 98, 105, 115];
@@ -8,5 +12,8 @@ pub const VORBIS_SETUP_HEAD: &[u8] = &[5, 118, 111, 114, 98, 105, 115];
 pub const OPUSTAGS: &[u8] = &[79, 112, 117, 115, 84, 97, 103, 115];
 pub const OPUSHEAD: &[u8] = &[79, 112, 117, 115, 72, 101, 97, 100];
 
+// https://xiph.org/flac/ogg_mapping.html
+pub const OGG_FLAC_HEAD: &[u8] = &[127, 70, 76, 65, 67];
+
 // https://www.speex.org/docs/manual/speex-manual/node8.html
 pub const SPEEXHEADER: &[u8] = &[83, 112, 101, 101, 120, 32, 32, 32];
@@ -0,0 +1,154 @@
+#[cfg(feature = "vorbis_comments")]
+use super::tag::VorbisComments;
+use super::verify_signature;
+use crate::error::{FileDecodingError, Result};
+use crate::file::{AudioFile, FileType};
+use crate::flac::block::{BLOCK_ID_STREAMINFO, BLOCK_ID_VORBIS_COMMENTS};
+use crate::flac::FlacProperties;
+use crate::ogg::constants::OGG_FLAC_HEAD;
+use crate::probe::ParseOptions;
+use crate::tag::TagType;
+
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+
+use lofty_attr::LoftyFile;
+use ogg_pager::Page;
+
+// The mapping header, followed by the `fLaC` signature and the STREAMINFO block header:
+//
+// Signature (5)
+// Mapping version (2)
+// Number of header packets (2)
+// `fLaC` (4)
+// STREAMINFO block header (4)
+const STREAM_INFO_OFFSET: usize = 17;
+const STREAM_INFO_LEN: usize = 34;
+
+/// An OGG FLAC file
+///
+/// ## Notes
+///
+/// * The properties are read from the embedded STREAMINFO block, the same as a [`FlacFile`](crate::flac::FlacFile)
+/// * Only the Vorbis Comments block is read, any other metadata blocks are skipped
+#[derive(LoftyFile)]
+#[lofty(no_audiofile_impl)]
+#[lofty(file_type = "OggFlac")]
+pub struct OggFlacFile {
+	/// The vorbis comments contained in the file
+	///
+	/// NOTE: While a metadata packet is required, it isn't required to actually have any data.
+	#[cfg(feature = "vorbis_comments")]
+	#[lofty(tag_type = "VorbisComments")]
+	#[lofty(always_present)]
+	pub(crate) vorbis_comments_tag: VorbisComments,
+	/// The file's audio properties
+	pub(crate) properties: FlacProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
+}
+
+impl OggFlacFile {
+	// Any pictures that fail to parse are skipped, and pushed to `options.parse_errors`
+	pub(crate) fn read_from_with_options<R>(
+		reader: &mut R,
+		read_properties: bool,
+		options: &mut ParseOptions<'_>,
+	) -> Result<Self>
+	where
+		R: Read + Seek,
+	{
+		let start = reader.stream_position()?;
+
+		let first_page = Page::read(reader, false)?;
+		verify_signature(&first_page, OGG_FLAC_HEAD)?;
+
+		let header = first_page.content();
+		if header.len() < STREAM_INFO_OFFSET + STREAM_INFO_LEN
+			|| &header[9..13] != b"fLaC"
+			|| header[13] & 0x7F != BLOCK_ID_STREAMINFO
+		{
+			return Err(FileDecodingError::new(
+				FileType::OggFlac,
+				"File missing a STREAMINFO block",
+			)
+			.into());
+		}
+
+		// Only version 1.0 of the mapping exists
+		if header[5] != 1 {
+			return Err(FileDecodingError::new(
+				FileType::OggFlac,
+				"Unsupported Ogg FLAC mapping version",
+			)
+			.into());
+		}
+
+		// The Vorbis Comments block is required to be the second header packet
+		let md_page = Page::read(reader, false)?;
+		if md_page.content().len() < 4 || md_page.content()[0] & 0x7F != BLOCK_ID_VORBIS_COMMENTS {
+			return Err(FileDecodingError::new(
+				FileType::OggFlac,
+				"File missing a Vorbis Comments block",
+			)
+			.into());
+		}
+
+		// Skip the block header
+		#[allow(unused_variables)]
+		let vorbis_comments = super::read::read_comment_packet(reader, &md_page, 4, options)?;
+
+		// Any other metadata blocks are in header pages, which have a granule position of 0
+		while let Ok(page) = Page::read(reader, true) {
+			if page.abgp != 0 {
+				reader.seek(SeekFrom::Start(page.start))?;
+				break;
+			}
+		}
+
+		// The audio data starts at the first page after the header packets
+		let audio_start = reader.stream_position()?;
+		let audio_end = reader.seek(SeekFrom::End(0))?;
+
+		let properties = if read_properties {
+			crate::flac::properties::read_properties(
+				&mut &header[STREAM_INFO_OFFSET..STREAM_INFO_OFFSET + STREAM_INFO_LEN],
+				audio_end.saturating_sub(audio_start),
+				audio_end - start,
+			)?
+		} else {
+			FlacProperties::default()
+		};
+
+		Ok(Self {
+			properties,
+			#[cfg(feature = "vorbis_comments")]
+			// Safe to unwrap, a metadata packet is mandatory in Ogg FLAC
+			vorbis_comments_tag: vorbis_comments.unwrap(),
+			audio_data_range: audio_start.min(audio_end)..audio_end,
+		})
+	}
+}
+
+impl AudioFile for OggFlacFile {
+	type Properties = FlacProperties;
+
+	fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<Self>
+	where
+		R: Read + Seek,
+	{
+		Self::read_from_with_options(reader, read_properties, &mut ParseOptions::new())
+	}
+
+	fn properties(&self) -> &Self::Properties {
+		&self.properties
+	}
+
+	fn contains_tag(&self) -> bool {
+		true
+	}
+
+	fn contains_tag_type(&self, tag_type: TagType) -> bool {
+		tag_type == TagType::VorbisComments
+	}
+}
@@ -3,7 +3,17 @@
 //! ## File notes
 //!
 //! The only supported tag format is [`VorbisComments`]
+//!
+//! ### Duration
+//!
+//! The duration is calculated from the granule positions of the stream's pages. For chained streams,
+//! the lengths of each link are summed, and any multiplexed streams are ignored.
+//!
+//! A page with a granule position of -1 (no packets finish on the page) is skipped. If none of a link's
+//! pages have a known granule position, that link is treated as having no length. If this is the case for
+//! every link, the duration will be zero.
 pub(crate) mod constants;
+pub(crate) mod flac;
 pub(crate) mod opus;
 pub(crate) mod read;
 pub(crate) mod speex;
@@ -12,7 +22,7 @@ pub(crate) mod vorbis;
 use crate::error::{FileDecodingError, Result};
 use crate::file::FileType;
 
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom};
 
 use ogg_pager::Page;
 
@@ -21,15 +31,17 @@ use ogg_pager::Page;
 cfg_if::cfg_if! {
 	if #[cfg(feature = "vorbis_comments")] {
 		pub(crate) mod write;
+		pub use write::PageRewrite;
 
 		pub(crate) mod tag;
 		pub use tag::VorbisComments;
 	}
 }
 
+pub use flac::OggFlacFile;
 pub use opus::properties::OpusProperties;
 pub use opus::OpusFile;
-pub use speex::properties::SpeexProperties;
+pub use speex::properties::{SpeexMode, SpeexProperties};
 pub use speex::SpeexFile;
 pub use vorbis::properties::VorbisProperties;
 pub use vorbis::VorbisFile;
@@ -46,15 +58,67 @@ pub(self) fn verify_signature(page: &Page, sig: &[u8]) -> Result<()> {
 	Ok(())
 }
 
-pub(self) fn find_last_page<R>(data: &mut R) -> Result<Page>
+// Gets the number of samples in the stream, starting from the page following the headers
+//
+// Chained streams (one logical stream following another) each start their granule positions over,
+// so the length of each link is summed. Otherwise, only the stream of `first_page` is considered,
+// as any other streams are multiplexed with it.
+//
+// `link_skip` is given the first page of each link, and returns the number of samples to subtract
+// from that link's length (Ex. the Opus pre-skip).
+//
+// Pages with a granule position of -1 are skipped, as no packets finish on them. A link without a
+// known granule position contributes nothing, and `None` is returned if none of them have one.
+pub(self) fn granule_count<R, F>(data: &mut R, first_page: &Page, link_skip: F) -> Option<u64>
 where
 	R: Read + Seek,
+	F: Fn(&Page) -> u64,
 {
-	let mut last_page = Page::read(data, true)?;
+	let link_len = |first_granule: u64, last_granule: Option<u64>, skip: u64| {
+		last_granule
+			.and_then(|last_granule| last_granule.checked_sub(first_granule))
+			.and_then(|len| len.checked_sub(skip))
+	};
+
+	let mut serial = first_page.serial;
+	let mut first_granule = first_page.abgp;
+	let mut last_granule = None;
+	let mut skip = link_skip(first_page);
+
+	let mut total: Option<u64> = None;
+	let mut in_link_headers = true;
 
 	while let Ok(page) = Page::read(data, true) {
-		last_page = page
+		if page.header_type() & ogg_pager::CONTAINS_FIRST_PAGE_OF_BITSTREAM != 0 {
+			// Multiplexed streams will all start together, so only the first stream is kept
+			if !in_link_headers {
+				if let Some(len) = link_len(first_granule, last_granule, skip) {
+					total = Some(total.unwrap_or(0).saturating_add(len));
+				}
+
+				serial = page.serial;
+				first_granule = page.abgp;
+				last_granule = None;
+
+				// The page was read without its content, which is needed for the skip
+				data.seek(SeekFrom::Start(page.start)).ok()?;
+				skip = Page::read(data, false).map_or(0, |page| link_skip(&page));
+
+				in_link_headers = true;
+			}
+
+			continue;
+		}
+
+		in_link_headers = false;
+
+		if page.serial == serial && page.abgp != u64::MAX {
+			last_granule = Some(page.abgp);
+		}
 	}
 
-	Ok(last_page)
+	match (total, link_len(first_granule, last_granule, skip)) {
+		(Some(total), Some(len)) => Some(total.saturating_add(len)),
+		(total, len) => total.or(len),
+	}
 }
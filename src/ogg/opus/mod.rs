@@ -1,15 +1,17 @@
 pub(super) mod properties;
 
-use super::find_last_page;
+use super::granule_count;
 #[cfg(feature = "vorbis_comments")]
 use super::tag::VorbisComments;
 use crate::error::Result;
 use crate::file::AudioFile;
 use crate::ogg::constants::{OPUSHEAD, OPUSTAGS};
+use crate::probe::ParseOptions;
 use crate::tag::TagType;
 use properties::OpusProperties;
 
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
 
 use lofty_attr::LoftyFile;
 
@@ -26,24 +28,46 @@ pub struct OpusFile {
 	pub(crate) vorbis_comments_tag: VorbisComments,
 	/// The file's audio properties
 	pub(crate) properties: OpusProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
 }
 
-impl AudioFile for OpusFile {
-	type Properties = OpusProperties;
-
-	fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<Self>
+impl OpusFile {
+	// Any pictures that fail to parse are skipped, and pushed to `options.parse_errors`
+	pub(crate) fn read_from_with_options<R>(
+		reader: &mut R,
+		read_properties: bool,
+		options: &mut ParseOptions<'_>,
+	) -> Result<Self>
 	where
 		R: Read + Seek,
 	{
-		let file_information = super::read::read_from(reader, OPUSHEAD, OPUSTAGS)?;
+		let file_information = super::read::read_from(reader, OPUSHEAD, OPUSTAGS, options)?;
+
+		// The audio data starts at the first page after the header packets
+		let audio_start = reader.stream_position()?;
+		let audio_end = reader.seek(SeekFrom::End(0))?;
+		reader.seek(SeekFrom::Start(audio_start))?;
 
 		Ok(Self {
 			properties: if read_properties {properties::read_properties(reader, &file_information.1)? } else { OpusProperties::default() },
 			#[cfg(feature = "vorbis_comments")]
 			// Safe to unwrap, a metadata packet is mandatory in Opus
 			vorbis_comments_tag: file_information.0.unwrap(),
+			audio_data_range: audio_start.min(audio_end)..audio_end,
 		})
 	}
+}
+
+impl AudioFile for OpusFile {
+	type Properties = OpusProperties;
+
+	fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<Self>
+	where
+		R: Read + Seek,
+	{
+		Self::read_from_with_options(reader, read_properties, &mut ParseOptions::new())
+	}
 
 	fn properties(&self) -> &Self::Properties {
 		&self.properties
@@ -1,4 +1,4 @@
-use super::find_last_page;
+use super::granule_count;
 use crate::error::{FileDecodingError, Result};
 use crate::file::FileType;
 use crate::properties::FileProperties;
@@ -19,6 +19,7 @@ pub struct OpusProperties {
 	pub(crate) channels: u8,
 	pub(crate) version: u8,
 	pub(crate) input_sample_rate: u32,
+	pub(crate) pre_skip: u16,
 }
 
 impl From<OpusProperties> for FileProperties {
@@ -27,7 +28,8 @@ impl From<OpusProperties> for FileProperties {
 			duration: input.duration,
 			overall_bitrate: Some(input.overall_bitrate),
 			audio_bitrate: Some(input.audio_bitrate),
-			sample_rate: Some(input.input_sample_rate),
+			// Opus always decodes at 48kHz, the input sample rate is purely informational
+			sample_rate: Some(OPUS_SAMPLE_RATE),
 			bit_depth: None,
 			channels: Some(input.channels),
 		}
@@ -60,12 +62,29 @@ impl OpusProperties {
 		self.version
 	}
 
+	/// Sample rate (Hz)
+	///
+	/// Opus is always decoded at 48kHz, so this will always be `48000`.
+	/// See [`OpusProperties::input_sample_rate`] for the original sample rate.
+	pub fn sample_rate(&self) -> u32 {
+		OPUS_SAMPLE_RATE
+	}
+
 	/// Input sample rate
+	///
+	/// This is the sample rate of the original input, and is **not** the playback rate.
 	pub fn input_sample_rate(&self) -> u32 {
 		self.input_sample_rate
 	}
+
+	/// Number of samples (at 48kHz) to discard from the start of the decoded stream
+	pub fn pre_skip(&self) -> u16 {
+		self.pre_skip
+	}
 }
 
+const OPUS_SAMPLE_RATE: u32 = 48000;
+
 pub(in crate::ogg) fn read_properties<R>(data: &mut R, first_page: &Page) -> Result<OpusProperties>
 where
 	R: Read + Seek,
@@ -80,15 +99,13 @@ where
 
 	let mut properties = OpusProperties::default();
 
-	let first_page_abgp = first_page.abgp;
-
 	// Skip identification header
 	let first_page_content = &mut &first_page.content()[8..];
 
 	properties.version = first_page_content.read_u8()?;
 	properties.channels = first_page_content.read_u8()?;
 
-	let pre_skip = first_page_content.read_u16::<LittleEndian>()?;
+	properties.pre_skip = first_page_content.read_u16::<LittleEndian>()?;
 
 	properties.input_sample_rate = first_page_content.read_u32::<LittleEndian>()?;
 
@@ -110,11 +127,19 @@ where
 	// Subtract the identification and metadata packet length from the total
 	let audio_size = stream_len - data.stream_position()?;
 
-	let last_page = find_last_page(data)?;
-	let last_page_abgp = last_page.abgp;
+	// Every link of a chained stream has its own pre-skip
+	let frame_count = granule_count(data, first_page, |page| {
+		let content = page.content();
+		match content.get(10..12) {
+			Some(pre_skip) if content.starts_with(b"OpusHead") => {
+				u64::from(u16::from_le_bytes([pre_skip[0], pre_skip[1]]))
+			},
+			_ => 0,
+		}
+	});
 
-	if let Some(frame_count) = last_page_abgp.checked_sub(first_page_abgp + u64::from(pre_skip)) {
-		let length = (frame_count as f64) * 1000.0 / 48000.0_f64 + 0.5;
+	if let Some(frame_count) = frame_count {
+		let length = (frame_count as f64) * 1000.0 / f64::from(OPUS_SAMPLE_RATE) + 0.5;
 		properties.duration = Duration::from_millis(length as u64);
 
 		properties.overall_bitrate = ((file_length as f64) * 8.0 / length) as u32;
@@ -2,9 +2,12 @@
 use super::tag::VorbisComments;
 use super::verify_signature;
 use crate::error::Result;
+#[cfg(feature = "vorbis_comments")]
+use crate::error::{ErrorKind, LoftyError};
 use crate::macros::err;
 #[cfg(feature = "vorbis_comments")]
-use crate::picture::Picture;
+use crate::picture::{MimeType, Picture, PictureInformation, PictureType};
+use crate::probe::ParseOptions;
 
 use std::io::{Read, Seek, SeekFrom};
 
@@ -13,13 +16,22 @@ use byteorder::{LittleEndian, ReadBytesExt};
 use ogg_pager::Page;
 
 #[cfg(feature = "vorbis_comments")]
-pub type OGGTags = (Option<VorbisComments>, Page);
+pub type OGGComments = Option<VorbisComments>;
 
 #[cfg(not(feature = "vorbis_comments"))]
-pub type OGGTags = (Option<()>, Page);
+pub type OGGComments = Option<()>;
+
+pub type OGGTags = (OGGComments, Page);
 
 #[cfg(feature = "vorbis_comments")]
-pub(crate) fn read_comments<R>(data: &mut R, mut len: u64, tag: &mut VorbisComments) -> Result<()>
+//
+// Any pictures that fail to parse are skipped, and pushed to `options.parse_errors`
+pub(crate) fn read_comments<R>(
+	data: &mut R,
+	mut len: u64,
+	tag: &mut VorbisComments,
+	options: &mut ParseOptions<'_>,
+) -> Result<()>
 where
 	R: Read,
 {
@@ -64,6 +76,9 @@ where
 
 	let comments_total_len = data.read_u32::<LittleEndian>()?;
 
+	let mut cover_art = Vec::new();
+	let mut cover_art_mime = Vec::new();
+
 	for _ in 0..comments_total_len {
 		let comment_len = data.read_u32::<LittleEndian>()?;
 		if u64::from(comment_len) > len {
@@ -87,9 +102,17 @@ where
 		// Make sure there was a separator present, otherwise just move on
 		if let Some(value) = comment_split.next() {
 			match key {
-				k if k.eq_ignore_ascii_case("METADATA_BLOCK_PICTURE") => tag
-					.pictures
-					.push(Picture::from_flac_bytes(value.as_bytes(), true)?),
+				k if k.eq_ignore_ascii_case("METADATA_BLOCK_PICTURE") => {
+					// A malformed picture shouldn't prevent the rest of the tag from being read
+					match Picture::from_flac_bytes(value.as_bytes(), true) {
+						Ok(picture) => tag.pictures.push(picture),
+						Err(e) => options.parse_errors.push(e),
+					}
+				},
+				k if k.eq_ignore_ascii_case("COVERART") => cover_art.push(value.to_string()),
+				k if k.eq_ignore_ascii_case("COVERARTMIME") => {
+					cover_art_mime.push(value.to_string())
+				},
 				// The valid range is 0x20..=0x7D not including 0x3D
 				k if k.chars().all(|c| (' '..='}').contains(&c) && c != '=') => {
 					tag.items.push((k.to_string(), value.to_string()))
@@ -99,10 +122,42 @@ where
 		}
 	}
 
+	// The legacy `COVERART` fields are only used when there are no `METADATA_BLOCK_PICTURE`s.
+	// Either way, they are discarded, and pictures are only written as `METADATA_BLOCK_PICTURE`s.
+	if tag.pictures.is_empty() {
+		for (idx, data) in cover_art.iter().enumerate() {
+			let data = match base64::decode(data) {
+				Ok(data) if !data.is_empty() => data,
+				Ok(_) => continue,
+				Err(_) => {
+					options
+						.parse_errors
+						.push(LoftyError::new(ErrorKind::NotAPicture));
+					continue;
+				},
+			};
+
+			let mime_type = match cover_art_mime.get(idx) {
+				Some(mime_type) => MimeType::from_str(mime_type),
+				None => MimeType::from_bytes(&data).unwrap_or(MimeType::None),
+			};
+
+			let picture = Picture::new_unchecked(PictureType::CoverFront, mime_type, None, data);
+			let info = PictureInformation::from_picture(&picture).unwrap_or_default();
+
+			tag.pictures.push((picture, info));
+		}
+	}
+
 	Ok(())
 }
 
-pub(crate) fn read_from<T>(data: &mut T, header_sig: &[u8], comment_sig: &[u8]) -> Result<OGGTags>
+pub(crate) fn read_from<T>(
+	data: &mut T,
+	header_sig: &[u8],
+	comment_sig: &[u8],
+	options: &mut ParseOptions<'_>,
+) -> Result<OGGTags>
 where
 	T: Read + Seek,
 {
@@ -112,9 +167,27 @@ where
 	let md_page = Page::read(data, false)?;
 	verify_signature(&md_page, comment_sig)?;
 
+	let tag = read_comment_packet(data, &md_page, comment_sig.len(), options)?;
+	Ok((tag, first_page))
+}
+
+// Reads the comment packet starting on `md_page`, along with any pages it continues onto
+//
+// The first `skip` bytes of the packet (the signature) are ignored. The reader is left at the
+// start of the first page after the packet.
+#[allow(unused_variables)]
+pub(crate) fn read_comment_packet<T>(
+	data: &mut T,
+	md_page: &Page,
+	skip: usize,
+	options: &mut ParseOptions<'_>,
+) -> Result<OGGComments>
+where
+	T: Read + Seek,
+{
 	let mut md_pages: Vec<u8> = Vec::new();
 
-	md_pages.extend_from_slice(&md_page.content()[comment_sig.len()..]);
+	md_pages.extend_from_slice(&md_page.content()[skip..]);
 
 	while let Ok(page) = Page::read(data, false) {
 		if md_pages.len() > 125_829_120 {
@@ -134,11 +207,11 @@ where
 		let mut tag = VorbisComments::default();
 
 		let reader = &mut &md_pages[..];
-		read_comments(reader, reader.len() as u64, &mut tag)?;
+		read_comments(reader, reader.len() as u64, &mut tag, options)?;
 
-		Ok((Some(tag), first_page))
+		Ok(Some(tag))
 	}
 
 	#[cfg(not(feature = "vorbis_comments"))]
-	Ok((None, first_page))
+	Ok(None)
 }
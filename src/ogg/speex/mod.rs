@@ -5,10 +5,12 @@ use super::tag::VorbisComments;
 use crate::error::Result;
 use crate::file::AudioFile;
 use crate::ogg::constants::SPEEXHEADER;
+use crate::probe::ParseOptions;
 use crate::tag::TagType;
 use properties::SpeexProperties;
 
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
 
 use lofty_attr::LoftyFile;
 
@@ -25,24 +27,46 @@ pub struct SpeexFile {
 	pub(crate) vorbis_comments_tag: VorbisComments,
 	/// The file's audio properties
 	pub(crate) properties: SpeexProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
 }
 
-impl AudioFile for SpeexFile {
-	type Properties = SpeexProperties;
-
-	fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<Self>
+impl SpeexFile {
+	// Any pictures that fail to parse are skipped, and pushed to `options.parse_errors`
+	pub(crate) fn read_from_with_options<R>(
+		reader: &mut R,
+		read_properties: bool,
+		options: &mut ParseOptions<'_>,
+	) -> Result<Self>
 	where
 		R: Read + Seek,
 	{
-		let file_information = super::read::read_from(reader, SPEEXHEADER, &[])?;
+		let file_information = super::read::read_from(reader, SPEEXHEADER, &[], options)?;
+
+		// The audio data starts at the first page after the header packets
+		let audio_start = reader.stream_position()?;
+		let audio_end = reader.seek(SeekFrom::End(0))?;
+		reader.seek(SeekFrom::Start(audio_start))?;
 
 		Ok(Self {
             properties: if read_properties { properties::read_properties(reader, &file_information.1)? } else { SpeexProperties::default() },
             #[cfg(feature = "vorbis_comments")]
             // Safe to unwrap, a metadata packet is mandatory in Speex
             vorbis_comments_tag: file_information.0.unwrap(),
+            audio_data_range: audio_start.min(audio_end)..audio_end,
         })
 	}
+}
+
+impl AudioFile for SpeexFile {
+	type Properties = SpeexProperties;
+
+	fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<Self>
+	where
+		R: Read + Seek,
+	{
+		Self::read_from_with_options(reader, read_properties, &mut ParseOptions::new())
+	}
 
 	fn properties(&self) -> &Self::Properties {
 		&self.properties
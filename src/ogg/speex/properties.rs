@@ -1,6 +1,6 @@
 use crate::error::{FileDecodingError, Result};
 use crate::file::FileType;
-use crate::ogg::find_last_page;
+use crate::ogg::granule_count;
 use crate::properties::FileProperties;
 
 use std::io::{Read, Seek, SeekFrom};
@@ -9,6 +9,34 @@ use std::time::Duration;
 use byteorder::{LittleEndian, ReadBytesExt};
 use ogg_pager::Page;
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// A Speex encoding mode
+pub enum SpeexMode {
+	/// Narrowband (8kHz)
+	NarrowBand = 0,
+	/// Wideband (16kHz)
+	WideBand = 1,
+	/// Ultra-wideband (32kHz)
+	UltraWideBand = 2,
+}
+
+impl Default for SpeexMode {
+	fn default() -> Self {
+		Self::NarrowBand
+	}
+}
+
+impl SpeexMode {
+	fn from_u32(mode: u32) -> Option<Self> {
+		match mode {
+			0 => Some(Self::NarrowBand),
+			1 => Some(Self::WideBand),
+			2 => Some(Self::UltraWideBand),
+			_ => None,
+		}
+	}
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 #[non_exhaustive]
 /// A Speex file's audio properties
@@ -16,9 +44,10 @@ pub struct SpeexProperties {
 	pub(crate) duration: Duration,
 	pub(crate) version: u32,
 	pub(crate) sample_rate: u32,
-	pub(crate) mode: u32,
+	pub(crate) mode: SpeexMode,
 	pub(crate) channels: u8,
 	pub(crate) vbr: bool,
+	pub(crate) frames_per_packet: u32,
 	pub(crate) overall_bitrate: u32,
 	pub(crate) audio_bitrate: u32,
 	pub(crate) nominal_bitrate: i32,
@@ -48,13 +77,13 @@ impl SpeexProperties {
 		self.version
 	}
 
-	/// Sample rate
+	/// Sample rate (Hz)
 	pub fn sample_rate(&self) -> u32 {
 		self.sample_rate
 	}
 
 	/// Speex encoding mode
-	pub fn mode(&self) -> u32 {
+	pub fn mode(&self) -> SpeexMode {
 		self.mode
 	}
 
@@ -68,6 +97,11 @@ impl SpeexProperties {
 		self.vbr
 	}
 
+	/// Number of frames stored in each Ogg packet
+	pub fn frames_per_packet(&self) -> u32 {
+		self.frames_per_packet
+	}
+
 	/// Overall bitrate (kbps)
 	pub fn overall_bitrate(&self) -> u32 {
 		self.overall_bitrate
@@ -88,8 +122,6 @@ pub(in crate::ogg) fn read_properties<R>(data: &mut R, first_page: &Page) -> Res
 where
 	R: Read + Seek,
 {
-	let first_page_abgp = first_page.abgp;
-
 	if first_page.content().len() < 80 {
 		return Err(FileDecodingError::new(FileType::Speex, "Header packet too small").into());
 	}
@@ -109,14 +141,24 @@ where
 	let _header_size = first_page_content.read_u32::<LittleEndian>()?;
 
 	properties.sample_rate = first_page_content.read_u32::<LittleEndian>()?;
-	properties.mode = first_page_content.read_u32::<LittleEndian>()?;
+	properties.mode = match SpeexMode::from_u32(first_page_content.read_u32::<LittleEndian>()?) {
+		Some(mode) => mode,
+		None => {
+			return Err(FileDecodingError::new(
+				FileType::Speex,
+				"Found invalid encoding mode, must be 0, 1, or 2",
+			)
+			.into())
+		},
+	};
 
 	// Version ID of the bitstream
 	let _mode_bitstream_version = first_page_content.read_u32::<LittleEndian>()?;
 
-	let channels = first_page_content.read_u32::<LittleEndian>()?;
+	// Some encoders leave this field as 0, so we treat it as mono
+	let channels = first_page_content.read_u32::<LittleEndian>()?.max(1);
 
-	if channels != 1 && channels != 2 {
+	if channels > 2 {
 		return Err(FileDecodingError::new(
 			FileType::Speex,
 			"Found invalid channel count, must be mono or stereo",
@@ -131,13 +173,13 @@ where
 	let _frame_size = first_page_content.read_u32::<LittleEndian>()?;
 
 	properties.vbr = first_page_content.read_u32::<LittleEndian>()? == 1;
+	properties.frames_per_packet = first_page_content.read_u32::<LittleEndian>()?;
 
-	let last_page = find_last_page(data)?;
-	let last_page_abgp = last_page.abgp;
+	let frame_count = granule_count(data, first_page, |_| 0);
 
 	let file_length = data.seek(SeekFrom::End(0))?;
 
-	if let Some(frame_count) = last_page_abgp.checked_sub(first_page_abgp) {
+	if let Some(frame_count) = frame_count {
 		if properties.sample_rate > 0 {
 			let length = ((frame_count as f64) * 1000.0) / f64::from(properties.sample_rate) + 0.5;
 			properties.duration = Duration::from_millis(length as u64);
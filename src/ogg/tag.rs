@@ -1,11 +1,13 @@
 use crate::error::{LoftyError, Result};
 use crate::file::FileType;
 use crate::flac::write;
+use crate::flac::FlacWriteOptions;
 use crate::macros::err;
-use crate::ogg::write::OGGFormat;
+use crate::ogg::write::{OGGFormat, PageRewrite};
 use crate::picture::{Picture, PictureInformation, PictureType};
 use crate::probe::Probe;
 use crate::tag::item::{ItemKey, ItemValue, TagItem};
+use crate::tag::utils::split_num_pair;
 use crate::tag::{Tag, TagType};
 use crate::traits::{Accessor, TagExt};
 
@@ -38,6 +40,7 @@ macro_rules! impl_accessor {
 /// ## Supported file types
 ///
 /// * [`FileType::FLAC`](crate::FileType::FLAC)
+/// * [`FileType::OggFlac`](crate::FileType::OggFlac)
 /// * [`FileType::Opus`](crate::FileType::Opus)
 /// * [`FileType::Speex`](crate::FileType::Speex)
 /// * [`FileType::Vorbis`](crate::FileType::Vorbis)
@@ -58,6 +61,9 @@ impl VorbisComments {
 	}
 
 	/// Sets the vendor string
+	///
+	/// When writing, an empty vendor string will keep the file's existing vendor.
+	/// If the file doesn't have one (or it is empty), a default lofty identifier is written instead.
 	pub fn set_vendor(&mut self, vendor: String) {
 		self.vendor = vendor
 	}
@@ -237,23 +243,78 @@ impl VorbisComments {
 	pub fn remove_picture(&mut self, index: usize) -> (Picture, PictureInformation) {
 		self.pictures.remove(index)
 	}
+
+	/// Writes the tag to a FLAC file, using the provided [`FlacWriteOptions`]
+	///
+	/// This returns `true` if the file was modified, see [`TagExt::save_to`] to write the tag with
+	/// the default options.
+	///
+	/// # Errors
+	///
+	/// * `file` is not a valid FLAC file
+	/// * See [`TagExt::save_to`]
+	pub fn save_to_flac(&self, file: &mut File, options: FlacWriteOptions) -> Result<bool> {
+		write::write_to(
+			file,
+			&mut VorbisCommentsRef {
+				vendor: self.vendor.as_str(),
+				items: self.items.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+				pictures: self.pictures.iter().map(|(p, i)| (p, *i)),
+			},
+			options,
+		)
+	}
+
+	/// Writes the tag to an Ogg FLAC, Opus, Vorbis, or Speex file, returning how much of the stream was rewritten
+	///
+	/// Only the comment header pages are rewritten when possible. If the number of header pages
+	/// changes, the rest of the stream has to be renumbered, which is reported as [`PageRewrite::Full`].
+	/// If the comment header is identical to the existing one, nothing is written ([`PageRewrite::Unchanged`]).
+	///
+	/// # Errors
+	///
+	/// * `file` is not an Ogg FLAC, Opus, Vorbis, or Speex file
+	/// * See [`TagExt::save_to`]
+	pub fn save_to_ogg(&self, file: &mut File) -> Result<PageRewrite> {
+		let probe = Probe::new(file).guess_file_type()?;
+		let format = match probe.file_type().and_then(OGGFormat::from_file_type) {
+			Some(format) => format,
+			None => err!(UnsupportedTag),
+		};
+
+		super::write::write(
+			probe.into_inner(),
+			&mut VorbisCommentsRef {
+				vendor: self.vendor.as_str(),
+				items: self.items.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+				pictures: self.pictures.iter().map(|(p, i)| (p, *i)),
+			},
+			format,
+		)
+	}
 }
 
 impl Accessor for VorbisComments {
 	impl_accessor!(
-		artist  => "ARTIST";
-		title   => "TITLE";
-		album   => "ALBUM";
-		genre   => "GENRE";
-		comment => "COMMENT";
+		artist       => "ARTIST";
+		title        => "TITLE";
+		album        => "ALBUM";
+		genre        => "GENRE";
+		comment      => "COMMENT";
+		album_artist => "ALBUMARTIST";
+		composer     => "COMPOSER";
+		initial_key  => "INITIALKEY";
+		encoder      => "ENCODER";
+		title_sort => "TITLESORT";
+		artist_sort => "ARTISTSORT";
+		album_sort => "ALBUMSORT";
+		album_artist_sort => "ALBUMARTISTSORT";
 	);
 
 	fn track(&self) -> Option<u32> {
-		if let Some(item) = self.get("TRACKNUMBER") {
-			return item.parse::<u32>().ok();
-		}
-
-		None
+		// Some applications store the total in the same field ("3/12")
+		self.get("TRACKNUMBER")
+			.and_then(|item| split_num_pair(item).0)
 	}
 
 	fn set_track(&mut self, value: u32) {
@@ -272,7 +333,8 @@ impl Accessor for VorbisComments {
 			return item.parse::<u32>().ok();
 		}
 
-		None
+		self.get("TRACKNUMBER")
+			.and_then(|item| split_num_pair(item).1)
 	}
 
 	fn set_track_total(&mut self, value: u32) {
@@ -286,11 +348,8 @@ impl Accessor for VorbisComments {
 	}
 
 	fn disk(&self) -> Option<u32> {
-		if let Some(item) = self.get("DISCNUMBER") {
-			return item.parse::<u32>().ok();
-		}
-
-		None
+		self.get("DISCNUMBER")
+			.and_then(|item| split_num_pair(item).0)
 	}
 
 	fn set_disk(&mut self, value: u32) {
@@ -309,7 +368,8 @@ impl Accessor for VorbisComments {
 			return item.parse::<u32>().ok();
 		}
 
-		None
+		self.get("DISCNUMBER")
+			.and_then(|item| split_num_pair(item).1)
 	}
 
 	fn set_disk_total(&mut self, value: u32) {
@@ -322,6 +382,18 @@ impl Accessor for VorbisComments {
 		let _ = self.remove("TOTALDISCS");
 	}
 
+	fn bpm(&self) -> Option<u16> {
+		self.get("BPM").and_then(crate::tag::utils::parse_bpm)
+	}
+
+	fn set_bpm(&mut self, value: u16) {
+		self.insert(String::from("BPM"), value.to_string(), true);
+	}
+
+	fn remove_bpm(&mut self) {
+		let _ = self.remove("BPM");
+	}
+
 	fn year(&self) -> Option<u32> {
 		if let Some(item) = self.get("YEAR").map_or_else(|| self.get("DATE"), Some) {
 			return item.chars().take(4).collect::<String>().parse::<u32>().ok();
@@ -357,7 +429,7 @@ impl TagExt for VorbisComments {
 	///
 	/// * `path` does not exist
 	/// * See [`VorbisComments::save_to`]
-	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err> {
 		self.save_to(&mut OpenOptions::new().read(true).write(true).open(path)?)
 	}
 
@@ -369,7 +441,7 @@ impl TagExt for VorbisComments {
 	/// * The file does not contain valid packets
 	/// * [`PictureInformation::from_picture`]
 	/// * [`std::io::Error`]
-	fn save_to(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err> {
 		VorbisCommentsRef {
 			vendor: self.vendor.as_str(),
 			items: self.items.iter().map(|(k, v)| (k.as_str(), v.as_str())),
@@ -415,10 +487,17 @@ impl From<VorbisComments> for Tag {
 		let mut tag = Tag::new(TagType::VorbisComments);
 
 		for (k, v) in input.items {
-			tag.items.push(TagItem::new(
+			let mut item = TagItem::new(
 				ItemKey::from_key(TagType::VorbisComments, &k),
 				ItemValue::Text(v),
-			));
+			);
+
+			// Unknown keys already keep their casing
+			if !matches!(item.item_key, ItemKey::Unknown(_)) {
+				item.original_key = Some(k);
+			}
+
+			tag.items.push(item);
 		}
 
 		// We need to preserve the vendor string
@@ -454,21 +533,18 @@ impl From<Tag> for VorbisComments {
 		}
 
 		for item in input.items {
-			let item_key = item.item_key;
-			let item_value = item.item_value;
+			let key = match vorbis_key(&item) {
+				None => continue,
+				Some(k) => k.to_string(),
+			};
 
 			// Discard binary items, as they are not allowed in Vorbis comments
-			let val = match item_value {
+			let val = match item.item_value {
 				ItemValue::Text(text) | ItemValue::Locator(text) => text,
 				_ => continue,
 			};
 
-			let key = match item_key.map_key(TagType::VorbisComments, true) {
-				None => continue,
-				Some(k) => k,
-			};
-
-			vorbis_comments.items.push((key.to_string(), val));
+			vorbis_comments.items.push((key, val));
 		}
 
 		for picture in input.pictures {
@@ -497,17 +573,19 @@ where
 	IP: Iterator<Item = (&'a Picture, PictureInformation)>,
 {
 	#[allow(clippy::shadow_unrelated)]
-	fn write_to(&mut self, file: &mut File) -> Result<()> {
+	fn write_to(&mut self, file: &mut File) -> Result<bool> {
 		let probe = Probe::new(file).guess_file_type()?;
 		let f_ty = probe.file_type();
 
 		let file = probe.into_inner();
 
 		match f_ty {
-			Some(FileType::FLAC) => write::write_to(file, self),
-			Some(FileType::Opus) => super::write::write(file, self, OGGFormat::Opus),
-			Some(FileType::Vorbis) => super::write::write(file, self, OGGFormat::Vorbis),
-			Some(FileType::Speex) => super::write::write(file, self, OGGFormat::Speex),
+			Some(FileType::FLAC) => write::write_to(file, self, FlacWriteOptions::default()),
+			Some(f_ty) => match OGGFormat::from_file_type(f_ty) {
+				Some(format) => super::write::write(file, self, format)
+					.map(|rewrite| rewrite != PageRewrite::Unchanged),
+				None => err!(UnsupportedTag),
+			},
 			_ => err!(UnsupportedTag),
 		}
 	}
@@ -521,6 +599,20 @@ where
 	}
 }
 
+// Maps the item's key, keeping the key it was read with (and its casing) if it still applies
+fn vorbis_key(item: &TagItem) -> Option<&str> {
+	let key = item.key().map_key(TagType::VorbisComments, true)?;
+
+	match &item.original_key {
+		Some(original_key)
+			if &ItemKey::from_key(TagType::VorbisComments, original_key) == item.key() =>
+		{
+			Some(original_key)
+		},
+		_ => Some(key),
+	}
+}
+
 pub(crate) fn create_vorbis_comments_ref(
 	tag: &Tag,
 ) -> (
@@ -531,10 +623,9 @@ pub(crate) fn create_vorbis_comments_ref(
 	let vendor = tag.get_string(&ItemKey::EncoderSoftware).unwrap_or("");
 
 	let items = tag.items.iter().filter_map(|i| match i.value() {
-		ItemValue::Text(val) | ItemValue::Locator(val) => i
-			.key()
-			.map_key(TagType::VorbisComments, true)
-			.map(|key| (key, val.as_str())),
+		ItemValue::Text(val) | ItemValue::Locator(val) => {
+			vorbis_key(i).map(|key| (key, val.as_str()))
+		},
 		_ => None,
 	});
 
@@ -547,14 +638,22 @@ pub(crate) fn create_vorbis_comments_ref(
 
 #[cfg(test)]
 mod tests {
+	use crate::error::ErrorKind;
 	use crate::ogg::VorbisComments;
+	use crate::probe::ParseOptions;
 	use crate::{Tag, TagExt, TagType};
 
 	fn read_tag(tag: &[u8]) -> VorbisComments {
 		let mut reader = std::io::Cursor::new(tag);
 		let mut parsed_tag = VorbisComments::default();
 
-		crate::ogg::read::read_comments(&mut reader, tag.len() as u64, &mut parsed_tag).unwrap();
+		crate::ogg::read::read_comments(
+			&mut reader,
+			tag.len() as u64,
+			&mut parsed_tag,
+			&mut ParseOptions::new(),
+		)
+		.unwrap();
 		parsed_tag
 	}
 
@@ -632,4 +731,77 @@ mod tests {
 		assert_eq!(tag.pictures().len(), 1);
 		assert!(tag.items.is_empty());
 	}
+
+	fn comments_bytes(comments: &[String]) -> Vec<u8> {
+		// Empty vendor
+		let mut bytes = vec![0, 0, 0, 0];
+		bytes.extend((comments.len() as u32).to_le_bytes());
+
+		for comment in comments {
+			bytes.extend((comment.len() as u32).to_le_bytes());
+			bytes.extend(comment.as_bytes());
+		}
+
+		bytes
+	}
+
+	#[test]
+	fn malformed_picture() {
+		let bytes = comments_bytes(&[
+			String::from("METADATA_BLOCK_PICTURE=Not base64!"),
+			String::from("COVERART=Not base64 either!"),
+			String::from("TITLE=Foo title"),
+		]);
+
+		let mut tag = VorbisComments::default();
+		let mut options = ParseOptions::new();
+		crate::ogg::read::read_comments(&mut &*bytes, bytes.len() as u64, &mut tag, &mut options)
+			.unwrap();
+
+		assert!(tag.pictures().is_empty());
+		assert_eq!(tag.get("TITLE"), Some("Foo title"));
+
+		// Both of the pictures are reported
+		assert_eq!(options.parse_errors.len(), 2);
+		assert!(options
+			.parse_errors
+			.iter()
+			.all(|e| matches!(e.kind(), ErrorKind::NotAPicture)));
+	}
+
+	#[test]
+	fn legacy_cover_art() {
+		use crate::{MimeType, PictureType};
+
+		let png = crate::tag::utils::test_utils::read_path("tests/picture/assets/png_640x628.png");
+
+		let tag = read_tag(&comments_bytes(&[
+			format!("COVERART={}", base64::encode(&png)),
+			String::from("COVERARTMIME=image/png"),
+		]));
+
+		assert!(tag.items.is_empty());
+		assert_eq!(tag.pictures().len(), 1);
+
+		let (picture, info) = &tag.pictures()[0];
+		assert_eq!(picture.pic_type(), PictureType::CoverFront);
+		assert_eq!(picture.mime_type(), &MimeType::Png);
+		assert_eq!(picture.data(), &*png);
+		assert_eq!((info.width, info.height), (640, 628));
+
+		// The picture is written back as a `METADATA_BLOCK_PICTURE`
+		let mut writer = vec![0, 0, 0, 0];
+		tag.dump_to(&mut writer).unwrap();
+		assert_eq!(read_tag(&writer), tag);
+
+		// `METADATA_BLOCK_PICTURE` takes precedence
+		let picture_block = String::from_utf8(picture.as_flac_bytes(*info, true)).unwrap();
+		let tag = read_tag(&comments_bytes(&[
+			format!("METADATA_BLOCK_PICTURE={}", picture_block),
+			format!("COVERART={}", base64::encode(b"\xFF\xD8Not a JPEG")),
+		]));
+
+		assert_eq!(tag.pictures().len(), 1);
+		assert_eq!(tag.pictures()[0].0.mime_type(), &MimeType::Png);
+	}
 }
@@ -2,16 +2,18 @@ pub(super) mod properties;
 #[cfg(feature = "vorbis_comments")]
 pub(in crate::ogg) mod write;
 
-use super::find_last_page;
+use super::granule_count;
 #[cfg(feature = "vorbis_comments")]
 use super::tag::VorbisComments;
 use crate::error::Result;
 use crate::file::AudioFile;
 use crate::ogg::constants::{VORBIS_COMMENT_HEAD, VORBIS_IDENT_HEAD};
+use crate::probe::ParseOptions;
 use crate::tag::TagType;
 use properties::VorbisProperties;
 
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
 
 use lofty_attr::LoftyFile;
 
@@ -28,25 +30,47 @@ pub struct VorbisFile {
 	pub(crate) vorbis_comments_tag: VorbisComments,
 	/// The file's audio properties
 	pub(crate) properties: VorbisProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
 }
 
-impl AudioFile for VorbisFile {
-	type Properties = VorbisProperties;
-
-	fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<Self>
+impl VorbisFile {
+	// Any pictures that fail to parse are skipped, and pushed to `options.parse_errors`
+	pub(crate) fn read_from_with_options<R>(
+		reader: &mut R,
+		read_properties: bool,
+		options: &mut ParseOptions<'_>,
+	) -> Result<Self>
 	where
 		R: Read + Seek,
 	{
 		let file_information =
-			super::read::read_from(reader, VORBIS_IDENT_HEAD, VORBIS_COMMENT_HEAD)?;
+			super::read::read_from(reader, VORBIS_IDENT_HEAD, VORBIS_COMMENT_HEAD, options)?;
+
+		// The audio data starts at the first page after the header packets
+		let audio_start = reader.stream_position()?;
+		let audio_end = reader.seek(SeekFrom::End(0))?;
+		reader.seek(SeekFrom::Start(audio_start))?;
 
 		Ok(Self {
 			properties: if read_properties { properties::read_properties(reader, &file_information.1)? } else { VorbisProperties::default() },
 			#[cfg(feature = "vorbis_comments")]
 			// Safe to unwrap, a metadata packet is mandatory in OGG Vorbis
 			vorbis_comments_tag: file_information.0.unwrap(),
+			audio_data_range: audio_start.min(audio_end)..audio_end,
 		})
 	}
+}
+
+impl AudioFile for VorbisFile {
+	type Properties = VorbisProperties;
+
+	fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<Self>
+	where
+		R: Read + Seek,
+	{
+		Self::read_from_with_options(reader, read_properties, &mut ParseOptions::new())
+	}
 
 	fn properties(&self) -> &Self::Properties {
 		&self.properties
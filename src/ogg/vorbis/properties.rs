@@ -1,4 +1,4 @@
-use super::find_last_page;
+use super::granule_count;
 use crate::error::Result;
 use crate::properties::FileProperties;
 
@@ -90,8 +90,6 @@ pub(in crate::ogg) fn read_properties<R>(
 where
 	R: Read + Seek,
 {
-	let first_page_abgp = first_page.abgp;
-
 	let mut properties = VorbisProperties::default();
 
 	// Skip identification header
@@ -106,12 +104,11 @@ where
 	properties.bitrate_nominal = first_page_content.read_i32::<LittleEndian>()?;
 	properties.bitrate_minimum = first_page_content.read_i32::<LittleEndian>()?;
 
-	let last_page = find_last_page(data)?;
-	let last_page_abgp = last_page.abgp;
+	let frame_count = granule_count(data, first_page, |_| 0);
 
 	let file_length = data.seek(SeekFrom::End(0))?;
 
-	if let Some(frame_count) = last_page_abgp.checked_sub(first_page_abgp) {
+	if let Some(frame_count) = frame_count {
 		if properties.sample_rate > 0 {
 			let length = frame_count * 1000 / u64::from(properties.sample_rate);
 			properties.duration = Duration::from_millis(length);
@@ -14,8 +14,6 @@ pub(crate) fn write_to(
 	first_md_content: Vec<u8>,
 	pages: &mut [Page],
 ) -> Result<()> {
-	let mut remaining = Vec::new();
-
 	let reached_md_end: bool;
 
 	// Find the total comment count in the first page's content
@@ -38,7 +36,6 @@ pub(crate) fn write_to(
 
 		if p.header_type() & 0x01 != 1 {
 			data.seek(SeekFrom::Start(p.start))?;
-			data.read_to_end(&mut remaining)?;
 
 			reached_md_end = true;
 			break;
@@ -93,8 +90,6 @@ pub(crate) fn write_to(
 
 	build_remaining_header(writer, last_page, &setup)?;
 
-	writer.write_all(&remaining)?;
-
 	Ok(())
 }
 
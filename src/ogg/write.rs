@@ -2,8 +2,10 @@ use super::verify_signature;
 use crate::error::{FileEncodingError, Result};
 use crate::file::FileType;
 use crate::flac;
+use crate::flac::block::{BLOCK_ID_VORBIS_COMMENTS, MAX_BLOCK_SIZE};
+use crate::flac::FlacWriteOptions;
 use crate::macros::{err, try_vec};
-use crate::ogg::constants::{OPUSTAGS, VORBIS_COMMENT_HEAD};
+use crate::ogg::constants::{DEFAULT_VENDOR, OPUSTAGS, VORBIS_COMMENT_HEAD};
 use crate::ogg::tag::{create_vorbis_comments_ref, VorbisCommentsRef};
 use crate::picture::PictureInformation;
 use crate::tag::{Tag, TagType};
@@ -15,25 +17,52 @@ use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use ogg_pager::Page;
 
+/// Describes how much of an Ogg stream had to be rewritten to update its comment header
+///
+/// See [`VorbisComments::save_to_ogg`](crate::ogg::VorbisComments::save_to_ogg)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum PageRewrite {
+	/// The comment header was identical to the existing one, so nothing was written
+	Unchanged,
+	/// The new header pages were the same size as the old ones, and were written over them
+	InPlace,
+	/// The header pages changed in size, so the following pages were moved, but otherwise left untouched
+	Moved,
+	/// The number of header pages changed, so every following page in the stream was
+	/// renumbered, and had its checksum recalculated
+	Full,
+}
+
 #[derive(PartialEq, Copy, Clone)]
 pub(crate) enum OGGFormat {
 	Opus,
 	Vorbis,
 	Speex,
+	Flac,
 }
 
 impl OGGFormat {
+	pub(crate) fn from_file_type(file_type: FileType) -> Option<Self> {
+		match file_type {
+			FileType::Opus => Some(OGGFormat::Opus),
+			FileType::Vorbis => Some(OGGFormat::Vorbis),
+			FileType::Speex => Some(OGGFormat::Speex),
+			FileType::OggFlac => Some(OGGFormat::Flac),
+			_ => None,
+		}
+	}
+
 	#[allow(clippy::trivially_copy_pass_by_ref)]
 	pub(crate) fn comment_signature(&self) -> Option<&[u8]> {
 		match self {
 			OGGFormat::Opus => Some(OPUSTAGS),
 			OGGFormat::Vorbis => Some(VORBIS_COMMENT_HEAD),
-			OGGFormat::Speex => None,
+			OGGFormat::Speex | OGGFormat::Flac => None,
 		}
 	}
 }
 
-pub(crate) fn write_to(file: &mut File, tag: &Tag, file_type: FileType) -> Result<()> {
+pub(crate) fn write_to(file: &mut File, tag: &Tag, file_type: FileType) -> Result<bool> {
 	match tag.tag_type() {
 		#[cfg(feature = "vorbis_comments")]
 		TagType::VorbisComments => {
@@ -46,17 +75,12 @@ pub(crate) fn write_to(file: &mut File, tag: &Tag, file_type: FileType) -> Resul
 			};
 
 			if file_type == FileType::FLAC {
-				return flac::write::write_to(file, &mut comments_ref);
+				return flac::write::write_to(file, &mut comments_ref, FlacWriteOptions::default());
 			}
 
-			let format = match file_type {
-				FileType::Opus => OGGFormat::Opus,
-				FileType::Vorbis => OGGFormat::Vorbis,
-				FileType::Speex => OGGFormat::Speex,
-				_ => unreachable!(),
-			};
+			let format = OGGFormat::from_file_type(file_type).unwrap();
 
-			write(file, &mut comments_ref, format)
+			write(file, &mut comments_ref, format).map(|rewrite| rewrite != PageRewrite::Unchanged)
 		},
 		#[cfg(feature = "id3v2")]
 		TagType::ID3v2 if file_type == FileType::FLAC => {
@@ -101,6 +125,23 @@ pub(super) fn create_pages<'a, II, IP>(
 	stream_serial: u32,
 	add_framing_bit: bool,
 ) -> Result<Vec<Page>>
+where
+	II: Iterator<Item = (&'a str, &'a str)>,
+	IP: Iterator<Item = (&'a crate::picture::Picture, PictureInformation)>,
+{
+	create_comment_packet(tag, writer, add_framing_bit)?;
+
+	// Checksum is calculated later
+	Ok(ogg_pager::paginate(writer.get_ref(), stream_serial, 0, 0))
+}
+
+// Writes the comments to `writer`, which is expected to already contain the packet's signature and vendor string
+#[cfg(feature = "vorbis_comments")]
+fn create_comment_packet<'a, II, IP>(
+	tag: &mut VorbisCommentsRef<'a, II, IP>,
+	writer: &mut Cursor<Vec<u8>>,
+	add_framing_bit: bool,
+) -> Result<()>
 where
 	II: Iterator<Item = (&'a str, &'a str)>,
 	IP: Iterator<Item = (&'a crate::picture::Picture, PictureInformation)>,
@@ -142,8 +183,7 @@ where
 	writer.write_u32::<LittleEndian>(count)?;
 	writer.seek(SeekFrom::Start(packet_end))?;
 
-	// Checksum is calculated later
-	Ok(ogg_pager::paginate(writer.get_ref(), stream_serial, 0, 0))
+	Ok(())
 }
 
 #[cfg(feature = "vorbis_comments")]
@@ -151,7 +191,7 @@ pub(super) fn write<'a, II, IP>(
 	data: &mut File,
 	tag: &mut VorbisCommentsRef<'a, II, IP>,
 	format: OGGFormat,
-) -> Result<()>
+) -> Result<PageRewrite>
 where
 	II: Iterator<Item = (&'a str, &'a str)>,
 	IP: Iterator<Item = (&'a crate::picture::Picture, PictureInformation)>,
@@ -170,23 +210,63 @@ where
 		verify_signature(&first_md_page, comment_signature)?;
 	}
 
-	let comment_signature = comment_signature.unwrap_or_default();
+	let comment_signature = match format {
+		// The comments are stored in a FLAC metadata block, the header is updated once the size is known
+		OGGFormat::Flac => {
+			let content = first_md_page.content();
+			if content.len() < 4 || content[0] & 0x7F != BLOCK_ID_VORBIS_COMMENTS {
+				return Err(FileEncodingError::new(
+					FileType::OggFlac,
+					"File missing a Vorbis Comments block",
+				)
+				.into());
+			}
+
+			&content[..4]
+		},
+		_ => comment_signature.unwrap_or_default(),
+	};
 
-	// Retain the file's vendor string
 	let md_reader = &mut &first_md_page.content()[comment_signature.len()..];
 
 	let vendor_len = md_reader.read_u32::<LittleEndian>()?;
-	let mut vendor = try_vec![0; vendor_len as usize];
-	md_reader.read_exact(&mut vendor)?;
+	let mut file_vendor = try_vec![0; vendor_len as usize];
+	md_reader.read_exact(&mut file_vendor)?;
+
+	// Retain the file's vendor string, unless the tag has its own
+	let vendor = if !tag.vendor.is_empty() {
+		tag.vendor.as_bytes()
+	} else if file_vendor.is_empty() {
+		DEFAULT_VENDOR.as_bytes()
+	} else {
+		file_vendor.as_slice()
+	};
 
 	let mut packet = Cursor::new(Vec::new());
 
 	packet.write_all(comment_signature)?;
-	packet.write_u32::<LittleEndian>(vendor_len)?;
-	packet.write_all(&vendor)?;
+	packet.write_u32::<LittleEndian>(vendor.len() as u32)?;
+	packet.write_all(vendor)?;
 
 	let needs_framing_bit = format == OGGFormat::Vorbis;
-	let mut pages = create_pages(tag, &mut packet, ser, needs_framing_bit)?;
+	create_comment_packet(tag, &mut packet, needs_framing_bit)?;
+
+	if format == OGGFormat::Flac {
+		let block_len = packet.get_ref().len() - 4;
+		if block_len > MAX_BLOCK_SIZE {
+			err!(TooMuchData);
+		}
+
+		// The last block flag is kept
+		packet.get_mut()[1..4].copy_from_slice(&(block_len as u32).to_be_bytes()[1..]);
+	}
+
+	if packet_unchanged(data, &first_md_page, packet.get_ref())? {
+		return Ok(PageRewrite::Unchanged);
+	}
+
+	// Checksum is calculated later
+	let mut pages = ogg_pager::paginate(packet.get_ref(), ser, 0, 0);
 
 	match format {
 		OGGFormat::Vorbis => {
@@ -203,13 +283,114 @@ where
 		OGGFormat::Speex => {
 			replace_packet(data, &mut writer, &mut pages, FileType::Speex)?;
 		},
+		OGGFormat::Flac => {
+			replace_packet(data, &mut writer, &mut pages, FileType::OggFlac)?;
+		},
+	}
+
+	write_pages(data, &writer, ser, first_page.start)
+}
+
+// Whether the comment packet starting in `first_md_page` is identical to `packet`
+//
+// The packet can be followed by another one (Vorbis) or by padding (Opus), so only the start of the
+// existing pages is compared. Since the packet starts with the item count, and each item with its
+// length, any differing item will be caught.
+fn packet_unchanged(data: &mut File, first_md_page: &Page, packet: &[u8]) -> Result<bool> {
+	let pos = data.stream_position()?;
+
+	let mut existing = first_md_page.content().to_vec();
+	while existing.len() < packet.len() {
+		match Page::read(data, false) {
+			Ok(page) if page.serial != first_md_page.serial => {},
+			// The packet continues onto this page
+			Ok(page) if page.header_type() & 0x01 == 0x01 => existing.extend(page.content()),
+			_ => break,
+		}
 	}
 
-	data.rewind()?;
-	data.set_len(first_page.end)?;
-	data.write_all(&writer)?;
+	data.seek(SeekFrom::Start(pos))?;
+	Ok(existing.starts_with(packet))
+}
+
+// Writes the new header pages, with `data` positioned at the first page following the old ones
+//
+// The following pages only need to be touched if the header pages changed in size. Their sequence
+// numbers are only updated if the number of header pages changed.
+fn write_pages(data: &mut File, header: &[u8], serial: u32, start: u64) -> Result<PageRewrite> {
+	let header_end = data.stream_position()?;
+
+	let mut next_seq_num = 0;
+	let mut header_reader = header;
+	while !header_reader.is_empty() {
+		let mut page_reader = Cursor::new(header_reader);
+		let page = Page::read(&mut page_reader, true)?;
+
+		next_seq_num = page.seq_num.wrapping_add(1);
+		header_reader = &header_reader[page.end as usize..];
+	}
 
-	Ok(())
+	// Other logical streams can be interleaved with this one, so only its own pages are checked
+	let mut needs_renumbering = false;
+	while let Ok(page) = Page::read(data, true) {
+		if page.serial == serial {
+			needs_renumbering = page.seq_num != next_seq_num;
+			break;
+		}
+	}
+
+	data.seek(SeekFrom::Start(header_end))?;
+
+	if !needs_renumbering && header.len() as u64 == header_end - start {
+		data.seek(SeekFrom::Start(start))?;
+		data.write_all(header)?;
+
+		return Ok(PageRewrite::InPlace);
+	}
+
+	let mut remaining = Vec::new();
+	data.read_to_end(&mut remaining)?;
+
+	if needs_renumbering {
+		renumber_pages(&mut remaining, serial, next_seq_num);
+	}
+
+	data.seek(SeekFrom::Start(start))?;
+	data.set_len(start)?;
+	data.write_all(header)?;
+	data.write_all(&remaining)?;
+
+	if needs_renumbering {
+		Ok(PageRewrite::Full)
+	} else {
+		Ok(PageRewrite::Moved)
+	}
+}
+
+// This works on the raw page bytes, since `Page` can't preserve the segment table
+// of a page containing multiple packets
+fn renumber_pages(content: &mut [u8], serial: u32, mut seq_num: u32) {
+	let mut page_bounds = Vec::new();
+
+	let mut reader = Cursor::new(&*content);
+	while let Ok(page) = Page::read(&mut reader, true) {
+		if page.serial == serial {
+			page_bounds.push((page.start as usize, page.end as usize));
+		}
+	}
+
+	for (start, end) in page_bounds {
+		let page = &mut content[start..end];
+
+		page[18..22].copy_from_slice(&seq_num.to_le_bytes());
+
+		// The checksum is calculated with the checksum field zeroed
+		page[22..26].copy_from_slice(&[0; 4]);
+		let checksum = ogg_pager::crc32(page);
+		page[22..26].copy_from_slice(&checksum.to_le_bytes());
+
+		seq_num = seq_num.wrapping_add(1);
+	}
 }
 
 fn replace_packet(
@@ -234,16 +415,11 @@ fn replace_packet(
 		return Err(FileEncodingError::new(file_type, "File ends with comment header").into());
 	}
 
-	let mut remaining = Vec::new();
-	data.read_to_end(&mut remaining)?;
-
 	for p in pages.iter_mut() {
 		p.gen_crc()?;
 
 		writer.write_all(&p.as_bytes()?)?;
 	}
 
-	writer.write_all(&remaining)?;
-
 	Ok(())
 }
@@ -8,13 +8,17 @@ use crate::{
 
 use std::borrow::Cow;
 use std::fmt::{Debug, Formatter};
-#[cfg(any(feature = "vorbis_comments", feature = "ape", feature = "id3v2"))]
+#[cfg(any(
+	feature = "vorbis_comments",
+	feature = "ape",
+	feature = "id3v2",
+	feature = "picture-tools"
+))]
 use std::io::Cursor;
-use std::io::Read;
 #[cfg(feature = "id3v2")]
 use std::io::Write;
-#[cfg(any(feature = "vorbis_comments", feature = "ape"))]
-use std::io::{Seek, SeekFrom};
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
 
 #[cfg(any(feature = "vorbis_comments"))]
 use byteorder::BigEndian;
@@ -50,6 +54,7 @@ pub const APE_PICTURE_TYPES: [&str; 21] = [
 ];
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 /// Mime types for pictures.
 pub enum MimeType {
@@ -63,6 +68,8 @@ pub enum MimeType {
 	Bmp,
 	/// GIF image
 	Gif,
+	/// WEBP image
+	Webp,
 	/// Some unknown mimetype
 	Unknown(String),
 	/// No mimetype
@@ -77,6 +84,7 @@ impl ToString for MimeType {
 			MimeType::Tiff => "image/tiff".to_string(),
 			MimeType::Bmp => "image/bmp".to_string(),
 			MimeType::Gif => "image/gif".to_string(),
+			MimeType::Webp => "image/webp".to_string(),
 			MimeType::Unknown(unknown) => unknown.clone(),
 			MimeType::None => String::new(),
 		}
@@ -93,11 +101,38 @@ impl MimeType {
 			"image/tiff" => Self::Tiff,
 			"image/bmp" => Self::Bmp,
 			"image/gif" => Self::Gif,
+			"image/webp" => Self::Webp,
 			"" => Self::None,
 			_ => Self::Unknown(mime_type.to_string()),
 		}
 	}
 
+	/// Guess a `MimeType` from the signature of picture data
+	///
+	/// This will return `None` if the format isn't recognized.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::MimeType;
+	///
+	/// let png_signature = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+	/// assert_eq!(MimeType::from_bytes(&png_signature), Some(MimeType::Png));
+	///
+	/// assert_eq!(MimeType::from_bytes(b"Not a picture"), None);
+	/// ```
+	pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+		match bytes {
+			[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Some(Self::Png),
+			[0xFF, 0xD8, ..] => Some(Self::Jpeg),
+			[b'G', b'I', b'F', 0x38, 0x37 | 0x39, b'a', ..] => Some(Self::Gif),
+			[b'B', b'M', ..] => Some(Self::Bmp),
+			[b'I', b'I', b'*', 0x00, ..] | [b'M', b'M', 0x00, b'*', ..] => Some(Self::Tiff),
+			[b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some(Self::Webp),
+			_ => None,
+		}
+	}
+
 	/// Get a &str from a `MimeType`
 	pub fn as_str(&self) -> &str {
 		match self {
@@ -106,6 +141,7 @@ impl MimeType {
 			MimeType::Tiff => "image/tiff",
 			MimeType::Bmp => "image/bmp",
 			MimeType::Gif => "image/gif",
+			MimeType::Webp => "image/webp",
 			MimeType::Unknown(unknown) => unknown,
 			MimeType::None => "",
 		}
@@ -115,6 +151,7 @@ impl MimeType {
 /// The picture type, according to ID3v2 APIC
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum PictureType {
 	Other,
@@ -449,7 +486,26 @@ impl PictureInformation {
 }
 
 /// Represents a picture.
+///
+/// With the `serde` feature, the picture data is (de)serialized as a base64 string.
+///
+/// ## Placeholders
+///
+/// When reading with [`ProbeOptions::read_pictures`](crate::ProbeOptions::read_pictures) disabled,
+/// pictures are created as placeholders. These only know where their data is stored in the file,
+/// and will have no data until loaded with [`Picture::load`] (or [`Tag::load_picture`](crate::Tag::load_picture)).
+/// Placeholders can not be written.
+///
+/// NOTE: Placeholders are only created for FLAC `PICTURE` blocks. Pictures in any other tag
+/// (Ex. ID3v2 `APIC` frames, MP4 `covr` atoms) are always read in full.
+///
+/// ## Equality
+///
+/// Pictures are compared (and hashed) by their type, mimetype, description, and data.
+/// Placeholders are also compared by the location of their data, since they have none.
+/// See [`Picture::content_hash`] for a hash that can be stored.
 #[derive(Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Picture {
 	/// The picture type according to ID3v2 APIC
 	pub(crate) pic_type: PictureType,
@@ -458,7 +514,36 @@ pub struct Picture {
 	/// The picture's description
 	pub(crate) description: Option<Cow<'static, str>>,
 	/// The binary data of the picture
+	#[cfg_attr(feature = "serde", serde(with = "base64_data"))]
 	pub(crate) data: Cow<'static, [u8]>,
+	/// The location of the picture data in the file, if it hasn't been loaded yet
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub(crate) data_range: Option<Range<u64>>,
+}
+
+// Binary data isn't representable in formats such as JSON, so it is stored as base64
+#[cfg(feature = "serde")]
+mod base64_data {
+	use std::borrow::Cow;
+
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub(super) fn serialize<S>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&base64::encode(data))
+	}
+
+	pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<Cow<'static, [u8]>, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let encoded = String::deserialize(deserializer)?;
+		base64::decode(encoded)
+			.map(Cow::Owned)
+			.map_err(serde::de::Error::custom)
+	}
 }
 
 impl Debug for Picture {
@@ -468,6 +553,7 @@ impl Debug for Picture {
 			.field("mime_type", &self.mime_type)
 			.field("description", &self.description)
 			.field("data", &format!("<{} bytes>", self.data.len()))
+			.field("data_range", &self.data_range)
 			.finish()
 	}
 }
@@ -481,12 +567,12 @@ impl Picture {
 	/// a [`File`](std::fs::File) for example.
 	/// * `pic_type` will always be [`PictureType::Other`],
 	/// be sure to change it accordingly if writing.
+	/// * If the format isn't recognized (see [`MimeType::from_bytes`]), the [`MimeType`] will be
+	/// [`MimeType::Unknown`], with the generic `application/octet-stream` type.
 	///
 	/// # Errors
 	///
-	/// * `reader` contains less than 8 bytes
-	/// * `reader` does not contain a supported format.
-	/// See [`MimeType`] for valid formats
+	/// * `reader` could not be read
 	pub fn from_reader<R>(reader: &mut R) -> Result<Self>
 	where
 		R: Read,
@@ -494,18 +580,31 @@ impl Picture {
 		let mut data = Vec::new();
 		reader.read_to_end(&mut data)?;
 
-		if data.len() < 8 {
-			err!(NotAPicture);
-		}
+		Ok(Self::from_data(data))
+	}
+
+	/// Create a [`Picture`] from picture data
+	///
+	/// The [`MimeType`] is determined from the signature of `bytes`, falling back to
+	/// [`MimeType::Unknown`] (see [`Picture::from_reader`]).
+	///
+	/// NOTE: `pic_type` will always be [`PictureType::Other`],
+	/// be sure to change it accordingly if writing.
+	pub fn from_bytes(bytes: &[u8]) -> Self {
+		Self::from_data(bytes.to_vec())
+	}
 
-		let mime_type = Self::mimetype_from_bin(&data[..8])?;
+	fn from_data(data: Vec<u8>) -> Self {
+		let mime_type = MimeType::from_bytes(&data)
+			.unwrap_or_else(|| MimeType::Unknown(String::from("application/octet-stream")));
 
-		Ok(Self {
+		Self {
 			pic_type: PictureType::Other,
 			mime_type,
 			description: None,
 			data: data.into(),
-		})
+			data_range: None,
+		}
 	}
 
 	/// Create a new `Picture`
@@ -524,6 +623,7 @@ impl Picture {
 			mime_type,
 			description: description.map(Cow::from),
 			data: Cow::from(data),
+			data_range: None,
 		}
 	}
 
@@ -556,10 +656,163 @@ impl Picture {
 	}
 
 	/// Returns the picture data
+	///
+	/// NOTE: This will be empty if the picture is a placeholder, see [`Picture::is_loaded`]
 	pub fn data(&self) -> &[u8] {
 		&self.data
 	}
 
+	/// Returns a stable 64-bit hash of the picture's content
+	///
+	/// This covers the picture type, mimetype, description, and data, so identical pictures
+	/// (across files, for example) can be found without comparing all of their data.
+	///
+	/// NOTES:
+	///
+	/// * This is **not** a cryptographic hash, and shouldn't be relied on where collisions
+	///   would be a security concern.
+	/// * Unlike the [`Hash`] implementation, the value doesn't depend on the platform or the Rust
+	///   version. It will only change between major versions of lofty.
+	/// * Placeholders have no data, so all placeholders with the same type, mimetype, and description
+	///   will have the same hash, see [`Picture::is_loaded`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{MimeType, Picture, PictureType};
+	///
+	/// let picture = Picture::new_unchecked(PictureType::CoverFront, MimeType::Png, None, vec![1, 2, 3]);
+	/// let mut other = picture.clone();
+	/// assert_eq!(picture.content_hash(), other.content_hash());
+	///
+	/// other.set_pic_type(PictureType::CoverBack);
+	/// assert_ne!(picture.content_hash(), other.content_hash());
+	/// ```
+	pub fn content_hash(&self) -> u64 {
+		// 64-bit FNV-1a
+		const OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+		const PRIME: u64 = 0x0000_0100_0000_01B3;
+
+		fn write(hash: &mut u64, bytes: &[u8]) {
+			for b in bytes {
+				*hash ^= u64::from(*b);
+				*hash = hash.wrapping_mul(PRIME);
+			}
+		}
+
+		// Variable length fields are prefixed with their length, so they can't run into each other
+		fn write_field(hash: &mut u64, field: &[u8]) {
+			write(hash, &(field.len() as u64).to_le_bytes());
+			write(hash, field);
+		}
+
+		let mut hash = OFFSET_BASIS;
+
+		write(&mut hash, &[self.pic_type.as_u8()]);
+		write_field(&mut hash, self.mime_type.as_str().as_bytes());
+		match self.description.as_deref() {
+			Some(description) => {
+				write(&mut hash, &[1]);
+				write_field(&mut hash, description.as_bytes());
+			},
+			None => write(&mut hash, &[0]),
+		}
+		write_field(&mut hash, &self.data);
+
+		hash
+	}
+
+	/// Whether the picture data has been loaded
+	///
+	/// This is only `false` for placeholders, see [`Picture::data_range`].
+	pub fn is_loaded(&self) -> bool {
+		self.data_range.is_none()
+	}
+
+	/// Returns the location of the picture data in the file, if it hasn't been loaded
+	pub fn data_range(&self) -> Option<Range<u64>> {
+		self.data_range.clone()
+	}
+
+	/// Load the data of a placeholder from `reader`
+	///
+	/// `reader` must be the same file the picture was read from. This does nothing
+	/// if the data is already loaded.
+	///
+	/// # Errors
+	///
+	/// * `reader` ends before the end of the picture data
+	pub fn load<R>(&mut self, reader: &mut R) -> Result<()>
+	where
+		R: Read + Seek,
+	{
+		use crate::macros::try_vec;
+
+		if let Some(range) = self.data_range.take() {
+			reader.seek(SeekFrom::Start(range.start))?;
+
+			let mut data = try_vec![0; (range.end - range.start) as usize];
+			if let Err(e) = reader.read_exact(&mut data) {
+				self.data_range = Some(range);
+				return Err(e.into());
+			}
+
+			self.mime_type = Self::detect_mime_type(&data, self.mime_type.clone());
+			self.data = Cow::from(data);
+		}
+
+		Ok(())
+	}
+
+	#[cfg(feature = "picture-tools")]
+	/// Create a scaled down copy of the picture, encoded as JPEG
+	///
+	/// The picture is scaled to fit within `max_dimension`x`max_dimension`, preserving its aspect ratio.
+	/// Pictures that already fit are only re-encoded.
+	///
+	/// NOTE: The format is determined from the data, rather than the [`MimeType`], since
+	/// embedded pictures are commonly mislabeled (Ex. a JPEG with a PNG mimetype)
+	///
+	/// # Errors
+	///
+	/// * The data is not in a supported format
+	/// * The picture fails to decode or encode
+	pub fn thumbnail(&self, max_dimension: u32) -> Result<Self> {
+		use image::{DynamicImage, GenericImageView, ImageFormat, ImageOutputFormat};
+
+		let format = match MimeType::from_bytes(&self.data) {
+			Some(MimeType::Png) => ImageFormat::Png,
+			Some(MimeType::Jpeg) => ImageFormat::Jpeg,
+			Some(MimeType::Gif) => ImageFormat::Gif,
+			Some(MimeType::Bmp) => ImageFormat::Bmp,
+			Some(MimeType::Tiff) => ImageFormat::Tiff,
+			Some(MimeType::Webp) => ImageFormat::WebP,
+			_ => err!(UnsupportedPicture),
+		};
+
+		let mut image = image::load_from_memory_with_format(&self.data, format)?;
+
+		let max_dimension = max_dimension.max(1);
+		let (width, height) = image.dimensions();
+		if width > max_dimension || height > max_dimension {
+			image = image.thumbnail(max_dimension, max_dimension);
+		}
+
+		// JPEG has no alpha channel
+		let image = DynamicImage::ImageRgb8(image.to_rgb8());
+
+		let mut data = Vec::new();
+		image.write_to(&mut Cursor::new(&mut data), ImageOutputFormat::Jpeg(90))?;
+
+		Ok(Self {
+			pic_type: self.pic_type,
+			mime_type: MimeType::Jpeg,
+			description: self.description.clone(),
+			data: Cow::from(data),
+			data_range: None,
+		})
+	}
+
 	#[cfg(feature = "id3v2")]
 	/// Convert a [`Picture`] to a ID3v2 A/PIC byte Vec
 	///
@@ -571,7 +824,8 @@ impl Picture {
 	///
 	/// ID3v2.2:
 	///
-	/// * The mimetype is not [`MimeType::Png`] or [`MimeType::Jpeg`]
+	/// * The mimetype is not [`MimeType::Png`], [`MimeType::Jpeg`], [`MimeType::Gif`],
+	/// [`MimeType::Bmp`], or [`MimeType::Tiff`]
 	pub fn as_apic_bytes(
 		&self,
 		version: ID3v2Version,
@@ -592,6 +846,9 @@ impl Picture {
 			let format = match self.mime_type {
 				MimeType::Png => "PNG",
 				MimeType::Jpeg => "JPG",
+				MimeType::Gif => "GIF",
+				MimeType::Bmp => "BMP",
+				MimeType::Tiff => "TIF",
 				_ => {
 					return Err(ID3v2Error::new(ID3v2ErrorKind::BadPictureFormat(
 						self.mime_type.to_string(),
@@ -636,7 +893,8 @@ impl Picture {
 	///
 	/// ID3v2.2:
 	///
-	/// * The format is not "PNG" or "JPG"
+	/// * The format is not "PNG", "JPG", "GIF", "BMP", or "TIF", and it can't be
+	/// determined from the data
 	pub fn from_apic_bytes(bytes: &[u8], version: ID3v2Version) -> Result<(Self, TextEncoding)> {
 		use crate::id3::v2::util::text_utils;
 
@@ -647,18 +905,22 @@ impl Picture {
 			None => err!(NotAPicture),
 		};
 
+		// ID3v2.2 uses a 3 character image format, rather than a mimetype
+		let mut unknown_format = None;
 		let mime_type = if version == ID3v2Version::V2 {
 			let mut format = [0; 3];
 			cursor.read_exact(&mut format)?;
-
-			match format {
-				[b'P', b'N', b'G'] => MimeType::Png,
-				[b'J', b'P', b'G'] => MimeType::Jpeg,
+			format.make_ascii_uppercase();
+
+			match &format {
+				b"PNG" => MimeType::Png,
+				b"JPG" => MimeType::Jpeg,
+				b"GIF" => MimeType::Gif,
+				b"BMP" => MimeType::Bmp,
+				b"TIF" => MimeType::Tiff,
 				_ => {
-					return Err(ID3v2Error::new(ID3v2ErrorKind::BadPictureFormat(
-						String::from_utf8_lossy(&format).into_owned(),
-					))
-					.into())
+					unknown_format = Some(format);
+					MimeType::None
 				},
 			}
 		} else {
@@ -673,12 +935,21 @@ impl Picture {
 		let mut data = Vec::new();
 		cursor.read_to_end(&mut data)?;
 
+		let mime_type = Self::detect_mime_type(&data, mime_type);
+		if let (MimeType::None, Some(format)) = (&mime_type, unknown_format) {
+			return Err(ID3v2Error::new(ID3v2ErrorKind::BadPictureFormat(
+				String::from_utf8_lossy(&format).into_owned(),
+			))
+			.into());
+		}
+
 		Ok((
 			Picture {
 				pic_type,
 				mime_type,
 				description,
 				data: Cow::from(data),
+				data_range: None,
 			},
 			encoding,
 		))
@@ -812,12 +1083,15 @@ impl Picture {
 			let mut data = try_vec![0; data_len];
 
 			if let Ok(()) = reader.read_exact(&mut data) {
+				let mime_type = Self::detect_mime_type(&data, MimeType::from_str(mime_type_str));
+
 				return Ok((
 					Self {
 						pic_type: PictureType::from_u8(pic_ty as u8),
-						mime_type: MimeType::from_str(mime_type_str),
+						mime_type,
 						description,
 						data: Cow::from(data),
+						data_range: None,
 					},
 					PictureInformation {
 						width,
@@ -832,6 +1106,67 @@ impl Picture {
 		err!(NotAPicture)
 	}
 
+	// Creates a placeholder from a FLAC `PICTURE` block, skipping over the picture data
+	#[cfg(feature = "vorbis_comments")]
+	pub(crate) fn flac_placeholder<R>(
+		reader: &mut R,
+		block_len: u64,
+	) -> Result<(Self, PictureInformation)>
+	where
+		R: Read + Seek,
+	{
+		use crate::macros::try_vec;
+
+		let block_end = reader.stream_position()? + block_len;
+
+		let read_field = |reader: &mut R| -> Result<Vec<u8>> {
+			let len = reader.read_u32::<BigEndian>()?;
+			if reader.stream_position()? + u64::from(len) > block_end {
+				err!(NotAPicture);
+			}
+
+			let mut field = try_vec![0; len as usize];
+			reader.read_exact(&mut field)?;
+			Ok(field)
+		};
+
+		let pic_ty = reader.read_u32::<BigEndian>()?;
+		if pic_ty > 255 {
+			err!(NotAPicture);
+		}
+
+		let mime_type = read_field(reader)?;
+		let description = read_field(reader)?;
+
+		let picture_information = PictureInformation {
+			width: reader.read_u32::<BigEndian>()?,
+			height: reader.read_u32::<BigEndian>()?,
+			color_depth: reader.read_u32::<BigEndian>()?,
+			num_colors: reader.read_u32::<BigEndian>()?,
+		};
+
+		let data_len = u64::from(reader.read_u32::<BigEndian>()?);
+		let data_start = reader.stream_position()?;
+
+		if data_start + data_len > block_end {
+			err!(NotAPicture);
+		}
+
+		reader.seek(SeekFrom::Start(block_end))?;
+
+		Ok((
+			Self {
+				pic_type: PictureType::from_u8(pic_ty as u8),
+				mime_type: MimeType::from_str(std::str::from_utf8(&mime_type)?),
+				description: (!description.is_empty())
+					.then(|| Cow::from(String::from_utf8_lossy(&description).into_owned())),
+				data: Cow::from(Vec::new()),
+				data_range: Some(data_start..data_start + data_len),
+			},
+			picture_information,
+		))
+	}
+
 	#[cfg(feature = "ape")]
 	/// Convert a [`Picture`] to an APE Cover Art byte vec:
 	///
@@ -887,12 +1222,7 @@ impl Picture {
 			description = Some(Cow::from(desc_text));
 		}
 
-		let mime_type = {
-			let mut identifier = [0; 8];
-			reader.read_exact(&mut identifier)?;
-
-			Self::mimetype_from_bin(&identifier[..])?
-		};
+		let mime_type = Self::mimetype_from_bin(&bytes[pos..])?;
 
 		let data = Cow::from(bytes[pos..].to_vec());
 
@@ -901,17 +1231,19 @@ impl Picture {
 			mime_type,
 			description,
 			data,
+			data_range: None,
 		})
 	}
 
 	fn mimetype_from_bin(bytes: &[u8]) -> Result<MimeType> {
-		match bytes[..8] {
-			[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A] => Ok(MimeType::Png),
-			[0xFF, 0xD8, ..] => Ok(MimeType::Jpeg),
-			[b'G', b'I', b'F', 0x38, 0x37 | 0x39, b'a', ..] => Ok(MimeType::Gif),
-			[b'B', b'M', ..] => Ok(MimeType::Bmp),
-			[b'I', b'I', b'*', 0x00, ..] | [b'M', b'M', 0x00, b'*', ..] => Ok(MimeType::Tiff),
-			_ => err!(NotAPicture),
+		match MimeType::from_bytes(bytes) {
+			Some(mime_type) => Ok(mime_type),
+			None => err!(NotAPicture),
 		}
 	}
+
+	// The stored mimetype can't always be trusted, so the signature of the data takes precedence
+	fn detect_mime_type(data: &[u8], stored: MimeType) -> MimeType {
+		MimeType::from_bytes(data).unwrap_or(stored)
+	}
 }
@@ -1,22 +1,24 @@
-use crate::ape::ApeFile;
-use crate::error::Result;
+use crate::aac::header::verify_adts_sync;
+use crate::error::{ErrorKind, LoftyError, Result};
 use crate::file::{AudioFile, FileType, TaggedFile};
 use crate::flac::FlacFile;
-use crate::iff::aiff::AiffFile;
-use crate::iff::wav::WavFile;
 use crate::macros::err;
-use crate::mp4::Mp4File;
+use crate::mka::MkaFile;
 use crate::mpeg::header::search_for_frame_sync;
-use crate::mpeg::MPEGFile;
+use crate::musepack::MpcFile;
+use crate::ogg::flac::OggFlacFile;
 use crate::ogg::opus::OpusFile;
 use crate::ogg::speex::SpeexFile;
 use crate::ogg::vorbis::VorbisFile;
 use crate::resolve::CUSTOM_RESOLVERS;
+use crate::tag::TagType;
 use crate::wavpack::WavPackFile;
 
 use std::fs::File;
 use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
 use std::path::Path;
+#[cfg(feature = "rayon")]
+use std::path::PathBuf;
 
 /// A format agnostic reader
 ///
@@ -77,6 +79,286 @@ use std::path::Path;
 pub struct Probe<R: Read> {
 	inner: R,
 	f_ty: Option<FileType>,
+	options: ProbeOptions,
+}
+
+/// Options to control how a [`Probe`] reads a file
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::{Probe, ProbeOptions, TagType};
+///
+/// # fn main() -> lofty::Result<()> {
+/// # let path = "tests/files/assets/minimal/full_test.mp3";
+/// // Only look for an ID3v2 tag, without reading the audio properties
+/// let options = ProbeOptions::new()
+/// 	.read_properties(false)
+/// 	.tag_types(Some(vec![TagType::ID3v2]));
+///
+/// let tagged_file = Probe::open(path)?.options(options).read(true)?;
+///
+/// assert_eq!(tagged_file.tags().len(), 1);
+/// assert!(tagged_file.tag(TagType::ID3v2).is_some());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ProbeOptions {
+	/// Whether to read the audio properties, defaults to `true`
+	///
+	/// If this is `false`, the properties will be zeroed out, regardless of the value
+	/// passed to [`Probe::read`].
+	pub read_properties: bool,
+	/// The tag types to read, defaults to `None` (read all tags)
+	///
+	/// Any other tags will be skipped. When the properties are not being read, some formats
+	/// are able to stop reading as soon as all of these tags are found. For example, reading
+	/// only the ID3v2 tag of an MP3 file will stop right after the tag, without searching for
+	/// any tags at the end of the file.
+	pub tag_types: Option<Vec<TagType>>,
+	/// Whether to read the picture data, defaults to `true`
+	///
+	/// If this is `false`, pictures will be read as placeholders, only recording the location
+	/// of their data. They can be loaded later with [`Tag::load_picture`](crate::Tag::load_picture).
+	///
+	/// NOTE: This is currently only supported for FLAC `PICTURE` blocks, pictures in other
+	/// formats will always be read.
+	pub read_pictures: bool,
+	/// How to handle invalid data, defaults to [`ParseMode::Strict`]
+	pub parse_mode: ParseMode,
+	/// The order of preference for the primary tag, defaults to `None` (the [`FileType`]'s primary tag)
+	///
+	/// The first of these tag types present in the file will be used as the primary tag.
+	/// See [`TaggedFile::primary_tag_type`].
+	pub tag_preference: Option<Vec<TagType>>,
+	/// The maximum number of pictures in a tag, defaults to `Some(32)`
+	///
+	/// Reading a tag with more pictures will fail with [`ErrorKind::TooManyPictures`]. With
+	/// [`ParseMode::Relaxed`], the extra pictures are skipped instead. A value of `None` disables the limit.
+	///
+	/// NOTE: This is enforced for ID3v2 tags, FLAC `PICTURE` blocks, and MP4 `covr` atoms.
+	pub max_pictures: Option<u32>,
+	/// The maximum size of a picture in bytes, defaults to `Some(64 MiB)`
+	///
+	/// Reading a larger picture will fail with [`ErrorKind::PictureTooLarge`]. With
+	/// [`ParseMode::Relaxed`], the picture is skipped instead. A value of `None` disables the limit.
+	///
+	/// NOTE: This is enforced for ID3v2 tags, FLAC `PICTURE` blocks, and MP4 `covr` atoms.
+	pub max_picture_bytes: Option<u64>,
+	/// The maximum size of a tag in bytes, defaults to `Some(256 MiB)`
+	///
+	/// Reading a larger tag will fail with [`ErrorKind::TagTooLarge`]. For FLAC files, this is
+	/// the combined size of the Vorbis Comments and `PICTURE` blocks. A value of `None` disables the limit.
+	///
+	/// NOTE: This is enforced for ID3v2 tags, FLAC files, and MP4 `ilst` atoms.
+	pub max_tag_bytes: Option<u64>,
+}
+
+impl Default for ProbeOptions {
+	fn default() -> Self {
+		Self {
+			read_properties: true,
+			tag_types: None,
+			read_pictures: true,
+			parse_mode: ParseMode::Strict,
+			tag_preference: None,
+			max_pictures: Some(32),
+			max_picture_bytes: Some(64 * 1024 * 1024),
+			max_tag_bytes: Some(256 * 1024 * 1024),
+		}
+	}
+}
+
+impl ProbeOptions {
+	/// Creates a new `ProbeOptions`, same as `Default`
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Whether to read the audio properties
+	///
+	/// See [`ProbeOptions::read_properties`](#structfield.read_properties)
+	#[must_use]
+	pub fn read_properties(mut self, read_properties: bool) -> Self {
+		self.read_properties = read_properties;
+		self
+	}
+
+	/// The tag types to read
+	///
+	/// See [`ProbeOptions::tag_types`](#structfield.tag_types)
+	#[must_use]
+	pub fn tag_types(mut self, tag_types: Option<Vec<TagType>>) -> Self {
+		self.tag_types = tag_types;
+		self
+	}
+
+	/// Whether to read the picture data
+	///
+	/// See [`ProbeOptions::read_pictures`](#structfield.read_pictures)
+	#[must_use]
+	pub fn read_pictures(mut self, read_pictures: bool) -> Self {
+		self.read_pictures = read_pictures;
+		self
+	}
+
+	/// How to handle invalid data
+	///
+	/// See [`ProbeOptions::parse_mode`](#structfield.parse_mode)
+	#[must_use]
+	pub fn parse_mode(mut self, parse_mode: ParseMode) -> Self {
+		self.parse_mode = parse_mode;
+		self
+	}
+
+	/// The order of preference for the primary tag
+	///
+	/// See [`ProbeOptions::tag_preference`](#structfield.tag_preference)
+	#[must_use]
+	pub fn tag_preference(mut self, tag_preference: Option<Vec<TagType>>) -> Self {
+		self.tag_preference = tag_preference;
+		self
+	}
+
+	/// The maximum number of pictures in a tag
+	///
+	/// See [`ProbeOptions::max_pictures`](#structfield.max_pictures)
+	#[must_use]
+	pub fn max_pictures(mut self, max_pictures: Option<u32>) -> Self {
+		self.max_pictures = max_pictures;
+		self
+	}
+
+	/// The maximum size of a picture in bytes
+	///
+	/// See [`ProbeOptions::max_picture_bytes`](#structfield.max_picture_bytes)
+	#[must_use]
+	pub fn max_picture_bytes(mut self, max_picture_bytes: Option<u64>) -> Self {
+		self.max_picture_bytes = max_picture_bytes;
+		self
+	}
+
+	/// The maximum size of a tag in bytes
+	///
+	/// See [`ProbeOptions::max_tag_bytes`](#structfield.max_tag_bytes)
+	#[must_use]
+	pub fn max_tag_bytes(mut self, max_tag_bytes: Option<u64>) -> Self {
+		self.max_tag_bytes = max_tag_bytes;
+		self
+	}
+}
+
+// The limits from `ProbeOptions` that are enforced while reading tags
+//
+// These are only set when reading through a `Probe`, concrete file types are read without limits.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ReadLimits {
+	pub(crate) max_pictures: Option<u32>,
+	pub(crate) max_picture_bytes: Option<u64>,
+	pub(crate) max_tag_bytes: Option<u64>,
+}
+
+impl ReadLimits {
+	pub(crate) fn check_tag_size(&self, size: u64) -> Result<()> {
+		match self.max_tag_bytes {
+			Some(limit) if size > limit => {
+				Err(LoftyError::new(ErrorKind::TagTooLarge { size, limit }))
+			},
+			_ => Ok(()),
+		}
+	}
+
+	// `count` is the number of pictures read so far, including the current one
+	pub(crate) fn check_picture_count(&self, count: usize) -> Result<()> {
+		match self.max_pictures {
+			Some(limit) if count > limit as usize => {
+				Err(LoftyError::new(ErrorKind::TooManyPictures { limit }))
+			},
+			_ => Ok(()),
+		}
+	}
+
+	pub(crate) fn check_picture_size(&self, size: u64) -> Result<()> {
+		match self.max_picture_bytes {
+			Some(limit) if size > limit => {
+				Err(LoftyError::new(ErrorKind::PictureTooLarge { size, limit }))
+			},
+			_ => Ok(()),
+		}
+	}
+}
+
+// The options from `ProbeOptions` that are passed down to the format readers
+//
+// With `ParseMode::Relaxed`, any errors skipped over are pushed to `parse_errors`
+pub(crate) struct ParseOptions<'a> {
+	pub(crate) tag_types: Option<&'a [TagType]>,
+	pub(crate) read_pictures: bool,
+	pub(crate) parse_mode: ParseMode,
+	pub(crate) limits: ReadLimits,
+	pub(crate) parse_errors: Vec<LoftyError>,
+}
+
+impl<'a> ParseOptions<'a> {
+	// Used when reading a concrete file type outside of a `Probe`
+	pub(crate) fn new() -> Self {
+		Self {
+			tag_types: None,
+			read_pictures: true,
+			parse_mode: ParseMode::Strict,
+			limits: ReadLimits::default(),
+			parse_errors: Vec::new(),
+		}
+	}
+
+	fn from_probe_options(options: &'a ProbeOptions) -> Self {
+		Self {
+			tag_types: options.tag_types.as_deref(),
+			read_pictures: options.read_pictures,
+			parse_mode: options.parse_mode,
+			limits: ReadLimits {
+				max_pictures: options.max_pictures,
+				max_picture_bytes: options.max_picture_bytes,
+				max_tag_bytes: options.max_tag_bytes,
+			},
+			parse_errors: Vec::new(),
+		}
+	}
+
+	// Whether a picture that was checked against the limits should be kept
+	//
+	// With `ParseMode::Relaxed`, a picture exceeding the limits is skipped rather than failing the read
+	pub(crate) fn keep_picture(&mut self, check: Result<()>) -> Result<bool> {
+		match check {
+			Ok(()) => Ok(true),
+			Err(e) if self.parse_mode == ParseMode::Relaxed => {
+				self.parse_errors.push(e);
+				Ok(false)
+			},
+			Err(e) => Err(e),
+		}
+	}
+}
+
+/// How a [`Probe`] handles invalid data
+///
+/// See [`ProbeOptions::parse_mode`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseMode {
+	/// Any invalid data will cause reading to fail
+	Strict,
+	/// Skip over any ID3v2 frames that fail to parse, keeping the rest of the tag
+	///
+	/// Pictures exceeding [`ProbeOptions::max_pictures`] or [`ProbeOptions::max_picture_bytes`]
+	/// are also skipped.
+	///
+	/// The errors that were skipped over are available through [`TaggedFile::parse_errors`].
+	///
+	/// NOTE: If a frame's header is unusable, the rest of the tag will be skipped, since there's
+	/// no way to tell where the next frame starts.
+	Relaxed,
 }
 
 impl<R: Read> Probe<R> {
@@ -104,6 +386,7 @@ impl<R: Read> Probe<R> {
 		Self {
 			inner: reader,
 			f_ty: None,
+			options: ProbeOptions::default(),
 		}
 	}
 
@@ -133,6 +416,7 @@ impl<R: Read> Probe<R> {
 		Self {
 			inner: reader,
 			f_ty: Some(file_type),
+			options: ProbeOptions::default(),
 		}
 	}
 
@@ -175,6 +459,26 @@ impl<R: Read> Probe<R> {
 		self.f_ty = Some(file_type)
 	}
 
+	/// Set the [`ProbeOptions`] to use when reading the file
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{Probe, ProbeOptions};
+	///
+	/// # fn main() -> lofty::Result<()> {
+	/// # let reader = std::io::Cursor::new(&[]);
+	/// let options = ProbeOptions::new().read_properties(false);
+	///
+	/// let probe = Probe::new(reader).options(options);
+	/// # Ok(()) }
+	/// ```
+	#[must_use]
+	pub fn options(mut self, options: ProbeOptions) -> Self {
+		self.options = options;
+		self
+	}
+
 	/// Extract the reader
 	///
 	/// # Examples
@@ -225,6 +529,7 @@ impl Probe<BufReader<File>> {
 		Ok(Self {
 			inner: BufReader::new(File::open(path)?),
 			f_ty: FileType::from_path(path),
+			options: ProbeOptions::default(),
 		})
 	}
 }
@@ -262,6 +567,32 @@ impl<R: Read + Seek> Probe<R> {
 		Ok(self)
 	}
 
+	/// Attempts to get the [`FileType`] based only on the data in the reader
+	///
+	/// Unlike [`Probe::guess_file_type`], this will not keep the current file type (for example,
+	/// one guessed from the extension) if the content can't be identified.
+	///
+	/// # Errors
+	///
+	/// See [`Probe::guess_file_type`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{FileType, Probe};
+	///
+	/// # fn main() -> lofty::Result<()> {
+	/// let reader = std::io::Cursor::new(b"Not an audio file");
+	/// let probe = Probe::with_file_type(reader, FileType::MPEG).guess_file_type_by_content()?;
+	///
+	/// assert_eq!(probe.file_type(), None);
+	/// # Ok(()) }
+	/// ```
+	pub fn guess_file_type_by_content(mut self) -> std::io::Result<Self> {
+		self.f_ty = self.guess_inner()?;
+		Ok(self)
+	}
+
 	#[allow(clippy::shadow_unrelated)]
 	fn guess_inner(&mut self) -> std::io::Result<Option<FileType>> {
 		// temporary buffer for storing 36 bytes
@@ -300,6 +631,8 @@ impl<R: Read + Seek> Probe<R> {
 				let file_type_after_id3_block = match &ident {
 					[b'M', b'A', b'C', ..] => Ok(Some(FileType::APE)),
 					b"fLaC" => Ok(Some(FileType::FLAC)),
+					b"TTA1" => Ok(Some(FileType::TTA)),
+					[0xFF, b, ..] if verify_adts_sync([0xFF, *b]) => Ok(Some(FileType::AAC)),
 					// Search for a frame sync, which may be preceded by junk
 					_ if search_for_frame_sync(&mut self.inner)?.is_some() => {
 						Ok(Some(FileType::MPEG))
@@ -329,7 +662,10 @@ impl<R: Read + Seek> Probe<R> {
 
 	/// Attempts to extract a [`TaggedFile`] from the reader
 	///
-	/// If `read_properties` is false, the properties will be zeroed out.
+	/// If `read_properties` is false, the properties will be zeroed out. This is also
+	/// the case if [`ProbeOptions::read_properties`] is false.
+	///
+	/// If [`ProbeOptions::tag_types`] is set, only the tags of those types will be kept.
 	///
 	/// # Errors
 	///
@@ -359,18 +695,83 @@ impl<R: Read + Seek> Probe<R> {
 	/// ```
 	pub fn read(mut self, read_properties: bool) -> Result<TaggedFile> {
 		let reader = &mut self.inner;
+		let read_properties = read_properties && self.options.read_properties;
+		let mut options = ParseOptions::from_probe_options(&self.options);
+		let parse_options = &mut options;
 
-		match self.f_ty {
-			Some(f_type) => Ok(match f_type {
-				FileType::AIFF => AiffFile::read_from(reader, read_properties)?.into(),
-				FileType::APE => ApeFile::read_from(reader, read_properties)?.into(),
-				FileType::FLAC => FlacFile::read_from(reader, read_properties)?.into(),
-				FileType::MPEG => MPEGFile::read_from(reader, read_properties)?.into(),
-				FileType::Opus => OpusFile::read_from(reader, read_properties)?.into(),
-				FileType::Vorbis => VorbisFile::read_from(reader, read_properties)?.into(),
-				FileType::WAV => WavFile::read_from(reader, read_properties)?.into(),
-				FileType::MP4 => Mp4File::read_from(reader, read_properties)?.into(),
-				FileType::Speex => SpeexFile::read_from(reader, read_properties)?.into(),
+		let mut tagged_file: TaggedFile = match self.f_ty {
+			Some(f_type) => match f_type {
+				FileType::AAC => crate::aac::read::read_from_with_options(
+					reader,
+					read_properties,
+					parse_options,
+				)?
+				.into(),
+				FileType::AIFF => crate::iff::aiff::read::read_from_with_options(
+					reader,
+					read_properties,
+					parse_options,
+				)?
+				.into(),
+				FileType::APE => crate::ape::read::read_from_with_options(
+					reader,
+					read_properties,
+					parse_options,
+				)?
+				.into(),
+				FileType::DSF => crate::dsf::read::read_from_with_options(
+					reader,
+					read_properties,
+					parse_options,
+				)?
+				.into(),
+				FileType::FLAC => crate::flac::read::read_from_with_options(
+					reader,
+					read_properties,
+					parse_options,
+				)?
+				.into(),
+				FileType::Mka => MkaFile::read_from(reader, read_properties)?.into(),
+				FileType::MPEG => crate::mpeg::read::read_from_with_options(
+					reader,
+					read_properties,
+					parse_options,
+				)?
+				.into(),
+				FileType::OggFlac => {
+					OggFlacFile::read_from_with_options(reader, read_properties, parse_options)?
+						.into()
+				},
+				FileType::Opus => {
+					OpusFile::read_from_with_options(reader, read_properties, parse_options)?.into()
+				},
+				FileType::Vorbis => {
+					VorbisFile::read_from_with_options(reader, read_properties, parse_options)?
+						.into()
+				},
+				FileType::WAV => crate::iff::wav::read::read_from_with_options(
+					reader,
+					read_properties,
+					parse_options,
+				)?
+				.into(),
+				FileType::MP4 => crate::mp4::read::read_from_with_options(
+					reader,
+					read_properties,
+					parse_options,
+				)?
+				.into(),
+				FileType::Musepack => MpcFile::read_from(reader, read_properties)?.into(),
+				FileType::Speex => {
+					SpeexFile::read_from_with_options(reader, read_properties, parse_options)?
+						.into()
+				},
+				FileType::TTA => crate::tta::read::read_from_with_options(
+					reader,
+					read_properties,
+					parse_options,
+				)?
+				.into(),
 				FileType::WavPack => WavPackFile::read_from(reader, read_properties)?.into(),
 				FileType::Custom(c) => {
 					if let Some(r) = crate::resolve::lookup_resolver(c) {
@@ -382,9 +783,20 @@ impl<R: Read + Seek> Probe<R> {
 						);
 					}
 				},
-			}),
+			},
 			None => err!(UnknownFormat),
+		};
+
+		if let Some(tag_types) = options.tag_types {
+			tagged_file
+				.tags
+				.retain(|tag| tag_types.contains(&tag.tag_type()));
 		}
+
+		tagged_file.parse_errors = options.parse_errors;
+		tagged_file.tag_preference = self.options.tag_preference.clone();
+
+		Ok(tagged_file)
 	}
 }
 
@@ -418,13 +830,15 @@ pub fn read_from(file: &mut File, read_properties: bool) -> Result<TaggedFile> {
 
 /// Read a [`TaggedFile`] from a path
 ///
-/// NOTE: This will determine the [`FileType`] from the extension
+/// NOTE: This will determine the [`FileType`] from the content, only falling back to the
+/// extension if the content can't be identified. See [`Probe::guess_file_type`].
 ///
 /// # Errors
 ///
 /// See:
 ///
 /// * [`Probe::open`]
+/// * [`Probe::guess_file_type`]
 /// * [`Probe::read`]
 ///
 /// # Examples
@@ -441,15 +855,569 @@ pub fn read_from_path<P>(path: P, read_properties: bool) -> Result<TaggedFile>
 where
 	P: AsRef<Path>,
 {
-	Probe::open(path)?.read(read_properties)
+	Probe::open(path)?.guess_file_type()?.read(read_properties)
+}
+
+/// Read every [`TaggedFile`] in a directory, in parallel
+///
+/// The directory is searched recursively, and any file without a known extension is
+/// skipped (see [`FileType::from_path`]). The files are then read with [`read_from_path`]
+/// across multiple threads.
+///
+/// Every file has its own result, so a single unreadable file will not stop the rest from
+/// being read. Errors that occur while searching the directory are returned along with the
+/// path that caused them.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lofty::read_dir_parallel;
+///
+/// for (path, result) in read_dir_parallel("path/to/music", false) {
+/// 	match result {
+/// 		Ok(tagged_file) => println!("{}: {:?}", path.display(), tagged_file.file_type()),
+/// 		Err(e) => eprintln!("Failed to read {}: {}", path.display(), e),
+/// 	}
+/// }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn read_dir_parallel<P>(
+	path: P,
+	read_properties: bool,
+) -> impl Iterator<Item = (PathBuf, Result<TaggedFile>)>
+where
+	P: AsRef<Path>,
+{
+	use rayon::prelude::*;
+
+	let mut paths = Vec::new();
+	let mut errors = Vec::new();
+	find_audio_files(path.as_ref(), &mut paths, &mut errors);
+
+	// Keep the order consistent, regardless of the order the files are listed in
+	paths.sort();
+
+	let results: Vec<_> = paths
+		.into_par_iter()
+		.map(|path| {
+			let result = read_from_path(&path, read_properties);
+			(path, result)
+		})
+		.collect();
+
+	errors.into_iter().chain(results)
+}
+
+#[cfg(feature = "rayon")]
+fn find_audio_files(
+	dir: &Path,
+	paths: &mut Vec<PathBuf>,
+	errors: &mut Vec<(PathBuf, Result<TaggedFile>)>,
+) {
+	let entries = match std::fs::read_dir(dir) {
+		Ok(entries) => entries,
+		Err(e) => {
+			errors.push((dir.to_path_buf(), Err(e.into())));
+			return;
+		},
+	};
+
+	for entry in entries {
+		let entry = match entry {
+			Ok(entry) => entry,
+			Err(e) => {
+				errors.push((dir.to_path_buf(), Err(e.into())));
+				continue;
+			},
+		};
+
+		let path = entry.path();
+		match entry.file_type() {
+			Ok(file_type) if file_type.is_dir() => find_audio_files(&path, paths, errors),
+			Ok(_) if FileType::from_path(&path).is_some() => paths.push(path),
+			Ok(_) => {},
+			Err(e) => errors.push((path, Err(e.into()))),
+		}
+	}
+}
+
+/// Read a [`TaggedFile`] from a reader that doesn't implement [`Seek`]
+///
+/// This is useful for sources such as network streams, where random access isn't possible.
+/// Only as much of the stream as is needed to read the tags at the start of the file will be
+/// buffered.
+///
+/// NOTES:
+///
+/// * Audio properties will **not** be read, [`TaggedFile::properties`] will be empty
+/// * Only tags at the start of the stream are read. For example, MP3 files will only have their
+/// ID3v2 tag read, any ID3v1 or APE tags at the end of the stream are not available.
+///
+/// The supported formats are:
+///
+/// * [`FileType::AAC`] and [`FileType::MPEG`] (with an ID3v2 tag)
+/// * [`FileType::FLAC`]
+/// * [`FileType::OggFlac`], [`FileType::Opus`], [`FileType::Speex`], and [`FileType::Vorbis`]
+///
+/// # Errors
+///
+/// * The format could not be determined
+/// * The format stores its tags at the end of the file, or at a location that isn't
+/// known ahead of time ([`ErrorKind::RequiresSeek`](crate::error::ErrorKind::RequiresSeek))
+/// * The stream contains invalid data
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::{read_from_reader, FileType};
+///
+/// # fn main() -> lofty::Result<()> {
+/// # let path = "tests/files/assets/minimal/full_test.flac";
+/// let content = std::fs::read(path)?;
+///
+/// // `&[u8]` only implements `Read`
+/// let parsed_file = read_from_reader(&mut &*content)?;
+///
+/// assert_eq!(parsed_file.file_type(), FileType::FLAC);
+/// # Ok(()) }
+/// ```
+pub fn read_from_reader<R>(reader: &mut R) -> Result<TaggedFile>
+where
+	R: Read,
+{
+	use crate::id3::v2::read_id3v2_header;
+
+	let mut buf = Vec::new();
+	fill_buf(reader, &mut buf, 36)?;
+
+	// Start of the content after an ID3v2 tag
+	let mut start = 0;
+
+	let mut id3v2_header = None;
+	if buf.len() >= 10 && &buf[..3] == b"ID3" {
+		let header = read_id3v2_header(&mut &buf[..10])?;
+
+		start = 10 + header.size as usize;
+		if header.flags.footer {
+			start += 10;
+		}
+
+		fill_buf(reader, &mut buf, start + 36)?;
+		id3v2_header = Some(header);
+	}
+
+	let file_type = match FileType::from_buffer(&buf[start.min(buf.len())..]) {
+		Some(f_ty) => f_ty,
+		None => err!(UnknownFormat),
+	};
+
+	let read_properties = false;
+
+	match file_type {
+		FileType::AAC | FileType::MPEG => {
+			let header = match id3v2_header {
+				Some(header) => header,
+				// Without an ID3v2 tag, the only tags are at the end of the stream
+				None => err!(RequiresSeek),
+			};
+
+			#[allow(unused_mut)]
+			let mut tags = Vec::new();
+
+			#[cfg(feature = "id3v2")]
+			{
+				use crate::id3::v2::read::parse_id3v2;
+
+				let id3v2 = parse_id3v2(&mut &buf[10..], header)?;
+				tags.push(id3v2.into());
+			}
+
+			#[cfg(not(feature = "id3v2"))]
+			let _ = header;
+
+			Ok(TaggedFile::new(
+				file_type,
+				crate::properties::FileProperties::default(),
+				tags,
+			))
+		},
+		FileType::FLAC => {
+			// Skip the stream marker
+			let mut pos = start + 4;
+
+			loop {
+				if !fill_buf(reader, &mut buf, pos + 4)? {
+					break;
+				}
+
+				let is_last = buf[pos] & 0x80 != 0;
+				let block_size = u32::from_be_bytes([0, buf[pos + 1], buf[pos + 2], buf[pos + 3]]);
+
+				pos += 4 + block_size as usize;
+				fill_buf(reader, &mut buf, pos)?;
+
+				if is_last {
+					break;
+				}
+			}
+
+			// The ID3v2 tag is handled by the FLAC reader
+			Ok(FlacFile::read_from(&mut Cursor::new(buf), read_properties)?.into())
+		},
+		FileType::OggFlac | FileType::Opus | FileType::Speex | FileType::Vorbis => {
+			let mut pos = start;
+
+			// The identification header, and the first page of the comment header
+			for _ in 0..2 {
+				match read_ogg_page(reader, &mut buf, pos)? {
+					Some((_, end)) => pos = end,
+					None => break,
+				}
+			}
+
+			// Then, any pages the comment header continues onto (and the first that it doesn't)
+			while let Some((header_type, end)) = read_ogg_page(reader, &mut buf, pos)? {
+				pos = end;
+
+				if header_type & 0x01 == 0 {
+					break;
+				}
+			}
+
+			let reader = &mut Cursor::new(&buf[start.min(buf.len())..]);
+
+			Ok(match file_type {
+				FileType::OggFlac => OggFlacFile::read_from(reader, read_properties)?.into(),
+				FileType::Opus => OpusFile::read_from(reader, read_properties)?.into(),
+				FileType::Speex => SpeexFile::read_from(reader, read_properties)?.into(),
+				_ => VorbisFile::read_from(reader, read_properties)?.into(),
+			})
+		},
+		_ => err!(RequiresSeek),
+	}
+}
+
+// Reads from `reader` until `buf` is `len` bytes long, returning whether it was able to
+fn fill_buf<R>(reader: &mut R, buf: &mut Vec<u8>, len: usize) -> Result<bool>
+where
+	R: Read,
+{
+	if buf.len() < len {
+		let remaining = (len - buf.len()) as u64;
+		reader.take(remaining).read_to_end(buf)?;
+	}
+
+	Ok(buf.len() >= len)
+}
+
+// Buffers the OGG page starting at `pos`, returning its header type and end position
+fn read_ogg_page<R>(reader: &mut R, buf: &mut Vec<u8>, pos: usize) -> Result<Option<(u8, usize)>>
+where
+	R: Read,
+{
+	// The page header is 27 bytes, followed by the segment table
+	if !fill_buf(reader, buf, pos + 27)? {
+		return Ok(None);
+	}
+
+	let header_type = buf[pos + 5];
+	let segment_count = buf[pos + 26] as usize;
+
+	let segment_table_end = pos + 27 + segment_count;
+	if !fill_buf(reader, buf, segment_table_end)? {
+		return Ok(None);
+	}
+
+	let content_len = buf[pos + 27..segment_table_end]
+		.iter()
+		.map(|s| *s as usize)
+		.sum::<usize>();
+
+	let end = segment_table_end + content_len;
+	if !fill_buf(reader, buf, end)? {
+		return Ok(None);
+	}
+
+	Ok(Some((header_type, end)))
 }
 
 #[cfg(test)]
 mod tests {
-	use crate::{FileType, Probe};
+	use crate::error::ErrorKind;
+	use crate::{Accessor, AudioFile, FileType, ParseMode, Probe, ProbeOptions, TagType};
 
 	use std::fs::File;
 
+	#[test]
+	fn read_with_options() {
+		let path = "tests/files/assets/minimal/full_test.mp3";
+
+		let options = ProbeOptions::new()
+			.read_properties(false)
+			.tag_types(Some(vec![TagType::ID3v1, TagType::APE]));
+
+		let tagged_file = Probe::open(path)
+			.unwrap()
+			.options(options)
+			.read(true)
+			.unwrap();
+
+		assert!(tagged_file.properties().duration().is_zero());
+		assert_eq!(tagged_file.tags().len(), 2);
+		assert!(tagged_file.tag(TagType::ID3v1).is_some());
+		assert!(tagged_file.tag(TagType::APE).is_some());
+	}
+
+	#[test]
+	fn read_with_tag_preference() {
+		let path = "tests/files/assets/minimal/full_test.mp3";
+
+		let read = |tag_preference: Vec<TagType>| {
+			let options = ProbeOptions::new().tag_preference(Some(tag_preference));
+
+			Probe::open(path)
+				.unwrap()
+				.options(options)
+				.read(false)
+				.unwrap()
+		};
+
+		let tagged_file = read(vec![TagType::APE, TagType::ID3v2]);
+		assert_eq!(tagged_file.primary_tag_type(), TagType::APE);
+		assert_eq!(
+			tagged_file.primary_tag().unwrap().artist().as_deref(),
+			Some("Baz artist")
+		);
+
+		// Tag types that aren't present are skipped
+		let tagged_file = read(vec![TagType::VorbisComments, TagType::ID3v1]);
+		assert_eq!(tagged_file.primary_tag_type(), TagType::ID3v1);
+
+		// Falls back to the file type's primary tag
+		let tagged_file = read(vec![TagType::VorbisComments]);
+		assert_eq!(tagged_file.primary_tag_type(), TagType::ID3v2);
+	}
+
+	#[test]
+	fn read_with_options_stops_early() {
+		// An ID3v2.4 tag with a single TIT2 frame, followed by a stream too short to hold
+		// any tags at the end of the file
+		let mut file_bytes = b"ID3\x04\x00\x00\x00\x00\x00\x0E".to_vec();
+		file_bytes.extend(b"TIT2\x00\x00\x00\x04\x00\x00\x03Foo");
+		file_bytes.extend(b"junk");
+
+		let probe = || Probe::with_file_type(std::io::Cursor::new(&file_bytes), FileType::MPEG);
+
+		assert!(probe().read(false).is_err());
+
+		let options = ProbeOptions::new()
+			.read_properties(false)
+			.tag_types(Some(vec![TagType::ID3v2]));
+
+		let tagged_file = probe().options(options).read(false).unwrap();
+		let tag = tagged_file.tag(TagType::ID3v2).unwrap();
+		assert_eq!(tag.title().as_deref(), Some("Foo"));
+	}
+
+	#[test]
+	fn read_with_limits() {
+		use crate::{MimeType, Picture, PictureType, Tag, TagExt};
+
+		// An ID3v2 tag with 3 pictures, each with 4 bytes of data
+		let mut tag = Tag::new(TagType::ID3v2);
+		for pic_type in [
+			PictureType::CoverFront,
+			PictureType::CoverBack,
+			PictureType::Band,
+		] {
+			tag.push_picture(Picture::new_unchecked(
+				pic_type,
+				MimeType::Png,
+				None,
+				vec![1, 2, 3, 4],
+			));
+		}
+
+		let mut file_bytes = Vec::new();
+		tag.dump_to(&mut file_bytes).unwrap();
+		file_bytes.extend(b"junk");
+
+		let read = |options: ProbeOptions| {
+			let options = options
+				.read_properties(false)
+				.tag_types(Some(vec![TagType::ID3v2]));
+
+			Probe::with_file_type(std::io::Cursor::new(&file_bytes), FileType::MPEG)
+				.options(options)
+				.read(false)
+		};
+
+		let tagged_file = read(ProbeOptions::default()).unwrap();
+		assert_eq!(tagged_file.tag(TagType::ID3v2).unwrap().picture_count(), 3);
+
+		let err = read(ProbeOptions::new().max_pictures(Some(2)))
+			.err()
+			.unwrap();
+		assert!(matches!(
+			err.kind(),
+			ErrorKind::TooManyPictures { limit: 2 }
+		));
+
+		let err = read(ProbeOptions::new().max_picture_bytes(Some(3)))
+			.err()
+			.unwrap();
+		assert!(matches!(
+			err.kind(),
+			ErrorKind::PictureTooLarge { size: 4, limit: 3 }
+		));
+
+		let err = read(ProbeOptions::new().max_tag_bytes(Some(10)))
+			.err()
+			.unwrap();
+		assert!(matches!(
+			err.kind(),
+			ErrorKind::TagTooLarge { limit: 10, .. }
+		));
+
+		// The limits can be disabled
+		let tagged_file = read(
+			ProbeOptions::new()
+				.max_pictures(None)
+				.max_picture_bytes(None)
+				.max_tag_bytes(None),
+		)
+		.unwrap();
+		assert_eq!(tagged_file.tag(TagType::ID3v2).unwrap().picture_count(), 3);
+	}
+
+	#[test]
+	fn read_relaxed() {
+		// An ID3v2.4 tag with a frame that has an invalid ID between two valid frames
+		let mut file_bytes = b"ID3\x04\x00\x00\x00\x00\x00\x2A".to_vec();
+		file_bytes.extend(b"TIT2\x00\x00\x00\x04\x00\x00\x03Foo");
+		file_bytes.extend(b"T!T2\x00\x00\x00\x04\x00\x00\x03Bar");
+		file_bytes.extend(b"TALB\x00\x00\x00\x04\x00\x00\x03Baz");
+		file_bytes.extend(b"junk");
+
+		let probe = |parse_mode| {
+			let options = ProbeOptions::new()
+				.read_properties(false)
+				.tag_types(Some(vec![TagType::ID3v2]))
+				.parse_mode(parse_mode);
+
+			Probe::with_file_type(std::io::Cursor::new(&file_bytes), FileType::MPEG)
+				.options(options)
+				.read(false)
+		};
+
+		assert!(probe(ParseMode::Strict).is_err());
+
+		let tagged_file = probe(ParseMode::Relaxed).unwrap();
+		let tag = tagged_file.tag(TagType::ID3v2).unwrap();
+		assert_eq!(tag.title().as_deref(), Some("Foo"));
+		assert_eq!(tag.album().as_deref(), Some("Baz"));
+
+		let errors = tagged_file.parse_errors();
+		assert_eq!(errors.len(), 1);
+
+		let location = errors[0].location().unwrap();
+		assert_eq!(location.id(), "T!T2");
+		assert_eq!(location.offset(), 24);
+	}
+
+	#[test]
+	#[cfg(feature = "vorbis_comments")]
+	fn read_pictures_placeholder() {
+		use crate::{MimeType, Picture, PictureInformation, PictureType, TagExt};
+
+		let picture = Picture::new_unchecked(
+			PictureType::CoverFront,
+			MimeType::Png,
+			Some(String::from("Foo")),
+			b"\x89PNG\r\n\x1a\nfoo".to_vec(),
+		);
+
+		let content = picture.as_flac_bytes(PictureInformation::default(), false);
+
+		// Insert a `PICTURE` block right after the `STREAMINFO` block
+		let mut file_bytes = std::fs::read("tests/files/assets/minimal/full_test.flac").unwrap();
+		let mut block = vec![6];
+		block.extend(&(content.len() as u32).to_be_bytes()[1..]);
+		block.extend(content);
+		file_bytes.splice(42..42, block);
+
+		let mut reader = std::io::Cursor::new(file_bytes);
+
+		let options = ProbeOptions::new().read_pictures(false);
+
+		let mut tagged_file = Probe::with_file_type(&mut reader, FileType::FLAC)
+			.options(options)
+			.read(false)
+			.unwrap();
+
+		let tag = tagged_file.primary_tag_mut().unwrap();
+
+		let placeholder = &tag.pictures()[0];
+		assert!(!placeholder.is_loaded());
+		assert!(placeholder.data().is_empty());
+		assert_eq!(placeholder.description(), Some("Foo"));
+
+		// Placeholders can't be written
+		assert!(tag.dump_to(&mut Vec::new()).is_err());
+
+		assert_eq!(tag.load_picture(0, &mut reader).unwrap(), &picture);
+		assert!(tag.dump_to(&mut Vec::new()).is_ok());
+	}
+
+	#[test]
+	fn read_from_reader() {
+		let paths = [
+			("tests/files/assets/minimal/full_test.mp3", FileType::MPEG),
+			("tests/files/assets/minimal/full_test.flac", FileType::FLAC),
+			("tests/files/assets/flac_with_id3v2.flac", FileType::FLAC),
+			("tests/files/assets/minimal/full_test.opus", FileType::Opus),
+			("tests/files/assets/minimal/full_test.spx", FileType::Speex),
+			("tests/files/assets/minimal/full_test.ogg", FileType::Vorbis),
+			(
+				"tests/files/assets/minimal/full_test.oga",
+				FileType::OggFlac,
+			),
+		];
+
+		for (path, file_type) in paths {
+			let content = std::fs::read(path).unwrap();
+
+			// `&[u8]` is only `Read`
+			let tagged_file = super::read_from_reader(&mut &*content).unwrap();
+			assert_eq!(tagged_file.file_type(), file_type);
+
+			let expected = super::read_from_path(path, false).unwrap();
+
+			let mut expected_tags = expected.tags().to_vec();
+			if file_type == FileType::MPEG {
+				// The ID3v1 and APE tags are at the end of the stream
+				expected_tags.retain(|t| t.tag_type() == crate::TagType::ID3v2);
+			}
+
+			assert_eq!(tagged_file.tags().len(), expected_tags.len());
+			for (tag, expected_tag) in tagged_file.tags().iter().zip(expected_tags) {
+				assert_eq!(tag.tag_type(), expected_tag.tag_type());
+				assert_eq!(tag.items(), expected_tag.items());
+				assert_eq!(tag.pictures(), expected_tag.pictures());
+			}
+		}
+	}
+
+	#[test]
+	fn read_from_reader_requires_seek() {
+		let content = std::fs::read("tests/files/assets/minimal/full_test.wv").unwrap();
+
+		match super::read_from_reader(&mut &*content) {
+			Err(e) => assert!(matches!(e.kind(), ErrorKind::RequiresSeek)),
+			Ok(_) => panic!("Expected a `RequiresSeek` error"),
+		}
+	}
+
 	#[test]
 	fn mp3_id3v2_trailing_junk() {
 		// test data that contains 4 bytes of junk (0x20) between the ID3 portion and the first MP3 frame
@@ -494,16 +1462,32 @@ mod tests {
 		assert_eq!(probe.file_type(), Some(expected_file_type_guess));
 	}
 
+	#[test]
+	fn probe_aac() {
+		test_probe("tests/files/assets/minimal/full_test.aac", FileType::AAC);
+	}
+
 	#[test]
 	fn probe_aiff() {
 		test_probe("tests/files/assets/minimal/full_test.aiff", FileType::AIFF);
 	}
 
+	#[test]
+	fn probe_mpc() {
+		test_probe("tests/files/assets/minimal/mpc_sv7.mpc", FileType::Musepack);
+		test_probe("tests/files/assets/minimal/mpc_sv8.mpc", FileType::Musepack);
+	}
+
 	#[test]
 	fn probe_ape_with_id3v2() {
 		test_probe("tests/files/assets/minimal/full_test.ape", FileType::APE);
 	}
 
+	#[test]
+	fn probe_dsf() {
+		test_probe("tests/files/assets/minimal/full_test.dsf", FileType::DSF);
+	}
+
 	#[test]
 	fn probe_flac() {
 		test_probe("tests/files/assets/minimal/full_test.flac", FileType::FLAC);
@@ -529,11 +1513,31 @@ mod tests {
 		test_probe("tests/files/assets/minimal/full_test.opus", FileType::Opus);
 	}
 
+	#[test]
+	fn probe_ogg_flac() {
+		// "oga" is used for any Ogg audio, so it can only be detected from the content
+		test_probe_file(
+			"tests/files/assets/minimal/full_test.oga",
+			FileType::OggFlac,
+		);
+		assert_eq!(
+			Probe::open("tests/files/assets/minimal/full_test.oga")
+				.unwrap()
+				.file_type(),
+			None
+		);
+	}
+
 	#[test]
 	fn probe_speex() {
 		test_probe("tests/files/assets/minimal/full_test.spx", FileType::Speex);
 	}
 
+	#[test]
+	fn probe_mka() {
+		test_probe("tests/files/assets/minimal/full_test.mka", FileType::Mka);
+	}
+
 	#[test]
 	fn probe_mp4() {
 		test_probe(
@@ -542,6 +1546,11 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn probe_tta() {
+		test_probe("tests/files/assets/minimal/full_test.tta", FileType::TTA);
+	}
+
 	#[test]
 	fn probe_wav() {
 		test_probe(
@@ -549,4 +1558,66 @@ mod tests {
 			FileType::WAV,
 		);
 	}
+
+	#[test]
+	fn read_from_path_misnamed() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let path = dir.path().join("actually_flac.mp3");
+		std::fs::copy("tests/files/assets/minimal/full_test.flac", &path).unwrap();
+
+		assert_eq!(
+			Probe::open(&path).unwrap().file_type(),
+			Some(FileType::MPEG)
+		);
+
+		let tagged_file = super::read_from_path(&path, true).unwrap();
+		assert_eq!(tagged_file.file_type(), FileType::FLAC);
+	}
+
+	#[test]
+	#[cfg(feature = "rayon")]
+	fn read_dir_parallel() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let nested = dir.path().join("nested");
+		std::fs::create_dir(&nested).unwrap();
+
+		std::fs::copy(
+			"tests/files/assets/minimal/full_test.mp3",
+			dir.path().join("a.mp3"),
+		)
+		.unwrap();
+		std::fs::copy(
+			"tests/files/assets/minimal/full_test.flac",
+			nested.join("b.flac"),
+		)
+		.unwrap();
+
+		// Not an audio file, so it is skipped
+		std::fs::write(dir.path().join("cover.txt"), b"Not audio").unwrap();
+		// Unreadable, but it shouldn't stop the others from being read
+		std::fs::write(dir.path().join("corrupt.ogg"), b"Not an Ogg stream").unwrap();
+
+		let results = super::read_dir_parallel(dir.path(), false).collect::<Vec<_>>();
+		assert_eq!(results.len(), 3);
+
+		let result = |name: &str| {
+			&results
+				.iter()
+				.find(|(path, _)| path.file_name().unwrap() == name)
+				.unwrap()
+				.1
+		};
+
+		assert_eq!(
+			result("a.mp3").as_ref().unwrap().file_type(),
+			FileType::MPEG
+		);
+		assert_eq!(
+			result("b.flac").as_ref().unwrap().file_type(),
+			FileType::FLAC
+		);
+		assert!(result("corrupt.ogg").is_err());
+	}
 }
@@ -59,14 +59,21 @@ impl FileProperties {
 
 #[cfg(test)]
 mod tests {
+	use crate::aac::{AacFile, AacProfile, AacProperties};
 	use crate::ape::{ApeFile, ApeProperties};
-	use crate::flac::FlacFile;
-	use crate::iff::{AiffFile, WavFile, WavFormat, WavProperties};
+	use crate::dsf::{DsfFile, DsfProperties};
+	use crate::flac::{FlacFile, FlacProperties};
+	use crate::iff::{AiffFile, AiffProperties, WavFile, WavFormat, WavProperties};
+	use crate::mka::{MkaFile, MkaProperties};
 	use crate::mp4::{AudioObjectType, Mp4Codec, Mp4File, Mp4Properties};
+	use crate::mpeg::header::LameHeader;
 	use crate::mpeg::{ChannelMode, Emphasis, Layer, MPEGFile, MPEGProperties, MpegVersion};
+	use crate::musepack::{MpcFile, MpcProperties};
 	use crate::ogg::{
-		OpusFile, OpusProperties, SpeexFile, SpeexProperties, VorbisFile, VorbisProperties,
+		OggFlacFile, OpusFile, OpusProperties, SpeexFile, SpeexMode, SpeexProperties, VorbisFile,
+		VorbisProperties,
 	};
+	use crate::tta::{TtaFile, TtaProperties};
 	use crate::wavpack::{WavPackFile, WavPackProperties};
 	use crate::{AudioFile, FileProperties};
 
@@ -77,13 +84,27 @@ mod tests {
 	// There is a chance they will be +/- 1, anything greater (for real world files)
 	// is an issue.
 
-	const AIFF_PROPERTIES: FileProperties = FileProperties {
+	const AAC_PROPERTIES: AacProperties = AacProperties {
+		profile: AacProfile::LowComplexity,
+		duration: Duration::from_millis(1021),
+		overall_bitrate: 7,
+		audio_bitrate: 5,
+		sample_rate: 44100,
+		channels: 2,
+		channel_configuration: 2,
+	};
+
+	const AIFF_PROPERTIES: AiffProperties = AiffProperties {
 		duration: Duration::from_millis(1428),
-		overall_bitrate: Some(1542),
-		audio_bitrate: Some(1536),
-		sample_rate: Some(48000),
-		bit_depth: Some(16),
-		channels: Some(2),
+		overall_bitrate: 1542,
+		audio_bitrate: 1536,
+		sample_rate: 48000,
+		bit_depth: 16,
+		channels: 2,
+		sample_frames: 68546,
+		aiff_c: false,
+		compression_type: None,
+		compression_name: None,
 	};
 
 	const APE_PROPERTIES: ApeProperties = ApeProperties {
@@ -94,15 +115,45 @@ mod tests {
 		sample_rate: 48000,
 		bit_depth: 16,
 		channels: 2,
+		compression_level: 2000,
+		total_frames: 1,
+	};
+
+	const DSF_PROPERTIES: DsfProperties = DsfProperties {
+		duration: Duration::from_millis(11),
+		overall_bitrate: 6048,
+		audio_bitrate: 5644,
+		sample_rate: 2_822_400,
+		channels: 2,
+		bits_per_sample: 1,
 	};
 
-	const FLAC_PROPERTIES: FileProperties = FileProperties {
+	const FLAC_PROPERTIES: FlacProperties = FlacProperties {
 		duration: Duration::from_millis(1428),
-		overall_bitrate: Some(321),
-		audio_bitrate: Some(275),
-		sample_rate: Some(48000),
+		overall_bitrate: 321,
+		audio_bitrate: 275,
+		sample_rate: 48000,
+		bit_depth: 16,
+		channels: 2,
+		min_block_size: 4608,
+		max_block_size: 4608,
+		min_frame_size: 783,
+		max_frame_size: 4744,
+		md5_signature: [
+			0x7B, 0xC2, 0xB7, 0x37, 0xE1, 0xCD, 0x61, 0x1F, 0xD8, 0x91, 0xC7, 0x18, 0xDF, 0x68,
+			0x4B, 0xD3,
+		],
+		total_samples: 68546,
+		duration_estimated: false,
+	};
+
+	const MKA_PROPERTIES: MkaProperties = MkaProperties {
+		duration: Duration::from_millis(1000),
+		overall_bitrate: 10,
+		audio_bitrate: 8,
+		sample_rate: 48000,
 		bit_depth: Some(16),
-		channels: Some(2),
+		channels: 2,
 	};
 
 	const MP1_PROPERTIES: MPEGProperties = MPEGProperties {
@@ -118,6 +169,8 @@ mod tests {
 		sample_rate: 32000,
 		channels: 2,
 		emphasis: Emphasis::None,
+		vbr: false,
+		lame: None,
 	};
 
 	const MP2_PROPERTIES: MPEGProperties = MPEGProperties {
@@ -133,6 +186,8 @@ mod tests {
 		sample_rate: 48000,
 		channels: 2,
 		emphasis: Emphasis::None,
+		vbr: false,
+		lame: None,
 	};
 
 	const MP3_PROPERTIES: MPEGProperties = MPEGProperties {
@@ -148,6 +203,15 @@ mod tests {
 		sample_rate: 48000,
 		channels: 2,
 		emphasis: Emphasis::None,
+		vbr: true,
+		lame: Some(LameHeader {
+			version: *b"Lavc58.91",
+			peak: 0,
+			radio_gain: 0,
+			audiophile_gain: 0,
+			delay: 576,
+			padding: 1150,
+		}),
 	};
 
 	const MP4_AAC_PROPERTIES: Mp4Properties = Mp4Properties {
@@ -194,6 +258,43 @@ mod tests {
 		channels: 2,
 	};
 
+	const MPC_SV7_PROPERTIES: MpcProperties = MpcProperties {
+		version: 7,
+		duration: Duration::from_millis(1552),
+		overall_bitrate: 17,
+		audio_bitrate: 16,
+		sample_rate: 44100,
+		channels: 2,
+	};
+
+	const MPC_SV8_PROPERTIES: MpcProperties = MpcProperties {
+		version: 8,
+		duration: Duration::from_millis(1428),
+		overall_bitrate: 24,
+		audio_bitrate: 23,
+		sample_rate: 48000,
+		channels: 2,
+	};
+
+	const OGG_FLAC_PROPERTIES: FlacProperties = FlacProperties {
+		duration: Duration::from_millis(1428),
+		overall_bitrate: 277,
+		audio_bitrate: 276,
+		sample_rate: 48000,
+		bit_depth: 16,
+		channels: 2,
+		min_block_size: 4608,
+		max_block_size: 4608,
+		min_frame_size: 783,
+		max_frame_size: 4744,
+		md5_signature: [
+			0x7B, 0xC2, 0xB7, 0x37, 0xE1, 0xCD, 0x61, 0x1F, 0xD8, 0x91, 0xC7, 0x18, 0xDF, 0x68,
+			0x4B, 0xD3,
+		],
+		total_samples: 68546,
+		duration_estimated: false,
+	};
+
 	const OPUS_PROPERTIES: OpusProperties = OpusProperties {
 		duration: Duration::from_millis(1428),
 		overall_bitrate: 120,
@@ -201,15 +302,17 @@ mod tests {
 		channels: 2,
 		version: 1,
 		input_sample_rate: 48000,
+		pre_skip: 312,
 	};
 
 	const SPEEX_PROPERTIES: SpeexProperties = SpeexProperties {
 		duration: Duration::from_millis(1469),
 		version: 1,
 		sample_rate: 32000,
-		mode: 2,
+		mode: SpeexMode::UltraWideBand,
 		channels: 2,
 		vbr: false,
+		frames_per_packet: 1,
 		overall_bitrate: 32,
 		audio_bitrate: 29,
 		nominal_bitrate: 29600,
@@ -227,6 +330,16 @@ mod tests {
 		bitrate_minimum: 0,
 	};
 
+	const TTA_PROPERTIES: TtaProperties = TtaProperties {
+		duration: Duration::from_millis(1000),
+		overall_bitrate: 3,
+		audio_bitrate: 1,
+		sample_rate: 44100,
+		bit_depth: 16,
+		channels: 2,
+		total_samples: 44100,
+	};
+
 	const WAV_PROPERTIES: WavProperties = WavProperties {
 		format: WavFormat::PCM,
 		duration: Duration::from_millis(1428),
@@ -234,14 +347,16 @@ mod tests {
 		audio_bitrate: 1536,
 		sample_rate: 48000,
 		bit_depth: 16,
+		valid_bits_per_sample: None,
 		channels: 2,
+		channel_mask: None,
 	};
 
 	const WAVPACK_PROPERTIES: WavPackProperties = WavPackProperties {
 		version: 1040,
-		duration: Duration::from_millis(1428),
-		overall_bitrate: 599,
-		audio_bitrate: 598,
+		duration: Some(Duration::from_millis(1428)),
+		overall_bitrate: Some(599),
+		audio_bitrate: Some(598),
 		sample_rate: 48000,
 		channels: 2,
 		bit_depth: 16,
@@ -260,6 +375,14 @@ mod tests {
 		audio_file.properties().clone()
 	}
 
+	#[test]
+	fn aac_properties() {
+		assert_eq!(
+			get_properties::<AacFile>("tests/files/assets/minimal/full_test.aac"),
+			AAC_PROPERTIES
+		);
+	}
+
 	#[test]
 	fn aiff_properties() {
 		assert_eq!(
@@ -268,6 +391,40 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn aiff_c_properties() {
+		let content = std::fs::read("tests/files/assets/minimal/full_test.aiff").unwrap();
+
+		// Convert the file to AIFF-C, adding a compression type and name to the COMM chunk
+		let mut comm = content[20..38].to_vec();
+		comm.extend(b"NONE");
+		comm.push(14);
+		comm.extend(b"Not compressed");
+		comm.push(0);
+
+		let mut aiff_c = b"FORM\0\0\0\0AIFCCOMM".to_vec();
+		aiff_c.extend((comm.len() as u32).to_be_bytes());
+		aiff_c.extend(comm);
+		aiff_c.extend(&content[38..]);
+
+		let form_size = (aiff_c.len() - 8) as u32;
+		aiff_c[4..8].copy_from_slice(&form_size.to_be_bytes());
+
+		let file = AiffFile::read_from(&mut std::io::Cursor::new(aiff_c), true).unwrap();
+		let properties = file.properties();
+
+		assert!(properties.is_aiff_c());
+		assert_eq!(properties.compression_type(), Some(*b"NONE"));
+		assert_eq!(properties.compression_name(), Some("Not compressed"));
+		assert_eq!(properties.sample_frames(), AIFF_PROPERTIES.sample_frames);
+		assert_eq!(properties.duration(), AIFF_PROPERTIES.duration);
+		assert_eq!(properties.sample_rate(), AIFF_PROPERTIES.sample_rate);
+
+		assert!(!AIFF_PROPERTIES.is_aiff_c());
+		assert_eq!(AIFF_PROPERTIES.compression_type(), None);
+		assert_eq!(AIFF_PROPERTIES.compression_name(), None);
+	}
+
 	#[test]
 	fn ape_properties() {
 		assert_eq!(
@@ -276,11 +433,30 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn dsf_properties() {
+		assert_eq!(
+			get_properties::<DsfFile>("tests/files/assets/minimal/full_test.dsf"),
+			DSF_PROPERTIES
+		);
+	}
+
 	#[test]
 	fn flac_properties() {
 		assert_eq!(
 			get_properties::<FlacFile>("tests/files/assets/minimal/full_test.flac"),
 			FLAC_PROPERTIES
+		);
+
+		// An all zero signature means it wasn't calculated
+		assert!(FlacProperties::default().md5_signature().is_none());
+	}
+
+	#[test]
+	fn mka_properties() {
+		assert_eq!(
+			get_properties::<MkaFile>("tests/files/assets/minimal/full_test.mka"),
+			MKA_PROPERTIES
 		)
 	}
 
@@ -308,6 +484,33 @@ mod tests {
 		)
 	}
 
+	#[test]
+	fn mp3_lame_header() {
+		let mut properties = MP3_PROPERTIES;
+
+		assert_eq!(properties.encoder_delay(), Some(576));
+		assert_eq!(properties.encoder_padding(), Some(1150));
+		assert_eq!(properties.lame_version(), Some("Lavc58.91"));
+		assert_eq!(properties.lame_replay_gain(), None);
+
+		// Radio gain of -6.5 dB, and a peak of 0.5
+		properties.lame = Some(LameHeader {
+			peak: 1 << 22,
+			radio_gain: 0b001_000_1_001000001,
+			..LameHeader::default()
+		});
+
+		let replay_gain = properties.lame_replay_gain().unwrap();
+		assert_eq!(replay_gain.track_gain, Some(-6.5));
+		assert_eq!(replay_gain.track_peak, Some(0.5));
+		assert_eq!(replay_gain.album_gain, None);
+		assert_eq!(properties.lame_version(), Some(""));
+
+		properties.lame = None;
+		assert_eq!(properties.encoder_delay(), None);
+		assert_eq!(properties.lame_version(), None);
+	}
+
 	#[test]
 	fn mp4_aac_properties() {
 		assert_eq!(
@@ -340,6 +543,30 @@ mod tests {
 		)
 	}
 
+	#[test]
+	fn mpc_sv7_properties() {
+		assert_eq!(
+			get_properties::<MpcFile>("tests/files/assets/minimal/mpc_sv7.mpc"),
+			MPC_SV7_PROPERTIES
+		)
+	}
+
+	#[test]
+	fn mpc_sv8_properties() {
+		assert_eq!(
+			get_properties::<MpcFile>("tests/files/assets/minimal/mpc_sv8.mpc"),
+			MPC_SV8_PROPERTIES
+		)
+	}
+
+	#[test]
+	fn ogg_flac_properties() {
+		assert_eq!(
+			get_properties::<OggFlacFile>("tests/files/assets/minimal/full_test.oga"),
+			OGG_FLAC_PROPERTIES
+		)
+	}
+
 	#[test]
 	fn opus_properties() {
 		assert_eq!(
@@ -364,6 +591,14 @@ mod tests {
 		)
 	}
 
+	#[test]
+	fn tta_properties() {
+		assert_eq!(
+			get_properties::<TtaFile>("tests/files/assets/minimal/full_test.tta"),
+			TTA_PROPERTIES
+		);
+	}
+
 	#[test]
 	fn wav_properties() {
 		assert_eq!(
@@ -372,6 +607,31 @@ mod tests {
 		)
 	}
 
+	#[test]
+	fn rf64_properties() {
+		let content = std::fs::read("tests/files/assets/minimal/wav_format_pcm.wav").unwrap();
+		let data_size = u64::from(u32::from_le_bytes(content[40..44].try_into().unwrap()));
+
+		// Convert the file to RF64, moving the RIFF and data sizes to a ds64 chunk
+		let mut rf64 = b"RF64\xFF\xFF\xFF\xFFWAVEds64\x1C\0\0\0".to_vec();
+		rf64.extend((content.len() as u64 + 28).to_le_bytes());
+		rf64.extend(data_size.to_le_bytes());
+		rf64.extend((data_size / 4).to_le_bytes());
+		rf64.extend(0_u32.to_le_bytes());
+		rf64.extend(&content[12..40]);
+		rf64.extend(b"\xFF\xFF\xFF\xFF");
+		rf64.extend(&content[44..]);
+
+		let file = WavFile::read_from(&mut std::io::Cursor::new(rf64), true).unwrap();
+		let properties = file.properties();
+
+		assert_eq!(properties.duration(), WAV_PROPERTIES.duration);
+		assert_eq!(properties.bitrate(), WAV_PROPERTIES.audio_bitrate);
+		assert_eq!(properties.sample_rate(), WAV_PROPERTIES.sample_rate);
+		assert_eq!(properties.bit_depth(), WAV_PROPERTIES.bit_depth);
+		assert_eq!(properties.channels(), WAV_PROPERTIES.channels);
+	}
+
 	#[test]
 	fn wavpack_properties() {
 		assert_eq!(
@@ -379,4 +639,21 @@ mod tests {
 			WAVPACK_PROPERTIES
 		)
 	}
+
+	#[test]
+	fn wavpack_unknown_length() {
+		let mut content = std::fs::read("tests/files/assets/minimal/full_test.wv").unwrap();
+
+		// Replace the total sample count of the first block with the "unknown length" sentinel
+		content[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+
+		let file = WavPackFile::read_from(&mut std::io::Cursor::new(content), true).unwrap();
+		let properties = file.properties();
+
+		assert_eq!(properties.duration(), None);
+		assert_eq!(properties.overall_bitrate(), None);
+		assert_eq!(properties.audio_bitrate(), None);
+		assert_eq!(properties.sample_rate(), WAVPACK_PROPERTIES.sample_rate);
+		assert!(!properties.is_hybrid());
+	}
 }
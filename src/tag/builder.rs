@@ -0,0 +1,170 @@
+use super::item::{ItemKey, ItemValue, TagItem};
+use super::{Tag, TagType};
+use crate::error::{ErrorKind, LoftyError, Result};
+use crate::picture::Picture;
+
+/// A builder for [`Tag`]
+///
+/// Every item is checked against the [`TagType`] as it is added. If an item (or picture)
+/// can't be represented in the tag, [`TagBuilder::build`] will return an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::{Accessor, ItemKey, TagBuilder, TagType};
+///
+/// # fn main() -> lofty::Result<()> {
+/// let tag = TagBuilder::new(TagType::ID3v2)
+/// 	.with_title("Foo title")
+/// 	.with_artist("Bar artist")
+/// 	.with_track(1)
+/// 	.with_text(ItemKey::Composer, "Baz composer")
+/// 	.build()?;
+///
+/// assert_eq!(tag.title(), Some("Foo title"));
+/// assert_eq!(tag.track(), Some(1));
+///
+/// // Binary items can't be stored in Vorbis Comments
+/// let result = TagBuilder::new(TagType::VorbisComments)
+/// 	.with_title("Foo title")
+/// 	.with_item(lofty::TagItem::new(
+/// 		ItemKey::EncoderSettings,
+/// 		lofty::ItemValue::Binary(vec![1, 2, 3]),
+/// 	))
+/// 	.build();
+///
+/// assert!(result.is_err());
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct TagBuilder {
+	tag: Tag,
+	error: Option<LoftyError>,
+}
+
+macro_rules! impl_text_setters {
+	($($item_key:ident => $name:ident),+) => {
+		$(
+			#[doc = concat!("Sets the [`ItemKey::", stringify!($item_key), "`] item")]
+			#[must_use]
+			pub fn $name(self, value: impl Into<String>) -> Self {
+				self.with_text(ItemKey::$item_key, value)
+			}
+		)+
+	}
+}
+
+macro_rules! impl_number_setters {
+	($($item_key:ident => $name:ident),+) => {
+		$(
+			#[doc = concat!("Sets the [`ItemKey::", stringify!($item_key), "`] item")]
+			#[must_use]
+			pub fn $name(self, value: u32) -> Self {
+				self.with_text(ItemKey::$item_key, value.to_string())
+			}
+		)+
+	}
+}
+
+impl TagBuilder {
+	/// Create a new builder for a [`Tag`] of `tag_type`
+	pub fn new(tag_type: TagType) -> Self {
+		Self {
+			tag: Tag::new(tag_type),
+			error: None,
+		}
+	}
+
+	impl_text_setters!(
+		TrackTitle  => with_title,
+		TrackArtist => with_artist,
+		AlbumTitle  => with_album,
+		Genre       => with_genre,
+		Comment     => with_comment
+	);
+
+	impl_number_setters!(
+		TrackNumber => with_track,
+		TrackTotal  => with_track_total,
+		DiscNumber  => with_disk,
+		DiscTotal   => with_disk_total
+	);
+
+	/// Sets the year
+	///
+	/// This uses [`ItemKey::Year`], falling back to [`ItemKey::RecordingDate`] if the
+	/// [`TagType`] only supports the latter.
+	#[must_use]
+	pub fn with_year(self, value: u32) -> Self {
+		let year = TagItem::new(ItemKey::Year, ItemValue::Text(value.to_string()));
+		if year.re_map(self.tag.tag_type) {
+			return self.with_item(year);
+		}
+
+		self.with_text(ItemKey::RecordingDate, value.to_string())
+	}
+
+	/// Insert a text item, replacing any existing item with the same [`ItemKey`]
+	///
+	/// See [`Tag::insert_text`]
+	#[must_use]
+	pub fn with_text(self, item_key: ItemKey, value: impl Into<String>) -> Self {
+		self.with_item(TagItem::new(item_key, ItemValue::Text(value.into())))
+	}
+
+	/// Insert a [`TagItem`], replacing any existing item with the same [`ItemKey`]
+	///
+	/// See [`Tag::insert_item`]
+	#[must_use]
+	pub fn with_item(mut self, item: TagItem) -> Self {
+		if self.error.is_some() {
+			return self;
+		}
+
+		let tag_type = self.tag.tag_type;
+		let binary_lost =
+			!tag_type.supports_binary_items() && matches!(item.value(), ItemValue::Binary(_));
+
+		let key = item.key().clone();
+		if binary_lost || !self.tag.insert_item(item) {
+			self.error = Some(LoftyError::new(ErrorKind::UnsupportedItemKey(
+				tag_type, key,
+			)));
+		}
+
+		self
+	}
+
+	/// Append a [`Picture`]
+	///
+	/// See [`Tag::push_picture`]
+	#[must_use]
+	pub fn with_picture(mut self, picture: Picture) -> Self {
+		if self.error.is_some() {
+			return self;
+		}
+
+		if self.tag.tag_type.supports_pictures() {
+			self.tag.push_picture(picture);
+		} else {
+			self.error = Some(LoftyError::new(ErrorKind::UnsupportedPicture));
+		}
+
+		self
+	}
+
+	/// Consume the builder, returning the [`Tag`]
+	///
+	/// # Errors
+	///
+	/// * An item was added that can't be represented in the [`TagType`]
+	///   ([`ErrorKind::UnsupportedItemKey`])
+	/// * A picture was added to a [`TagType`] that doesn't support pictures
+	///   ([`ErrorKind::UnsupportedPicture`])
+	pub fn build(self) -> Result<Tag> {
+		match self.error {
+			Some(error) => Err(error),
+			None => Ok(self.tag),
+		}
+	}
+}
@@ -0,0 +1,83 @@
+/// Gapless playback information
+///
+/// This is stored by iTunes (and many other AAC encoders) in an `iTunSMPB` comment, which is a
+/// `COMM` frame in ID3v2, and a `----:com.apple.iTunes:iTunSMPB` atom in MP4.
+///
+/// See [`ID3v2Tag::gapless_info`](crate::id3::v2::ID3v2Tag::gapless_info) and
+/// [`Ilst::gapless_info`](crate::mp4::Ilst::gapless_info).
+///
+/// NOTE: The `iTunSMPB` comment is preserved as-is when editing a tag, this is only for reading it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GaplessInfo {
+	/// The number of samples added to the start of the stream by the encoder
+	pub encoder_delay: u32,
+	/// The number of samples added to the end of the stream by the encoder
+	pub padding: u32,
+	/// The number of samples (per channel) in the original audio, without the delay and padding
+	pub original_sample_count: u64,
+}
+
+impl GaplessInfo {
+	/// Parse the value of an `iTunSMPB` comment
+	///
+	/// The value is made up of hex encoded fields separated by spaces, of which only the
+	/// first four are used. The first field is always zero.
+	///
+	/// This will return `None` if any of the four fields is missing or invalid.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::GaplessInfo;
+	///
+	/// let itunsmpb = " 00000000 00000840 000001C0 0000000000A3C800 00000000 00000000";
+	///
+	/// let gapless_info = GaplessInfo::from_itunsmpb(itunsmpb).unwrap();
+	/// assert_eq!(gapless_info.encoder_delay, 2112);
+	/// assert_eq!(gapless_info.padding, 448);
+	/// assert_eq!(gapless_info.original_sample_count, 10_733_568);
+	/// ```
+	pub fn from_itunsmpb(value: &str) -> Option<Self> {
+		let mut fields = value
+			.trim_matches(|c: char| c == '\0' || c.is_whitespace())
+			.split_whitespace();
+
+		// Reserved
+		u32::from_str_radix(fields.next()?, 16).ok()?;
+
+		let encoder_delay = u32::from_str_radix(fields.next()?, 16).ok()?;
+		let padding = u32::from_str_radix(fields.next()?, 16).ok()?;
+		let original_sample_count = u64::from_str_radix(fields.next()?, 16).ok()?;
+
+		Some(Self {
+			encoder_delay,
+			padding,
+			original_sample_count,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::GaplessInfo;
+
+	#[test]
+	fn parse_itunsmpb() {
+		assert_eq!(
+			GaplessInfo::from_itunsmpb(" 00000000 00000210 0000099C 0000000000183D24\0"),
+			Some(GaplessInfo {
+				encoder_delay: 528,
+				padding: 2460,
+				original_sample_count: 1_588_516,
+			})
+		);
+
+		for invalid in [
+			"",
+			" 00000000 00000210 0000099C",
+			" 00000000 00000210 0000099C foo",
+		] {
+			assert_eq!(GaplessInfo::from_itunsmpb(invalid), None);
+		}
+	}
+}
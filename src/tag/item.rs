@@ -1,6 +1,9 @@
+use crate::tag::utils::parse_leading_num;
 use crate::tag::TagType;
 
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
 macro_rules! first_key {
 	($key:tt $(| $remaining:expr)*) => {
@@ -107,6 +110,14 @@ gen_map!(
 	"ISRC"						   => ISRC,
 	"Barcode"					   => Barcode,
 	"CatalogNumber"				   => CatalogNumber,
+	"MUSICBRAINZ_RELEASETRACKID"   => MusicBrainzTrackId,
+	"MUSICBRAINZ_ALBUMID"		   => MusicBrainzReleaseId,
+	"MUSICBRAINZ_RELEASEGROUPID"   => MusicBrainzReleaseGroupId,
+	"MUSICBRAINZ_ARTISTID"		   => MusicBrainzArtistId,
+	"MUSICBRAINZ_ALBUMARTISTID"	   => MusicBrainzReleaseArtistId,
+	"MUSICBRAINZ_WORKID"		   => MusicBrainzWorkId,
+	"ACOUSTID_ID"				   => AcoustId,
+	"ACOUSTID_FINGERPRINT"		   => AcoustIdFingerprint,
 	"Compilation"				   => FlagCompilation,
 	"Media"						   => OriginalMediaType,
 	"EncodedBy"					   => EncodedBy,
@@ -116,6 +127,9 @@ gen_map!(
 	"REPLAYGAIN_TRACK_PEAK"        => ReplayGainTrackPeak,
 	"Genre"						   => Genre,
 	"Mood"						   => Mood,
+	"BPM"						   => BPM,
+	"InitialKey"				   => InitialKey,
+	"Rating"					   => Popularimeter,
 	"Copyright"					   => CopyrightMessage,
 	"Comment"					   => Comment,
 	"language"					   => Language,
@@ -130,6 +144,7 @@ gen_map! (
 	"TALB"			        => AlbumTitle,
 	"TSST"			        => SetSubtitle,
 	"TIT1" | "GRP1"         => ContentGroup,
+	"Work"			        => Work,
 	"TIT2"			        => TrackTitle,
 	"TIT3"			        => TrackSubtitle,
 	"TOAL"			        => OriginalAlbumTitle,
@@ -162,8 +177,17 @@ gen_map! (
 	"TDRC"			        => RecordingDate,
 	"TDOR"			        => OriginalReleaseDate,
 	"TSRC"			        => ISRC,
-	"MVNM"			        => Movement,
-	"MVIN"			        => MovementIndex,
+	"MusicBrainz Release Track Id"	=> MusicBrainzTrackId,
+	"MusicBrainz Album Id"			=> MusicBrainzReleaseId,
+	"MusicBrainz Release Group Id"	=> MusicBrainzReleaseGroupId,
+	"MusicBrainz Artist Id"			=> MusicBrainzArtistId,
+	"MusicBrainz Album Artist Id"	=> MusicBrainzReleaseArtistId,
+	"MusicBrainz Work Id"			=> MusicBrainzWorkId,
+	"Acoustid Id"					=> AcoustId,
+	"Acoustid Fingerprint"			=> AcoustIdFingerprint,
+	"MVNM"			        => MovementName,
+	"MVIN"			        => MovementNumber,
+	"MVIN"			        => MovementCount,
 	"TCMP"			        => FlagCompilation,
 	"PCST"			        => FlagPodcast,
 	"TFLT"			        => FileType,
@@ -173,8 +197,9 @@ gen_map! (
 	"TOFN"			        => OriginalFileName,
 	"TMED"			        => OriginalMediaType,
 	"TENC"			        => EncodedBy,
-	"TSSE"			        => EncoderSoftware,
+	// The last duplicate key wins when reading, `TSSE` is more commonly the encoder's name
 	"TSSE"			        => EncoderSettings,
+	"TSSE"			        => EncoderSoftware,
 	"TDEN"			        => EncodingTime,
 	"REPLAYGAIN_ALBUM_GAIN" => ReplayGainAlbumGain,
 	"REPLAYGAIN_ALBUM_PEAK" => ReplayGainAlbumPeak,
@@ -204,6 +229,47 @@ gen_map! (
 	"USLT"			        => Lyrics
 );
 
+gen_map!(
+	#[cfg(feature = "matroska_tags")]
+	MATROSKA_MAP;
+
+	"TITLE"							  => TrackTitle,
+	"SUBTITLE"						  => TrackSubtitle,
+	"ARTIST"						  => TrackArtist,
+	"LEAD_PERFORMER"				  => Performer,
+	"ARRANGER"						  => Arranger,
+	"WRITTEN_BY"					  => Writer,
+	"COMPOSER"						  => Composer,
+	"CONDUCTOR"						  => Conductor,
+	"LYRICIST"						  => Lyricist,
+	"MIXED_BY"						  => MixEngineer,
+	"REMIXED_BY"					  => Remixer,
+	"PRODUCER"						  => Producer,
+	"ENGINEER"						  => Engineer,
+	"PUBLISHER"						  => Publisher,
+	"LABEL"							  => Label,
+	"PART_NUMBER"					  => TrackNumber,
+	"DATE_RECORDED" | "DATE_RELEASED" => RecordingDate,
+	"DATE_TAGGED"					  => TaggingTime,
+	"DATE_ENCODED"					  => EncodingTime,
+	"ISRC"							  => ISRC,
+	"BARCODE"						  => Barcode,
+	"CATALOG_NUMBER"				  => CatalogNumber,
+	"ORIGINAL_MEDIA_TYPE"			  => OriginalMediaType,
+	"ENCODED_BY"					  => EncodedBy,
+	"ENCODER"						  => EncoderSoftware,
+	"ENCODER_SETTINGS"				  => EncoderSettings,
+	"GENRE"							  => Genre,
+	"INITIAL_KEY"					  => InitialKey,
+	"MOOD"							  => Mood,
+	"BPM"							  => BPM,
+	"COPYRIGHT"						  => CopyrightMessage,
+	"LICENSE"						  => License,
+	"COMMENT"						  => Comment,
+	"DESCRIPTION"					  => Description,
+	"LYRICS"						  => Lyrics
+);
+
 gen_map! (
 	#[cfg(feature = "mp4_ilst")]
 	ILST_MAP;
@@ -212,6 +278,10 @@ gen_map! (
 	"----:com.apple.iTunes:DISCSUBTITLE"	=> SetSubtitle,
 	"tvsh"									=> ShowName,
 	"\u{a9}grp"								=> ContentGroup,
+	"\u{a9}wrk"								=> Work,
+	"\u{a9}mvn"								=> MovementName,
+	"\u{a9}mvi"								=> MovementNumber,
+	"\u{a9}mvc"								=> MovementCount,
 	"\u{a9}nam"								=> TrackTitle,
 	"----:com.apple.iTunes:SUBTITLE"		=> TrackSubtitle,
 	"soal"									=> AlbumTitleSortOrder,
@@ -241,14 +311,24 @@ gen_map! (
 	"----:com.apple.iTunes:ISRC"			=> ISRC,
 	"----:com.apple.iTunes:BARCODE"			=> Barcode,
 	"----:com.apple.iTunes:CATALOGNUMBER"	=> CatalogNumber,
+	"----:com.apple.iTunes:MusicBrainz Release Track Id"	=> MusicBrainzTrackId,
+	"----:com.apple.iTunes:MusicBrainz Album Id"			=> MusicBrainzReleaseId,
+	"----:com.apple.iTunes:MusicBrainz Release Group Id"	=> MusicBrainzReleaseGroupId,
+	"----:com.apple.iTunes:MusicBrainz Artist Id"			=> MusicBrainzArtistId,
+	"----:com.apple.iTunes:MusicBrainz Album Artist Id"		=> MusicBrainzReleaseArtistId,
+	"----:com.apple.iTunes:MusicBrainz Work Id"				=> MusicBrainzWorkId,
+	"----:com.apple.iTunes:Acoustid Id"						=> AcoustId,
+	"----:com.apple.iTunes:Acoustid Fingerprint"			=> AcoustIdFingerprint,
 	"cpil"									=> FlagCompilation,
 	"pcst"									=> FlagPodcast,
+	"pgap"									=> FlagGaplessPlayback,
 	"----:com.apple.iTunes:MEDIA"			=> OriginalMediaType,
 	"\u{a9}enc"								=> EncodedBy,
 	"\u{a9}too"								=> EncoderSoftware,
 	"\u{a9}gen"								=> Genre,
 	"----:com.apple.iTunes:MOOD"			=> Mood,
 	"tmpo"									=> BPM,
+	"----:com.apple.iTunes:initialkey"		=> InitialKey,
 	"cprt"									=> CopyrightMessage,
 	"----:com.apple.iTunes:LICENSE"			=> License,
 	"ldes"									=> PodcastDescription,
@@ -263,6 +343,18 @@ gen_map! (
 	"\u{a9}lyr"								=> Lyrics
 );
 
+gen_map!(
+	#[cfg(feature = "lyrics3v2")]
+	LYRICS3V2_MAP;
+
+	"EAL"			=> AlbumTitle,
+	"ETT"			=> TrackTitle,
+	"EAR"			=> TrackArtist,
+	"AUT"			=> Lyricist,
+	"INF"			=> Comment,
+	"LYR"			=> Lyrics
+);
+
 gen_map! (
 	#[cfg(feature = "riff_info_list")]
 	RIFF_INFO_MAP;
@@ -272,6 +364,7 @@ gen_map! (
 	"IART"			=> TrackArtist,
 	"IWRI"			=> Writer,
 	"IMUS"			=> Composer,
+	"IENG"			=> Engineer,
 	"IPRO"			=> Producer,
 	"IPRT" | "ITRK" => TrackNumber,
 	"IFRM"			=> TrackTotal,
@@ -294,6 +387,10 @@ gen_map!(
 	"ALBUM" 	      			   => AlbumTitle,
 	"DISCSUBTITLE"    			   => SetSubtitle,
 	"GROUPING"	   	  			   => ContentGroup,
+	"WORK"		   	  			   => Work,
+	"MOVEMENTNAME" 	  			   => MovementName,
+	"MOVEMENT" 	  	  			   => MovementNumber,
+	"MOVEMENTTOTAL"   			   => MovementCount,
 	"TITLE"		   	  			   => TrackTitle,
 	"SUBTITLE"	   	  			   => TrackSubtitle,
 	"ALBUMSORT"	   	  			   => AlbumTitleSortOrder,
@@ -325,9 +422,17 @@ gen_map!(
 	"ORIGINALDATE" 				   => OriginalReleaseDate,
 	"ISRC" 						   => ISRC,
 	"CATALOGNUMBER" 			   => CatalogNumber,
+	"MUSICBRAINZ_RELEASETRACKID"   => MusicBrainzTrackId,
+	"MUSICBRAINZ_ALBUMID"		   => MusicBrainzReleaseId,
+	"MUSICBRAINZ_RELEASEGROUPID"   => MusicBrainzReleaseGroupId,
+	"MUSICBRAINZ_ARTISTID"		   => MusicBrainzArtistId,
+	"MUSICBRAINZ_ALBUMARTISTID"	   => MusicBrainzReleaseArtistId,
+	"MUSICBRAINZ_WORKID"		   => MusicBrainzWorkId,
+	"ACOUSTID_ID"				   => AcoustId,
+	"ACOUSTID_FINGERPRINT"		   => AcoustIdFingerprint,
 	"COMPILATION" 				   => FlagCompilation,
 	"MEDIA" 					   => OriginalMediaType,
-	"ENCODED-BY" 				   => EncodedBy,
+	"ENCODED-BY" | "ENCODEDBY"	   => EncodedBy,
 	"ENCODER" 					   => EncoderSoftware,
 	"ENCODING" | "ENCODERSETTINGS" => EncoderSettings,
 	"REPLAYGAIN_ALBUM_GAIN"        => ReplayGainAlbumGain,
@@ -337,6 +442,7 @@ gen_map!(
 	"GENRE" 					   => Genre,
 	"MOOD" 						   => Mood,
 	"BPM" 						   => BPM,
+	"INITIALKEY" 				   => InitialKey,
 	"COPYRIGHT" 				   => CopyrightMessage,
 	"LICENSE" 					   => License,
 	"COMMENT" 					   => Comment,
@@ -358,6 +464,7 @@ macro_rules! gen_item_keys {
 		]
 	) => {
 		#[derive(PartialEq, Clone, Debug, Eq, Hash)]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 		#[allow(missing_docs)]
 		#[non_exhaustive]
 		/// A generic representation of a tag's key
@@ -401,7 +508,8 @@ macro_rules! gen_item_keys {
 				}
 
 				if let ItemKey::Unknown(ref unknown) = self {
-					if allow_unknown {
+					// "COMM" items hold raw ID3v2 comments, which are meaningless to other formats
+					if allow_unknown && (unknown != "COMM" || tag_type == TagType::ID3v2) {
 						return Some(unknown)
 					}
 				}
@@ -423,6 +531,12 @@ gen_item_keys!(
 		#[cfg(feature = "id3v2")]
 		[TagType::ID3v2, ID3V2_MAP],
 
+		#[cfg(feature = "lyrics3v2")]
+		[TagType::Lyrics3v2, LYRICS3V2_MAP],
+
+		#[cfg(feature = "matroska_tags")]
+		[TagType::Matroska, MATROSKA_MAP],
+
 		#[cfg(feature = "mp4_ilst")]
 		[TagType::MP4ilst, ILST_MAP],
 
@@ -439,6 +553,7 @@ gen_item_keys!(
 		SetSubtitle,
 		ShowName,
 		ContentGroup,
+		Work,
 		TrackTitle,
 		TrackSubtitle,
 
@@ -493,12 +608,22 @@ gen_item_keys!(
 		ISRC,
 		Barcode,
 		CatalogNumber,
-		Movement,
-		MovementIndex,
+		MovementName,
+		MovementNumber,
+		MovementCount,
+		MusicBrainzTrackId,
+		MusicBrainzReleaseId,
+		MusicBrainzReleaseGroupId,
+		MusicBrainzArtistId,
+		MusicBrainzReleaseArtistId,
+		MusicBrainzWorkId,
+		AcoustId,
+		AcoustIdFingerprint,
 
 		// Flags
 		FlagCompilation,
 		FlagPodcast,
+		FlagGaplessPlayback,
 
 		// File Information
 		FileType,
@@ -556,6 +681,7 @@ gen_item_keys!(
 );
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a tag item's value
 pub enum ItemValue {
 	/// Any UTF-8 encoded text
@@ -609,10 +735,63 @@ impl ItemValue {
 			_ => None,
 		}
 	}
+
+	/// Parses the value as a `u32` if the variant is `Text`
+	///
+	/// Number pairs, such as a track number stored as "current/total", will only have
+	/// the first number parsed.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ItemValue;
+	///
+	/// assert_eq!(ItemValue::Text(String::from("5/12")).as_u32(), Some(5));
+	/// assert_eq!(ItemValue::Text(String::from("2020")).as_u32(), Some(2020));
+	/// assert_eq!(ItemValue::Text(String::from("foo")).as_u32(), None);
+	/// ```
+	pub fn as_u32(&self) -> Option<u32> {
+		self.text().and_then(parse_leading_num)
+	}
+
+	/// Parses the value as an `i64` if the variant is `Text`
+	///
+	/// See [`ItemValue::as_u32`]
+	pub fn as_i64(&self) -> Option<i64> {
+		self.text().and_then(parse_leading_num)
+	}
+}
+
+impl TryFrom<ItemValue> for String {
+	type Error = ItemValue;
+
+	/// Converts an [`ItemValue::Text`] or [`ItemValue::Locator`] into a `String`
+	///
+	/// The original value is returned if the variant is `Binary`
+	fn try_from(value: ItemValue) -> std::result::Result<Self, Self::Error> {
+		match value {
+			ItemValue::Text(s) | ItemValue::Locator(s) => Ok(s),
+			other => Err(other),
+		}
+	}
+}
+
+impl TryFrom<ItemValue> for Vec<u8> {
+	type Error = ItemValue;
+
+	/// Converts an [`ItemValue::Binary`] into a `Vec<u8>`
+	///
+	/// The original value is returned if the variant is `Text` or `Locator`
+	fn try_from(value: ItemValue) -> std::result::Result<Self, Self::Error> {
+		match value {
+			ItemValue::Binary(b) => Ok(b),
+			other => Err(other),
+		}
+	}
 }
 
 pub(crate) enum ItemValueRef<'a> {
-	Text(&'a str),
+	Text(Cow<'a, str>),
 	Locator(&'a str),
 	Binary(&'a [u8]),
 }
@@ -620,18 +799,38 @@ pub(crate) enum ItemValueRef<'a> {
 impl<'a> Into<ItemValueRef<'a>> for &'a ItemValue {
 	fn into(self) -> ItemValueRef<'a> {
 		match self {
-			ItemValue::Text(text) => ItemValueRef::Text(text),
+			ItemValue::Text(text) => ItemValueRef::Text(Cow::Borrowed(text)),
 			ItemValue::Locator(locator) => ItemValueRef::Locator(locator),
 			ItemValue::Binary(binary) => ItemValueRef::Binary(binary),
 		}
 	}
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a tag item (key/value)
 pub struct TagItem {
 	pub(crate) item_key: ItemKey,
 	pub(crate) item_value: ItemValue,
+	// The key as it was read from the file, so its casing can be preserved when it's written back.
+	// This is only used by formats with case-insensitive keys, and is ignored when comparing items.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub(crate) original_key: Option<String>,
+}
+
+impl PartialEq for TagItem {
+	fn eq(&self, other: &Self) -> bool {
+		self.item_key == other.item_key && self.item_value == other.item_value
+	}
+}
+
+impl Eq for TagItem {}
+
+impl Hash for TagItem {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.item_key.hash(state);
+		self.item_value.hash(state);
+	}
 }
 
 impl TagItem {
@@ -650,6 +849,7 @@ impl TagItem {
 		item_key.map_key(tag_type, false).is_some().then(|| Self {
 			item_key,
 			item_value,
+			original_key: None,
 		})
 	}
 
@@ -658,6 +858,7 @@ impl TagItem {
 		Self {
 			item_key,
 			item_value,
+			original_key: None,
 		}
 	}
 
@@ -694,6 +895,13 @@ impl TagItem {
 			return VALID_ITEMKEYS.contains(&self.item_key);
 		}
 
+		#[cfg(feature = "matroska_tags")]
+		if tag_type == TagType::Matroska
+			&& crate::mka::tag::TARGETED_ITEMKEYS.contains(&self.item_key)
+		{
+			return true;
+		}
+
 		self.item_key.map_key(tag_type, false).is_some()
 	}
 }
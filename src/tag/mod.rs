@@ -1,8 +1,14 @@
+pub(crate) mod builder;
+pub(crate) mod gapless;
 pub(crate) mod item;
+pub(crate) mod replay_gain;
 pub(crate) mod utils;
 
 use crate::error::{LoftyError, Result};
 use crate::file::FileType;
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::Popularimeter;
+use crate::id3::{find_lyrics3v2, ID3FindResults};
 use crate::macros::err;
 use crate::picture::{Picture, PictureType};
 use crate::probe::Probe;
@@ -10,7 +16,7 @@ use crate::traits::{Accessor, TagExt};
 use item::{ItemKey, ItemValue, TagItem};
 
 use std::fs::{File, OpenOptions};
-use std::io::Write;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
 
 macro_rules! impl_accessor {
@@ -42,7 +48,8 @@ macro_rules! impl_accessor {
 	}
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a parsed tag
 ///
 /// This is a tag that is loosely bound to a specific [`TagType`].
@@ -120,15 +127,20 @@ impl Accessor for Tag {
 		TrackTitle  => title,
 		AlbumTitle  => album,
 		Genre       => genre,
-		Comment     => comment
+		Comment     => comment,
+		AlbumArtist => album_artist,
+		Composer    => composer,
+		InitialKey  => initial_key,
+		EncoderSoftware => encoder,
+		TrackTitleSortOrder => title_sort,
+		TrackArtistSortOrder => artist_sort,
+		AlbumTitleSortOrder => album_sort,
+		AlbumArtistSortOrder => album_artist_sort
 	);
 
 	fn track(&self) -> Option<u32> {
-		if let Some(i) = self.get_string(&ItemKey::TrackNumber) {
-			return i.parse::<u32>().ok();
-		}
-
-		None
+		self.get_item_ref(&ItemKey::TrackNumber)
+			.and_then(|i| i.value().as_u32())
 	}
 
 	fn set_track(&mut self, value: u32) {
@@ -140,11 +152,8 @@ impl Accessor for Tag {
 	}
 
 	fn track_total(&self) -> Option<u32> {
-		if let Some(i) = self.get_string(&ItemKey::TrackTotal) {
-			return i.parse::<u32>().ok();
-		}
-
-		None
+		self.get_item_ref(&ItemKey::TrackTotal)
+			.and_then(|i| i.value().as_u32())
 	}
 
 	fn set_track_total(&mut self, value: u32) {
@@ -156,11 +165,8 @@ impl Accessor for Tag {
 	}
 
 	fn disk(&self) -> Option<u32> {
-		if let Some(i) = self.get_string(&ItemKey::DiscNumber) {
-			return i.parse::<u32>().ok();
-		}
-
-		None
+		self.get_item_ref(&ItemKey::DiscNumber)
+			.and_then(|i| i.value().as_u32())
 	}
 
 	fn set_disk(&mut self, value: u32) {
@@ -172,11 +178,8 @@ impl Accessor for Tag {
 	}
 
 	fn disk_total(&self) -> Option<u32> {
-		if let Some(i) = self.get_string(&ItemKey::DiscTotal) {
-			return i.parse::<u32>().ok();
-		}
-
-		None
+		self.get_item_ref(&ItemKey::DiscTotal)
+			.and_then(|i| i.value().as_u32())
 	}
 
 	fn set_disk_total(&mut self, value: u32) {
@@ -187,6 +190,18 @@ impl Accessor for Tag {
 		self.remove_key(&ItemKey::DiscTotal);
 	}
 
+	fn bpm(&self) -> Option<u16> {
+		self.get_string(&ItemKey::BPM).and_then(utils::parse_bpm)
+	}
+
+	fn set_bpm(&mut self, value: u16) {
+		self.insert_text(ItemKey::BPM, value.to_string());
+	}
+
+	fn remove_bpm(&mut self) {
+		self.remove_key(&ItemKey::BPM);
+	}
+
 	fn year(&self) -> Option<u32> {
 		if let Some(item) = self
 			.get_string(&ItemKey::Year)
@@ -224,9 +239,55 @@ impl Tag {
 	}
 
 	/// Change the [`TagType`], remapping all items
-	pub fn re_map(&mut self, tag_type: TagType) {
-		self.retain_items(|i| i.re_map(tag_type));
-		self.tag_type = tag_type
+	///
+	/// This will remove any items that can't be represented in `tag_type`:
+	///
+	/// * Items with no [`ItemKey`] mapping for `tag_type`
+	/// * Binary items, if `tag_type` can only store text
+	///
+	/// Pictures are removed as well if `tag_type` doesn't support them.
+	///
+	/// This returns the keys of all removed items, in the order they first appeared.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{Accessor, ItemKey, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::ID3v2);
+	/// tag.set_artist(String::from("Foo artist"));
+	/// tag.insert_text(ItemKey::Label, String::from("Bar label"));
+	///
+	/// // ID3v1 has no field for the label
+	/// let lost = tag.re_map(TagType::ID3v1);
+	/// assert_eq!(lost, [ItemKey::Label]);
+	///
+	/// assert_eq!(tag.tag_type(), TagType::ID3v1);
+	/// assert_eq!(tag.artist(), Some("Foo artist"));
+	/// ```
+	pub fn re_map(&mut self, tag_type: TagType) -> Vec<ItemKey> {
+		let text_only = !tag_type.supports_binary_items();
+
+		let mut lost = Vec::new();
+		self.retain_items(|i| {
+			let binary_lost = text_only && matches!(i.item_value, ItemValue::Binary(_));
+			if !binary_lost && i.re_map(tag_type) {
+				return true;
+			}
+
+			if !lost.contains(&i.item_key) {
+				lost.push(i.item_key.clone());
+			}
+
+			false
+		});
+
+		if !tag_type.supports_pictures() {
+			self.pictures.clear();
+		}
+
+		self.tag_type = tag_type;
+		lost
 	}
 
 	/// Returns the [`TagType`]
@@ -340,6 +401,54 @@ impl Tag {
 		self.insert_item(TagItem::new(item_key, ItemValue::Text(text)))
 	}
 
+	/// Insert multiple text values for an [`ItemKey`], replacing any existing items of the same key
+	///
+	/// NOTE: This **will** verify an [`ItemKey`] mapping exists for the target [`TagType`]
+	///
+	/// Not all formats support multiple values for a key. In that case, only the first value
+	/// will be written. See [`Tag::push_item`].
+	///
+	/// This will return `true` if the values were inserted. Nothing is inserted if `values` is empty.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{Accessor, ItemKey, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::VorbisComments);
+	/// tag.insert_text_multiple(
+	/// 	ItemKey::TrackArtist,
+	/// 	[String::from("Foo artist"), String::from("Bar artist")],
+	/// );
+	///
+	/// // `Accessor` methods will only return the first value
+	/// assert_eq!(tag.artist(), Some("Foo artist"));
+	///
+	/// let artists = tag.get_strings(&ItemKey::TrackArtist).collect::<Vec<_>>();
+	/// assert_eq!(artists, ["Foo artist", "Bar artist"]);
+	/// ```
+	pub fn insert_text_multiple<I>(&mut self, item_key: ItemKey, values: I) -> bool
+	where
+		I: IntoIterator<Item = String>,
+	{
+		let mut items = values
+			.into_iter()
+			.map(move |value| TagItem::new(item_key.clone(), ItemValue::Text(value)))
+			.peekable();
+
+		match items.peek() {
+			Some(item) if item.re_map(self.tag_type) => {
+				let item_key = item.item_key.clone();
+				self.remove_key(&item_key);
+			},
+			_ => return false,
+		}
+
+		self.items.extend(items);
+
+		true
+	}
+
 	/// Removes all items with the specified [`ItemKey`], and returns them
 	pub fn take(&mut self, key: &ItemKey) -> impl Iterator<Item = TagItem> + '_ {
 		// TODO: drain_filter
@@ -415,6 +524,79 @@ impl Tag {
 		self.items.retain(f)
 	}
 
+	/// Returns the rating as a number of stars (0-5)
+	///
+	/// For `ID3v2`, this is read from the first "POPM" frame, normalizing its 0-255 rating
+	/// (see [`Popularimeter::star_rating`](crate::id3::v2::Popularimeter::star_rating)).
+	/// All other formats store the rating as text on a 0-100 scale.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::VorbisComments);
+	/// tag.set_rating(4);
+	///
+	/// assert_eq!(tag.rating(), Some(4));
+	/// assert_eq!(tag.get_string(&lofty::ItemKey::Popularimeter), Some("80"));
+	/// ```
+	pub fn rating(&self) -> Option<u8> {
+		match self.get_item_ref(&ItemKey::Popularimeter)?.value() {
+			#[cfg(feature = "id3v2")]
+			ItemValue::Binary(popm) => Popularimeter::parse(&mut &popm[..])
+				.ok()
+				.map(|popm| popm.star_rating()),
+			ItemValue::Text(text) => match text.trim().parse::<u8>() {
+				Ok(rating @ 0..=100) => Some((rating + 10) / 20),
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	/// Sets the rating as a number of stars (0-5)
+	///
+	/// Values greater than 5 will be treated as 5 stars.
+	///
+	/// For `ID3v2`, this will update the first "POPM" frame, keeping its email and play counter.
+	/// If there is no existing frame, one is created with an empty email and a counter of 0.
+	///
+	/// NOTE: Nothing will change if the tag already has this rating, preserving the underlying value.
+	pub fn set_rating(&mut self, rating: u8) {
+		let rating = rating.min(5);
+		if self.rating() == Some(rating) {
+			return;
+		}
+
+		#[cfg(feature = "id3v2")]
+		if self.tag_type == TagType::ID3v2 {
+			let mut popm = self
+				.get_binary(&ItemKey::Popularimeter, false)
+				.and_then(|popm| Popularimeter::parse(&mut &popm[..]).ok())
+				.unwrap_or(Popularimeter {
+					email: String::new(),
+					rating: 0,
+					counter: 0,
+				});
+
+			popm.set_star_rating(rating);
+
+			self.insert_item_unchecked(TagItem::new(
+				ItemKey::Popularimeter,
+				ItemValue::Binary(popm.as_bytes()),
+			));
+			return;
+		}
+
+		self.insert_text(ItemKey::Popularimeter, (u16::from(rating) * 20).to_string());
+	}
+
+	/// Removes the rating
+	pub fn remove_rating(&mut self) {
+		self.remove_key(&ItemKey::Popularimeter)
+	}
+
 	/// Returns the stored [`Picture`]s as a slice
 	pub fn pictures(&self) -> &[Picture] {
 		&self.pictures
@@ -437,6 +619,57 @@ impl Tag {
 		self.pictures.retain(|p| p.pic_type != picture_type)
 	}
 
+	/// Returns the first [`PictureType::CoverFront`] picture
+	///
+	/// NOTE: This does not fall back to any other [`PictureType`], even if the tag only has a
+	/// [`PictureType::Other`] picture. Use [`Tag::get_picture_type`] to look for those.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{MimeType, Picture, PictureType, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::ID3v2);
+	///
+	/// let other = Picture::new_unchecked(PictureType::Other, MimeType::Png, None, Vec::new());
+	/// tag.push_picture(other);
+	/// assert!(tag.front_cover().is_none());
+	///
+	/// let front_cover = Picture::new_unchecked(PictureType::Other, MimeType::Jpeg, None, Vec::new());
+	/// tag.set_front_cover(front_cover);
+	///
+	/// assert_eq!(tag.front_cover().map(Picture::mime_type), Some(&MimeType::Jpeg));
+	/// assert_eq!(tag.picture_count(), 2);
+	/// ```
+	pub fn front_cover(&self) -> Option<&Picture> {
+		self.get_picture_type(PictureType::CoverFront)
+	}
+
+	/// Sets the front cover, replacing any existing [`PictureType::CoverFront`] pictures
+	///
+	/// The `picture`'s type will be changed to [`PictureType::CoverFront`]. It takes the place of
+	/// the first existing front cover, or is appended if there isn't one.
+	pub fn set_front_cover(&mut self, mut picture: Picture) {
+		picture.pic_type = PictureType::CoverFront;
+
+		match self
+			.pictures
+			.iter()
+			.position(|p| p.pic_type == PictureType::CoverFront)
+		{
+			Some(index) => {
+				self.remove_front_cover();
+				self.pictures.insert(index, picture);
+			},
+			None => self.push_picture(picture),
+		}
+	}
+
+	/// Removes all [`PictureType::CoverFront`] pictures
+	pub fn remove_front_cover(&mut self) {
+		self.remove_picture_type(PictureType::CoverFront)
+	}
+
 	/// Replaces the picture at the given `index`
 	///
 	/// NOTE: If `index` is out of bounds, the `picture` will be appended
@@ -503,6 +736,162 @@ impl Tag {
 	pub fn remove_picture(&mut self, index: usize) -> Picture {
 		self.pictures.remove(index)
 	}
+
+	/// Loads the data of the picture at `index`
+	///
+	/// This is only needed for placeholders, which are only created for FLAC `PICTURE` blocks.
+	/// See [`Picture::load`].
+	///
+	/// # Panics
+	///
+	/// `index` is out of bounds
+	///
+	/// # Errors
+	///
+	/// See [`Picture::load`]
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::{Probe, ProbeOptions};
+	///
+	/// # fn main() -> lofty::Result<()> {
+	/// let path = "path/to/my.flac";
+	/// let options = ProbeOptions::new().read_pictures(false);
+	///
+	/// let mut tagged_file = Probe::open(path)?.options(options).read(false)?;
+	/// let tag = tagged_file.primary_tag_mut().unwrap();
+	///
+	/// assert!(!tag.pictures()[0].is_loaded());
+	///
+	/// let picture = tag.load_picture(0, &mut std::fs::File::open(path)?)?;
+	/// assert!(picture.is_loaded());
+	/// # Ok(()) }
+	/// ```
+	pub fn load_picture<R>(&mut self, index: usize, reader: &mut R) -> Result<&Picture>
+	where
+		R: Read + Seek,
+	{
+		let picture = &mut self.pictures[index];
+		picture.load(reader)?;
+
+		Ok(picture)
+	}
+
+	/// Merge the items and pictures of `other` into this tag
+	///
+	/// Conflicting keys are resolved with `policy`, see [`MergePolicy`].
+	///
+	/// Any items that can't be represented in this tag's [`TagType`] are discarded, as are the
+	/// pictures if the format doesn't support them. Pictures are otherwise combined, skipping any
+	/// duplicates (same [`PictureType`] and data).
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{Accessor, MergePolicy, Tag, TagType};
+	///
+	/// let mut id3v2 = Tag::new(TagType::ID3v2);
+	/// id3v2.set_title(String::from("Foo title"));
+	///
+	/// let mut ape = Tag::new(TagType::APE);
+	/// ape.set_title(String::from("Bar title"));
+	/// ape.set_artist(String::from("Bar artist"));
+	///
+	/// id3v2.merge(ape, MergePolicy::KeepSelf);
+	///
+	/// assert_eq!(id3v2.title(), Some("Foo title"));
+	/// assert_eq!(id3v2.artist(), Some("Bar artist"));
+	/// ```
+	pub fn merge(&mut self, other: Tag, policy: MergePolicy) {
+		let tag_type = self.tag_type;
+		let text_only = !tag_type.supports_binary_items();
+
+		// Group the items by key, so a key's values are all resolved together
+		let mut other_items: Vec<(ItemKey, Vec<TagItem>)> = Vec::new();
+		for item in other.items {
+			let binary_lost = text_only && matches!(item.item_value, ItemValue::Binary(_));
+			if binary_lost || !item.re_map(tag_type) {
+				continue;
+			}
+
+			match other_items
+				.iter_mut()
+				.find(|(key, _)| key == &item.item_key)
+			{
+				Some((_, items)) => items.push(item),
+				None => other_items.push((item.item_key.clone(), vec![item])),
+			}
+		}
+
+		for (key, items) in other_items {
+			match policy {
+				MergePolicy::KeepSelf => {
+					if self.get_item_ref(&key).is_none() {
+						self.items.extend(items);
+					}
+				},
+				MergePolicy::KeepOther => {
+					self.remove_key(&key);
+					self.items.extend(items);
+				},
+				MergePolicy::PreferNonEmpty => {
+					if self.get_items(&key).all(|i| is_empty_value(&i.item_value)) {
+						self.remove_key(&key);
+						self.items.extend(items);
+					}
+				},
+				MergePolicy::Union => {
+					for item in items {
+						if !self
+							.get_items(&key)
+							.any(|i| i.item_value == item.item_value)
+						{
+							self.items.push(item);
+						}
+					}
+				},
+			}
+		}
+
+		if !tag_type.supports_pictures() {
+			return;
+		}
+
+		for picture in other.pictures {
+			let duplicate = self
+				.pictures
+				.iter()
+				.any(|p| p.pic_type == picture.pic_type && p.data() == picture.data());
+
+			if !duplicate {
+				self.pictures.push(picture);
+			}
+		}
+	}
+}
+
+fn is_empty_value(value: &ItemValue) -> bool {
+	match value {
+		ItemValue::Text(s) | ItemValue::Locator(s) => s.is_empty(),
+		ItemValue::Binary(b) => b.is_empty(),
+	}
+}
+
+/// How to resolve conflicting keys in [`Tag::merge`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MergePolicy {
+	/// Keep the existing items, only adding keys that aren't present
+	KeepSelf,
+	/// Replace the existing items with those of the other tag
+	KeepOther,
+	/// Keep the existing items, unless they are all empty
+	PreferNonEmpty,
+	/// Keep all values, adding those of the other tag that aren't present
+	///
+	/// NOTE: Not all formats support multiple values for a key, see [`Tag::push_item`]
+	Union,
 }
 
 impl TagExt for Tag {
@@ -519,7 +908,7 @@ impl TagExt for Tag {
 	/// * Path doesn't exist
 	/// * Path is not writable
 	/// * See [`Tag::save_to`]
-	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err> {
 		self.save_to(&mut OpenOptions::new().read(true).write(true).open(path)?)
 	}
 
@@ -529,7 +918,7 @@ impl TagExt for Tag {
 	///
 	/// * A [`FileType`](crate::FileType) couldn't be determined from the File
 	/// * Attempting to write a tag to a format that does not support it. See [`FileType::supports_tag_type`](crate::FileType::supports_tag_type)
-	fn save_to(&self, file: &mut File) -> std::result::Result<(), Self::Err> {
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err> {
 		let probe = Probe::new(file).guess_file_type()?;
 
 		match probe.file_type() {
@@ -574,6 +963,7 @@ impl TagExt for Tag {
 
 /// The tag's format
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum TagType {
 	/// This covers both APEv1 and APEv2 as it doesn't matter much
@@ -590,9 +980,24 @@ pub enum TagType {
 	RIFFInfo,
 	/// Represents AIFF text chunks
 	AIFFText,
+	/// Represents a Lyrics3v2 tag
+	Lyrics3v2,
+	/// Represents Matroska tags
+	Matroska,
 }
 
 impl TagType {
+	pub(crate) fn supports_pictures(self) -> bool {
+		matches!(
+			self,
+			TagType::APE | TagType::ID3v2 | TagType::MP4ilst | TagType::VorbisComments
+		)
+	}
+
+	pub(crate) fn supports_binary_items(self) -> bool {
+		matches!(self, TagType::APE | TagType::ID3v2 | TagType::MP4ilst)
+	}
+
 	/// Remove a tag from a [`Path`]
 	///
 	/// # Errors
@@ -626,14 +1031,96 @@ impl TagType {
 		}
 
 		let file = probe.into_inner();
-		utils::write_tag(&Tag::new(*self), file, file_type)
+		utils::write_tag(&Tag::new(*self), file, file_type)?;
+
+		Ok(())
 	}
 }
 
+/// Remove every tag from a [`Path`]
+///
+/// # Errors
+///
+/// See [`remove_all_tags`]
+pub fn remove_all_tags_from_path(path: impl AsRef<Path>) -> Result<Vec<TagType>> {
+	let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+	remove_all_tags(&mut file)
+}
+
+/// Remove every tag from a [`File`]
+///
+/// This removes all tags present in the file, rather than a single [`TagType`]. For formats that
+/// store tags at the end of the file (AAC, MPEG, APE, Musepack, TTA, and WavPack), this includes any Lyrics3v2 tag.
+///
+/// The [`TagType`]s that were removed are returned. Lyrics3v2 tags are only read from MPEG files,
+/// so they aren't included for any other format.
+///
+/// # Errors
+///
+/// * It is unable to guess the file format
+/// * The format doesn't support removing one of its tags (Ex. ID3v2 in DSF)
+/// * It is unable to write to the file
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lofty::TagType;
+///
+/// # fn main() -> lofty::Result<()> {
+/// # let path = "tests/files/assets/minimal/full_test.mp3";
+/// let removed = lofty::remove_all_tags_from_path(path)?;
+///
+/// assert!(removed.contains(&TagType::ID3v2));
+/// # Ok(()) }
+/// ```
+pub fn remove_all_tags(file: &mut File) -> Result<Vec<TagType>> {
+	let tagged_file = crate::probe::read_from(file, false)?;
+	let file_type = tagged_file.file_type();
+
+	let mut removed = Vec::new();
+	for tag in tagged_file.tags() {
+		let tag_type = tag.tag_type();
+		if removed.contains(&tag_type) {
+			continue;
+		}
+
+		file.rewind()?;
+		tag_type.remove_from(file)?;
+
+		removed.push(tag_type);
+	}
+
+	// With the ID3v1 and APE tags gone, any Lyrics3v2 tag is now at the end of the file
+	if matches!(
+		file_type,
+		FileType::AAC
+			| FileType::APE
+			| FileType::MPEG
+			| FileType::Musepack
+			| FileType::TTA
+			| FileType::WavPack
+	) && file.metadata()?.len() >= 15
+	{
+		file.seek(SeekFrom::End(0))?;
+
+		let ID3FindResults(header, _) = find_lyrics3v2(file)?;
+		if header.is_some() {
+			let lyrics3v2_start = file.stream_position()?;
+			file.set_len(lyrics3v2_start)?;
+		}
+	}
+
+	Ok(removed)
+}
+
 #[cfg(test)]
 mod tests {
+	use crate::id3::v1::ID3v1Tag;
 	use crate::tag::utils::test_utils::read_path;
-	use crate::{Accessor, Picture, PictureType, Tag, TagExt, TagType};
+	use crate::{
+		Accessor, ItemKey, ItemValue, MergePolicy, MimeType, Picture, PictureType, Tag, TagBuilder,
+		TagExt, TagItem, TagType,
+	};
 	use std::io::{Seek, Write};
 	use std::process::Command;
 
@@ -678,4 +1165,606 @@ mod tests {
 		tag.set_title(String::new());
 		assert_eq!(tag.title(), None);
 	}
+
+	#[test]
+	fn insert_text_multiple() {
+		let mut tag = Tag::new(TagType::VorbisComments);
+		tag.set_artist(String::from("Foo artist"));
+
+		assert!(tag.insert_text_multiple(
+			ItemKey::TrackArtist,
+			[String::from("Bar artist"), String::from("Baz artist")]
+		));
+
+		assert_eq!(tag.artist(), Some("Bar artist"));
+		assert_eq!(
+			tag.get_strings(&ItemKey::TrackArtist).collect::<Vec<_>>(),
+			["Bar artist", "Baz artist"]
+		);
+
+		// Nothing to insert, the existing items are left alone
+		assert!(!tag.insert_text_multiple(ItemKey::TrackArtist, []));
+		assert_eq!(tag.item_count(), 2);
+	}
+
+	#[test]
+	fn multiple_values_to_single_value_format() {
+		let mut tag = Tag::new(TagType::ID3v1);
+		assert!(tag.insert_text_multiple(
+			ItemKey::TrackArtist,
+			[String::from("Foo artist"), String::from("Bar artist")]
+		));
+
+		// ID3v1 only keeps the first value
+		let id3v1: ID3v1Tag = tag.into();
+		assert_eq!(id3v1.artist(), Some("Foo artist"));
+	}
+
+	#[test]
+	fn re_map_reports_lost_items() {
+		let mut tag = Tag::new(TagType::ID3v2);
+		tag.set_artist(String::from("Foo artist"));
+		tag.insert_text(ItemKey::Label, String::from("Bar label"));
+		tag.push_item_unchecked(TagItem::new(
+			ItemKey::Unknown(String::from("FOO")),
+			ItemValue::Binary(vec![1, 2, 3]),
+		));
+		tag.push_picture(Picture::new_unchecked(
+			PictureType::CoverFront,
+			MimeType::Png,
+			None,
+			vec![1, 2, 3],
+		));
+
+		let mut vorbis_comments = tag.clone();
+		assert_eq!(
+			vorbis_comments.re_map(TagType::VorbisComments),
+			[ItemKey::Unknown(String::from("FOO"))]
+		);
+		assert_eq!(vorbis_comments.tag_type(), TagType::VorbisComments);
+		assert_eq!(vorbis_comments.artist(), Some("Foo artist"));
+		assert_eq!(
+			vorbis_comments.get_string(&ItemKey::Label),
+			Some("Bar label")
+		);
+		assert_eq!(vorbis_comments.picture_count(), 1);
+
+		assert_eq!(
+			tag.re_map(TagType::ID3v1),
+			[ItemKey::Label, ItemKey::Unknown(String::from("FOO"))]
+		);
+		assert_eq!(tag.artist(), Some("Foo artist"));
+		assert_eq!(tag.item_count(), 1);
+		assert_eq!(tag.picture_count(), 0);
+	}
+
+	#[test]
+	fn album_artist_and_composer() {
+		fn verify<T: Accessor + Default + Into<Tag>>() {
+			let mut tag = T::default();
+			tag.set_album_artist(String::from("Foo artist"));
+			tag.set_composer(String::from("Bar composer"));
+
+			assert_eq!(tag.album_artist(), Some("Foo artist"));
+			assert_eq!(tag.composer(), Some("Bar composer"));
+
+			let tag: Tag = tag.into();
+			assert_eq!(tag.get_string(&ItemKey::AlbumArtist), Some("Foo artist"));
+			assert_eq!(tag.get_string(&ItemKey::Composer), Some("Bar composer"));
+			assert_eq!(tag.album_artist(), Some("Foo artist"));
+			assert_eq!(tag.composer(), Some("Bar composer"));
+		}
+
+		verify::<crate::ape::ApeTag>();
+		verify::<crate::id3::v2::ID3v2Tag>();
+		verify::<crate::mp4::Ilst>();
+		verify::<crate::ogg::VorbisComments>();
+	}
+
+	#[test]
+	fn number_pairs() {
+		fn verify<T: Accessor + Default + Into<Tag> + From<Tag>>() {
+			let mut tag = T::default();
+
+			// The total is kept, even without a track number
+			tag.set_track_total(12);
+			assert_eq!(tag.track(), None);
+			assert_eq!(tag.track_total(), Some(12));
+
+			tag.set_track(3);
+			assert_eq!(tag.track(), Some(3));
+			assert_eq!(tag.track_total(), Some(12));
+
+			tag.set_disk(1);
+			tag.set_disk_total(2);
+
+			// Both sides of the pairs survive a conversion
+			let generic: Tag = tag.into();
+			assert_eq!(generic.track(), Some(3));
+			assert_eq!(generic.track_total(), Some(12));
+			assert_eq!(generic.disk(), Some(1));
+			assert_eq!(generic.disk_total(), Some(2));
+
+			let mut tag = T::from(generic);
+			assert_eq!(tag.track(), Some(3));
+			assert_eq!(tag.track_total(), Some(12));
+			assert_eq!(tag.disk(), Some(1));
+			assert_eq!(tag.disk_total(), Some(2));
+
+			tag.remove_disk_total();
+			assert_eq!(tag.disk(), Some(1));
+			assert_eq!(tag.disk_total(), None);
+
+			tag.remove_track();
+			assert_eq!(tag.track(), None);
+			assert_eq!(tag.track_total(), Some(12));
+		}
+
+		verify::<crate::ape::ApeTag>();
+		verify::<crate::id3::v2::ID3v2Tag>();
+		verify::<crate::mp4::Ilst>();
+		verify::<crate::ogg::VorbisComments>();
+
+		let mut id3v2 = crate::id3::v2::ID3v2Tag::default();
+		id3v2.set_track(3);
+		id3v2.set_track_total(12);
+		assert!(matches!(
+			id3v2.get("TRCK").map(crate::id3::v2::Frame::content),
+			Some(crate::id3::v2::FrameValue::Text { value, .. }) if value == "3/12"
+		));
+
+		// Vorbis comments can also have the total in "TRACKNUMBER"
+		let mut vorbis_comments = crate::ogg::VorbisComments::default();
+		vorbis_comments.insert(String::from("TRACKNUMBER"), String::from("3/12"), true);
+		assert_eq!(vorbis_comments.track(), Some(3));
+		assert_eq!(vorbis_comments.track_total(), Some(12));
+
+		// Writing a `Tag` produces a single pair
+		let mut tag = Tag::new(TagType::ID3v2);
+		tag.set_track(3);
+		tag.set_track_total(12);
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer).unwrap();
+
+		let mut reader = std::io::Cursor::new(&writer[..]);
+		let header = crate::id3::v2::read_id3v2_header(&mut reader).unwrap();
+		let id3v2 = crate::id3::v2::read::parse_id3v2(&mut reader, header).unwrap();
+
+		assert_eq!(id3v2.frames().len(), 1);
+		assert_eq!(id3v2.track(), Some(3));
+		assert_eq!(id3v2.track_total(), Some(12));
+	}
+
+	#[test]
+	fn mp4_number_pairs() {
+		let mut ilst = crate::mp4::Ilst::default();
+
+		ilst.set_track(2);
+		ilst.set_track_total(5);
+		ilst.set_track(3);
+		assert_eq!(ilst.track(), Some(3));
+		assert_eq!(ilst.track_total(), Some(5));
+
+		ilst.set_disk(2);
+		ilst.set_disk_total(5);
+		assert_eq!(ilst.disk(), Some(2));
+		assert_eq!(ilst.disk_total(), Some(5));
+
+		ilst.set_disk(3);
+		assert_eq!(ilst.disk(), Some(3));
+		assert_eq!(ilst.disk_total(), Some(5));
+	}
+
+	#[test]
+	fn bpm_and_initial_key() {
+		fn verify<T: Accessor + Default + Into<Tag>>() {
+			let mut tag = T::default();
+			tag.set_bpm(128);
+			tag.set_initial_key(String::from("Am"));
+
+			assert_eq!(tag.bpm(), Some(128));
+			assert_eq!(tag.initial_key(), Some("Am"));
+
+			let tag: Tag = tag.into();
+			assert_eq!(tag.get_string(&ItemKey::BPM), Some("128"));
+			assert_eq!(tag.bpm(), Some(128));
+
+			assert_eq!(tag.initial_key(), Some("Am"));
+		}
+
+		verify::<crate::ape::ApeTag>();
+		verify::<crate::id3::v2::ID3v2Tag>();
+		verify::<crate::mp4::Ilst>();
+		verify::<crate::ogg::VorbisComments>();
+	}
+
+	#[test]
+	fn encoder() {
+		fn verify<T: Accessor + Default + Into<Tag>>() {
+			let mut tag = T::default();
+			tag.set_encoder(String::from("Lavf58.76.100"));
+
+			assert_eq!(tag.encoder(), Some("Lavf58.76.100"));
+
+			let mut tag: Tag = tag.into();
+			assert_eq!(
+				tag.get_string(&ItemKey::EncoderSoftware),
+				Some("Lavf58.76.100")
+			);
+			assert_eq!(tag.encoder(), Some("Lavf58.76.100"));
+
+			// The person or organization that encoded the file is kept separate
+			tag.insert_text(ItemKey::EncodedBy, String::from("Foo"));
+			assert_eq!(tag.encoder(), Some("Lavf58.76.100"));
+
+			tag.remove_encoder();
+			assert_eq!(tag.encoder(), None);
+			assert_eq!(tag.get_string(&ItemKey::EncodedBy), Some("Foo"));
+		}
+
+		verify::<crate::id3::v2::ID3v2Tag>();
+		verify::<crate::iff::RIFFInfoList>();
+		verify::<crate::mp4::Ilst>();
+		verify::<crate::ogg::VorbisComments>();
+	}
+
+	#[test]
+	fn sort_order() {
+		fn verify<T: Accessor + Default + Into<Tag>>() {
+			let mut tag = T::default();
+			tag.set_title_sort(String::from("Title, The"));
+			tag.set_artist_sort(String::from("Artist, Foo"));
+			tag.set_album_sort(String::from("Album, The"));
+			tag.set_album_artist_sort(String::from("Artist, Bar"));
+
+			let mut tag: Tag = tag.into();
+			assert_eq!(
+				tag.get_string(&ItemKey::TrackTitleSortOrder),
+				Some("Title, The")
+			);
+			assert_eq!(
+				tag.get_string(&ItemKey::TrackArtistSortOrder),
+				Some("Artist, Foo")
+			);
+			assert_eq!(
+				tag.get_string(&ItemKey::AlbumTitleSortOrder),
+				Some("Album, The")
+			);
+			assert_eq!(
+				tag.get_string(&ItemKey::AlbumArtistSortOrder),
+				Some("Artist, Bar")
+			);
+
+			// The vendor string from Vorbis Comments can't be mapped to APE
+			tag.remove_key(&ItemKey::EncoderSoftware);
+
+			for tag_type in [
+				TagType::APE,
+				TagType::ID3v2,
+				TagType::MP4ilst,
+				TagType::VorbisComments,
+			] {
+				let mut tag = tag.clone();
+				assert!(tag.re_map(tag_type).is_empty());
+
+				assert_eq!(tag.title_sort(), Some("Title, The"));
+				assert_eq!(tag.artist_sort(), Some("Artist, Foo"));
+				assert_eq!(tag.album_sort(), Some("Album, The"));
+				assert_eq!(tag.album_artist_sort(), Some("Artist, Bar"));
+			}
+		}
+
+		verify::<crate::ape::ApeTag>();
+		verify::<crate::id3::v2::ID3v2Tag>();
+		verify::<crate::mp4::Ilst>();
+		verify::<crate::ogg::VorbisComments>();
+	}
+
+	#[test]
+	fn parse_bpm() {
+		let mut tag = Tag::new(TagType::VorbisComments);
+
+		for (value, expected) in [
+			("120", Some(120)),
+			(" 127.6 ", Some(128)),
+			("-5", None),
+			("Foo", None),
+			("99999", None),
+		] {
+			tag.insert_text(ItemKey::BPM, String::from(value));
+			assert_eq!(tag.bpm(), expected);
+		}
+	}
+
+	#[test]
+	fn mp4_bpm_is_integer() {
+		use crate::mp4::{AtomData, AtomIdent, Ilst};
+
+		let mut tag = Tag::new(TagType::MP4ilst);
+		tag.insert_text(ItemKey::BPM, String::from("95.2"));
+
+		let ilst: Ilst = tag.into();
+		let atom = ilst.atom(&AtomIdent::Fourcc(*b"tmpo")).unwrap();
+		assert_eq!(
+			atom.data().next(),
+			Some(&AtomData::Unknown {
+				code: 21,
+				data: vec![0, 95]
+			})
+		);
+		assert_eq!(ilst.bpm(), Some(95));
+	}
+
+	#[test]
+	fn id3v2_rating() {
+		use crate::id3::v2::{Frame, FrameFlags, FrameValue, ID3v2Tag, Popularimeter};
+
+		let popm = Popularimeter {
+			email: String::from("foo@bar.com"),
+			rating: 200,
+			counter: 42,
+		};
+
+		let mut id3v2 = ID3v2Tag::default();
+		id3v2.insert(
+			Frame::new(
+				"POPM",
+				FrameValue::Popularimeter(popm.clone()),
+				FrameFlags::default(),
+			)
+			.unwrap(),
+		);
+
+		let mut tag: Tag = id3v2.into();
+		assert_eq!(tag.rating(), Some(4));
+
+		// Setting the same rating shouldn't touch the raw value
+		tag.set_rating(4);
+
+		let id3v2: ID3v2Tag = tag.clone().into();
+		assert_eq!(
+			id3v2.get("POPM").unwrap().content(),
+			&FrameValue::Popularimeter(popm.clone())
+		);
+		match id3v2.get("POPM").unwrap().content() {
+			FrameValue::Popularimeter(p) => assert_eq!((p.rating, p.counter), (200, 42)),
+			_ => unreachable!(),
+		}
+
+		// The email and counter are kept
+		tag.set_rating(5);
+		assert_eq!(tag.rating(), Some(5));
+
+		let id3v2: ID3v2Tag = tag.into();
+		match id3v2.get("POPM").unwrap().content() {
+			FrameValue::Popularimeter(p) => {
+				assert_eq!(p.email, popm.email);
+				assert_eq!((p.rating, p.counter), (255, 42));
+			},
+			_ => unreachable!(),
+		}
+
+		// Defaults are used when there is no existing frame
+		let mut tag = Tag::new(TagType::ID3v2);
+		tag.set_rating(2);
+
+		let id3v2: ID3v2Tag = tag.into();
+		match id3v2.get("POPM").unwrap().content() {
+			FrameValue::Popularimeter(p) => {
+				assert!(p.email.is_empty());
+				assert_eq!((p.rating, p.counter), (64, 0));
+			},
+			_ => unreachable!(),
+		}
+	}
+
+	#[test]
+	fn text_rating() {
+		for tag_type in [TagType::VorbisComments, TagType::APE] {
+			let mut tag = Tag::new(tag_type);
+			assert_eq!(tag.rating(), None);
+
+			tag.set_rating(3);
+			assert_eq!(tag.get_string(&ItemKey::Popularimeter), Some("60"));
+			assert_eq!(tag.rating(), Some(3));
+
+			// Values are clamped
+			tag.set_rating(10);
+			assert_eq!(tag.get_string(&ItemKey::Popularimeter), Some("100"));
+
+			// Existing values on the 0-100 scale are kept if unchanged
+			tag.insert_text(ItemKey::Popularimeter, String::from("95"));
+			assert_eq!(tag.rating(), Some(5));
+			tag.set_rating(5);
+			assert_eq!(tag.get_string(&ItemKey::Popularimeter), Some("95"));
+
+			tag.remove_rating();
+			assert_eq!(tag.rating(), None);
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn serde_round_trip() {
+		let mut tag = Tag::new(TagType::ID3v2);
+		tag.set_title(String::from("Foo title"));
+		tag.insert_item_unchecked(TagItem::new(
+			ItemKey::Unknown(String::from("FOO")),
+			ItemValue::Binary(vec![1, 2, 3]),
+		));
+		tag.push_picture(Picture::new_unchecked(
+			PictureType::CoverFront,
+			MimeType::Jpeg,
+			Some(String::from("Foo description")),
+			vec![0xFF, 0xD8, 0xFF, 0xE0],
+		));
+
+		let json = serde_json::to_string(&tag).unwrap();
+
+		// The picture data is stored as base64
+		assert!(json.contains("\"/9j/4A==\""));
+
+		let deserialized: Tag = serde_json::from_str(&json).unwrap();
+		assert_eq!(deserialized, tag);
+	}
+
+	#[test]
+	fn item_value_conversions() {
+		let text = ItemValue::Text(String::from(" 3/10"));
+		assert_eq!(text.as_u32(), Some(3));
+		assert_eq!(text.as_i64(), Some(3));
+		assert_eq!(ItemValue::Text(String::from("-5")).as_i64(), Some(-5));
+		assert_eq!(ItemValue::Text(String::from("-5")).as_u32(), None);
+		assert_eq!(ItemValue::Locator(String::from("1")).as_u32(), None);
+
+		assert_eq!(String::try_from(text).unwrap(), " 3/10");
+		assert_eq!(
+			String::try_from(ItemValue::Binary(vec![1])),
+			Err(ItemValue::Binary(vec![1]))
+		);
+		assert_eq!(
+			Vec::<u8>::try_from(ItemValue::Binary(vec![1])).unwrap(),
+			vec![1]
+		);
+
+		let mut tag = Tag::new(TagType::APE);
+		tag.insert_text(ItemKey::TrackNumber, String::from("3/10"));
+		assert_eq!(tag.track(), Some(3));
+	}
+
+	#[test]
+	fn front_cover() {
+		let picture = |pic_type, data: &[u8]| {
+			Picture::new_unchecked(pic_type, MimeType::Png, None, data.to_vec())
+		};
+
+		let mut tag = Tag::new(TagType::ID3v2);
+		tag.push_picture(picture(PictureType::Other, &[1]));
+		tag.push_picture(picture(PictureType::CoverFront, &[2]));
+		tag.push_picture(picture(PictureType::CoverBack, &[3]));
+		tag.push_picture(picture(PictureType::CoverFront, &[4]));
+
+		assert_eq!(tag.front_cover().unwrap().data(), &[2]);
+
+		// Every existing front cover is replaced, and the new one takes the place of the first
+		tag.set_front_cover(picture(PictureType::Other, &[5]));
+		assert_eq!(tag.picture_count(), 3);
+		assert_eq!(tag.pictures()[1], picture(PictureType::CoverFront, &[5]));
+
+		tag.remove_front_cover();
+		assert!(tag.front_cover().is_none());
+		assert_eq!(tag.picture_count(), 2);
+
+		tag.set_front_cover(picture(PictureType::CoverFront, &[6]));
+		assert_eq!(tag.pictures()[2].data(), &[6]);
+	}
+
+	#[test]
+	fn merge() {
+		let picture = |data: Vec<u8>| {
+			Picture::new_unchecked(PictureType::CoverFront, MimeType::Png, None, data)
+		};
+
+		let mut tag = Tag::new(TagType::ID3v2);
+		tag.insert_text(ItemKey::TrackTitle, String::from("Foo title"));
+		tag.insert_text(ItemKey::TrackArtist, String::new());
+		tag.push_item(TagItem::new(
+			ItemKey::Genre,
+			ItemValue::Text(String::from("Rock")),
+		));
+		tag.push_picture(picture(vec![1]));
+
+		let mut other = Tag::new(TagType::APE);
+		other.insert_text(ItemKey::TrackTitle, String::from("Bar title"));
+		other.insert_text(ItemKey::TrackArtist, String::from("Bar artist"));
+		other.insert_text(ItemKey::AlbumTitle, String::from("Bar album"));
+		other.push_item(TagItem::new(
+			ItemKey::Genre,
+			ItemValue::Text(String::from("Rock")),
+		));
+		other.push_item(TagItem::new(
+			ItemKey::Genre,
+			ItemValue::Text(String::from("Pop")),
+		));
+		other.push_picture(picture(vec![1]));
+		other.push_picture(picture(vec![2]));
+
+		let mut keep_self = tag.clone();
+		keep_self.merge(other.clone(), MergePolicy::KeepSelf);
+		assert_eq!(keep_self.title(), Some("Foo title"));
+		assert_eq!(keep_self.artist(), Some(""));
+		assert_eq!(keep_self.album(), Some("Bar album"));
+		assert_eq!(keep_self.get_strings(&ItemKey::Genre).count(), 1);
+		// The duplicate picture is skipped
+		assert_eq!(keep_self.picture_count(), 2);
+
+		let mut keep_other = tag.clone();
+		keep_other.merge(other.clone(), MergePolicy::KeepOther);
+		assert_eq!(keep_other.title(), Some("Bar title"));
+		assert_eq!(keep_other.artist(), Some("Bar artist"));
+		assert_eq!(keep_other.get_strings(&ItemKey::Genre).count(), 2);
+
+		let mut prefer_non_empty = tag.clone();
+		prefer_non_empty.merge(other.clone(), MergePolicy::PreferNonEmpty);
+		assert_eq!(prefer_non_empty.title(), Some("Foo title"));
+		assert_eq!(prefer_non_empty.artist(), Some("Bar artist"));
+
+		let mut union = tag;
+		union.merge(other.clone(), MergePolicy::Union);
+		assert_eq!(union.title(), Some("Foo title"));
+		assert_eq!(
+			union.get_strings(&ItemKey::Genre).collect::<Vec<_>>(),
+			vec!["Rock", "Pop"]
+		);
+		assert_eq!(union.get_strings(&ItemKey::TrackTitle).count(), 2);
+
+		// Pictures can't be merged into formats that don't support them
+		let mut riff_info = Tag::new(TagType::RIFFInfo);
+		riff_info.merge(other, MergePolicy::KeepSelf);
+		assert_eq!(riff_info.picture_count(), 0);
+		assert_eq!(riff_info.title(), Some("Bar title"));
+	}
+
+	#[test]
+	fn tag_builder() {
+		let tag = TagBuilder::new(TagType::ID3v1)
+			.with_title("Foo title")
+			.with_year(2022)
+			.with_track(1)
+			.build()
+			.unwrap();
+
+		assert_eq!(tag.title(), Some("Foo title"));
+		assert_eq!(tag.year(), Some(2022));
+		assert_eq!(tag.track(), Some(1));
+
+		// ID3v2 only has a recording date
+		let tag = TagBuilder::new(TagType::ID3v2)
+			.with_year(2022)
+			.build()
+			.unwrap();
+		assert_eq!(tag.get_string(&ItemKey::RecordingDate), Some("2022"));
+
+		// ID3v1 has no track total
+		assert!(TagBuilder::new(TagType::ID3v1)
+			.with_track_total(10)
+			.build()
+			.is_err());
+
+		let picture =
+			Picture::new_unchecked(PictureType::CoverFront, MimeType::Png, None, Vec::new());
+		assert!(TagBuilder::new(TagType::RIFFInfo)
+			.with_picture(picture.clone())
+			.build()
+			.is_err());
+		assert_eq!(
+			TagBuilder::new(TagType::APE)
+				.with_picture(picture)
+				.build()
+				.unwrap()
+				.picture_count(),
+			1
+		);
+	}
 }
@@ -0,0 +1,169 @@
+use crate::tag::item::ItemKey;
+use crate::tag::Tag;
+
+/// ReplayGain information
+///
+/// This is read from, and written to, the following keys:
+///
+/// * [`ItemKey::ReplayGainTrackGain`]
+/// * [`ItemKey::ReplayGainTrackPeak`]
+/// * [`ItemKey::ReplayGainAlbumGain`]
+/// * [`ItemKey::ReplayGainAlbumPeak`]
+///
+/// See [`Tag::replay_gain`] and [`Tag::set_replay_gain`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ReplayGain {
+	/// The track gain, in dB
+	pub track_gain: Option<f32>,
+	/// The track peak, where 1.0 is full scale
+	pub track_peak: Option<f32>,
+	/// The album gain, in dB
+	pub album_gain: Option<f32>,
+	/// The album peak, where 1.0 is full scale
+	pub album_peak: Option<f32>,
+}
+
+impl ReplayGain {
+	fn is_empty(&self) -> bool {
+		self.track_gain.is_none()
+			&& self.track_peak.is_none()
+			&& self.album_gain.is_none()
+			&& self.album_peak.is_none()
+	}
+}
+
+// Values may be written as "-6.5 dB", or with a comma as the decimal separator
+fn parse_value(value: &str) -> Option<f32> {
+	let mut value = value.trim();
+
+	let suffix_start = value.len().saturating_sub(2);
+	if let Some(suffix) = value.get(suffix_start..) {
+		if suffix.eq_ignore_ascii_case("dB") {
+			value = value[..suffix_start].trim_end();
+		}
+	}
+
+	value.replace(',', ".").parse().ok()
+}
+
+impl Tag {
+	/// Returns the tag's ReplayGain information
+	///
+	/// This will return `None` if none of the ReplayGain values are present, or if
+	/// none of them could be parsed.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::{ItemKey, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::VorbisComments);
+	/// tag.insert_text(ItemKey::ReplayGainTrackGain, String::from("-6,50 dB"));
+	///
+	/// let replay_gain = tag.replay_gain().unwrap();
+	/// assert_eq!(replay_gain.track_gain, Some(-6.5));
+	/// assert_eq!(replay_gain.album_gain, None);
+	/// ```
+	pub fn replay_gain(&self) -> Option<ReplayGain> {
+		let get = |key| self.get_string(&key).and_then(parse_value);
+
+		let replay_gain = ReplayGain {
+			track_gain: get(ItemKey::ReplayGainTrackGain),
+			track_peak: get(ItemKey::ReplayGainTrackPeak),
+			album_gain: get(ItemKey::ReplayGainAlbumGain),
+			album_peak: get(ItemKey::ReplayGainAlbumPeak),
+		};
+
+		(!replay_gain.is_empty()).then(|| replay_gain)
+	}
+
+	/// Sets the tag's ReplayGain information
+	///
+	/// Gains are written in the form "-6.50 dB", and peaks in the form "0.988553".
+	/// Any value that is `None` will be removed from the tag.
+	///
+	/// NOTE: Nothing will be written if the tag's [`TagType`](crate::TagType) has no mapping for the ReplayGain keys
+	pub fn set_replay_gain(&mut self, replay_gain: ReplayGain) {
+		let values = [
+			(
+				ItemKey::ReplayGainTrackGain,
+				replay_gain.track_gain.map(|gain| format!("{:.2} dB", gain)),
+			),
+			(
+				ItemKey::ReplayGainTrackPeak,
+				replay_gain.track_peak.map(|peak| format!("{:.6}", peak)),
+			),
+			(
+				ItemKey::ReplayGainAlbumGain,
+				replay_gain.album_gain.map(|gain| format!("{:.2} dB", gain)),
+			),
+			(
+				ItemKey::ReplayGainAlbumPeak,
+				replay_gain.album_peak.map(|peak| format!("{:.6}", peak)),
+			),
+		];
+
+		for (key, value) in values {
+			match value {
+				Some(value) => {
+					let _ = self.insert_text(key, value);
+				},
+				None => self.remove_key(&key),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{ItemKey, ReplayGain, Tag, TagType};
+
+	#[test]
+	fn parse_replay_gain() {
+		let mut tag = Tag::new(TagType::APE);
+		assert!(tag.replay_gain().is_none());
+
+		tag.insert_text(ItemKey::ReplayGainTrackGain, String::from(" -6.50 dB"));
+		tag.insert_text(ItemKey::ReplayGainTrackPeak, String::from("0,988553"));
+		tag.insert_text(ItemKey::ReplayGainAlbumGain, String::from("+1.2db"));
+		tag.insert_text(ItemKey::ReplayGainAlbumPeak, String::from("1€"));
+
+		assert_eq!(
+			tag.replay_gain(),
+			Some(ReplayGain {
+				track_gain: Some(-6.5),
+				track_peak: Some(0.988_553),
+				album_gain: Some(1.2),
+				album_peak: None,
+			})
+		);
+	}
+
+	#[test]
+	fn replay_gain_round_trip() {
+		let replay_gain = ReplayGain {
+			track_gain: Some(-6.5),
+			track_peak: Some(0.988_553),
+			album_gain: None,
+			album_peak: Some(1.0),
+		};
+
+		for tag_type in [TagType::ID3v2, TagType::VorbisComments, TagType::APE] {
+			let mut tag = Tag::new(tag_type);
+			tag.insert_text(ItemKey::ReplayGainAlbumGain, String::from("-3.00 dB"));
+
+			tag.set_replay_gain(replay_gain);
+
+			assert_eq!(
+				tag.get_string(&ItemKey::ReplayGainTrackGain),
+				Some("-6.50 dB")
+			);
+			assert_eq!(
+				tag.get_string(&ItemKey::ReplayGainTrackPeak),
+				Some("0.988553")
+			);
+			assert_eq!(tag.get_string(&ItemKey::ReplayGainAlbumGain), None);
+			assert_eq!(tag.replay_gain(), Some(replay_gain));
+		}
+	}
+}
@@ -1,9 +1,13 @@
 use crate::error::Result;
 use crate::file::FileType;
 use crate::macros::err;
+use crate::picture::Picture;
+use crate::tag::item::ItemKey;
 use crate::tag::{Tag, TagType};
-use crate::{ape, iff, mpeg, wavpack};
+use crate::{ape, iff, mpeg, musepack, wavpack};
 
+#[cfg(feature = "lyrics3v2")]
+use crate::id3::lyrics3v2::tag::Lyrics3v2TagRef;
 #[cfg(feature = "id3v1")]
 use crate::id3::v1::tag::Id3v1TagRef;
 #[cfg(feature = "id3v2")]
@@ -13,7 +17,7 @@ use crate::mp4::Ilst;
 #[cfg(feature = "vorbis_comments")]
 use crate::ogg::tag::{create_vorbis_comments_ref, VorbisCommentsRef};
 #[cfg(feature = "ape")]
-use ape::tag::ApeTagRef;
+use ape::tag::{ApeTagRef, ApeTagVersion};
 #[cfg(feature = "aiff_text_chunks")]
 use iff::aiff::tag::AiffTextChunksRef;
 #[cfg(feature = "riff_info_list")]
@@ -21,22 +25,30 @@ use iff::wav::tag::RIFFInfoListRef;
 
 use std::fs::File;
 use std::io::Write;
+use std::str::FromStr;
 
 #[allow(unreachable_patterns)]
-pub(crate) fn write_tag(tag: &Tag, file: &mut File, file_type: FileType) -> Result<()> {
+pub(crate) fn write_tag(tag: &Tag, file: &mut File, file_type: FileType) -> Result<bool> {
+	verify_pictures_loaded(tag.pictures())?;
+
 	match file_type {
+		FileType::AAC => crate::aac::write::write_to(file, tag),
 		FileType::AIFF => iff::aiff::write::write_to(file, tag),
 		FileType::APE => ape::write::write_to(file, tag),
 		#[cfg(feature = "vorbis_comments")]
-		FileType::FLAC | FileType::Opus | FileType::Speex | FileType::Vorbis => {
-			crate::ogg::write::write_to(file, tag, file_type)
-		},
+		FileType::FLAC
+		| FileType::OggFlac
+		| FileType::Opus
+		| FileType::Speex
+		| FileType::Vorbis => crate::ogg::write::write_to(file, tag, file_type),
 		FileType::MPEG => mpeg::write::write_to(file, tag),
 		#[cfg(feature = "mp4_ilst")]
 		FileType::MP4 => {
 			crate::mp4::ilst::write::write_to(file, &mut Into::<Ilst>::into(tag.clone()).as_ref())
 		},
+		FileType::Musepack => musepack::write::write_to(file, tag),
 		FileType::WAV => iff::wav::write::write_to(file, tag),
+		FileType::TTA => crate::tta::write::write_to(file, tag),
 		FileType::WavPack => wavpack::write::write_to(file, tag),
 		_ => err!(UnsupportedTag),
 	}
@@ -44,11 +56,14 @@ pub(crate) fn write_tag(tag: &Tag, file: &mut File, file_type: FileType) -> Resu
 
 #[allow(unreachable_patterns)]
 pub(crate) fn dump_tag<W: Write>(tag: &Tag, writer: &mut W) -> Result<()> {
+	verify_pictures_loaded(tag.pictures())?;
+
 	match tag.tag_type() {
 		#[cfg(feature = "ape")]
 		TagType::APE => ApeTagRef {
 			read_only: false,
-			items: ape::tag::tagitems_into_ape(tag.items()),
+			version: ApeTagVersion::V2,
+			items: ape::tag::tagitems_into_ape(tag),
 		}
 		.dump_to(writer),
 		#[cfg(feature = "id3v1")]
@@ -56,6 +71,7 @@ pub(crate) fn dump_tag<W: Write>(tag: &Tag, writer: &mut W) -> Result<()> {
 		#[cfg(feature = "id3v2")]
 		TagType::ID3v2 => Id3v2TagRef {
 			flags: ID3v2TagFlags::default(),
+			version: v2::ID3v2Version::V4,
 			frames: v2::tag::tag_frames(tag),
 		}
 		.dump_to(writer),
@@ -72,6 +88,11 @@ pub(crate) fn dump_tag<W: Write>(tag: &Tag, writer: &mut W) -> Result<()> {
 			}
 			.dump_to(writer)
 		},
+		#[cfg(feature = "lyrics3v2")]
+		TagType::Lyrics3v2 => Lyrics3v2TagRef {
+			fields: crate::id3::lyrics3v2::tag::tagitems_into_lyrics3v2(tag.items()),
+		}
+		.dump_to(writer),
 		#[cfg(feature = "riff_info_list")]
 		TagType::RIFFInfo => RIFFInfoListRef {
 			items: iff::wav::tag::tagitems_into_riff(tag.items()),
@@ -90,10 +111,87 @@ pub(crate) fn dump_tag<W: Write>(tag: &Tag, writer: &mut W) -> Result<()> {
 			}
 		}
 		.dump_to(writer),
+		#[cfg(feature = "matroska_tags")]
+		TagType::Matroska => err!(UnsupportedTag),
 		_ => Ok(()),
 	}
 }
 
+// Placeholder pictures have no data, writing them would replace the original pictures with empty ones
+pub(crate) fn verify_pictures_loaded<'a>(
+	pictures: impl IntoIterator<Item = &'a Picture>,
+) -> Result<()> {
+	if pictures.into_iter().any(|picture| !picture.is_loaded()) {
+		err!(UnloadedPicture);
+	}
+
+	Ok(())
+}
+
+// BPM values are stored as strings in most formats, and may be floating point
+pub(crate) fn parse_bpm(value: &str) -> Option<u16> {
+	let bpm = value.trim().parse::<f64>().ok()?.round();
+
+	(bpm >= 0.0 && bpm <= f64::from(u16::MAX)).then(|| bpm as u16)
+}
+
+// Number pairs are stored as "current/total", where either side can be missing, or 0 if there is a total
+pub(crate) fn split_num_pair(value: &str) -> (Option<u32>, Option<u32>) {
+	// Only the first value is used if there are multiple
+	let value = value.split('\0').next().unwrap_or_default();
+
+	let total = value
+		.split_once('/')
+		.and_then(|(_, total)| total.trim().parse::<u32>().ok());
+
+	let nonzero = |num: &u32| *num != 0;
+	(
+		parse_leading_num(value).filter(nonzero),
+		total.filter(nonzero),
+	)
+}
+
+// Parses the first number of a (possibly "current/total") value
+pub(crate) fn parse_leading_num<T: FromStr>(value: &str) -> Option<T> {
+	let value = value.split('\0').next().unwrap_or_default();
+	let current = value.split_once('/').map_or(value, |(current, _)| current);
+
+	current.trim().parse().ok()
+}
+
+pub(crate) fn num_pair(current: Option<u32>, total: Option<u32>) -> Option<String> {
+	match (current, total) {
+		(current, Some(total)) => Some(format!("{}/{}", current.unwrap_or(0), total)),
+		(Some(current), None) => Some(current.to_string()),
+		(None, None) => None,
+	}
+}
+
+// The number pair items of a `Tag`, for formats that store them together
+pub(crate) fn join_num_pair(
+	tag: &Tag,
+	current_key: &ItemKey,
+	total_key: &ItemKey,
+) -> Option<String> {
+	match (tag.get_string(current_key), tag.get_string(total_key)) {
+		(current, Some(total)) => Some(format!("{}/{}", current.unwrap_or("0"), total)),
+		(Some(current), None) => Some(String::from(current)),
+		(None, None) => None,
+	}
+}
+
+pub(crate) fn is_num_pair_key(key: &ItemKey) -> bool {
+	matches!(
+		key,
+		ItemKey::TrackNumber
+			| ItemKey::TrackTotal
+			| ItemKey::DiscNumber
+			| ItemKey::DiscTotal
+			| ItemKey::MovementNumber
+			| ItemKey::MovementCount
+	)
+}
+
 #[cfg(test)]
 // Used for tag conversion tests
 pub(crate) mod test_utils {
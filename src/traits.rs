@@ -112,9 +112,14 @@ macro_rules! accessor_trait {
 accessor_trait! {
 	[artist]<&str, String>, [title]<&str, String>,
 	[album]<&str, String>,  [genre]<&str, String>,
+	[album artist]<&str, String>, [composer]<&str, String>,
 	[track]<u32>,			[track total]<u32>,
 	[disk]<u32>,		  	[disk total]<u32>,
 	[year]<u32>,			[comment]<&str, String>,
+	[bpm]<u16>,				[initial key]<&str, String>,
+	[encoder]<&str, String>,
+	[title sort]<&str, String>,		[artist sort]<&str, String>,
+	[album sort]<&str, String>,		[album artist sort]<&str, String>,
 }
 
 use crate::tag::Tag;
@@ -154,20 +159,28 @@ pub trait TagExt: Accessor + Into<Tag> + Sized {
 
 	/// Save the tag to a path
 	///
+	/// See [`TagExt::save_to`]
+	///
 	/// # Errors
 	///
 	/// * Path doesn't exist
 	/// * Path is not writable
 	/// * See [`TagExt::save_to`]
-	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err>;
+	fn save_to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<bool, Self::Err>;
 
 	/// Save the tag to a [`File`]
 	///
+	/// This returns `true` if the file was modified. If the new tag is identical to the
+	/// existing one, nothing is written.
+	///
+	/// NOTE: Only APE, ID3v1, ID3v2, MP4 `ilst`, and Vorbis Comments (FLAC and Ogg) tags are
+	/// currently compared, any other tag will always be written.
+	///
 	/// # Errors
 	///
 	/// * The file format could not be determined
 	/// * Attempting to write a tag to a format that does not support it.
-	fn save_to(&self, file: &mut File) -> std::result::Result<(), Self::Err>;
+	fn save_to(&self, file: &mut File) -> std::result::Result<bool, Self::Err>;
 
 	#[allow(clippy::missing_errors_doc)]
 	/// Dump the tag to a writer
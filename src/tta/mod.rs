@@ -0,0 +1,44 @@
+//! TrueAudio specific items
+//!
+//! ## File notes
+//!
+//! Only the `TTA1` stream format is supported. Older versions will be rejected.
+mod properties;
+pub(crate) mod read;
+pub(crate) mod write;
+
+pub use properties::TtaProperties;
+
+#[cfg(feature = "ape")]
+use crate::ape::tag::ApeTag;
+#[cfg(feature = "id3v1")]
+use crate::id3::v1::tag::ID3v1Tag;
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::tag::ID3v2Tag;
+
+use std::ops::Range;
+
+use lofty_attr::LoftyFile;
+
+/// A TrueAudio file
+#[derive(LoftyFile, Default)]
+#[lofty(read_fn = "read::read_from")]
+#[lofty(file_type = "TTA")]
+pub struct TtaFile {
+	/// An ID3v2 tag
+	#[cfg(feature = "id3v2")]
+	#[lofty(tag_type = "ID3v2")]
+	pub(crate) id3v2_tag: Option<ID3v2Tag>,
+	/// An ID3v1 tag
+	#[cfg(feature = "id3v1")]
+	#[lofty(tag_type = "ID3v1")]
+	pub(crate) id3v1_tag: Option<ID3v1Tag>,
+	/// An APEv1/v2 tag
+	#[cfg(feature = "ape")]
+	#[lofty(tag_type = "APE")]
+	pub(crate) ape_tag: Option<ApeTag>,
+	/// The file's audio properties
+	pub(crate) properties: TtaProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
+}
@@ -0,0 +1,111 @@
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+use crate::properties::FileProperties;
+
+use std::time::Duration;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+/// A TrueAudio file's audio properties
+pub struct TtaProperties {
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) bit_depth: u8,
+	pub(crate) channels: u8,
+	pub(crate) total_samples: u32,
+}
+
+impl From<TtaProperties> for FileProperties {
+	fn from(input: TtaProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: Some(input.bit_depth),
+			channels: Some(input.channels),
+		}
+	}
+}
+
+impl TtaProperties {
+	/// Duration
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Bits per sample
+	pub fn bit_depth(&self) -> u8 {
+		self.bit_depth
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+
+	/// Total number of samples (per channel)
+	pub fn total_samples(&self) -> u32 {
+		self.total_samples
+	}
+}
+
+// The `TTA1` header, following the signature:
+//
+// Audio format (2)
+// Channels (2)
+// Bits per sample (2)
+// Sample rate (4)
+// Total samples (4)
+pub(super) fn read_properties(
+	mut header: &[u8],
+	stream_length: u64,
+	file_length: u64,
+) -> Result<TtaProperties> {
+	let _audio_format = header.read_u16::<LittleEndian>()?;
+	let channels = header.read_u16::<LittleEndian>()? as u8;
+	let bit_depth = header.read_u16::<LittleEndian>()? as u8;
+	let sample_rate = header.read_u32::<LittleEndian>()?;
+	let total_samples = header.read_u32::<LittleEndian>()?;
+
+	if sample_rate == 0 {
+		return Err(FileDecodingError::new(FileType::TTA, "File has a sample rate of 0").into());
+	}
+
+	let length = u64::from(total_samples) * 1000 / u64::from(sample_rate);
+
+	let mut properties = TtaProperties {
+		duration: Duration::from_millis(length),
+		sample_rate,
+		bit_depth,
+		channels,
+		total_samples,
+		..TtaProperties::default()
+	};
+
+	if length > 0 {
+		properties.overall_bitrate = ((file_length * 8) / length) as u32;
+		properties.audio_bitrate = ((stream_length * 8) / length) as u32;
+	}
+
+	Ok(properties)
+}
@@ -0,0 +1,165 @@
+use super::properties::TtaProperties;
+use super::TtaFile;
+use crate::ape::constants::APE_PREAMBLE;
+use crate::ape::header::read_ape_header;
+#[cfg(feature = "ape")]
+use crate::ape::tag::read::read_ape_tag;
+use crate::error::{FileDecodingError, Result};
+use crate::file::FileType;
+#[cfg(feature = "id3v2")]
+use crate::id3::v2::read::parse_id3v2_with_options;
+use crate::id3::{find_id3v1, find_id3v2, find_lyrics3v2, ID3FindResults};
+use crate::probe::ParseOptions;
+
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+// The header, including the signature and CRC
+const HEADER_LEN: u64 = 22;
+
+pub(super) fn read_from<R>(reader: &mut R, read_properties: bool) -> Result<TtaFile>
+where
+	R: Read + Seek,
+{
+	read_from_with_options(reader, read_properties, &mut ParseOptions::new())
+}
+
+#[allow(unused_variables)]
+pub(crate) fn read_from_with_options<R>(
+	reader: &mut R,
+	read_properties: bool,
+	options: &mut ParseOptions<'_>,
+) -> Result<TtaFile>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+	let file_length = reader.seek(SeekFrom::End(0))? - start;
+	reader.seek(SeekFrom::Start(start))?;
+
+	let mut file = TtaFile::default();
+
+	#[allow(unused_variables)]
+	if let ID3FindResults(Some(header), Some(content)) = find_id3v2(reader, true)? {
+		#[cfg(feature = "id3v2")]
+		{
+			let reader = &mut &*content;
+
+			let id3v2 = parse_id3v2_with_options(reader, header, options)?;
+			file.id3v2_tag = Some(id3v2);
+		}
+	}
+
+	let stream_start = reader.stream_position()?;
+
+	let mut header = [0; 18];
+	reader.read_exact(&mut header)?;
+
+	if &header[..4] != b"TTA1" {
+		return Err(
+			FileDecodingError::new(FileType::TTA, "File missing \"TTA1\" signature").into(),
+		);
+	}
+
+	let crc = reader.read_u32::<LittleEndian>()?;
+	if crc32(&header) != crc {
+		return Err(FileDecodingError::new(FileType::TTA, "TTA header CRC mismatch").into());
+	}
+
+	#[allow(unused_variables)]
+	let ID3FindResults(id3v1_header, id3v1) = find_id3v1(reader, true)?;
+
+	if id3v1_header.is_some() {
+		#[cfg(feature = "id3v1")]
+		{
+			file.id3v1_tag = id3v1;
+		}
+	}
+
+	// Next, check for a Lyrics3v2 tag, and skip over it, as it's no use to us
+	find_lyrics3v2(reader)?;
+
+	// Next, search for an APE tag footer
+	//
+	// Starts with ['A', 'P', 'E', 'T', 'A', 'G', 'E', 'X']
+	// Exactly 32 bytes long
+	// Strongly recommended to be at the end of the file
+	let mut stream_end = reader.stream_position()?;
+
+	if stream_end >= stream_start + HEADER_LEN + 32 {
+		reader.seek(SeekFrom::Current(-32))?;
+
+		let mut ape_preamble = [0; 8];
+		reader.read_exact(&mut ape_preamble)?;
+
+		if &ape_preamble == APE_PREAMBLE {
+			let ape_header = read_ape_header(reader, true)?;
+			stream_end = stream_end.saturating_sub(u64::from(ape_header.size));
+
+			#[cfg(feature = "ape")]
+			{
+				let ape = read_ape_tag(reader, ape_header)?;
+				file.ape_tag = Some(ape);
+			}
+		}
+	}
+
+	file.audio_data_range = stream_start.min(stream_end)..stream_end;
+
+	file.properties = if read_properties {
+		super::properties::read_properties(
+			&header[4..],
+			stream_end.saturating_sub(stream_start),
+			file_length,
+		)?
+	} else {
+		TtaProperties::default()
+	};
+
+	Ok(file)
+}
+
+// CRC-32 (IEEE 802.3)
+fn crc32(content: &[u8]) -> u32 {
+	let mut crc = 0xFFFF_FFFF_u32;
+
+	for byte in content {
+		crc ^= u32::from(*byte);
+
+		for _ in 0..8 {
+			crc = if crc & 1 == 1 {
+				(crc >> 1) ^ 0xEDB8_8320
+			} else {
+				crc >> 1
+			};
+		}
+	}
+
+	!crc
+}
+
+#[cfg(test)]
+mod tests {
+	use super::TtaFile;
+	use crate::error::ErrorKind;
+	use crate::AudioFile;
+
+	#[test]
+	fn crc32() {
+		assert_eq!(super::crc32(b"123456789"), 0xCBF4_3926);
+	}
+
+	#[test]
+	fn crc_mismatch() {
+		let mut content = std::fs::read("tests/files/assets/minimal/full_test.tta").unwrap();
+
+		// The first byte of the sample rate, right after the ID3v2 tag and the start of the header
+		content[31 + 10] ^= 0xFF;
+
+		match TtaFile::read_from(&mut std::io::Cursor::new(content), false) {
+			Err(err) => assert!(matches!(err.kind(), ErrorKind::FileDecoding(_))),
+			Ok(_) => panic!("Read a TTA file with a bad header CRC"),
+		}
+	}
+}
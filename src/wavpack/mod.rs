@@ -8,6 +8,8 @@ use crate::ape::tag::ApeTag;
 #[cfg(feature = "id3v1")]
 use crate::id3::v1::tag::ID3v1Tag;
 
+use std::ops::Range;
+
 use lofty_attr::LoftyFile;
 
 // Exports
@@ -27,4 +29,6 @@ pub struct WavPackFile {
 	pub(crate) ape_tag: Option<ApeTag>,
 	/// The file's audio properties
 	pub(crate) properties: WavPackProperties,
+	/// The range of the audio data in the stream
+	pub(crate) audio_data_range: Range<u64>,
 }
@@ -13,9 +13,9 @@ use byteorder::{LittleEndian, ReadBytesExt};
 /// A WavPack file's audio properties
 pub struct WavPackProperties {
 	pub(crate) version: u16,
-	pub(crate) duration: Duration,
-	pub(crate) overall_bitrate: u32,
-	pub(crate) audio_bitrate: u32,
+	pub(crate) duration: Option<Duration>,
+	pub(crate) overall_bitrate: Option<u32>,
+	pub(crate) audio_bitrate: Option<u32>,
 	pub(crate) sample_rate: u32,
 	pub(crate) channels: u8,
 	pub(crate) bit_depth: u8,
@@ -25,9 +25,9 @@ pub struct WavPackProperties {
 impl From<WavPackProperties> for FileProperties {
 	fn from(input: WavPackProperties) -> Self {
 		Self {
-			duration: input.duration,
-			overall_bitrate: Some(input.overall_bitrate),
-			audio_bitrate: Some(input.audio_bitrate),
+			duration: input.duration.unwrap_or_default(),
+			overall_bitrate: input.overall_bitrate,
+			audio_bitrate: input.audio_bitrate,
 			sample_rate: Some(input.sample_rate),
 			bit_depth: Some(input.bit_depth),
 			channels: Some(input.channels),
@@ -37,17 +37,23 @@ impl From<WavPackProperties> for FileProperties {
 
 impl WavPackProperties {
 	/// Duration
-	pub fn duration(&self) -> Duration {
+	///
+	/// This will be `None` if the file doesn't store its length.
+	pub fn duration(&self) -> Option<Duration> {
 		self.duration
 	}
 
 	/// Overall bitrate (kbps)
-	pub fn overall_bitrate(&self) -> u32 {
+	///
+	/// This will be `None` if the duration is unknown, see [`WavPackProperties::duration`].
+	pub fn overall_bitrate(&self) -> Option<u32> {
 		self.overall_bitrate
 	}
 
 	/// Audio bitrate (kbps)
-	pub fn audio_bitrate(&self) -> u32 {
+	///
+	/// This will be `None` if the duration is unknown, see [`WavPackProperties::duration`].
+	pub fn audio_bitrate(&self) -> Option<u32> {
 		self.audio_bitrate
 	}
 
@@ -70,6 +76,13 @@ impl WavPackProperties {
 	pub fn is_lossless(&self) -> bool {
 		self.lossless
 	}
+
+	/// Whether the file was encoded in hybrid (lossy) mode
+	///
+	/// NOTE: A hybrid file can be restored to lossless if its correction file (`.wvc`) is available.
+	pub fn is_hybrid(&self) -> bool {
+		!self.lossless
+	}
 }
 
 // Thanks MultimediaWiki :)
@@ -94,6 +107,9 @@ const ID_MULTICHANNEL: u8 = 0x0D;
 const ID_NON_STANDARD_SAMPLE_RATE: u8 = 0x27;
 const ID_DSD: u8 = 0xE;
 
+// Used in place of the total sample count when the length is unknown
+const UNKNOWN_TOTAL_SAMPLES: u32 = u32::MAX;
+
 const MIN_STREAM_VERSION: u16 = 0x402;
 const MAX_STREAM_VERSION: u16 = 0x410;
 
@@ -172,13 +188,17 @@ where
 		offset += u64::from(block_header.block_size + 8);
 	}
 
-	if total_samples > 0 && properties.sample_rate > 0 {
-		let length = u64::from(total_samples * 1000 / properties.sample_rate);
-		properties.duration = Duration::from_millis(length);
-		properties.audio_bitrate = crate::div_ceil(stream_length * 8, length) as u32;
+	// If the length is unknown, the duration and bitrates are left unset
+	if total_samples > 0 && total_samples != UNKNOWN_TOTAL_SAMPLES && properties.sample_rate > 0 {
+		let length = u64::from(total_samples) * 1000 / u64::from(properties.sample_rate);
+		properties.duration = Some(Duration::from_millis(length));
 
-		let file_length = reader.seek(SeekFrom::End(0))?;
-		properties.overall_bitrate = crate::div_ceil(file_length * 8, length) as u32;
+		if length > 0 {
+			properties.audio_bitrate = Some(crate::div_ceil(stream_length * 8, length) as u32);
+
+			let file_length = reader.seek(SeekFrom::End(0))?;
+			properties.overall_bitrate = Some(crate::div_ceil(file_length * 8, length) as u32);
+		}
 	}
 
 	Ok(properties)
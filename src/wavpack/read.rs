@@ -43,6 +43,7 @@ where
 	// Starts with ['A', 'P', 'E', 'T', 'A', 'G', 'E', 'X']
 	// Exactly 32 bytes long
 	// Strongly recommended to be at the end of the file
+	let mut audio_end = reader.stream_position()?;
 	reader.seek(SeekFrom::Current(-32))?;
 
 	let mut ape_preamble = [0; 8];
@@ -51,6 +52,7 @@ where
 	if &ape_preamble == APE_PREAMBLE {
 		let ape_header = read_ape_header(reader, true)?;
 		stream_length -= u64::from(ape_header.size);
+		audio_end = audio_end.saturating_sub(u64::from(ape_header.size));
 
 		#[cfg(feature = "ape")]
 		{
@@ -72,5 +74,6 @@ where
 		} else {
 			WavPackProperties::default()
 		},
+		audio_data_range: current_pos.min(audio_end)..audio_end,
 	})
 }
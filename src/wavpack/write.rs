@@ -10,12 +10,13 @@ use crate::tag::{Tag, TagType};
 use std::fs::File;
 
 #[allow(unused_variables)]
-pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<()> {
+pub(crate) fn write_to(data: &mut File, tag: &Tag) -> Result<bool> {
 	match tag.tag_type() {
 		#[cfg(feature = "ape")]
 		TagType::APE => ape::tag::ApeTagRef {
 			read_only: false,
-			items: ape::tag::tagitems_into_ape(tag.items()),
+			version: ape::tag::ApeTagVersion::V2,
+			items: ape::tag::tagitems_into_ape(tag),
 		}
 		.write_to(data),
 		#[cfg(feature = "id3v1")]
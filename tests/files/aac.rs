@@ -0,0 +1,85 @@
+use crate::{set_artist, temp_file, verify_artist};
+use lofty::aac::AacFile;
+use lofty::{AudioFile, FileType, ItemKey, ItemValue, TagExt, TagItem, TagType};
+use std::io::{Seek, Write};
+
+#[test]
+fn read() {
+	// Here we have an AAC file with an ID3v2, ID3v1, and an APEv2 tag
+	let file = lofty::read_from_path("tests/files/assets/minimal/full_test.aac", false).unwrap();
+
+	assert_eq!(file.file_type(), FileType::AAC);
+
+	// Verify the ID3v2 tag first
+	crate::verify_artist!(file, primary_tag, "Foo artist", 1);
+
+	// Now verify ID3v1
+	crate::verify_artist!(file, tag, TagType::ID3v1, "Bar artist", 1);
+
+	// Finally, verify APEv2
+	crate::verify_artist!(file, tag, TagType::APE, "Baz artist", 1);
+}
+
+#[test]
+fn read_audio_data_range() {
+	let path = "tests/files/assets/minimal/full_test.aac";
+
+	// The audio data is between the leading ID3v2 tag, and the trailing APEv2 and ID3v1 tags
+	let aac_file = AacFile::read_from(&mut std::fs::File::open(path).unwrap(), false).unwrap();
+	assert_eq!(aac_file.audio_data_range(), 31..735);
+}
+
+#[test]
+fn read_short_stream() {
+	// A lone ADTS frame, too short to hold any trailing tags
+	let content = std::fs::read("tests/files/assets/minimal/full_test.aac").unwrap();
+	let mut reader = std::io::Cursor::new(&content[31..41]);
+
+	let aac_file = AacFile::read_from(&mut reader, false).unwrap();
+	assert!(aac_file.id3v1().is_none());
+	assert!(aac_file.ape().is_none());
+	assert_eq!(aac_file.audio_data_range(), 0..10);
+}
+
+#[test]
+fn write() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.aac");
+
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+
+	assert_eq!(tagged_file.file_type(), FileType::AAC);
+
+	// ID3v2
+	crate::set_artist!(tagged_file, primary_tag_mut, "Foo artist", 1 => file, "Bar artist");
+
+	// ID3v1
+	crate::set_artist!(tagged_file, tag_mut, TagType::ID3v1, "Bar artist", 1 => file, "Baz artist");
+
+	// APEv2
+	crate::set_artist!(tagged_file, tag_mut, TagType::APE, "Baz artist", 1 => file, "Qux artist");
+
+	// Now reread the file
+	file.rewind().unwrap();
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+
+	crate::set_artist!(tagged_file, primary_tag_mut, "Bar artist", 1 => file, "Foo artist");
+
+	crate::set_artist!(tagged_file, tag_mut, TagType::ID3v1, "Baz artist", 1 => file, "Bar artist");
+
+	crate::set_artist!(tagged_file, tag_mut, TagType::APE, "Qux artist", 1 => file, "Baz artist");
+}
+
+#[test]
+fn remove_id3v2() {
+	crate::remove_tag!("tests/files/assets/minimal/full_test.aac", TagType::ID3v2);
+}
+
+#[test]
+fn remove_id3v1() {
+	crate::remove_tag!("tests/files/assets/minimal/full_test.aac", TagType::ID3v1);
+}
+
+#[test]
+fn remove_ape() {
+	crate::remove_tag!("tests/files/assets/minimal/full_test.aac", TagType::APE);
+}
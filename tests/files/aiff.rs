@@ -16,6 +16,14 @@ fn read() {
 	crate::verify_artist!(file, tag, TagType::AIFFText, "Bar artist", 1);
 }
 
+#[test]
+fn read_audio_data_range() {
+	let file = lofty::read_from_path("tests/files/assets/minimal/full_test.aiff", false).unwrap();
+
+	// The range should start after the SSND offset and block size
+	assert_eq!(file.audio_data_range(), Some(72..274_256));
+}
+
 #[test]
 fn write() {
 	let mut file = temp_file!("tests/files/assets/minimal/full_test.aiff");
@@ -1,6 +1,6 @@
 use crate::{set_artist, temp_file, verify_artist};
 use lofty::{FileType, ItemKey, ItemValue, TagExt, TagItem, TagType};
-use std::io::{Seek, Write};
+use std::io::{Read, Seek, Write};
 
 #[test]
 fn read() {
@@ -57,3 +57,145 @@ fn remove_id3v1() {
 fn remove_id3v2() {
 	crate::remove_tag!("tests/files/assets/minimal/full_test.ape", TagType::ID3v2);
 }
+
+#[test]
+fn write_in_place() {
+	let original = std::fs::read("tests/files/assets/minimal/full_test.ape").unwrap();
+
+	// The APE tag has a header, so the first preamble marks the start of the tag
+	let ape_start = original.windows(8).position(|w| w == b"APETAGEX").unwrap();
+
+	for artist in ["Foo", "Foo artist with a much longer name"] {
+		let mut file = temp_file!("tests/files/assets/minimal/full_test.ape");
+		let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+
+		let tag = tagged_file.primary_tag_mut().unwrap();
+		tag.insert_item_unchecked(TagItem::new(
+			ItemKey::TrackArtist,
+			ItemValue::Text(String::from(artist)),
+		));
+
+		file.rewind().unwrap();
+		tag.save_to(&mut file).unwrap();
+
+		file.rewind().unwrap();
+		let mut written = Vec::new();
+		file.read_to_end(&mut written).unwrap();
+
+		// Everything preceding the tag must be untouched
+		assert_eq!(written[..ape_start], original[..ape_start]);
+
+		// The ID3v1 tag following the APE tag must also be preserved
+		assert_eq!(
+			written[written.len() - 128..],
+			original[original.len() - 128..]
+		);
+
+		file.rewind().unwrap();
+		let tagged_file = lofty::read_from(&mut file, false).unwrap();
+		let tag = tagged_file.primary_tag().unwrap();
+		assert_eq!(tag.get_string(&ItemKey::TrackArtist), Some(artist));
+	}
+}
+
+#[test]
+fn write_read_only_item() {
+	use lofty::ape::{ApeFile, ApeItem, ApeTag};
+	use lofty::AudioFile;
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.ape");
+
+	let cover_art = |value: &[u8]| {
+		let mut item = ApeItem::new(
+			String::from("Cover Art (Front)"),
+			ItemValue::Binary(value.to_vec()),
+		)
+		.unwrap();
+		item.read_only = true;
+		item
+	};
+
+	// Mark the existing cover art as read only
+	let mut tag = ApeTag::default();
+	tag.insert(cover_art(b"Original"));
+	tag.save_to(&mut file).unwrap();
+
+	// The incoming tag has its own read only cover art, which can't replace the existing one
+	let mut tag = ApeTag::default();
+	tag.insert(cover_art(b"Replacement"));
+	tag.insert(
+		ApeItem::new(
+			String::from("Title"),
+			ItemValue::Text(String::from("Foo title")),
+		)
+		.unwrap(),
+	);
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let ape_file = ApeFile::read_from(&mut file, false).unwrap();
+	let tag = ape_file.ape().unwrap();
+
+	let covers = tag
+		.items()
+		.iter()
+		.filter(|item| item.key().eq_ignore_ascii_case("Cover Art (Front)"))
+		.collect::<Vec<_>>();
+
+	assert_eq!(covers.len(), 1);
+	assert!(covers[0].read_only);
+	assert_eq!(covers[0].value(), &ItemValue::Binary(b"Original".to_vec()));
+
+	assert_eq!(
+		tag.get_key("Title").map(ApeItem::value),
+		Some(&ItemValue::Text(String::from("Foo title")))
+	);
+}
+
+#[test]
+fn write_read_only_item_invalid_key() {
+	use lofty::ape::{ApeFile, ApeItem, ApeTag};
+	use lofty::AudioFile;
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.ape");
+
+	let mut item = ApeItem::new(String::from("Taf"), ItemValue::Text(String::from("Foo"))).unwrap();
+	item.read_only = true;
+
+	let mut tag = ApeTag::default();
+	tag.insert(item);
+	tag.save_to(&mut file).unwrap();
+
+	// Turn the key into the reserved "Tag"
+	let mut content = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut content).unwrap();
+
+	let key_pos = content.windows(4).position(|w| w == b"Taf\0").unwrap();
+	content[key_pos + 2] = b'g';
+
+	file.rewind().unwrap();
+	file.write_all(&content).unwrap();
+
+	// The read only item can't be written back, so it's dropped rather than failing the write
+	let mut tag = ApeTag::default();
+	tag.insert(
+		ApeItem::new(
+			String::from("Title"),
+			ItemValue::Text(String::from("Foo title")),
+		)
+		.unwrap(),
+	);
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let ape_file = ApeFile::read_from(&mut file, false).unwrap();
+	let tag = ape_file.ape().unwrap();
+
+	assert!(tag.get_key("Tag").is_none());
+	assert!(tag.get_key("Title").is_some());
+}
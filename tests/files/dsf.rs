@@ -0,0 +1,28 @@
+use crate::verify_artist;
+use lofty::dsf::DsfFile;
+use lofty::{AudioFile, FileType, ItemKey, ItemValue, TagItem, TagType};
+use std::io::Cursor;
+
+#[test]
+fn read() {
+	// Here we have a DSF file with an ID3v2 tag
+	let file = lofty::read_from_path("tests/files/assets/minimal/full_test.dsf", false).unwrap();
+
+	assert_eq!(file.file_type(), FileType::DSF);
+
+	crate::verify_artist!(file, primary_tag, "Foo artist", 1);
+}
+
+#[test]
+fn read_no_metadata_pointer() {
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.dsf").unwrap();
+
+	// Zero out the metadata pointer in the "DSD " chunk
+	content[20..28].copy_from_slice(&[0; 8]);
+
+	let file = DsfFile::read_from(&mut Cursor::new(content), true).unwrap();
+
+	assert!(!file.contains_tag());
+	assert!(!file.contains_tag_type(TagType::ID3v2));
+	assert_eq!(file.properties().channels(), 2);
+}
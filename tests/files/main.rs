@@ -1,8 +1,13 @@
+mod aac;
 mod aiff;
 mod ape;
+mod dsf;
+mod mka;
 mod mp4;
+mod mpc;
 mod mpeg;
 mod ogg;
+mod tta;
 pub(crate) mod util;
 mod wav;
 mod wavpack;
@@ -0,0 +1,53 @@
+use lofty::mka::{MkaFile, TargetType};
+use lofty::{Accessor, AudioFile, FileType, ItemKey, TagExt, TagType};
+use std::io::{Seek, Write};
+
+#[test]
+fn read() {
+	// Here we have a Matroska file with album and track level tags
+	let file = lofty::read_from_path("tests/files/assets/minimal/full_test.mka", false).unwrap();
+
+	assert_eq!(file.file_type(), FileType::Mka);
+
+	let tag = file.primary_tag().unwrap();
+	assert_eq!(tag.tag_type(), TagType::Matroska);
+
+	assert_eq!(tag.get_string(&ItemKey::AlbumTitle), Some("Foo album"));
+	assert_eq!(tag.get_string(&ItemKey::AlbumArtist), Some("Bar artist"));
+	assert_eq!(tag.get_string(&ItemKey::TrackTitle), Some("Foo title"));
+	assert_eq!(tag.get_string(&ItemKey::TrackArtist), Some("Foo artist"));
+	assert_eq!(tag.get_string(&ItemKey::Genre), Some("Classical"));
+
+	assert!(file.audio_data_range().is_none());
+}
+
+#[test]
+fn read_concrete() {
+	let mka_file = MkaFile::read_from(
+		&mut std::fs::File::open("tests/files/assets/minimal/full_test.mka").unwrap(),
+		false,
+	)
+	.unwrap();
+
+	let tag = mka_file.matroska().unwrap();
+	assert_eq!(tag.simple_tags().len(), 5);
+
+	assert_eq!(tag.title(), Some("Foo title"));
+	assert_eq!(tag.album(), Some("Foo album"));
+	assert_eq!(tag.get(TargetType::Track, "genre"), Some("Classical"));
+	assert_eq!(tag.simple_tags()[0].language(), "und");
+}
+
+#[test]
+fn write() {
+	let mut file = crate::temp_file!("tests/files/assets/minimal/full_test.mka");
+
+	let tagged_file = lofty::read_from(&mut file, false).unwrap();
+
+	// Matroska tags are read only
+	assert!(tagged_file
+		.primary_tag()
+		.unwrap()
+		.save_to(&mut file)
+		.is_err());
+}
@@ -1,5 +1,8 @@
-use crate::{set_artist, temp_file, verify_artist};
-use lofty::{FileType, ItemKey, ItemValue, TagExt, TagItem, TagType};
+use crate::{save_unchanged, set_artist, temp_file, verify_artist};
+use lofty::mp4::{Atom, AtomData, AtomIdent, Mp4File};
+use lofty::{
+	Accessor, AudioFile, FileType, GaplessInfo, ItemKey, ItemValue, TagExt, TagItem, TagType,
+};
 use std::io::{Seek, Write};
 
 #[test]
@@ -14,6 +17,14 @@ fn read() {
 	crate::verify_artist!(file, primary_tag, "Foo artist", 1);
 }
 
+#[test]
+fn read_audio_data_range() {
+	// The content of the `mdat` atom
+	let file =
+		lofty::read_from_path("tests/files/assets/minimal/m4a_codec_aac.m4a", false).unwrap();
+	assert_eq!(file.audio_data_range(), Some(44..22533));
+}
+
 #[test]
 fn write() {
 	let mut file = temp_file!("tests/files/assets/minimal/m4a_codec_aac.m4a");
@@ -33,6 +44,14 @@ fn write() {
 	crate::set_artist!(tagged_file, tag_mut, TagType::MP4ilst, "Bar artist", 1 => file, "Foo artist");
 }
 
+#[test]
+fn save_unchanged() {
+	save_unchanged!(
+		"tests/files/assets/minimal/m4a_codec_aac.m4a",
+		TagType::MP4ilst
+	);
+}
+
 #[test]
 fn remove() {
 	crate::remove_tag!(
@@ -40,3 +59,48 @@ fn remove() {
 		TagType::MP4ilst
 	);
 }
+
+#[test]
+fn preserve_itunes_gapless_info() {
+	let mut file = temp_file!("tests/files/assets/minimal/m4a_codec_aac.m4a");
+
+	let mut mp4_file = Mp4File::read_from(&mut file, false).unwrap();
+	let ilst = mp4_file.ilst_mut().unwrap();
+	ilst.insert_atom(Atom::new(
+		AtomIdent::Freeform {
+			mean: String::from("com.apple.iTunes"),
+			name: String::from("iTunSMPB"),
+		},
+		AtomData::UTF8(String::from(
+			" 00000000 00000840 000001C0 0000000000A3C800 00000000 00000000",
+		)),
+	));
+
+	file.rewind().unwrap();
+	ilst.save_to(&mut file).unwrap();
+
+	// Edit the tag generically
+	file.rewind().unwrap();
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+	tagged_file
+		.primary_tag_mut()
+		.unwrap()
+		.set_title(String::from("Foo title"));
+
+	file.rewind().unwrap();
+	tagged_file.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let mp4_file = Mp4File::read_from(&mut file, false).unwrap();
+	let ilst = mp4_file.ilst().unwrap();
+
+	assert_eq!(ilst.title().as_deref(), Some("Foo title"));
+	assert_eq!(
+		ilst.gapless_info(),
+		Some(GaplessInfo {
+			encoder_delay: 2112,
+			padding: 448,
+			original_sample_count: 10_733_568,
+		})
+	);
+}
@@ -1,6 +1,15 @@
 use crate::{set_artist, temp_file, verify_artist};
-use lofty::{Accessor, FileType, ItemKey, ItemValue, TagExt, TagItem, TagType};
-use std::io::{Seek, Write};
+use lofty::ape::{ApeFile, ApeTag};
+use lofty::error::ErrorKind;
+use lofty::id3::v2::{
+	EncodedTextFrame, Frame, FrameFlags, FrameValue, ID3v2Tag, Language, LanguageFrame,
+	TextEncoding,
+};
+use lofty::mpeg::MPEGFile;
+use lofty::{
+	Accessor, AudioFile, FileType, GaplessInfo, ItemKey, ItemValue, Tag, TagExt, TagItem, TagType,
+};
+use std::io::{Read, Seek, Write};
 
 #[test]
 fn read() {
@@ -19,6 +28,47 @@ fn read() {
 	crate::verify_artist!(file, tag, TagType::APE, "Baz artist", 1);
 }
 
+#[test]
+fn encoder() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	// Without a `TSSE` frame, the LAME header is used
+	let mpeg_file = MPEGFile::read_from(&mut file, true).unwrap();
+	assert_eq!(mpeg_file.encoder(), Some("Lavc58.91"));
+
+	let mut id3v2 = mpeg_file.id3v2().unwrap().clone();
+	id3v2.set_encoder(String::from("LAME 3.100"));
+	assert!(id3v2.get("TSSE").is_some());
+
+	file.rewind().unwrap();
+	id3v2.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MPEGFile::read_from(&mut file, true).unwrap();
+	assert_eq!(mpeg_file.encoder(), Some("LAME 3.100"));
+
+	// The LAME header isn't available without the properties
+	id3v2.remove_encoder();
+	file.rewind().unwrap();
+	id3v2.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MPEGFile::read_from(&mut file, false).unwrap();
+	assert_eq!(mpeg_file.encoder(), None);
+}
+
+#[test]
+fn read_audio_data_range() {
+	let path = "tests/files/assets/minimal/full_test.mp3";
+
+	// The audio data is between the leading ID3v2 tag, and the trailing APEv2 and ID3v1 tags
+	let file = lofty::read_from_path(path, true).unwrap();
+	assert_eq!(file.audio_data_range(), Some(31..11527));
+
+	let mpeg_file = MPEGFile::read_from(&mut std::fs::File::open(path).unwrap(), false).unwrap();
+	assert_eq!(mpeg_file.audio_data_range(), 31..11527);
+}
+
 #[test]
 fn read_with_junk_bytes_between_frames() {
 	// Read a file that includes an ID3v2.3 data block followed by four bytes of junk data (0x20)
@@ -33,7 +83,7 @@ fn read_with_junk_bytes_between_frames() {
 	assert_eq!(id3v2_tag.album(), Some("album test"));
 	assert_eq!(id3v2_tag.title(), Some("title test"));
 	assert_eq!(
-		id3v2_tag.get_string(&ItemKey::EncoderSettings),
+		id3v2_tag.get_string(&ItemKey::EncoderSoftware),
 		Some("Lavf58.62.100")
 	);
 
@@ -71,6 +121,149 @@ fn write() {
 	crate::set_artist!(tagged_file, tag_mut, TagType::APE, "Qux artist", 1 => file, "Baz artist");
 }
 
+#[test]
+fn save_unchanged() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+	let tagged_file = lofty::read_from(&mut file, false).unwrap();
+
+	for tag_type in [TagType::ID3v2, TagType::APE, TagType::ID3v1] {
+		let mut tag = tagged_file.tag(tag_type).unwrap().clone();
+
+		// The first save may change the layout of the existing tag
+		file.rewind().unwrap();
+		tag.save_to(&mut file).unwrap();
+
+		// Now the tag is identical, so nothing should be written
+		file.rewind().unwrap();
+		assert!(!tag.save_to(&mut file).unwrap());
+
+		tag.set_artist(String::from("Qux artist"));
+
+		file.rewind().unwrap();
+		assert!(tag.save_to(&mut file).unwrap());
+
+		file.rewind().unwrap();
+		assert!(!tag.save_to(&mut file).unwrap());
+	}
+
+	file.rewind().unwrap();
+	let tagged_file = lofty::read_from(&mut file, false).unwrap();
+	assert_eq!(tagged_file.tags().len(), 3);
+	for tag in tagged_file.tags() {
+		assert_eq!(tag.artist().as_deref(), Some("Qux artist"));
+	}
+
+	// An unchanged file reports nothing was written
+	file.rewind().unwrap();
+	assert!(!tagged_file.save_to(&mut file).unwrap());
+}
+
+#[test]
+fn save_to_mpeg_id3v1_policy() {
+	use lofty::mpeg::{ID3v1Policy, MPEGWriteOptions};
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+	let mut id3v2 = MPEGFile::read_from(&mut file, false)
+		.unwrap()
+		.id3v2()
+		.unwrap()
+		.clone();
+	id3v2.set_title(String::from("Qux title"));
+	id3v2.set_year(2022);
+
+	let save = |file: &mut std::fs::File, id3v2: &ID3v2Tag, id3v1: ID3v1Policy| {
+		file.rewind().unwrap();
+		let changed = id3v2
+			.save_to_mpeg(file, MPEGWriteOptions::new().id3v1(id3v1))
+			.unwrap();
+
+		file.rewind().unwrap();
+		(changed, MPEGFile::read_from(file, false).unwrap())
+	};
+
+	// The ID3v1 tag is left alone by default
+	let (changed, mpeg_file) = save(&mut file, &id3v2, ID3v1Policy::Keep);
+	assert!(changed);
+	assert_eq!(
+		mpeg_file.id3v2().unwrap().title().as_deref(),
+		Some("Qux title")
+	);
+	assert_eq!(
+		mpeg_file.id3v1().unwrap().artist().as_deref(),
+		Some("Bar artist")
+	);
+
+	let (changed, mpeg_file) = save(&mut file, &id3v2, ID3v1Policy::Mirror);
+	assert!(changed);
+	let id3v1 = mpeg_file.id3v1().unwrap();
+	assert_eq!(id3v1.title().as_deref(), Some("Qux title"));
+	assert_eq!(id3v1.artist().as_deref(), Some("Foo artist"));
+	assert_eq!(id3v1.year(), Some(2022));
+
+	// The mirrored tag is already up to date
+	let (changed, _) = save(&mut file, &id3v2, ID3v1Policy::Mirror);
+	assert!(!changed);
+
+	let (changed, mpeg_file) = save(&mut file, &id3v2, ID3v1Policy::Remove);
+	assert!(changed);
+	assert!(mpeg_file.id3v1().is_none());
+	assert!(mpeg_file.ape().is_some());
+
+	// Removing a tag that doesn't exist does nothing
+	let len = file.metadata().unwrap().len();
+	let (changed, _) = save(&mut file, &id3v2, ID3v1Policy::Remove);
+	assert!(!changed);
+	assert_eq!(file.metadata().unwrap().len(), len);
+
+	// Only MPEG files are supported
+	let mut flac = temp_file!("tests/files/assets/minimal/full_test.flac");
+	assert!(id3v2
+		.save_to_mpeg(&mut flac, MPEGWriteOptions::default())
+		.is_err());
+}
+
+#[test]
+fn tagged_file_save_to_mpeg() {
+	use lofty::mpeg::{ID3v1Policy, MPEGWriteOptions};
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+	tagged_file
+		.tag_mut(TagType::ID3v2)
+		.unwrap()
+		.set_title(String::from("Qux title"));
+
+	file.rewind().unwrap();
+	let changed = tagged_file
+		.save_to_mpeg(
+			&mut file,
+			MPEGWriteOptions::new().id3v1(ID3v1Policy::Mirror),
+		)
+		.unwrap();
+	assert!(changed);
+
+	file.rewind().unwrap();
+	let mpeg_file = MPEGFile::read_from(&mut file, false).unwrap();
+	assert_eq!(
+		mpeg_file.id3v1().unwrap().title().as_deref(),
+		Some("Qux title")
+	);
+
+	file.rewind().unwrap();
+	let changed = tagged_file
+		.save_to_mpeg(
+			&mut file,
+			MPEGWriteOptions::new().id3v1(ID3v1Policy::Remove),
+		)
+		.unwrap();
+	assert!(changed);
+
+	file.rewind().unwrap();
+	let mpeg_file = MPEGFile::read_from(&mut file, false).unwrap();
+	assert!(mpeg_file.id3v1().is_none());
+	assert!(mpeg_file.id3v2().is_some());
+}
+
 #[test]
 fn remove_id3v2() {
 	crate::remove_tag!("tests/files/assets/minimal/full_test.mp3", TagType::ID3v2);
@@ -85,3 +278,283 @@ fn remove_id3v1() {
 fn remove_ape() {
 	crate::remove_tag!("tests/files/assets/minimal/full_test.mp3", TagType::APE);
 }
+
+#[test]
+fn write_lyrics3v2() {
+	use lofty::id3::lyrics3v2::Lyrics3v2Tag;
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	let mut lyrics3v2 = Lyrics3v2Tag::default();
+	lyrics3v2.insert(String::from("LYR"), String::from("Foo lyrics"));
+	lyrics3v2.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MPEGFile::read_from(&mut file, false).unwrap();
+
+	assert_eq!(mpeg_file.lyrics3v2(), Some(&lyrics3v2));
+
+	// The tags surrounding it are still found
+	assert!(mpeg_file.ape().is_some());
+	assert!(mpeg_file.id3v1().is_some());
+}
+
+#[test]
+fn read_malformed_lyrics3v2() {
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap();
+
+	// A Lyrics3v2 tag with an invalid field ID
+	let lyrics3v2 = b"LYRICSBEGIN1ND0000200000021LYRICS200";
+	let id3v1_start = content.len() - 128;
+	content.splice(id3v1_start..id3v1_start, lyrics3v2.iter().copied());
+
+	// The tag is skipped, rather than failing the entire read
+	let mpeg_file = MPEGFile::read_from(&mut std::io::Cursor::new(content), false).unwrap();
+	assert!(mpeg_file.lyrics3v2().is_none());
+	assert!(mpeg_file.ape().is_some());
+	assert!(mpeg_file.id3v1().is_some());
+}
+
+#[test]
+fn remove_all_tags() {
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap();
+
+	// Insert a Lyrics3v2 tag between the APE and ID3v1 tags
+	let lyrics3v2 = b"LYRICSBEGININD0000200000021LYRICS200";
+	let id3v1_start = content.len() - 128;
+	content.splice(id3v1_start..id3v1_start, lyrics3v2.iter().copied());
+
+	let mut file = tempfile::tempfile().unwrap();
+	file.write_all(&content).unwrap();
+	file.rewind().unwrap();
+
+	let removed = lofty::remove_all_tags(&mut file).unwrap();
+
+	assert_eq!(removed.len(), 4);
+	for tag_type in [
+		TagType::ID3v2,
+		TagType::ID3v1,
+		TagType::APE,
+		TagType::Lyrics3v2,
+	] {
+		assert!(removed.contains(&tag_type));
+	}
+
+	file.rewind().unwrap();
+	let tagged_file = lofty::read_from(&mut file, false).unwrap();
+	assert!(tagged_file.tags().is_empty());
+
+	// The Lyrics3v2 tag is gone too
+	file.rewind().unwrap();
+	let mut written = Vec::new();
+	std::io::Read::read_to_end(&mut file, &mut written).unwrap();
+	assert!(!written.windows(9).any(|w| w == b"LYRICS200"));
+
+	// Nothing is left to remove
+	file.rewind().unwrap();
+	assert!(lofty::remove_all_tags(&mut file).unwrap().is_empty());
+}
+
+#[test]
+fn save_copy() {
+	let source = "tests/files/assets/minimal/full_test.mp3";
+	let original = std::fs::read(source).unwrap();
+
+	let mut tagged_file = lofty::read_from_path(source, false).unwrap();
+	tagged_file
+		.primary_tag_mut()
+		.unwrap()
+		.set_artist(String::from("Qux artist"));
+
+	let dest = tempfile::NamedTempFile::new().unwrap();
+	tagged_file.save_copy_to(source, dest.path()).unwrap();
+
+	// The source file should be untouched
+	assert_eq!(std::fs::read(source).unwrap(), original);
+
+	// The temporary file has no extension
+	let copy = lofty::Probe::open(dest.path())
+		.unwrap()
+		.guess_file_type()
+		.unwrap()
+		.read(false)
+		.unwrap();
+
+	crate::verify_artist!(copy, primary_tag, "Qux artist", 1);
+	crate::verify_artist!(copy, tag, TagType::ID3v1, "Bar artist", 1);
+	crate::verify_artist!(copy, tag, TagType::APE, "Baz artist", 1);
+}
+
+#[test]
+fn preserve_unknown_id3v2_frames() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	// Proprietary/unknown frames are stored as binary
+	let xsop_content = b"\x03Foo artist sort".to_vec();
+	let txxx_frame = Frame::new(
+		"TXXX",
+		FrameValue::UserText(EncodedTextFrame {
+			encoding: TextEncoding::UTF8,
+			description: String::from("MusicBrainz Album Id"),
+			content: String::from("a1b2c3d4"),
+		}),
+		FrameFlags::default(),
+	)
+	.unwrap();
+
+	let mut id3v2 = ID3v2Tag::default();
+	id3v2.insert(
+		Frame::new(
+			"XSOP",
+			FrameValue::Binary(xsop_content.clone()),
+			FrameFlags::default(),
+		)
+		.unwrap(),
+	);
+	id3v2.insert(txxx_frame.clone());
+	id3v2.save_to(&mut file).unwrap();
+
+	// Edit the tag generically
+	file.rewind().unwrap();
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+	tagged_file
+		.primary_tag_mut()
+		.unwrap()
+		.set_title(String::from("Foo title"));
+
+	file.rewind().unwrap();
+	tagged_file.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MPEGFile::read_from(&mut file, false).unwrap();
+	let id3v2 = mpeg_file.id3v2().unwrap();
+
+	assert_eq!(id3v2.title(), Some("Foo title"));
+	assert_eq!(
+		id3v2.get("XSOP").unwrap().content(),
+		&FrameValue::Binary(xsop_content)
+	);
+	assert!(id3v2
+		.iter()
+		.any(|f| f == &txxx_frame && f.content() == txxx_frame.content()));
+}
+
+#[test]
+fn preserve_itunes_comments() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	let itunes_comments = [
+		("iTunNORM", " 00000A2B 00000A2B 00003C6B 00003C6B"),
+		("iTunSMPB", " 00000000 00000210 0000099C 0000000000183D24"),
+	];
+
+	let mut id3v2 = ID3v2Tag::default();
+	id3v2.set_comment(String::from("Foo comment"));
+	for (description, content) in itunes_comments {
+		id3v2.insert(
+			Frame::new(
+				"COMM",
+				FrameValue::Comment(LanguageFrame {
+					encoding: TextEncoding::Latin1,
+					language: Language::ENGLISH,
+					description: String::from(description),
+					content: String::from(content),
+				}),
+				FrameFlags::default(),
+			)
+			.unwrap(),
+		);
+	}
+	id3v2.save_to(&mut file).unwrap();
+
+	// Edit the tag generically
+	file.rewind().unwrap();
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+	let tag = tagged_file.primary_tag_mut().unwrap();
+
+	// The iTunes comments aren't mixed in with the displayable comment
+	assert_eq!(tag.get_strings(&ItemKey::Comment).count(), 1);
+	tag.set_title(String::from("Foo title"));
+
+	// The raw comments only make sense in ID3v2, they shouldn't end up in other formats
+	let mut ape_file = temp_file!("tests/files/assets/minimal/full_test.ape");
+	let mut ape_tag = Tag::new(TagType::APE);
+	for item in tag.items() {
+		ape_tag.push_item_unchecked(item.clone());
+	}
+	ape_tag.save_to(&mut ape_file).unwrap();
+
+	ape_file.rewind().unwrap();
+	let ape_file = ApeFile::read_from(&mut ape_file, false).unwrap();
+	let ape_tag = ape_file.ape().unwrap();
+	assert_eq!(
+		ape_tag.get_key("Title").unwrap().value(),
+		&ItemValue::Text(String::from("Foo title"))
+	);
+	assert!(ape_tag.get_key("COMM").is_none());
+
+	file.rewind().unwrap();
+	tagged_file.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MPEGFile::read_from(&mut file, false).unwrap();
+	let id3v2 = mpeg_file.id3v2().unwrap();
+
+	assert_eq!(id3v2.title(), Some("Foo title"));
+	assert_eq!(id3v2.comment(), Some("Foo comment"));
+
+	let comments = id3v2.comments().collect::<Vec<_>>();
+	assert_eq!(comments.len(), 3);
+	for (description, content) in itunes_comments {
+		assert!(comments.contains(&(Language::ENGLISH, description, content)));
+	}
+
+	assert_eq!(
+		id3v2.gapless_info(),
+		Some(GaplessInfo {
+			encoder_delay: 528,
+			padding: 2460,
+			original_sample_count: 1_588_516,
+		})
+	);
+}
+
+#[test]
+fn write_ape_to_id3v1_only() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	// Leave only the ID3v1 tag
+	TagType::ID3v2.remove_from(&mut file).unwrap();
+	file.rewind().unwrap();
+	TagType::APE.remove_from(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let mut ape = ApeTag::default();
+	ape.set_artist(String::from("Foo artist"));
+	assert!(ape.save_to(&mut file).unwrap());
+
+	// The APE tag goes between the audio data and the ID3v1 tag
+	let mut bytes = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut bytes).unwrap();
+
+	let id3v1_start = bytes.len() - 128;
+	assert_eq!(&bytes[id3v1_start..id3v1_start + 3], b"TAG");
+	assert_eq!(&bytes[id3v1_start - 32..id3v1_start - 24], b"APETAGEX");
+
+	file.rewind().unwrap();
+	let mpeg_file = MPEGFile::read_from(&mut file, false).unwrap();
+	assert_eq!(
+		mpeg_file.ape().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+	assert_eq!(
+		mpeg_file.id3v1().unwrap().artist().as_deref(),
+		Some("Bar artist")
+	);
+
+	// Containers that can't hold an APE tag are rejected
+	let mut flac = temp_file!("tests/files/assets/minimal/full_test.flac");
+	let err = ape.save_to(&mut flac).unwrap_err();
+	assert!(matches!(err.kind(), ErrorKind::UnsupportedTag));
+}
@@ -1,6 +1,8 @@
-use crate::{set_artist, temp_file, verify_artist};
-use lofty::{FileType, ItemKey, ItemValue, TagExt, TagItem, TagType};
-use std::io::{Seek, Write};
+use crate::{save_unchanged, set_artist, temp_file, verify_artist};
+use lofty::ogg::{SpeexFile, SpeexMode};
+use lofty::{AudioFile, FileType, ItemKey, ItemValue, TagExt, TagItem, TagType};
+use std::io::{Cursor, Read, Seek, Write};
+use std::time::Duration;
 
 // The tests for OGG Opus/Vorbis are nearly identical
 // We have the vendor string and a title stored in the tag
@@ -42,6 +44,37 @@ fn flac_remove_vorbis_comments() {
 	);
 }
 
+#[test]
+fn ogg_flac_read() {
+	read(
+		"tests/files/assets/minimal/full_test.oga",
+		FileType::OggFlac,
+	)
+}
+
+#[test]
+fn ogg_flac_write() {
+	write(
+		"tests/files/assets/minimal/full_test.oga",
+		FileType::OggFlac,
+	)
+}
+
+#[test]
+fn ogg_flac_remove() {
+	remove(
+		"tests/files/assets/minimal/full_test.oga",
+		TagType::VorbisComments,
+	)
+}
+
+#[test]
+fn ogg_flac_audio_data_range() {
+	// The audio data begins at the first page after the header packets
+	let file = lofty::read_from_path("tests/files/assets/minimal/full_test.oga", false).unwrap();
+	assert_eq!(file.audio_data_range(), Some(153..49496));
+}
+
 #[test]
 fn vorbis_read() {
 	read("tests/files/assets/minimal/full_test.ogg", FileType::Vorbis)
@@ -78,6 +111,21 @@ fn speex_remove() {
 	)
 }
 
+#[test]
+fn speex_zero_channels() {
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.spx").unwrap();
+
+	// Zero out the channel count in the Speex header
+	let header_start = content.windows(8).position(|w| w == b"Speex   ").unwrap();
+	let channels_pos = header_start + 48;
+	content[channels_pos..channels_pos + 4].copy_from_slice(&[0; 4]);
+
+	let file = SpeexFile::read_from(&mut Cursor::new(content), true).unwrap();
+
+	assert_eq!(file.properties().channels(), 1);
+	assert_eq!(file.properties().mode(), SpeexMode::UltraWideBand);
+}
+
 fn read(path: &str, file_type: FileType) {
 	let file = lofty::read_from_path(path, false).unwrap();
 
@@ -135,7 +183,625 @@ fn flac_with_id3v2() {
 	assert!(flac_file.vorbis_comments().is_some());
 }
 
+#[test]
+fn flac_audio_data_range() {
+	// The audio data begins right after the last metadata block
+	let file = lofty::read_from_path("tests/files/assets/minimal/full_test.flac", false).unwrap();
+	assert_eq!(file.audio_data_range(), Some(8263..57386));
+}
+
+#[test]
+fn flac_cue_sheet() {
+	use lofty::flac::{CueSheet, CueSheetTrack, CueSheetTrackIndex, FlacFile};
+	use lofty::Accessor;
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let cue_sheet = CueSheet {
+		catalog_number: String::from("1234567890123"),
+		lead_in_samples: 88200,
+		is_compact_disc: true,
+		tracks: vec![
+			CueSheetTrack {
+				offset: 0,
+				number: 1,
+				isrc: String::from("USABC1234567"),
+				is_audio: true,
+				pre_emphasis: false,
+				indices: vec![CueSheetTrackIndex {
+					offset: 0,
+					number: 1,
+				}],
+			},
+			CueSheetTrack {
+				offset: 588,
+				number: 170,
+				isrc: String::new(),
+				is_audio: true,
+				pre_emphasis: false,
+				indices: Vec::new(),
+			},
+		],
+	};
+
+	cue_sheet.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let flac_file = FlacFile::read_from(&mut file, true).unwrap();
+
+	assert_eq!(flac_file.cue_sheet(), Some(&cue_sheet));
+	assert_eq!(
+		flac_file.vorbis_comments().unwrap().artist(),
+		Some("Foo artist")
+	);
+
+	// Writing the tag shouldn't touch the cue sheet
+	file.rewind().unwrap();
+	flac_file
+		.vorbis_comments()
+		.unwrap()
+		.save_to(&mut file)
+		.unwrap();
+
+	file.rewind().unwrap();
+	let flac_file = FlacFile::read_from(&mut file, true).unwrap();
+	assert_eq!(flac_file.cue_sheet(), Some(&cue_sheet));
+
+	file.rewind().unwrap();
+	CueSheet::remove_from(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let flac_file = FlacFile::read_from(&mut file, true).unwrap();
+	assert!(flac_file.cue_sheet().is_none());
+	assert_eq!(
+		flac_file.vorbis_comments().unwrap().artist(),
+		Some("Foo artist")
+	);
+}
+
+// Insert a metadata block right after the STREAMINFO block
+fn flac_with_block(ty: u8, content: &[u8]) -> Vec<u8> {
+	let mut file_bytes = std::fs::read("tests/files/assets/minimal/full_test.flac").unwrap();
+
+	let mut block = vec![ty];
+	block.extend(&(content.len() as u32).to_be_bytes()[1..]);
+	block.extend(content);
+
+	// `fLaC` (4), the STREAMINFO block header (4) and content (34)
+	file_bytes.splice(42..42, block);
+	file_bytes
+}
+
+#[test]
+fn flac_invalid_cue_sheet() {
+	use lofty::flac::FlacFile;
+
+	// Far too short to be a cue sheet
+	let file_bytes = flac_with_block(5, &[0; 4]);
+
+	let flac_file = FlacFile::read_from(&mut Cursor::new(file_bytes), true).unwrap();
+	assert!(flac_file.cue_sheet().is_none());
+	assert!(flac_file.vorbis_comments().is_some());
+}
+
+#[test]
+fn flac_invalid_application_block() {
+	use lofty::flac::FlacFile;
+
+	// Too short to contain an application ID
+	let file_bytes = flac_with_block(2, &[0; 2]);
+
+	let flac_file = FlacFile::read_from(&mut Cursor::new(file_bytes), true).unwrap();
+	assert!(flac_file.application_blocks().is_empty());
+	assert!(flac_file.vorbis_comments().is_some());
+}
+
+#[test]
+fn flac_application_blocks() {
+	use lofty::flac::{ApplicationBlock, FlacFile};
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+	let original_len = file.metadata().unwrap().len();
+
+	let foo = ApplicationBlock::new(*b"FOO ", vec![1, 2, 3]);
+	let bar = ApplicationBlock::new(*b"BAR ", vec![4, 5, 6, 7]);
+
+	foo.save_to(&mut file).unwrap();
+	bar.save_to(&mut file).unwrap();
+
+	// Replace the existing block with the same ID
+	let new_foo = ApplicationBlock::new(*b"FOO ", vec![8; 100]);
+	new_foo.save_to(&mut file).unwrap();
+
+	// The padding absorbs the new blocks
+	assert_eq!(file.metadata().unwrap().len(), original_len);
+
+	file.rewind().unwrap();
+	let flac_file = FlacFile::read_from(&mut file, true).unwrap();
+
+	assert_eq!(flac_file.application_blocks(), &[new_foo, bar.clone()]);
+	assert_eq!(
+		flac_file
+			.application_blocks_by_id(*b"BAR ")
+			.collect::<Vec<_>>(),
+		vec![&bar]
+	);
+	assert!(flac_file.vorbis_comments().is_some());
+
+	file.rewind().unwrap();
+	ApplicationBlock::remove_from(&mut file, *b"FOO ").unwrap();
+
+	file.rewind().unwrap();
+	let flac_file = FlacFile::read_from(&mut file, true).unwrap();
+	assert_eq!(flac_file.application_blocks(), &[bar]);
+	assert_eq!(file.metadata().unwrap().len(), original_len);
+}
+
+#[test]
+fn flac_padding() {
+	use lofty::flac::{FlacFile, FlacWriteOptions};
+	use lofty::Accessor;
+	use std::io::Read;
+
+	fn read_all(file: &mut std::fs::File) -> Vec<u8> {
+		let mut contents = Vec::new();
+		file.rewind().unwrap();
+		file.read_to_end(&mut contents).unwrap();
+		contents
+	}
+
+	// This file has 8KB of padding
+	let original = std::fs::read("tests/files/assets/minimal/full_test.flac").unwrap();
+	let audio = &original[4 + (4 + 34) + (4 + 42) + (4 + 8171)..];
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	file.rewind().unwrap();
+	let mut tag = FlacFile::read_from(&mut file, false)
+		.unwrap()
+		.vorbis_comments()
+		.unwrap()
+		.clone();
+	tag.set_title(String::from("Foo title"));
+
+	// The padding absorbs the small edit, so the audio stays in place
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+
+	let contents = read_all(&mut file);
+	assert_eq!(contents.len(), original.len());
+	assert!(contents.ends_with(audio));
+
+	// There isn't enough padding for this, so the file is rewritten with the requested padding
+	tag.insert(String::from("FOO"), "Bar".repeat(4096), false);
+
+	file.rewind().unwrap();
+	tag.save_to_flac(&mut file, FlacWriteOptions { padding: 4096 })
+		.unwrap();
+
+	let contents = read_all(&mut file);
+	assert!(contents.ends_with(audio));
+
+	let padding_end = contents.len() - audio.len();
+	let padding_start = padding_end - 4096;
+	assert_eq!(
+		&contents[padding_start - 4..padding_start],
+		&[0x81, 0, 0x10, 0]
+	);
+	assert!(contents[padding_start..padding_end].iter().all(|b| *b == 0));
+
+	file.rewind().unwrap();
+	let flac_file = FlacFile::read_from(&mut file, false).unwrap();
+	assert_eq!(flac_file.vorbis_comments(), Some(&tag));
+}
+
+#[test]
+fn flac_add_padding() {
+	use lofty::flac::{FlacFile, FlacWriteOptions};
+
+	// Strip the padding block, making the Vorbis Comments the last block
+	let original = std::fs::read("tests/files/assets/minimal/full_test.flac").unwrap();
+	let padding_start = 4 + (4 + 34) + (4 + 42);
+
+	let mut contents = original[..padding_start].to_vec();
+	contents[4 + (4 + 34)] |= 0x80;
+	contents.extend(&original[padding_start + 4 + 8171..]);
+
+	let mut file = tempfile::tempfile().unwrap();
+	file.write_all(&contents).unwrap();
+
+	file.rewind().unwrap();
+	let tag = FlacFile::read_from(&mut file, false)
+		.unwrap()
+		.vorbis_comments()
+		.unwrap()
+		.clone();
+
+	// No padding was requested, so there's nothing to write
+	file.rewind().unwrap();
+	assert!(!tag
+		.save_to_flac(&mut file, FlacWriteOptions { padding: 0 })
+		.unwrap());
+
+	// The tag is unchanged, but the padding still has to be added
+	file.rewind().unwrap();
+	assert!(tag
+		.save_to_flac(&mut file, FlacWriteOptions { padding: 1024 })
+		.unwrap());
+	assert_eq!(
+		file.metadata().unwrap().len(),
+		contents.len() as u64 + 4 + 1024
+	);
+
+	file.rewind().unwrap();
+	assert!(!tag
+		.save_to_flac(&mut file, FlacWriteOptions { padding: 1024 })
+		.unwrap());
+}
+
 #[test]
 fn flac_remove_id3v2() {
 	crate::remove_tag!("tests/files/assets/flac_with_id3v2.flac", TagType::ID3v2);
 }
+
+#[test]
+fn opus_chained_properties() {
+	let chained = crate::util::chain_ogg_stream("tests/files/assets/minimal/full_test.opus");
+
+	let file = lofty::ogg::OpusFile::read_from(&mut Cursor::new(chained), true).unwrap();
+
+	// Each link is ~1428ms long, once its own pre-skip is removed
+	assert_eq!(file.properties().duration(), Duration::from_millis(2856));
+}
+
+#[test]
+fn vorbis_chained_properties() {
+	let chained = crate::util::chain_ogg_stream("tests/files/assets/minimal/full_test.ogg");
+
+	let file = lofty::ogg::VorbisFile::read_from(&mut Cursor::new(chained), true).unwrap();
+	let properties = file.properties();
+
+	// Each link is ~1450.5ms long
+	assert_eq!(properties.duration(), Duration::from_millis(2901));
+	assert_eq!(properties.sample_rate(), 48000);
+}
+
+#[test]
+fn opus_page_rewrite() {
+	page_rewrite("tests/files/assets/minimal/full_test.opus", |file| {
+		lofty::ogg::OpusFile::read_from(file, false)
+			.unwrap()
+			.vorbis_comments()
+			.clone()
+	})
+}
+
+#[test]
+fn vorbis_page_rewrite() {
+	page_rewrite("tests/files/assets/minimal/full_test.ogg", |file| {
+		lofty::ogg::VorbisFile::read_from(file, false)
+			.unwrap()
+			.vorbis_comments()
+			.clone()
+	})
+}
+
+fn page_rewrite(path: &str, read: fn(&mut std::fs::File) -> lofty::ogg::VorbisComments) {
+	use lofty::ogg::PageRewrite;
+	use lofty::Accessor;
+	use std::io::Read;
+
+	let mut file = temp_file!(path);
+	let mut tag = read(&mut file);
+
+	// Nothing changed, so nothing is written
+	file.rewind().unwrap();
+	assert_eq!(tag.save_to_ogg(&mut file).unwrap(), PageRewrite::Unchanged);
+
+	tag.set_title(String::from("Foo title"));
+
+	file.rewind().unwrap();
+	tag.save_to_ogg(&mut file).unwrap();
+
+	// The size didn't change, so the header pages can be written over
+	tag.set_title(String::from("Bar title"));
+
+	file.rewind().unwrap();
+	assert_eq!(tag.save_to_ogg(&mut file).unwrap(), PageRewrite::InPlace);
+
+	// The comment header still fits on a single page
+	tag.set_title(String::from("A slightly longer title"));
+
+	file.rewind().unwrap();
+	assert_eq!(tag.save_to_ogg(&mut file).unwrap(), PageRewrite::Moved);
+
+	// The comment header now spans multiple pages, so the audio pages are renumbered
+	tag.insert(String::from("FOO"), "Bar".repeat(30_000), false);
+
+	file.rewind().unwrap();
+	assert_eq!(tag.save_to_ogg(&mut file).unwrap(), PageRewrite::Full);
+
+	let mut contents = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut contents).unwrap();
+
+	let mut reader = Cursor::new(&contents);
+	let mut expected_seq_num = 0;
+	while let Ok(page) = ogg_pager::Page::read(&mut reader, true) {
+		assert_eq!(page.seq_num, expected_seq_num);
+		expected_seq_num += 1;
+
+		let mut page_bytes = contents[page.start as usize..page.end as usize].to_vec();
+		page_bytes[22..26].copy_from_slice(&[0; 4]);
+		assert_eq!(ogg_pager::crc32(&page_bytes), page.checksum());
+	}
+
+	assert_eq!(reader.position(), contents.len() as u64);
+	assert!(expected_seq_num > 3);
+
+	file.rewind().unwrap();
+	assert_eq!(read(&mut file), tag);
+}
+
+#[test]
+fn opus_vendor() {
+	use lofty::ogg::{OpusFile, VorbisComments};
+	use lofty::Accessor;
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.opus");
+
+	let read = |file: &mut std::fs::File| {
+		file.rewind().unwrap();
+		OpusFile::read_from(file, false)
+			.unwrap()
+			.vorbis_comments()
+			.clone()
+	};
+
+	let original_vendor = read(&mut file).vendor().to_string();
+	assert!(!original_vendor.is_empty());
+
+	// An empty vendor keeps the file's existing vendor
+	let mut tag = VorbisComments::default();
+	tag.set_title(String::from("Foo title"));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+	assert_eq!(read(&mut file).vendor(), original_vendor);
+
+	tag.set_vendor(String::from("Foo vendor"));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+	assert_eq!(read(&mut file).vendor(), "Foo vendor");
+
+	// Replace the comment header with one that has an empty vendor
+	let mut content = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut content).unwrap();
+
+	let mut reader = Cursor::new(&content);
+	let _ident = ogg_pager::Page::read(&mut reader, true).unwrap();
+	let comments = ogg_pager::Page::read(&mut reader, true).unwrap();
+
+	let mut packet = b"OpusTags".to_vec();
+	packet.extend([0; 8]);
+
+	let mut page = ogg_pager::Page::new(0, comments.abgp, comments.serial, 1, packet).unwrap();
+	page.gen_crc().unwrap();
+	content.splice(
+		comments.start as usize..comments.end as usize,
+		page.as_bytes().unwrap(),
+	);
+
+	file.rewind().unwrap();
+	file.set_len(0).unwrap();
+	file.write_all(&content).unwrap();
+	assert!(read(&mut file).vendor().is_empty());
+
+	// Without an existing vendor, a default vendor is written
+	tag.set_vendor(String::new());
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+	assert!(read(&mut file).vendor().starts_with("lofty"));
+}
+
+#[test]
+fn flac_vendor() {
+	use lofty::flac::FlacFile;
+	use lofty::ogg::VorbisComments;
+	use lofty::Accessor;
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let read = |file: &mut std::fs::File| {
+		file.rewind().unwrap();
+		FlacFile::read_from(file, false)
+			.unwrap()
+			.vorbis_comments()
+			.cloned()
+	};
+
+	let original_vendor = read(&mut file).unwrap().vendor().to_string();
+
+	// An empty vendor keeps the file's existing vendor
+	let mut tag = VorbisComments::default();
+	tag.set_title(String::from("Foo title"));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+	assert_eq!(read(&mut file).unwrap().vendor(), original_vendor);
+
+	// Without an existing Vorbis Comments block, a default vendor is written
+	file.rewind().unwrap();
+	TagType::VorbisComments.remove_from(&mut file).unwrap();
+	assert!(read(&mut file).is_none());
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+
+	let vendor = read(&mut file).unwrap().vendor().to_string();
+	assert!(vendor.starts_with("lofty"));
+
+	tag.set_vendor(String::from("Foo vendor"));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+	assert_eq!(read(&mut file).unwrap().vendor(), "Foo vendor");
+}
+
+#[test]
+fn flac_preserve_key_casing() {
+	use lofty::flac::FlacFile;
+	use lofty::ogg::VorbisComments;
+	use lofty::Accessor;
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let mut tag = VorbisComments::default();
+	tag.insert(String::from("Title"), String::from("Foo title"), false);
+	tag.insert(
+		String::from("MusicBrainz_AlbumId"),
+		String::from("Foo id"),
+		false,
+	);
+	tag.insert(String::from("Custom"), String::from("Foo value"), false);
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+
+	// Edit an unrelated field through the generic `Tag`
+	file.rewind().unwrap();
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+	let generic_tag = tagged_file.primary_tag_mut().unwrap();
+	assert_eq!(generic_tag.title(), Some("Foo title"));
+	generic_tag.set_artist(String::from("Bar artist"));
+
+	file.rewind().unwrap();
+	tagged_file.save_to(&mut file).unwrap();
+
+	file.rewind().unwrap();
+	let flac_file = FlacFile::read_from(&mut file, false).unwrap();
+	let vorbis_comments = flac_file.vorbis_comments().unwrap();
+
+	// The untouched fields keep their casing, while the new field uses the default casing
+	assert_eq!(vorbis_comments.get("Title"), Some("Foo title"));
+	assert_eq!(vorbis_comments.get("MusicBrainz_AlbumId"), Some("Foo id"));
+	assert_eq!(vorbis_comments.get("Custom"), Some("Foo value"));
+	assert_eq!(vorbis_comments.get("ARTIST"), Some("Bar artist"));
+	assert!(vorbis_comments.get("TITLE").is_none());
+}
+
+#[test]
+fn flac_picture_limits() {
+	use lofty::error::ErrorKind;
+	use lofty::flac::FlacFile;
+	use lofty::{
+		MimeType, ParseMode, Picture, PictureInformation, PictureType, Probe, ProbeOptions,
+	};
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	file.rewind().unwrap();
+	let mut tag = FlacFile::read_from(&mut file, false)
+		.unwrap()
+		.vorbis_comments()
+		.unwrap()
+		.clone();
+
+	for pic_type in [
+		PictureType::CoverFront,
+		PictureType::CoverBack,
+		PictureType::Band,
+	] {
+		let picture = Picture::new_unchecked(pic_type, MimeType::Png, None, vec![1, 2, 3, 4]);
+		tag.insert_picture(picture, Some(PictureInformation::default()))
+			.unwrap();
+	}
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file).unwrap();
+
+	let mut read = |options: ProbeOptions| {
+		file.rewind().unwrap();
+		Probe::new(&mut file)
+			.guess_file_type()
+			.unwrap()
+			.options(options)
+			.read(false)
+	};
+
+	// The count is checked for placeholders as well
+	for read_pictures in [true, false] {
+		let err = read(
+			ProbeOptions::new()
+				.read_pictures(read_pictures)
+				.max_pictures(Some(2)),
+		)
+		.err()
+		.unwrap();
+		assert!(matches!(
+			err.kind(),
+			ErrorKind::TooManyPictures { limit: 2 }
+		));
+	}
+
+	let err = read(ProbeOptions::new().max_picture_bytes(Some(3)))
+		.err()
+		.unwrap();
+	assert!(matches!(
+		err.kind(),
+		ErrorKind::PictureTooLarge { size: 4, limit: 3 }
+	));
+
+	let err = read(ProbeOptions::new().max_tag_bytes(Some(64)))
+		.err()
+		.unwrap();
+	assert!(matches!(
+		err.kind(),
+		ErrorKind::TagTooLarge { limit: 64, .. }
+	));
+
+	// With `ParseMode::Relaxed`, only the offending pictures are skipped
+	let tagged_file = read(
+		ProbeOptions::new()
+			.parse_mode(ParseMode::Relaxed)
+			.max_pictures(Some(2)),
+	)
+	.unwrap();
+	let tag = tagged_file.tag(TagType::VorbisComments).unwrap();
+	assert_eq!(tag.picture_count(), 2);
+	assert_eq!(tagged_file.parse_errors().len(), 1);
+
+	let tagged_file = read(
+		ProbeOptions::new()
+			.parse_mode(ParseMode::Relaxed)
+			.max_picture_bytes(Some(3)),
+	)
+	.unwrap();
+	let tag = tagged_file.tag(TagType::VorbisComments).unwrap();
+	assert_eq!(tag.picture_count(), 0);
+	assert_eq!(tagged_file.parse_errors().len(), 3);
+
+	let tagged_file = read(ProbeOptions::default()).unwrap();
+	assert_eq!(
+		tagged_file
+			.tag(TagType::VorbisComments)
+			.unwrap()
+			.picture_count(),
+		3
+	);
+}
+
+#[test]
+fn save_unchanged() {
+	for path in [
+		"tests/files/assets/minimal/full_test.flac",
+		"tests/files/assets/minimal/full_test.ogg",
+		"tests/files/assets/minimal/full_test.opus",
+		"tests/files/assets/minimal/full_test.spx",
+	] {
+		save_unchanged!(path, TagType::VorbisComments);
+	}
+}
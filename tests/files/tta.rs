@@ -0,0 +1,73 @@
+use crate::{set_artist, temp_file, verify_artist};
+use lofty::tta::TtaFile;
+use lofty::{AudioFile, FileType, ItemKey, ItemValue, TagExt, TagItem, TagType};
+use std::io::{Seek, Write};
+
+#[test]
+fn read() {
+	// Here we have a TTA file with an ID3v2, ID3v1, and an APEv2 tag
+	let file = lofty::read_from_path("tests/files/assets/minimal/full_test.tta", false).unwrap();
+
+	assert_eq!(file.file_type(), FileType::TTA);
+
+	// Verify the ID3v2 tag first
+	crate::verify_artist!(file, primary_tag, "Foo artist", 1);
+
+	// Now verify ID3v1
+	crate::verify_artist!(file, tag, TagType::ID3v1, "Bar artist", 1);
+
+	// Finally, verify APEv2
+	crate::verify_artist!(file, tag, TagType::APE, "Baz artist", 1);
+}
+
+#[test]
+fn read_audio_data_range() {
+	let path = "tests/files/assets/minimal/full_test.tta";
+
+	// The audio data is between the leading ID3v2 tag, and the trailing APEv2 and ID3v1 tags
+	let tta_file = TtaFile::read_from(&mut std::fs::File::open(path).unwrap(), false).unwrap();
+	assert_eq!(tta_file.audio_data_range(), 31..161);
+}
+
+#[test]
+fn write() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.tta");
+
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+
+	assert_eq!(tagged_file.file_type(), FileType::TTA);
+
+	// ID3v2
+	crate::set_artist!(tagged_file, primary_tag_mut, "Foo artist", 1 => file, "Bar artist");
+
+	// ID3v1
+	crate::set_artist!(tagged_file, tag_mut, TagType::ID3v1, "Bar artist", 1 => file, "Baz artist");
+
+	// APEv2
+	crate::set_artist!(tagged_file, tag_mut, TagType::APE, "Baz artist", 1 => file, "Qux artist");
+
+	// Now reread the file
+	file.rewind().unwrap();
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+
+	crate::set_artist!(tagged_file, primary_tag_mut, "Bar artist", 1 => file, "Foo artist");
+
+	crate::set_artist!(tagged_file, tag_mut, TagType::ID3v1, "Baz artist", 1 => file, "Bar artist");
+
+	crate::set_artist!(tagged_file, tag_mut, TagType::APE, "Qux artist", 1 => file, "Baz artist");
+}
+
+#[test]
+fn remove_id3v2() {
+	crate::remove_tag!("tests/files/assets/minimal/full_test.tta", TagType::ID3v2);
+}
+
+#[test]
+fn remove_id3v1() {
+	crate::remove_tag!("tests/files/assets/minimal/full_test.tta", TagType::ID3v1);
+}
+
+#[test]
+fn remove_ape() {
+	crate::remove_tag!("tests/files/assets/minimal/full_test.tta", TagType::APE);
+}
@@ -91,3 +91,49 @@ macro_rules! remove_tag {
 		assert!(tagged_file.tag($tag_type).is_none());
 	};
 }
+
+#[macro_export]
+macro_rules! save_unchanged {
+	($path:tt, $tag_type:path) => {
+		let mut file = temp_file!($path);
+
+		let tagged_file = lofty::read_from(&mut file, false).unwrap();
+		let mut tag = tagged_file.tag($tag_type).unwrap().clone();
+
+		// The first save may change the layout of the existing tag
+		file.seek(std::io::SeekFrom::Start(0)).unwrap();
+		tag.save_to(&mut file).unwrap();
+
+		// Now the tag is identical, so nothing should be written
+		file.seek(std::io::SeekFrom::Start(0)).unwrap();
+		assert!(!tag.save_to(&mut file).unwrap());
+
+		tag.insert_text(ItemKey::TrackArtist, String::from("Qux artist"));
+
+		file.seek(std::io::SeekFrom::Start(0)).unwrap();
+		assert!(tag.save_to(&mut file).unwrap());
+
+		file.seek(std::io::SeekFrom::Start(0)).unwrap();
+		assert!(!tag.save_to(&mut file).unwrap());
+	};
+}
+
+// Appends a second link to an Ogg stream, which needs its own serial number
+pub fn chain_ogg_stream(path: &str) -> Vec<u8> {
+	let content = std::fs::read(path).unwrap();
+	let mut second_link = content.clone();
+
+	let mut reader = std::io::Cursor::new(&content);
+	while let Ok(page) = ogg_pager::Page::read(&mut reader, true) {
+		let page = &mut second_link[page.start as usize..page.end as usize];
+		page[14..18].copy_from_slice(&0xDEAD_BEEF_u32.to_le_bytes());
+
+		page[22..26].copy_from_slice(&[0; 4]);
+		let checksum = ogg_pager::crc32(page);
+		page[22..26].copy_from_slice(&checksum.to_le_bytes());
+	}
+
+	let mut chained = content;
+	chained.extend(second_link);
+	chained
+}
@@ -17,6 +17,14 @@ fn read() {
 	crate::verify_artist!(file, tag, TagType::RIFFInfo, "Bar artist", 1);
 }
 
+#[test]
+fn read_audio_data_range() {
+	// The content of the `data` chunk
+	let file =
+		lofty::read_from_path("tests/files/assets/minimal/wav_format_pcm.wav", false).unwrap();
+	assert_eq!(file.audio_data_range(), Some(44..274_228));
+}
+
 #[test]
 fn write() {
 	let mut file = temp_file!("tests/files/assets/minimal/wav_format_pcm.wav");
@@ -48,6 +56,83 @@ fn remove_id3v2() {
 	);
 }
 
+#[test]
+fn save_id3v2() {
+	use lofty::{Accessor, Tag};
+	use std::io::Read;
+
+	fn riff_size_matches(file: &mut std::fs::File) -> bool {
+		let mut header = [0; 8];
+		file.rewind().unwrap();
+		file.read_exact(&mut header).unwrap();
+
+		let riff_size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+		u64::from(riff_size) == file.metadata().unwrap().len() - 8
+	}
+
+	let mut file = temp_file!("tests/files/assets/minimal/wav_format_pcm.wav");
+	let original_len = file.metadata().unwrap().len();
+
+	// Saving an empty tag removes the chunk entirely
+	Tag::new(TagType::ID3v2).save_to(&mut file).unwrap();
+
+	assert!(file.metadata().unwrap().len() < original_len);
+	assert!(riff_size_matches(&mut file));
+
+	file.rewind().unwrap();
+	let tagged_file = lofty::read_from(&mut file, false).unwrap();
+	assert!(tagged_file.tag(TagType::ID3v2).is_none());
+	assert!(tagged_file.tag(TagType::RIFFInfo).is_some());
+
+	// And a new chunk is created
+	let mut tag = Tag::new(TagType::ID3v2);
+	tag.set_title(String::from("Foo title"));
+
+	file.rewind().unwrap();
+	assert!(tag.save_to(&mut file).unwrap());
+
+	assert!(riff_size_matches(&mut file));
+
+	// Saving the same tag again does nothing
+	file.rewind().unwrap();
+	assert!(!tag.save_to(&mut file).unwrap());
+
+	file.rewind().unwrap();
+	let tagged_file = lofty::read_from(&mut file, false).unwrap();
+	let id3v2 = tagged_file.tag(TagType::ID3v2).unwrap();
+	assert_eq!(id3v2.title().as_deref(), Some("Foo title"));
+	assert!(tagged_file.tag(TagType::RIFFInfo).is_some());
+
+	// The chunk ID is always written in lowercase
+	let mut content = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut content).unwrap();
+	assert!(content.windows(4).any(|window| window == b"id3 "));
+	assert!(!content.windows(4).any(|window| window == b"ID3 "));
+}
+
+#[test]
+fn read_id3v2_chunk_casing() {
+	let content = std::fs::read("tests/files/assets/minimal/wav_format_pcm.wav").unwrap();
+	let chunk_start = content
+		.windows(4)
+		.position(|window| window == b"ID3 ")
+		.unwrap();
+
+	for fourcc in [b"ID3 ", b"id3 ", b"Id3 "] {
+		let mut content = content.clone();
+		content[chunk_start..chunk_start + 4].copy_from_slice(fourcc);
+
+		let tagged_file = lofty::Probe::new(std::io::Cursor::new(content))
+			.guess_file_type()
+			.unwrap()
+			.read(false)
+			.unwrap();
+
+		crate::verify_artist!(tagged_file, primary_tag, "Foo artist", 1);
+	}
+}
+
 #[test]
 fn remove_riff_info() {
 	crate::remove_tag!(
@@ -55,3 +140,83 @@ fn remove_riff_info() {
 		TagType::RIFFInfo
 	);
 }
+
+#[test]
+fn write_riff_info_in_place() {
+	fn chunk(fourcc: &[u8; 4], content: &[u8]) -> Vec<u8> {
+		let mut chunk = fourcc.to_vec();
+		chunk.extend((content.len() as u32).to_le_bytes());
+		chunk.extend(content);
+		if content.len() % 2 != 0 {
+			chunk.push(0);
+		}
+
+		chunk
+	}
+
+	let mut fmt = Vec::new();
+	fmt.extend(1_u16.to_le_bytes()); // PCM
+	fmt.extend(1_u16.to_le_bytes()); // Channels
+	fmt.extend(8000_u32.to_le_bytes()); // Sample rate
+	fmt.extend(16000_u32.to_le_bytes()); // Bytes per second
+	fmt.extend(2_u16.to_le_bytes()); // Block align
+	fmt.extend(16_u16.to_le_bytes()); // Bits per sample
+
+	// A non-INFO LIST chunk with an odd size, followed by an INFO LIST with a padded item
+	let adtl = chunk(b"LIST", b"adtlfoo");
+	let info = chunk(
+		b"LIST",
+		&[&b"INFO"[..], &chunk(b"IART", b"Old artist\0")].concat(),
+	);
+
+	let mut content = b"WAVE".to_vec();
+	content.extend(chunk(b"fmt ", &fmt));
+	content.extend(chunk(b"data", &[0; 16]));
+	content.extend(adtl);
+	content.extend(&info);
+	content.extend(chunk(b"junk", b"end"));
+
+	let riff = chunk(b"RIFF", &content);
+
+	let mut file = tempfile::tempfile().unwrap();
+	file.write_all(&riff).unwrap();
+	file.rewind().unwrap();
+
+	let mut tagged_file = lofty::read_from(&mut file, false).unwrap();
+	crate::set_artist!(tagged_file, tag_mut, TagType::RIFFInfo, "Old artist", 1 => file, "New artist");
+
+	file.rewind().unwrap();
+	let mut output = Vec::new();
+	std::io::Read::read_to_end(&mut file, &mut output).unwrap();
+
+	// The outer size must be fixed up
+	assert_eq!(
+		u32::from_le_bytes(output[4..8].try_into().unwrap()) as usize,
+		output.len() - 8
+	);
+
+	// Walk the chunks, the INFO list should've been replaced where it was
+	let mut ids = Vec::new();
+	let mut pos = 12;
+	while pos < output.len() {
+		let size = u32::from_le_bytes(output[pos + 4..pos + 8].try_into().unwrap()) as usize;
+		ids.push(output[pos..pos + 4].to_vec());
+		pos += 8 + size + (size % 2);
+	}
+
+	assert_eq!(pos, output.len());
+	assert_eq!(
+		ids,
+		vec![
+			b"fmt ".to_vec(),
+			b"data".to_vec(),
+			b"LIST".to_vec(),
+			b"LIST".to_vec(),
+			b"junk".to_vec()
+		]
+	);
+
+	file.rewind().unwrap();
+	let tagged_file = lofty::read_from(&mut file, false).unwrap();
+	crate::verify_artist!(tagged_file, tag, TagType::RIFFInfo, "New artist", 1);
+}
@@ -0,0 +1,76 @@
+use lofty::{MimeType, Picture, PictureType};
+
+use std::collections::HashSet;
+
+const ORIGINAL_IMAGE: &[u8; 53368] = include_bytes!("assets/png_640x628.png");
+
+fn create_picture(pic_type: PictureType, description: Option<&str>) -> Picture {
+	Picture::new_unchecked(
+		pic_type,
+		MimeType::Png,
+		description.map(String::from),
+		ORIGINAL_IMAGE.to_vec(),
+	)
+}
+
+#[test]
+fn picture_equality() {
+	let front_cover = create_picture(PictureType::CoverFront, None);
+
+	assert_eq!(front_cover, create_picture(PictureType::CoverFront, None));
+	assert_ne!(front_cover, create_picture(PictureType::CoverBack, None));
+	assert_ne!(
+		front_cover,
+		create_picture(PictureType::CoverFront, Some("Foo"))
+	);
+
+	let other_data = Picture::new_unchecked(
+		PictureType::CoverFront,
+		MimeType::Png,
+		None,
+		ORIGINAL_IMAGE[1..].to_vec(),
+	);
+	assert_ne!(front_cover, other_data);
+
+	// Duplicates across files can be dropped
+	let pictures: HashSet<Picture> = [
+		create_picture(PictureType::CoverFront, None),
+		create_picture(PictureType::CoverFront, None),
+		create_picture(PictureType::CoverBack, None),
+	]
+	.into_iter()
+	.collect();
+	assert_eq!(pictures.len(), 2);
+}
+
+#[test]
+fn picture_content_hash() {
+	let front_cover = create_picture(PictureType::CoverFront, None);
+	assert_eq!(
+		front_cover.content_hash(),
+		create_picture(PictureType::CoverFront, None).content_hash()
+	);
+
+	// The hash is stable, so it can be stored
+	assert_eq!(
+		Picture::new_unchecked(PictureType::Other, MimeType::None, None, Vec::new()).content_hash(),
+		0x77E8_75B1_C7B6_A32D
+	);
+
+	// The fields can't run into each other
+	let with_description = create_picture(PictureType::CoverFront, Some(""));
+	assert_ne!(front_cover.content_hash(), with_description.content_hash());
+
+	for other in [
+		create_picture(PictureType::CoverBack, None),
+		create_picture(PictureType::CoverFront, Some("Foo")),
+		Picture::new_unchecked(
+			PictureType::CoverFront,
+			MimeType::Png,
+			None,
+			ORIGINAL_IMAGE[1..].to_vec(),
+		),
+	] {
+		assert_ne!(front_cover.content_hash(), other.content_hash());
+	}
+}
@@ -1,5 +1,5 @@
 use lofty::id3::v2::{ID3v2Version, TextEncoding};
-use lofty::{Picture, PictureInformation, PictureType};
+use lofty::{MimeType, Picture, PictureInformation, PictureType};
 
 use std::fs::File;
 use std::io::Read;
@@ -109,3 +109,21 @@ fn as_flac_bytes() {
 
 	assert_eq!(&*buf, original_as_flac);
 }
+
+#[test]
+fn apic_mislabeled_mime_type() {
+	let original_picture = Picture::new_unchecked(
+		PictureType::CoverFront,
+		MimeType::Jpeg,
+		None,
+		ORIGINAL_IMAGE.to_vec(),
+	);
+
+	let apic = original_picture
+		.as_apic_bytes(ID3v2Version::V4, TextEncoding::Latin1)
+		.unwrap();
+
+	// The data is a PNG, despite being stored as "image/jpeg"
+	let (pic, _) = Picture::from_apic_bytes(&apic, ID3v2Version::V4).unwrap();
+	assert_eq!(pic.mime_type(), &MimeType::Png);
+}
@@ -48,3 +48,26 @@ fn picture_from_reader_tiff() {
 
 	assert_eq!(pic.mime_type(), &MimeType::Tiff);
 }
+
+#[test]
+fn picture_from_bytes_webp() {
+	let mut webp = Vec::new();
+	webp.extend(b"RIFF");
+	webp.extend(8_u32.to_le_bytes());
+	webp.extend(b"WEBPVP8 ");
+
+	let pic = Picture::from_bytes(&webp);
+
+	assert_eq!(pic.mime_type(), &MimeType::Webp);
+}
+
+#[test]
+fn picture_from_bytes_unknown() {
+	let unknown = MimeType::Unknown(String::from("application/octet-stream"));
+
+	assert_eq!(Picture::from_bytes(b"Not a picture").mime_type(), &unknown);
+	assert_eq!(
+		Picture::from_bytes(&[0x89, b'P', b'N']).mime_type(),
+		&unknown
+	);
+}
@@ -1,3 +1,6 @@
+mod equality;
 mod format_parsers;
 mod from_reader;
 mod information;
+#[cfg(feature = "picture-tools")]
+mod thumbnail;
@@ -0,0 +1,59 @@
+use lofty::{MimeType, Picture, PictureInformation, PictureType};
+
+use std::fs::File;
+use std::io::Read;
+
+fn get_buf(path: &str) -> Vec<u8> {
+	let mut f = File::open(path).unwrap();
+
+	let mut buf = Vec::new();
+	f.read_to_end(&mut buf).unwrap();
+
+	buf
+}
+
+#[test]
+fn thumbnail_png() {
+	let picture =
+		Picture::from_reader(&mut &*get_buf("tests/picture/assets/png_640x628.png")).unwrap();
+
+	let thumbnail = picture.thumbnail(100).unwrap();
+	assert_eq!(thumbnail.mime_type(), &MimeType::Jpeg);
+
+	// 640x628, so the width is the limiting dimension
+	let information = PictureInformation::from_jpeg(thumbnail.data()).unwrap();
+	assert_eq!(information.width, 100);
+	assert!(information.height < 100);
+}
+
+#[test]
+fn thumbnail_already_small() {
+	let picture =
+		Picture::from_reader(&mut &*get_buf("tests/picture/assets/gif_640x628.gif")).unwrap();
+
+	let thumbnail = picture.thumbnail(1000).unwrap();
+	assert_eq!(thumbnail.mime_type(), &MimeType::Jpeg);
+
+	let information = PictureInformation::from_jpeg(thumbnail.data()).unwrap();
+	assert_eq!(information.width, 640);
+	assert_eq!(information.height, 628);
+}
+
+#[test]
+fn thumbnail_mislabeled_mime_type() {
+	// JPEG data, claiming to be a PNG
+	let picture = Picture::new_unchecked(
+		PictureType::CoverFront,
+		MimeType::Png,
+		Some(String::from("Cover")),
+		get_buf("tests/picture/assets/jpeg_640x628.jpg"),
+	);
+
+	let thumbnail = picture.thumbnail(64).unwrap();
+	assert_eq!(thumbnail.pic_type(), PictureType::CoverFront);
+	assert_eq!(thumbnail.mime_type(), &MimeType::Jpeg);
+	assert_eq!(thumbnail.description(), Some("Cover"));
+
+	let information = PictureInformation::from_jpeg(thumbnail.data()).unwrap();
+	assert_eq!(information.width, 64);
+}
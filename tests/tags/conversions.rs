@@ -1,6 +1,8 @@
 // Tests for special case conversions
 
-use lofty::id3::v2::{Frame, FrameFlags, FrameValue, ID3v2Tag, LanguageFrame, TextEncoding};
+use lofty::id3::v2::{
+	Frame, FrameFlags, FrameValue, ID3v2Tag, Language, LanguageFrame, TextEncoding,
+};
 use lofty::{ItemKey, Tag, TagType};
 
 #[test]
@@ -17,7 +19,7 @@ fn tag_to_id3v2_lang_frame() {
 			"USLT",
 			FrameValue::UnSyncText(LanguageFrame {
 				encoding: TextEncoding::UTF8,
-				language: *b"eng",
+				language: Language::ENGLISH,
 				description: String::new(),
 				content: String::from("Test lyrics")
 			}),
@@ -33,7 +35,7 @@ fn tag_to_id3v2_lang_frame() {
 			"COMM",
 			FrameValue::Comment(LanguageFrame {
 				encoding: TextEncoding::UTF8,
-				language: *b"eng",
+				language: Language::ENGLISH,
 				description: String::new(),
 				content: String::from("Test comment")
 			}),